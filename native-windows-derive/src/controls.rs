@@ -49,22 +49,13 @@ pub fn expand_flags(member_name: &syn::Ident, ty: &syn::Ident, flags: syn::Expr)
         other => panic!("Compressed flags must str, got {:?} for control {}", other, member_name)
     };
 
-    let flags = flags_value.value();
-    let splitted: Vec<&str> = flags.split('|').collect();
+    // The flags keep the span of the string literal so that an unknown flag is reported on the attribute
+    let flags_type = syn::Ident::new(&flags_type, flags_value.span());
+    let values = flags_value.value();
+    let values = values.split('|').map(|value| syn::Ident::new(value.trim(), flags_value.span()));
+    let final_flags = quote! { #(#flags_type::#values)|* };
 
-    let flags_count = splitted.len() - 1;
-    let mut final_flags: String = String::with_capacity(100);
-    for (i, value) in splitted.into_iter().enumerate() {
-        final_flags.push_str(&flags_type);
-        final_flags.push_str("::");
-        final_flags.push_str(value);
-
-        if i != flags_count {
-            final_flags.push('|');
-        }
-    }
-
-    match syn::parse_str(&final_flags) {
+    match syn::parse2(final_flags) {
         Ok(e) => e,
         Err(e) => panic!("Failed to parse flags value for control {}: {}", member_name, e)
     }
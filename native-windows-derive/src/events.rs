@@ -4,12 +4,13 @@ use syn::punctuated::Punctuated;
 use syn::parse::{Parse, ParseStream, ParseBuffer};
 use quote::{ToTokens};
 use std::collections::HashMap;
+use crate::shared::FieldStorage;
 
 
 /// A callback function definition
-struct CallbackFunction {
+pub(crate) struct CallbackFunction {
     path: syn::Path,
-    args: Option<Punctuated<syn::Ident, Token![,]>>
+    pub(crate) args: Option<Punctuated<syn::Ident, Token![,]>>
 }
 
 impl Parse for CallbackFunction {
@@ -35,10 +36,10 @@ impl Parse for CallbackFunction {
 
 /// A single pair of (PATH, CALLBACK_EVENT_ID): [CALLBACK_FUNCTIONS,]
 #[allow(unused)]
-struct CallbackDef {
-    field_name: Option<syn::Expr>,
-    callback_id: syn::Ident,
-    callbacks: Punctuated<CallbackFunction, Token![,]>
+pub(crate) struct CallbackDef {
+    pub(crate) field_name: Option<syn::Expr>,
+    pub(crate) callback_id: syn::Ident,
+    pub(crate) callbacks: Punctuated<CallbackFunction, Token![,]>
 }
 
 impl Parse for CallbackDef {
@@ -76,8 +77,8 @@ impl Parse for CallbackDef {
 }
 
 /// The callback definition in a `nwg_events` attribute
-struct CallbackDefinitions {
-    params: Punctuated<CallbackDef, Token![,]>
+pub(crate) struct CallbackDefinitions {
+    pub(crate) params: Punctuated<CallbackDef, Token![,]>
 }
 
 impl Parse for CallbackDefinitions {
@@ -94,6 +95,10 @@ impl Parse for CallbackDefinitions {
 #[derive(Debug)]
 struct EventCallback {
     member: syn::Expr,
+
+    /// If the member is a `Vec` or an array of controls
+    repeated: bool,
+
     path: syn::Path,
     args: Punctuated<syn::Expr, Token![,]>
 }
@@ -136,10 +141,19 @@ impl ControlEvents {
         }
     }
 
-    pub fn add_partial(&mut self, id: &syn::Ident) {
-        self.partials_callbacks.push(quote! {
-            evt_ui.#id.process_event(_evt, &_evt_data, _handle);
-        })
+    pub fn add_partial(&mut self, id: &syn::Ident, optional: bool) {
+        let callback = match optional {
+            true => quote! {
+                if let Some(partial) = evt_ui.#id.as_ref() {
+                    partial.process_event(_evt, &_evt_data, _handle);
+                }
+            },
+            false => quote! {
+                evt_ui.#id.process_event(_evt, &_evt_data, _handle);
+            }
+        };
+
+        self.partials_callbacks.push(callback);
     }
 
     pub fn parse(&mut self, field: &syn::Field) {
@@ -147,6 +161,7 @@ impl ControlEvents {
         if attrs.len() == 0 { return; }
 
         let member = field.ident.as_ref().expect("Cannot find member name when generating control");
+        let repeated = FieldStorage::parse(&field.ty).repeated();
         let attr = match find_events_attr(&attrs) {
             Some(a) => a,
             None => { return; }
@@ -166,8 +181,9 @@ impl ControlEvents {
             for cb_fn in callback_def.callbacks.iter() {
                 let callback = EventCallback {
                     member: Self::parse_member(&callback_def.field_name, &member),
+                    repeated,
                     path: cb_fn.path.clone(),
-                    args: map_callback_args(&member, &cb_fn.args, &self.callback_args_cache)
+                    args: map_callback_args(&member, repeated, &cb_fn.args, &self.callback_args_cache)
                 };
 
                 evt_callbacks.push(callback);
@@ -249,31 +265,31 @@ impl<'a> ToTokens for EventCallbackCol<'a> {
         let tk = match cb.len() {
             0 => quote!{ {} },
             1 => {
-                let member = &cb[0].member;
+                let member = HandleCheck(&cb[0].member, cb[0].repeated);
                 let path = &cb[0].path;
                 let args = &cb[0].args;
-                quote!{ if &_handle == &#member { #path(#args) } }
+                quote!{ if #member { #path(#args) } }
             }
             _ => {
                 
                 // Group callbacks by members
-                let mut members_callbacks: HashMap<&syn::Expr, Vec<(&syn::Path, &Args)>> = HashMap::new();
+                let mut members_callbacks: HashMap<&syn::Expr, (bool, Vec<PathArg>)> = HashMap::new();
                 for c in cb.iter() {
-                    let mc = members_callbacks.entry(&c.member).or_insert(Vec::new());
-                    mc.push((&c.path, &c.args));
+                    let mc = members_callbacks.entry(&c.member).or_insert((c.repeated, Vec::new()));
+                    mc.1.push((&c.path, &c.args));
                 }
 
-                let members: Vec<&&syn::Expr> = members_callbacks.keys().collect();
-                let values: Vec<PathArgs> = members_callbacks.values().map(|c| PathArgs(c) ).collect();
+                let members: Vec<HandleCheck> = members_callbacks.iter().map(|(m, c)| HandleCheck(m, c.0) ).collect();
+                let values: Vec<PathArgs> = members_callbacks.values().map(|c| PathArgs(&c.1) ).collect();
 
-                let member0 = members[0];
+                let member0 = &members[0];
                 let value0 = &values[0];
                 let members = &members[1..];
                 let values = &values[1..];
 
                 quote!{
-                    if &_handle == &#member0 { #value0 }
-                    #(else if &_handle == &#members { #values })*
+                    if #member0 { #value0 }
+                    #(else if #members { #values })*
                 }
             }
         };
@@ -283,8 +299,27 @@ impl<'a> ToTokens for EventCallbackCol<'a> {
 }
 
 
+/// The condition that matches the event handle with a member. 
+/// For repeated members, the index of the control is bound to `idx`
+struct HandleCheck<'a> (&'a syn::Expr, bool);
+
+impl<'a> ToTokens for HandleCheck<'a> {
+
+    fn to_tokens(&self, tokens: &mut pm2::TokenStream) {
+        let member = self.0;
+        let tk = match self.1 {
+            true => quote! { let Some(idx) = #member.iter().position(|c| &_handle == c) },
+            false => quote! { &_handle == &#member },
+        };
+
+        tk.to_tokens(tokens);
+    }
+}
+
+
 type Args = Punctuated<syn::Expr, Token![,]>;
-struct PathArgs<'a> (&'a [(&'a syn::Path, &'a Args)]);
+type PathArg<'a> = (&'a syn::Path, &'a Args);
+struct PathArgs<'a> (&'a [PathArg<'a>]);
 
 impl<'a> ToTokens for PathArgs<'a> {
 
@@ -330,25 +365,24 @@ fn top_level_window(field: &syn::Field) -> bool {
 }
 
 
-fn map_callback_args(member: &syn::Ident, args: &Option<Punctuated<syn::Ident, Token![,]>>, cache: &HashMap<usize, syn::Expr>) -> Punctuated<syn::Expr, Token![,]> {
+fn map_callback_args(member: &syn::Ident, repeated: bool, args: &Option<Punctuated<syn::Ident, Token![,]>>, cache: &HashMap<usize, syn::Expr>) -> Punctuated<syn::Expr, Token![,]> {
     let mut p = Punctuated::new();
     if args.is_none() {
         p.push(cache[&0].clone());
         return p;
     }
 
-    let values = ["SELF", "CTRL", "HANDLE", "EVT", "EVT_DATA", "RC_SELF"];
+    let values = ["SELF", "CTRL", "HANDLE", "EVT", "EVT_DATA", "RC_SELF", "IDX"];
     for a in args.as_ref().unwrap().iter() {
         let pos = values.iter().position(|v| &a == &v );
         match pos {
             Some(0) | Some(5) => { p.push(cache[&0].clone()); },
-            Some(1) => { 
-                let param = format!("&evt_ui.{}", member);
-                p.push(syn::parse_str(&param).unwrap());
-            },
+            Some(1) if repeated => { p.push(parse_quote!(&evt_ui.#member[idx])); },
+            Some(1) => { p.push(parse_quote!(&evt_ui.#member)); },
             Some(2) => { p.push(cache[&2].clone()); },
             Some(3) => { p.push(cache[&3].clone()); },
             Some(4) => { p.push(cache[&4].clone()); },
+            Some(6) => { p.push(parse_quote!(idx)); },
             Some(_) => { unreachable!(); }
             None => panic!("Unknown callback argument: {}. Should be one of those values: {:?}", a, values)
         }
@@ -1,12 +1,22 @@
 use crate::shared::Parameters;
 
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Debug)]
 pub struct GridLayoutChild {
-    pub col: u32,
-    pub row: u32,
-    pub col_span: u32,
-    pub row_span: u32
+    pub col: syn::Expr,
+    pub row: syn::Expr,
+    pub col_span: syn::Expr,
+    pub row_span: syn::Expr,
+    pub align: Option<syn::Expr>,
+    pub col_policy: Option<syn::Expr>,
+    pub row_policy: Option<syn::Expr>,
+}
+
+#[derive(Clone, Debug)]
+pub struct DockLayoutChild {
+    pub dock: syn::Expr,
+    pub size: Option<syn::Expr>,
+    pub min_size: Option<syn::Expr>,
 }
 
 #[derive(Clone, Debug)]
@@ -20,6 +30,7 @@ pub struct FlexboxLayoutChild {
 pub enum LayoutChild {
     Init { field_name: String, params: Parameters },
     Grid(GridLayoutChild),
+    Dock(DockLayoutChild),
     Flexbox(FlexboxLayoutChild),
 }
 
@@ -44,6 +55,8 @@ impl LayoutChild {
     pub fn parse(&mut self, parent_type: &syn::Ident) {
         if parent_type == "GridLayout" {
             *self = Self::parse_grid_layout_params(self);
+        } else if parent_type == "DockLayout" {
+            *self = Self::parse_dock_layout_params(self);
         } else if parent_type == "FlexboxLayout" {
             *self = Self::parse_flexbox_layout_params(self);
         } else {
@@ -71,7 +84,8 @@ impl LayoutChild {
     }
 
     fn parse_grid_layout_params(child: &mut LayoutChild) -> LayoutChild {
-        let [mut col, mut row, mut col_span, mut row_span] = [0, 0, 1, 1];
+        let [mut col, mut row, mut col_span, mut row_span]: [syn::Expr; 4] = [parse_quote!(0), parse_quote!(0), parse_quote!(1), parse_quote!(1)];
+        let [mut align, mut col_policy, mut row_policy] = [None, None, None];
 
         match child {
             LayoutChild::Init{ params: p, .. } => for p in p.params.iter() {
@@ -81,13 +95,41 @@ impl LayoutChild {
                     "row" => { row = Self::int_value(&p.e) },
                     "col_span" => { col_span = Self::int_value(&p.e) },
                     "row_span" => { row_span = Self::int_value(&p.e) },
+                    "align" => { align = Some(Self::enum_value(&p.e, "GridAlign")) },
+                    "col_policy" => { col_policy = Some(Self::enum_value(&p.e, "GridPolicy")) },
+                    "row_policy" => { row_policy = Some(Self::enum_value(&p.e, "GridPolicy")) },
                     _ => {}
                 }
             },
             _ => panic!("Called parse on a non-Init child layout")
         };
 
-        LayoutChild::Grid( GridLayoutChild { col, col_span, row, row_span } )
+        LayoutChild::Grid( GridLayoutChild { col, col_span, row, row_span, align, col_policy, row_policy } )
+    }
+
+    fn parse_dock_layout_params(child: &mut LayoutChild) -> LayoutChild {
+        let [mut dock, mut size, mut min_size] = [None, None, None];
+
+        match child {
+            LayoutChild::Init{ params: p, field_name } => {
+                for p in p.params.iter() {
+                    let attr_name = p.ident.to_string();
+                    match &attr_name as &str {
+                        "dock" => { dock = Some(Self::enum_value(&p.e, "Dock")) },
+                        "size" => { size = Some(p.e.clone()) },
+                        "min_size" => { min_size = Some(p.e.clone()) },
+                        _ => {}
+                    }
+                }
+
+                if dock.is_none() {
+                    panic!("Dock layout item \"{}\" is missing the `dock` parameter", field_name);
+                }
+            },
+            _ => panic!("Called parse on a non-Init child layout")
+        };
+
+        LayoutChild::Dock( DockLayoutChild { dock: dock.unwrap(), size, min_size } )
     }
 
     fn parse_flexbox_layout_params(child: &mut LayoutChild) -> LayoutChild {
@@ -110,14 +152,37 @@ impl LayoutChild {
         LayoutChild::Flexbox( FlexboxLayoutChild { param_names, param_values } )
     }
 
-    fn int_value(expr: &syn::Expr) -> u32 {
+    /// Prefix the enum name to the short enum values. Ex: `Center` => `GridAlign::Center`, `Fixed(50)` => `GridPolicy::Fixed(50)`
+    fn enum_value(expr: &syn::Expr, enum_name: &str) -> syn::Expr {
+        let enum_ident = syn::Ident::new(enum_name, pm2::Span::call_site());
+        let prefix = |path: &syn::Path| -> Option<syn::Path> {
+            path.get_ident().map(|ident| parse_quote!(#enum_ident::#ident))
+        };
+
         match expr {
-            syn::Expr::Lit(lit) => 
-                match &lit.lit {
-                    syn::Lit::Int(i) => { i.base10_parse().unwrap() },
-                    _ => panic!("Layout item members must be int literal.")
+            syn::Expr::Path(p) => match prefix(&p.path) {
+                Some(path) => parse_quote!(#path),
+                None => expr.clone()
+            },
+            syn::Expr::Call(call) => match &*call.func {
+                syn::Expr::Path(p) => match prefix(&p.path) {
+                    Some(path) => {
+                        let args = &call.args;
+                        parse_quote!(#path(#args))
+                    },
+                    None => expr.clone()
                 },
-            _ => panic!("Layout item members must be int literal.")
+                _ => expr.clone()
+            },
+            _ => expr.clone()
+        }
+    }
+
+    /// Int literals are used as is. Other expressions (ex: `idx + 1` in a repeated field) are casted to `u32`
+    fn int_value(expr: &syn::Expr) -> syn::Expr {
+        match expr {
+            syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Int(_), .. }) => expr.clone(),
+            _ => parse_quote!((#expr) as u32)
         }
     }
 
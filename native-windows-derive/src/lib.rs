@@ -15,6 +15,7 @@ mod controls;
 mod events;
 mod layouts;
 mod shared;
+mod validation;
 
 mod ui;
 use ui::NwgUi;
@@ -58,10 +59,10 @@ fn parse_base_names(d: &DeriveInput) -> BaseNames {
     }
 }
 
-fn parse_ui_data(d: &DeriveInput) -> Option<&syn::DataStruct> {
+fn parse_ui_data(d: &DeriveInput) -> syn::Result<&syn::DataStruct> {
     match &d.data {
-        syn::Data::Struct(ds) => Some(ds),
-        _ => None
+        syn::Data::Struct(ds) => Ok(ds),
+        _ => Err(syn::Error::new_spanned(&d.ident, "NWG derive can only be implemented on structs"))
     }
 }
 
@@ -184,6 +185,7 @@ That's very limiting. For example, if the same callback is used by two different
  - **HANDLE**: Sends the handle of the control. `&ControlHandle`
  - **EVT**: Sends the event that was triggered. `&Event`
  - **EVT_DATA**: Sends the data of the event that was triggered. `&EventData`
+ - **IDX**: Sends the index of the control that triggered the event in a `Vec` or an array field. `usize`
 
 It's also possible to not use any parameters, ex: `TestApp::callback1()`. 
 
@@ -219,6 +221,69 @@ Under the hood, both these attribute work the same way as `nwg_control`. `nwg_la
 
 NWD cannot guess the parent of layout items.
 
+`GridLayout` items also accept `align`, `col_policy` and `row_policy`. The enum name can be omitted:
+`col_policy` sets the policy of the item column, `row_policy` the policy of the item row.
+
+`DockLayout` items require a `dock` parameter (`Top`, `Bottom`, `Left`, `Right` or `Fill`) and accept an optional `size` and `min_size`.
+
+```
+struct Ui {
+    #[nwg_layout(parent: window, row_policies: &[nwg::GridPolicy::Fixed(30)])]
+    grid: nwg::GridLayout,
+
+    #[nwg_control(text: "Name")]
+    #[nwg_layout_item(layout: grid, col: 0, row: 1, col_policy: Auto, align: Right)]
+    label: nwg::Label,
+
+    #[nwg_control]
+    #[nwg_layout_item(layout: grid, col: 1, row: 1, col_policy: Weight(3))]
+    name: nwg::TextInput,
+}
+```
+
+## Repeated and optional fields
+
+Controls can be stored in a `Vec` or in an array. One control is built for each item and the index of the item is available as `idx`
+in the parameters of `nwg_control` and `nwg_layout_item`. A `Vec` field requires a `count` parameter.
+
+```
+struct Ui {
+    names: Vec<String>,
+
+    #[nwg_control(count: data.names.len(), text: &data.names[idx])]
+    #[nwg_layout_item(layout: grid, col: 0, row: idx)]
+    labels: Vec<nwg::Label>,
+
+    #[nwg_control(text: ["Ok", "Cancel"][idx])]
+    #[nwg_events( OnButtonClick: [Ui::clicked(SELF, IDX)] )]
+    buttons: [nwg::Button; 2],
+}
+```
+
+Partials can be stored in an `Option`. The partial is only built if the value is `Some`. The optional `when` parameter
+replaces the value by `Some(Default::default())` or `None` before the partial is built.
+
+```
+struct Ui {
+    advanced_mode: bool,
+
+    #[nwg_partial(parent: window, when: data.advanced_mode)]
+    advanced: Option<AdvancedPanel>,
+}
+```
+
+## Validation
+
+The attributes are checked before any code is generated. The following mistakes are reported as compile errors on the attribute itself:
+
+ - Unknown or duplicated parameters of the nwg controls, resources, layouts and layout items.
+ - A `parent` or `layout` value that does not name a field of the right kind.
+ - A layout without a parent (outside of `NwgPartial`).
+ - Two `GridLayout` items that use the same cell.
+ - Unknown event names and callback arguments in `nwg_events`.
+
+Controls and resources that are not part of native-windows-gui (ex: custom controls) are not checked.
+
 ## Partials
 
 Use the `nwg_partial` attribute to instance a partial from a struct field:
@@ -246,7 +311,14 @@ struct Ui {
 pub fn derive_ui(input: pm::TokenStream) -> pm::TokenStream {
     let base = parse_macro_input!(input as DeriveInput);
     let names = parse_base_names(&base);
-    let ui_data = parse_ui_data(&base).expect("NWG derive can only be implemented on structs");
+    let ui_data = match parse_ui_data(&base) {
+        Ok(data) => data,
+        Err(e) => { return e.to_compile_error().into(); }
+    };
+
+    if let Err(e) = validation::validate(ui_data, false) {
+        return e.to_compile_error().into();
+    }
 
     let module_name = &names.n_module;
     let struct_name = &names.n_struct;
@@ -388,7 +460,15 @@ pub fn derive_partial(input: pm::TokenStream) -> pm::TokenStream {
     let generics = quote! { #lt #generic_params #gt }; // <'a: 'b, T: Trait1, const C>
     let generic_names = quote! { #lt #generic_names #gt }; // <'a, T, C>
 
-    let ui_data = parse_ui_data(&base).expect("NWG derive can only be implemented on structs");
+    let ui_data = match parse_ui_data(&base) {
+        Ok(data) => data,
+        Err(e) => { return e.to_compile_error().into(); }
+    };
+
+    if let Err(e) = validation::validate(ui_data, true) {
+        return e.to_compile_error().into();
+    }
+
     let ui = NwgUi::build(&ui_data, true);
     let controls = ui.controls();
     let resources = ui.resources();
@@ -46,3 +46,67 @@ impl Parse for Parameters {
         Ok(parameters)
     }
 }
+
+
+/// How the value of a field is stored in the ui struct
+pub enum FieldStorage<'a> {
+    /// `T`
+    Single,
+
+    /// `Vec<T>`. The number of items is defined by the `count` parameter
+    Vec(&'a syn::Type),
+
+    /// `[T; N]`
+    Array(&'a syn::Type),
+
+    /// `Option<T>`
+    Option(&'a syn::Type),
+}
+
+impl<'a> FieldStorage<'a> {
+
+    pub fn parse(ty: &'a syn::Type) -> FieldStorage<'a> {
+        fn generic_arg<'b>(seg: &'b syn::PathSegment) -> Option<&'b syn::Type> {
+            match &seg.arguments {
+                syn::PathArguments::AngleBracketed(args) if args.args.len() == 1 => match &args.args[0] {
+                    syn::GenericArgument::Type(ty) => Some(ty),
+                    _ => None
+                },
+                _ => None
+            }
+        }
+
+        match ty {
+            syn::Type::Array(array) => FieldStorage::Array(&array.elem),
+            syn::Type::Path(p) => match p.path.segments.last() {
+                Some(seg) if seg.ident == "Vec" => generic_arg(seg).map(FieldStorage::Vec).unwrap_or(FieldStorage::Single),
+                Some(seg) if seg.ident == "Option" => generic_arg(seg).map(FieldStorage::Option).unwrap_or(FieldStorage::Single),
+                _ => FieldStorage::Single
+            },
+            _ => FieldStorage::Single
+        }
+    }
+
+    /// Returns true if the field holds many controls
+    pub fn repeated(&self) -> bool {
+        matches!(self, FieldStorage::Vec(_) | FieldStorage::Array(_))
+    }
+
+    /// Returns the type of the items in the field
+    pub fn item_type(&self, ty: &'a syn::Type) -> &'a syn::Type {
+        match self {
+            FieldStorage::Single => ty,
+            FieldStorage::Vec(t) | FieldStorage::Array(t) | FieldStorage::Option(t) => t,
+        }
+    }
+
+}
+
+/// Returns the last identifier of a type path. Ex: `nwg::Button` => `Button`.
+/// The item type is used for `Vec<T>`, `[T; N]` and `Option<T>`
+pub fn type_ident(ty: &syn::Type) -> Option<&syn::Ident> {
+    match FieldStorage::parse(ty).item_type(ty) {
+        syn::Type::Path(p) => p.path.segments.last().map(|seg| &seg.ident),
+        _ => None
+    }
+}
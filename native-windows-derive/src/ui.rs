@@ -1,7 +1,7 @@
 use quote::{ToTokens};
-use crate::layouts::{LayoutChild, FlexboxLayoutChild, GridLayoutChild, layout_parameters};
+use crate::layouts::{LayoutChild, FlexboxLayoutChild, GridLayoutChild, DockLayoutChild, layout_parameters};
 use crate::events::ControlEvents;
-use crate::shared::Parameters;
+use crate::shared::{Parameters, FieldStorage};
 
 const TOP_LEVEL: &'static [&'static str] = &[
     "Window", "MessageWindow", "ExternCanvas"
@@ -18,6 +18,12 @@ struct NwgControl<'a> {
 
     ty: syn::Ident,
 
+    /// `Vec` and array fields build one control per item
+    repeated: bool,
+
+    /// The number of items of a `Vec` field
+    count: Option<syn::Expr>,
+
     layout: Option<LayoutChild>,
     layout_index: usize,
 
@@ -65,12 +71,9 @@ impl<'a> NwgControl<'a> {
         }
         
         // Use field type
-        match &field.ty {
-            syn::Type::Path(p) => match p.path.segments.last() {
-                Some(seg) => seg.ident.clone(),
-                None => panic!("Impossible to parse type for field {:?}. Try specifying it in the nwg_control attribute.", field.ident)
-            },
-            _ => panic!("Impossible to parse type for field {:?}. Try specifying it in the nwg_control attribute.", field.ident)
+        match crate::shared::type_ident(&field.ty) {
+            Some(ty) => ty.clone(),
+            None => panic!("Impossible to parse type for field {:?}. Try specifying it in the nwg_control attribute.", field.ident)
         }
     }
 
@@ -93,7 +96,7 @@ impl<'a> NwgControl<'a> {
             syn::Expr::Path(p) => {
                 let id = &p.path.segments.last().unwrap().ident;
                 self.parent_id = Some(id.to_string());
-                syn::parse_str(&format!("&data.{}", id)).unwrap()
+                syn::parse2(quote! { &data.#id }).unwrap()
             },
             _ => { panic!("Bad expression type for parent of field {}", self.id); }
         };
@@ -199,7 +202,7 @@ impl<'a> NwgLayout<'a> {
         let parent_expr: syn::Expr = match &self.values[i] {
             syn::Expr::Path(p) => {
                 let id = &p.path.segments.last().unwrap().ident;
-                syn::parse_str(&format!("&ui.{}", id)).unwrap()
+                syn::parse2(quote! { &ui.#id }).unwrap()
             },
             _ => { panic!("Bad expression type for parent of field {}", self.id); }
         };
@@ -213,6 +216,10 @@ struct NwgPartial<'a> {
     id: &'a syn::Ident,
     ty: &'a syn::Ident,
     parent: Option<syn::Ident>,
+
+    /// `Option` fields are only built if the value is `Some` or if `when` is true
+    optional: bool,
+    when: Option<syn::Expr>,
 }
 
 
@@ -226,16 +233,13 @@ impl<'a> NwgPartial<'a> {
     }
 
     fn parse_type(field: &syn::Field) -> &syn::Ident {
-        match &field.ty {
-            syn::Type::Path(p) => match p.path.segments.last() {
-                Some(seg) => &seg.ident,
-                None => panic!("Impossible to parse type for field {:?}. Try specifying it in the nwg_partial attribute.", field.ident)
-            },
-            _ => panic!("Impossible to parse type for field {:?}. Try specifying it in the nwg_partial attribute.", field.ident)
+        match crate::shared::type_ident(&field.ty) {
+            Some(ty) => ty,
+            None => panic!("Impossible to parse type for field {:?}. Try specifying it in the nwg_partial attribute.", field.ident)
         }
     }
 
-    fn parse_parent(field: &syn::Field) -> Option<syn::Ident> {
+    fn parse_params(field: &syn::Field) -> (Option<syn::Ident>, Option<syn::Expr>) {
         let nwg_partial = |attr: &&syn::Attribute| {
             attr.path.get_ident()
               .map(|id| id == "nwg_partial" )
@@ -253,13 +257,17 @@ impl<'a> NwgPartial<'a> {
         };
 
         let parent_value = params.params.iter().find(|p| p.ident == "parent").map(|p| &p.e);
-        match parent_value {
+        let parent = match parent_value {
             Some(v) => match v {
                 syn::Expr::Path(p) => p.path.segments.last().map(|seg| seg.ident.clone()),
                 _ => None,
             },
             None => None
-        }
+        };
+
+        let when = params.params.iter().find(|p| p.ident == "when").map(|p| p.e.clone());
+
+        (parent, when)
     }
 }
 
@@ -281,10 +289,27 @@ impl<'a> ToTokens for NwgUiControls<'a> {
                 let member = item.id;
                 let names = &item.names;
                 let values = &item.values;
-                let control_tk = quote! {
-                    #ty::builder()
-                        #(.#names(#values))*
-                        .build(&mut data.#member)?;
+                let resize = item.count.as_ref().map(|count| quote! {
+                    {
+                        let count: usize = #count;
+                        data.#member.resize_with(count, Default::default);
+                    }
+                });
+
+                let control_tk = match item.repeated {
+                    true => quote! {
+                        #resize
+                        for idx in 0..data.#member.len() {
+                            #ty::builder()
+                                #(.#names(#values))*
+                                .build(&mut data.#member[idx])?;
+                        }
+                    },
+                    false => quote! {
+                        #ty::builder()
+                            #(.#names(#values))*
+                            .build(&mut data.#member)?;
+                    }
                 };
 
                 control_tk.to_tokens(tokens);
@@ -371,21 +396,50 @@ impl<'a> ToTokens for NwgUiLayouts<'a> {
             fn to_tokens(&self, tokens: &mut pm2::TokenStream) {
                 let c = &self.0;
                 let id = &c.id;
+                let control = match c.repeated {
+                    true => quote! { &ui.#id[idx] },
+                    false => quote! { &ui.#id },
+                };
 
                 let item_tk = match &c.layout {
-                    Some(LayoutChild::Grid( GridLayoutChild {col, row, col_span, row_span} )) => 
+                    Some(LayoutChild::Grid( GridLayoutChild {col, row, col_span, row_span, align, col_policy, row_policy} )) => {
+                        let align = align.as_ref().map(|a| quote!{ .align(#a) });
+                        let col_policy = col_policy.as_ref().map(|p| quote!{ .column_policy(#col, #p) });
+                        let row_policy = row_policy.as_ref().map(|p| quote!{ .row_policy(#row, #p) });
                         quote! { 
-                            child_item(GridLayoutItem::new(&ui.#id, #col, #row, #col_span, #row_span))
-                        },
+                            child_item(GridLayoutItem::new(#control, #col, #row, #col_span, #row_span) #align)
+                            #col_policy
+                            #row_policy
+                        }
+                    },
+                    Some(LayoutChild::Dock( DockLayoutChild { dock, size, min_size } )) => {
+                        let size = size.as_ref().map(|s| quote!{ .size(#s) });
+                        let min_size = min_size.as_ref().map(|s| quote!{ .min_size(#s) });
+                        quote! {
+                            child_item(DockLayoutItem::new(#control, #dock) #size #min_size)
+                        }
+                    },
                     Some(LayoutChild::Flexbox( FlexboxLayoutChild { param_names, param_values } )) => 
                         quote! { 
-                            child(&ui.#id)
+                            child(#control)
                             #(.#param_names(#param_values))*
                         },
                     Some(LayoutChild::Init{ field_name, .. }) => panic!("Unmatched layout item for field \"{}\", Did you forget the `layout` parameter?", field_name),
                     None => panic!("Unfiltered layout item")
                 };
 
+                // Repeated controls add one item per control
+                let item_tk = match c.repeated {
+                    true => quote! {
+                        for idx in 0..ui.#id.len() {
+                            layout_builder = layout_builder.#item_tk;
+                        }
+                    },
+                    false => quote! {
+                        layout_builder = layout_builder.#item_tk;
+                    }
+                };
+
                 item_tk.to_tokens(tokens);
             }
         }
@@ -403,11 +457,18 @@ impl<'a> ToTokens for NwgUiLayouts<'a> {
                 let values = &self.layout.values;
                 let children = &self.children;
 
-                let layout_tk = quote! {
-                    #ty::builder()
-                        #(.#names(#values))*
-                        #(.#children)*
-                        .build(&ui.#id)?;
+                let layout_tk = match children.is_empty() {
+                    true => quote! {
+                        #ty::builder()
+                            #(.#names(#values))*
+                            .build(&ui.#id)?;
+                    },
+                    false => quote! {
+                        let mut layout_builder = #ty::builder()
+                            #(.#names(#values))*;
+                        #(#children)*
+                        layout_builder.build(&ui.#id)?;
+                    }
                 };
                 layout_tk.to_tokens(tokens);
             }
@@ -451,13 +512,36 @@ impl<'a> ToTokens for NwgUiPartials<'a> {
                 let id = &i.id;
                 let parent = &i.parent;
 
-                let partial_tk = if parent.is_none() {
-                    quote! {
+                let partial_tk = match (i.optional, parent.is_none()) {
+                    (false, true) => quote! {
                         #ty::build_partial::<&Window>(&mut data.#id, None)?;
-                    }
-                } else {
-                    quote! {
+                    },
+                    (false, false) => quote! {
                         #ty::build_partial(&mut data.#id, Some(&data.#parent))?;
+                    },
+                    (true, _) => {
+                        let when = i.when.as_ref().map(|when| quote! {
+                            {
+                                let enabled: bool = #when;
+                                if !enabled {
+                                    data.#id = None;
+                                } else if data.#id.is_none() {
+                                    data.#id = Some(Default::default());
+                                }
+                            }
+                        });
+
+                        let parent = match parent {
+                            Some(parent) => quote! { Some(&data.#parent) },
+                            None => quote! { None::<&Window> }
+                        };
+
+                        quote! {
+                            #when
+                            if let Some(partial) = data.#id.as_mut() {
+                                #ty::build_partial(partial, #parent)?;
+                            }
+                        }
                     }
                 };
                 
@@ -510,12 +594,24 @@ impl<'a> NwgUi<'a> {
             if NwgControl::valid(field) {
                 let id = field.ident.as_ref().unwrap();
                 let ty = NwgControl::parse_type(field);
-                let (names, values) = crate::controls::parameters(field, "nwg_control");
+                let (mut names, mut values) = crate::controls::parameters(field, "nwg_control");
+                let repeated = FieldStorage::parse(&field.ty).repeated();
+
+                // `count` is not a builder parameter
+                let count = match names.iter().position(|n| n == "count") {
+                    Some(i) if repeated => {
+                        names.remove(i);
+                        Some(values.remove(i))
+                    },
+                    _ => None
+                };
 
                 let f = NwgControl {
                     id,
                     parent_id: None,
                     ty,
+                    repeated,
+                    count,
                     layout: LayoutChild::prepare(field),
                     layout_index: 0,
                     names,
@@ -557,13 +653,16 @@ impl<'a> NwgUi<'a> {
             }
 
             else if NwgPartial::valid(field) {
+                let (parent, when) = NwgPartial::parse_params(field);
                 let partial = NwgPartial {
                     id: field.ident.as_ref().unwrap(),
                     ty: NwgPartial::parse_type(field),
-                    parent: NwgPartial::parse_parent(field),
+                    parent,
+                    optional: matches!(FieldStorage::parse(&field.ty), FieldStorage::Option(_)),
+                    when,
                 };
 
-                events.add_partial(&partial.id);
+                events.add_partial(&partial.id, partial.optional);
                 events.parse(field);
 
                 partials.push(partial);
@@ -611,7 +710,7 @@ impl<'a> NwgUi<'a> {
                 // Rewind the controls set the parent to the nearest control that supports children
                 let parent = controls[0..i]
                     .iter().rev()
-                    .find(|i| AUTO_PARENT.iter().any(|top| i.ty == top) );
+                    .find(|i| !i.repeated && AUTO_PARENT.iter().any(|top| i.ty == top) );
             
                 if let Some(parent) = parent {
                     let parent_id = Some(parent.id.to_string());
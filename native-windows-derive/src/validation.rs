@@ -0,0 +1,715 @@
+/*!
+    Compile time checks of the nwg attributes. This pass runs before the code generation so that
+    mistakes are reported on the attribute tokens instead of inside the generated code.
+*/
+use crate::shared::{Parameters, Param, FieldStorage, type_ident};
+use crate::events::CallbackDefinitions;
+use syn::spanned::Spanned;
+use std::cell::RefCell;
+
+
+/// Builder parameters of the nwg controls. Controls not in this list (ex: custom controls) are not checked.
+const CONTROLS: &[(&str, &[&str])] = &[
+    ("AcceleratorTable", &["parent", "accelerators", "show_in_menu"]),
+    ("Animation", &["size", "position", "enabled", "flags", "ex_flags", "parent"]),
+    ("AnimationTimer", &["parent", "interval", "max_tick", "lifetime", "active"]),
+    ("Button", &["flags", "ex_flags", "text", "size", "position", "enabled", "font", "bitmap", "icon", "focus", "parent"]),
+    ("CheckBox", &["flags", "ex_flags", "text", "size", "position", "enabled", "focus", "check_state", "background_color", "font", "parent"]),
+    ("ComboBox", &["flags", "ex_flags", "size", "position", "font", "parent", "collection", "selected_index", "enabled", "focus", "v_align"]),
+    ("ComboBoxEx", &["size", "position", "enabled", "focus", "flags", "ex_flags", "style_ex", "font", "parent"]),
+    ("DatePicker", &["flags", "ex_flags", "size", "position", "font", "parent", "date", "format", "range", "focus"]),
+    ("ExternCanvas", &["flags", "ex_flags", "title", "size", "position", "icon", "parent"]),
+    ("Frame", &["flags", "ex_flags", "size", "position", "enabled", "parent"]),
+    ("HotKey", &["size", "position", "enabled", "focus", "flags", "ex_flags", "font", "parent"]),
+    ("ImageFrame", &["flags", "ex_flags", "size", "position", "bitmap", "icon", "parent", "background_color"]),
+    ("IpAddress", &["size", "position", "enabled", "focus", "flags", "ex_flags", "font", "parent"]),
+    ("Label", &["flags", "ex_flags", "text", "size", "position", "font", "background_color", "h_align", "v_align", "parent"]),
+    ("ListBox", &["flags", "ex_flags", "size", "position", "font", "parent", "collection", "selected_index", "multi_selection", "enabled", "focus"]),
+    ("ListView", &["parent", "flags", "ex_flags", "ex_window_flags", "size", "position", "double_buffer", "background_color", "text_color", "item_count", "list_style", "focus", "data_source", "auto_sort"]),
+    ("Menu", &["text", "disabled", "popup", "parent"]),
+    ("MenuItem", &["text", "disabled", "check", "parent"]),
+    ("MenuSeparator", &["parent"]),
+    ("MessageWindow", &[]),
+    ("MonthCalendar", &["size", "position", "flags", "ex_flags", "font", "parent", "date", "min_date", "max_date", "max_selection_count", "focus"]),
+    ("DropTarget", &["parent", "default_effect"]),
+    ("Notice", &["parent"]),
+    ("UiReceiver", &["parent", "mode"]),
+    ("NumberSelect", &["flags", "size", "position", "enabled", "font", "value_int", "step_int", "max_int", "min_int", "value_float", "step_float", "max_float", "min_float", "decimals", "parent"]),
+    ("Pager", &["size", "position", "enabled", "flags", "ex_flags", "parent"]),
+    ("Plotters", &["ex_flags", "size", "position", "parent"]),
+    ("ProgressBar", &["flags", "ex_flags", "size", "position", "state", "step", "pos", "range", "marquee", "marquee_update", "parent"]),
+    ("RadioButton", &["flags", "ex_flags", "text", "size", "position", "focus", "check_state", "background_color", "font", "parent"]),
+    ("Rebar", &["size", "position", "enabled", "flags", "ex_flags", "font", "parent", "bands"]),
+    ("RichLabel", &["text", "size", "position", "font", "flags", "ex_flags", "h_align", "background_color", "line_height", "parent"]),
+    ("RichTextBox", &["flags", "ex_flags", "text", "size", "position", "limit", "readonly", "font", "focus", "parent"]),
+    ("ScrollBar", &["flags", "ex_flags", "size", "position", "enabled", "focus", "range", "pos", "parent"]),
+    ("Splitter", &["flags", "size", "position", "enabled", "orientation", "first", "second", "ratio", "bar_size", "min_sizes", "collapsed", "nudge_step", "parent"]),
+    ("StatusBar", &["text", "font", "parts", "parent"]),
+    ("SysLink", &["text", "size", "position", "enabled", "flags", "ex_flags", "font", "parent"]),
+    ("Tab", &["text", "parent", "image_index"]),
+    ("TabsContainer", &["flags", "ex_flags", "size", "position", "parent", "font", "image_list"]),
+    ("TextBox", &["flags", "ex_flags", "text", "size", "position", "limit", "readonly", "focus", "font", "parent"]),
+    ("TextInput", &["flags", "ex_flags", "text", "placeholder_text", "size", "position", "limit", "password", "align", "readonly", "font", "background_color", "focus", "parent"]),
+    ("Timer", &["stopped", "interval", "parent"]),
+    ("Toolbar", &["size", "position", "enabled", "flags", "ex_flags", "font", "parent", "buttons", "button_size"]),
+    ("Tooltip", &["register", "register_callback", "decoration", "default_decoration"]),
+    ("TrackBar", &["flags", "ex_flags", "size", "position", "focus", "range", "selected_range", "pos", "parent", "background_color"]),
+    ("TrayNotification", &["parent", "icon", "realtime", "callback", "visible", "balloon_icon", "flags", "tip", "info", "info_title"]),
+    ("TreeView", &["flags", "ex_flags", "size", "position", "enabled", "focus", "font", "parent", "checkboxes", "tri_state", "image_list"]),
+    ("Window", &["flags", "ex_flags", "title", "size", "position", "icon", "accept_files", "topmost", "center", "maximized", "minimized", "parent"]),
+];
+
+/// Builder parameters of the nwg resources
+const RESOURCES: &[(&str, &[&str])] = &[
+    ("Bitmap", &["source_file", "source_bin", "source_system", "source_embed", "source_embed_id", "source_embed_str", "size", "strict"]),
+    ("ColorDialog", &["saved_color"]),
+    ("Cursor", &["source_file", "source_system", "source_embed", "source_embed_id", "source_embed_str", "size", "strict"]),
+    ("EmbedResource", &["module"]),
+    ("FileDialog", &["title", "default_folder", "filters", "action", "multiselect"]),
+    ("Font", &["size", "size_absolute", "weight", "family"]),
+    ("FontDialog", &[]),
+    ("Icon", &["source_file", "source_bin", "source_system", "source_embed", "source_embed_id", "source_embed_str", "size", "strict"]),
+    ("ImageDecoder", &[]),
+    ("ImageList", &["size", "initial", "grow"]),
+    ("TaskDialog", &["title", "main_instruction", "content", "icon", "buttons", "command_links", "default_button", "default_radio",
+        "verification", "verification_checked", "expanded_information", "expanded", "footer", "footer_icon", "progress_bar", "marquee",
+        "hyperlinks", "cancelable", "on_button_click", "on_hyperlink_click", "on_timer"]),
+];
+
+/// Builder parameters of the nwg layouts
+const LAYOUTS: &[(&str, &[&str])] = &[
+    ("GridLayout", &["parent", "margin", "spacing", "min_size", "max_size", "max_column", "max_row", "column_policy", "row_policy", "column_policies", "row_policies"]),
+    ("DockLayout", &["parent", "margin", "spacing", "min_size"]),
+    ("FlexboxLayout", &["parent", "auto_size", "auto_spacing", "direction", "flex_direction", "flex_wrap", "overflow", "align_items", "align_content", "justify_content", "padding", "border", "min_size", "max_size", "aspect_ratio", "style"]),
+    ("DynLayout", &["parent"]),
+];
+
+/// Parameters of `nwg_layout_item` for each layout type
+const LAYOUT_ITEMS: &[(&str, &[&str])] = &[
+    ("GridLayout", &["layout", "col", "row", "col_span", "row_span", "align", "col_policy", "row_policy"]),
+    ("DockLayout", &["layout", "dock", "size", "min_size"]),
+    ("FlexboxLayout", &["layout", "size", "position", "margin", "min_size", "max_size", "flex_grow", "flex_shrink", "flex_basis", "align_self"]),
+];
+
+/// The values of the `nwg::Event` enum, and the `MousePressEvent` shortcuts. Keep in sync with `native-windows-gui/src/events.rs`
+const EVENTS: &[&str] = &[
+    "OnMousePress", "MousePressLeftUp", "MousePressLeftDown", "MousePressRightUp", "MousePressRightDown",
+    "OnMouseMove", "OnMouseWheel", "OnContextMenu", "OnInit", "OnPaint", "OnChar", "OnKeyPress", "OnKeyRelease",
+    "OnSysKeyPress", "OnSysKeyRelease", "OnKeyEnter", "OnKeyEsc", "OnMinMaxInfo", "OnResize", "OnResizeBegin", "OnResizeEnd",
+    "OnWindowMaximize", "OnWindowMinimize", "OnMove", "OnVerticalScroll", "OnHorizontalScroll", "OnFileDrop", "OnClipboardUpdate",
+    "OnDragEnter", "OnDragOver", "OnDragLeave", "OnDragDrop",
+    "OnButtonClick", "OnButtonDoubleClick", "OnLabelClick", "OnLabelDoubleClick", "OnImageFrameClick", "OnImageFrameDoubleClick",
+    "OnTextInput", "OnComboBoxClosed", "OnComboBoxDropdown", "OnComboxBoxSelection", "OnDatePickerDropdown", "OnDatePickerClosed",
+    "OnDatePickerChanged", "OnMonthCalendarSelect", "OnMonthCalendarSelectionChanged", "OnMonthCalendarViewChange",
+    "OnListBoxDoubleClick", "OnListBoxSelect", "TabsContainerChanged", "TabsContainerChanging", "TrackBarUpdated",
+    "OnMenuOpen", "OnMenuEnter", "OnMenuExit", "OnMenuHover", "OnMenuItemSelected", "OnAccelerator", "OnTooltipText",
+    "OnTreeViewClick", "OnTreeViewDoubleClick", "OnTreeViewRightClick", "OnTreeViewBeginItemEdit", "OnTreeViewEndItemEdit", "OnTreeViewBeginDrag",
+    "OnTreeFocusLost", "OnTreeFocus", "OnTreeItemDelete", "OnTreeItemExpanded", "OnTreeItemExpanding", "OnTreeItemChanged", "OnTreeItemSelectionChanged",
+    "OnListViewClear", "OnListViewItemRemoved", "OnListViewItemInsert", "OnListViewItemActivated", "OnListViewClick",
+    "OnListViewRightClick", "OnListViewDoubleClick", "OnListViewColumnClick", "OnListViewItemChanged", "OnListViewFocus",
+    "OnListViewFocusLost", "OnListViewBeginEdit", "OnListViewEndEdit", "OnListViewBeginDrag", "OnTrayNotificationShow", "OnTrayNotificationHide",
+    "OnTrayNotificationTimeout", "OnTrayNotificationUserClose", "OnTimerTick", "OnTimerStop", "OnNotice", "OnWindowClose", "OnToolbarItemClick",
+    "OnToolbarDropDown", "OnSysLinkClick", "OnAnimationStart", "OnAnimationStop", "OnIpAddressFieldChanged",
+    "OnHotKeyChanged", "OnPagerScroll", "OnStatusBarClick", "OnStatusBarDoubleClick",
+];
+
+/// Controls that do not get an automatic parent. Same as `ui::TOP_LEVEL`
+const TOP_LEVEL: &[&str] = &["Window", "MessageWindow", "ExternCanvas"];
+
+/// The arguments that can be passed to the event callbacks
+const CALLBACK_ARGS: &[&str] = &["SELF", "RC_SELF", "CTRL", "HANDLE", "EVT", "EVT_DATA", "IDX"];
+
+
+fn lookup(table: &'static [(&'static str, &'static [&'static str])], ty: &str) -> Option<&'static [&'static str]> {
+    table.iter().find(|(name, _)| *name == ty).map(|(_, params)| *params)
+}
+
+fn find_attr<'a>(field: &'a syn::Field, name: &str) -> Option<&'a syn::Attribute> {
+    field.attrs.iter().find(|attr| attr.path.get_ident().map(|id| id == name).unwrap_or(false))
+}
+
+/// Return the identifier of a single value such as `window` or `Self::window`
+fn value_ident(e: &syn::Expr) -> Option<&syn::Ident> {
+    match e {
+        syn::Expr::Path(p) => p.path.segments.last().map(|seg| &seg.ident),
+        _ => None
+    }
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut previous = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let current = row[j + 1];
+            row[j + 1] = match ca == *cb {
+                true => previous,
+                false => 1 + previous.min(row[j]).min(row[j + 1]),
+            };
+            previous = current;
+        }
+    }
+
+    row[b.len()]
+}
+
+/// Return a `. Did you mean `x`?` hint if a known value is close to `value`
+fn suggestion(value: &str, known: &[&str]) -> String {
+    known.iter()
+        .map(|k| (edit_distance(value, k), k))
+        .filter(|(d, _)| *d <= 2)
+        .min_by_key(|(d, _)| *d)
+        .map(|(_, k)| format!(". Did you mean `{}`?", k))
+        .unwrap_or_default()
+}
+
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum FieldKind {
+    Control,
+    Resource,
+    Layout,
+    Partial,
+    Plain,
+}
+
+impl FieldKind {
+
+    fn name(self) -> &'static str {
+        match self {
+            FieldKind::Control => "a control",
+            FieldKind::Resource => "a resource",
+            FieldKind::Layout => "a layout",
+            FieldKind::Partial => "a partial",
+            FieldKind::Plain => "a field",
+        }
+    }
+
+}
+
+/// A struct field and its parsed nwg attributes
+struct FieldInfo<'a> {
+    ident: &'a syn::Ident,
+    kind: FieldKind,
+
+    /// The control, resource or layout type
+    ty: Option<String>,
+
+    field_ty: &'a syn::Type,
+    storage: FieldStorage<'a>,
+
+    /// The `nwg_control`, `nwg_resource`, `nwg_layout` or `nwg_partial` attribute
+    attr: Option<(&'a syn::Attribute, Parameters)>,
+
+    item: Option<(&'a syn::Attribute, Parameters)>,
+    events: Option<(&'a syn::Attribute, CallbackDefinitions)>,
+}
+
+impl<'a> FieldInfo<'a> {
+
+    fn parse(field: &'a syn::Field) -> syn::Result<FieldInfo<'a>> {
+        let ident = field.ident.as_ref().unwrap();
+
+        let kinds = [
+            ("nwg_control", FieldKind::Control),
+            ("nwg_resource", FieldKind::Resource),
+            ("nwg_layout", FieldKind::Layout),
+            ("nwg_partial", FieldKind::Partial),
+        ];
+
+        let mut kind = FieldKind::Plain;
+        let mut attr = None;
+        for &(name, k) in kinds.iter() {
+            if let Some(a) = find_attr(field, name) {
+                let params: Parameters = syn::parse2(a.tokens.clone())?;
+                kind = k;
+                attr = Some((a, params));
+                break;
+            }
+        }
+
+        // The `ty` parameter overrides the field type of the controls and resources
+        let ty = attr.as_ref()
+            .and_then(|(_, p)| p.params.iter().find(|p| p.ident == "ty"))
+            .and_then(|p| value_ident(&p.e))
+            .or_else(|| type_ident(&field.ty))
+            .map(|ty| ty.to_string());
+
+        let item = match find_attr(field, "nwg_layout_item") {
+            Some(a) => Some((a, syn::parse2(a.tokens.clone())?)),
+            None => None
+        };
+
+        let events = match find_attr(field, "nwg_events") {
+            Some(a) => Some((a, syn::parse2(a.tokens.clone())?)),
+            None => None
+        };
+
+        let field_ty = &field.ty;
+        let storage = FieldStorage::parse(field_ty);
+
+        Ok(FieldInfo { ident, kind, ty, field_ty, storage, attr, item, events })
+    }
+
+}
+
+
+struct Validator<'a> {
+    fields: Vec<FieldInfo<'a>>,
+    partial: bool,
+    errors: RefCell<Vec<syn::Error>>,
+}
+
+impl<'a> Validator<'a> {
+
+    fn error<T: quote::ToTokens, M: std::fmt::Display>(&self, tokens: T, message: M) {
+        self.errors.borrow_mut().push(syn::Error::new_spanned(tokens, message));
+    }
+
+    fn field(&self, ident: &syn::Ident) -> Option<&FieldInfo<'a>> {
+        self.fields.iter().find(|f| f.ident == ident)
+    }
+
+    /// Check that the parameters names exist and are not repeated
+    fn check_params(&self, params: &Parameters, known: &[&str], owner: &str) {
+        for (i, p) in params.params.iter().enumerate() {
+            let name = p.ident.to_string();
+            if !known.contains(&name.as_str()) {
+                let hint = suggestion(&name, known);
+                self.error(&p.ident, format!("unknown parameter `{}` for {}{}", name, owner, hint));
+            } else if params.params.iter().take(i).any(|p2| p2.ident == p.ident) {
+                self.error(&p.ident, format!("parameter `{}` is defined more than once", name));
+            }
+        }
+    }
+
+    /// Check that a `parent` parameter references a control
+    fn check_parent(&self, owner: &syn::Ident, parent: &Param) {
+        let id = match value_ident(&parent.e) {
+            Some(id) => id,
+            None => {
+                self.error(&parent.e, "`parent` must be the name of a field of the struct");
+                return;
+            }
+        };
+
+        if id == owner {
+            self.error(id, format!("`{}` cannot be its own parent", owner));
+            return;
+        }
+
+        match self.field(id).map(|f| f.kind) {
+            Some(_) if !matches!(self.field(id).unwrap().storage, FieldStorage::Single) => {
+                self.error(id, format!("`{}` holds many controls or an optional value. It cannot be used as a parent", id))
+            },
+            Some(FieldKind::Control) | Some(FieldKind::Plain) => {},
+            Some(kind) => self.error(id, format!("`{}` is {}. The parent must be a control", id, kind.name())),
+            None => {
+                let names: Vec<String> = self.fields.iter().map(|f| f.ident.to_string()).collect();
+                let names: Vec<&str> = names.iter().map(|n| n.as_str()).collect();
+                let hint = suggestion(&id.to_string(), &names);
+                self.error(id, format!("no field named `{}` in this struct{}", id, hint));
+            }
+        }
+    }
+
+    fn check_flags(&self, flags: &Param) {
+        let valid = match &flags.e {
+            syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(s), .. }) => s.value()
+                .split('|')
+                .all(|f| syn::parse_str::<syn::Ident>(f.trim()).is_ok()),
+            _ => false
+        };
+
+        if !valid {
+            self.error(&flags.e, "`flags` must be a string of flag names separated by `|`. Ex: \"VISIBLE|TAB_STOP\"");
+        }
+    }
+
+    fn check_attr(&self, index: usize) {
+        let field = &self.fields[index];
+        let (attr, params) = match field.attr.as_ref() {
+            Some(a) => a,
+            None => return
+        };
+
+        let ident = field.ident;
+        let kind = field.kind;
+        let ty = field.ty.clone().unwrap_or_default();
+        let parent = params.params.iter().find(|p| p.ident == "parent");
+        let count = params.params.iter().find(|p| p.ident == "count");
+
+        match (kind, &field.storage) {
+            (FieldKind::Control, FieldStorage::Option(_)) => {
+                self.error(field.field_ty, "`Option` fields are only supported by `nwg_partial`");
+            },
+            (FieldKind::Control, FieldStorage::Vec(_)) if count.is_none() => {
+                self.error(attr, format!("`{}` is a `Vec`. It requires a `count` parameter", ident));
+            },
+            (FieldKind::Control, FieldStorage::Array(_)) if count.is_some() => {
+                self.error(&count.unwrap().ident, "arrays are built with their length. Remove the `count` parameter");
+            },
+            (FieldKind::Control, FieldStorage::Vec(_)) | (FieldKind::Control, FieldStorage::Array(_)) if TOP_LEVEL.contains(&ty.as_str()) => {
+                self.error(field.field_ty, format!("top level controls (`{}`) cannot be repeated", ty));
+            },
+            (FieldKind::Resource, FieldStorage::Single) | (FieldKind::Layout, FieldStorage::Single) => {},
+            (FieldKind::Resource, _) | (FieldKind::Layout, _) => {
+                self.error(field.field_ty, format!("{} cannot be stored in a `Vec`, an array or an `Option`", kind.name()));
+            },
+            (FieldKind::Partial, FieldStorage::Vec(_)) | (FieldKind::Partial, FieldStorage::Array(_)) => {
+                self.error(field.field_ty, "partials cannot be repeated. Use an `Option` or a single value");
+            },
+            _ => {}
+        }
+
+        match kind {
+            FieldKind::Control => {
+                if let Some(known) = lookup(CONTROLS, &ty) {
+                    let mut known = known.to_vec();
+                    known.extend_from_slice(&["parent", "ty"]);
+                    if field.storage.repeated() {
+                        known.push("count");
+                    }
+
+                    self.check_params(params, &known, &format!("`{}`", ty));
+                }
+
+                if let Some(flags) = params.params.iter().find(|p| p.ident == "flags") {
+                    self.check_flags(flags);
+                }
+
+                // The parent of the top level controls is passed to the builder as is
+                if let (Some(parent), false) = (parent, TOP_LEVEL.contains(&ty.as_str())) {
+                    self.check_parent(ident, parent);
+                }
+            },
+            FieldKind::Resource => {
+                if let Some(known) = lookup(RESOURCES, &ty) {
+                    let mut known = known.to_vec();
+                    known.push("ty");
+                    self.check_params(params, &known, &format!("`{}`", ty));
+                }
+            },
+            FieldKind::Layout => {
+                if let Some(known) = lookup(LAYOUTS, &ty) {
+                    self.check_params(params, known, &format!("`{}`", ty));
+                }
+
+                match parent {
+                    Some(parent) => self.check_parent(ident, parent),
+                    None if !self.partial => self.error(attr, format!("layout `{}` requires a `parent` parameter", ident)),
+                    None => {}
+                }
+            },
+            FieldKind::Partial => {
+                match field.storage {
+                    FieldStorage::Option(_) => self.check_params(params, &["parent", "when"], "`nwg_partial`"),
+                    _ => self.check_params(params, &["parent"], "`nwg_partial`"),
+                }
+
+                if let Some(parent) = parent {
+                    self.check_parent(ident, parent);
+                }
+            },
+            FieldKind::Plain => {}
+        }
+    }
+
+    fn check_layout_item(&self, index: usize) {
+        let field = &self.fields[index];
+        let (attr, params) = match field.item.as_ref() {
+            Some(i) => i,
+            None => return
+        };
+
+        if field.kind != FieldKind::Control {
+            self.error(attr, "`nwg_layout_item` can only be used on a field with a `nwg_control` attribute");
+            return;
+        }
+
+        let layout = match params.params.iter().find(|p| p.ident == "layout") {
+            Some(layout) => layout,
+            None => {
+                self.error(attr, "`nwg_layout_item` requires a `layout` parameter");
+                return;
+            }
+        };
+
+        let layout_ident = match value_ident(&layout.e) {
+            Some(id) => id,
+            None => {
+                self.error(&layout.e, "`layout` must be the name of a layout field of the struct");
+                return;
+            }
+        };
+
+        let layout_ty = match self.field(layout_ident) {
+            Some(f) if f.kind == FieldKind::Layout => f.ty.clone().unwrap_or_default(),
+            Some(f) => {
+                let kind = f.kind.name();
+                self.error(layout_ident, format!("`{}` is {}, not a layout", layout_ident, kind));
+                return;
+            },
+            None => {
+                self.error(layout_ident, format!("no layout named `{}` in this struct", layout_ident));
+                return;
+            }
+        };
+
+        if let Some(known) = lookup(LAYOUT_ITEMS, &layout_ty) {
+            self.check_params(params, known, &format!("`{}` items", layout_ty));
+        } else if lookup(LAYOUTS, &layout_ty).is_some() {
+            self.error(attr, format!("`{}` does not support `nwg_layout_item`", layout_ty));
+            return;
+        }
+
+        match layout_ty.as_str() {
+            "GridLayout" if !field.storage.repeated() => {
+                for p in params.params.iter().filter(|p| ["col", "row", "col_span", "row_span"].iter().any(|n| p.ident == n)) {
+                    let valid = match &p.e {
+                        syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Int(i), .. }) => i.base10_parse::<u32>().is_ok(),
+                        _ => false
+                    };
+
+                    if !valid {
+                        self.error(&p.e, format!("`{}` must be an unsigned integer literal. Expressions are only allowed in `Vec` and array fields", p.ident));
+                    }
+                }
+            },
+            "DockLayout" if !params.params.iter().any(|p| p.ident == "dock") => {
+                self.error(attr, "`DockLayout` items require a `dock` parameter");
+            },
+            _ => {}
+        }
+    }
+
+    /// Two items of the same grid layout cannot use the same cell
+    fn check_grid_cells(&self) {
+        struct Cell<'b> {
+            field: &'b syn::Ident,
+            layout: &'b syn::Ident,
+            rect: [u32; 4],
+        }
+
+        let int_param = |params: &Parameters, name: &str, default: u32| -> u32 {
+            params.params.iter()
+                .find(|p| p.ident == name)
+                .and_then(|p| match &p.e {
+                    syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Int(i), .. }) => i.base10_parse().ok(),
+                    _ => None
+                })
+                .unwrap_or(default)
+        };
+
+        let mut cells: Vec<Cell> = Vec::new();
+        let mut errors = Vec::new();
+        for field in self.fields.iter() {
+            let (attr, params) = match field.item.as_ref() {
+                Some(i) if !field.storage.repeated() => i,
+                _ => continue
+            };
+
+            let layout = match params.params.iter().find(|p| p.ident == "layout").and_then(|p| value_ident(&p.e)) {
+                Some(layout) => layout,
+                None => continue
+            };
+
+            match self.field(layout) {
+                Some(f) if f.ty.as_ref().map(|ty| ty == "GridLayout").unwrap_or(false) => {},
+                _ => continue
+            }
+
+            let col = int_param(params, "col", 0);
+            let row = int_param(params, "row", 0);
+            let col_span = int_param(params, "col_span", 1).max(1);
+            let row_span = int_param(params, "row_span", 1).max(1);
+            let rect = [col, row, col + col_span, row + row_span];
+
+            let overlap = cells.iter()
+                .filter(|c| c.layout == layout)
+                .find(|c| rect[0] < c.rect[2] && c.rect[0] < rect[2] && rect[1] < c.rect[3] && c.rect[1] < rect[3]);
+
+            if let Some(other) = overlap {
+                let message = format!(
+                    "`{}` overlaps `{}` in the cells of `{}`. `{}` starts at col {}, row {}",
+                    field.ident, other.field, layout, other.field, other.rect[0], other.rect[1]
+                );
+                errors.push(syn::Error::new_spanned(attr, message));
+            }
+
+            cells.push(Cell { field: field.ident, layout, rect });
+        }
+
+        self.errors.borrow_mut().extend(errors);
+    }
+
+    fn check_events(&self, index: usize) {
+        let field = &self.fields[index];
+        let (attr, callbacks) = match field.events.as_ref() {
+            Some(e) => e,
+            None => return
+        };
+
+        if field.kind == FieldKind::Resource || field.kind == FieldKind::Layout {
+            let message = format!("`nwg_events` cannot be used on {}", field.kind.name());
+            self.error(attr, message);
+            return;
+        }
+
+        let mut errors = Vec::new();
+        for def in callbacks.params.iter() {
+            let event = def.callback_id.to_string();
+            if !EVENTS.contains(&event.as_str()) {
+                let hint = suggestion(&event, EVENTS);
+                errors.push(syn::Error::new(def.callback_id.span(), format!("unknown event `{}`{}", event, hint)));
+            }
+
+            // `(member, Event)` reads a member of the field. It cannot be done on many controls or on an optional partial
+            if let (Some(member), false) = (def.field_name.as_ref(), matches!(field.storage, FieldStorage::Single)) {
+                errors.push(syn::Error::new_spanned(member, format!("events on the members of `{}` are not supported. Bind them in the partial instead", field.ident)));
+            }
+
+            for cb in def.callbacks.iter() {
+                for arg in cb.args.iter().flat_map(|args| args.iter()) {
+                    let arg_name = arg.to_string();
+                    if !CALLBACK_ARGS.contains(&arg_name.as_str()) {
+                        let message = format!("unknown callback argument `{}`. Expected one of: {}", arg_name, CALLBACK_ARGS.join(", "));
+                        errors.push(syn::Error::new(arg.span(), message));
+                    } else if arg == "IDX" && !field.storage.repeated() {
+                        errors.push(syn::Error::new(arg.span(), "`IDX` is only available on `Vec` and array fields"));
+                    }
+                }
+            }
+        }
+
+        self.errors.borrow_mut().extend(errors);
+    }
+
+}
+
+/**
+    Validate the nwg attributes of a struct. All the errors found are returned combined.
+    `partial` is true for the `NwgPartial` derive.
+*/
+pub fn validate(data: &syn::DataStruct, partial: bool) -> syn::Result<()> {
+    let named_fields = match &data.fields {
+        syn::Fields::Named(n) => &n.named,
+        _ => return Err(syn::Error::new(data.struct_token.span(), "Ui structure must have named fields"))
+    };
+
+    let mut fields = Vec::with_capacity(named_fields.len());
+    for field in named_fields.iter() {
+        fields.push(FieldInfo::parse(field)?);
+    }
+
+    let validator = Validator { fields, partial, errors: RefCell::new(Vec::new()) };
+    for i in 0..validator.fields.len() {
+        validator.check_attr(i);
+        validator.check_layout_item(i);
+        validator.check_events(i);
+    }
+
+    validator.check_grid_cells();
+
+    let mut errors = validator.errors.into_inner().into_iter();
+    match errors.next() {
+        Some(mut error) => {
+            errors.for_each(|e| error.combine(e));
+            Err(error)
+        },
+        None => Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::{BTreeMap, HashMap};
+    use std::{fs, path::Path};
+
+    /// Builder methods and their number of arguments, by builder name
+    type BuilderMethods = HashMap<String, BTreeMap<String, usize>>;
+
+    fn collect_builder_methods(dir: &Path, methods: &mut BuilderMethods) {
+        for entry in fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                collect_builder_methods(&path, methods);
+            } else if path.extension().map(|ext| ext == "rs").unwrap_or(false) {
+                let source = fs::read_to_string(&path).unwrap();
+                let file = syn::parse_file(&source).unwrap_or_else(|e| panic!("{}: {}", path.display(), e));
+                collect_items(&file.items, methods);
+            }
+        }
+    }
+
+    fn collect_items(items: &[syn::Item], methods: &mut BuilderMethods) {
+        for item in items {
+            match item {
+                syn::Item::Impl(i) if i.trait_.is_none() => {
+                    let name = match &*i.self_ty {
+                        syn::Type::Path(p) => p.path.segments.last().unwrap().ident.to_string(),
+                        _ => continue
+                    };
+
+                    if !name.ends_with("Builder") {
+                        continue;
+                    }
+
+                    let builder = methods.entry(name).or_default();
+                    for impl_item in i.items.iter() {
+                        if let syn::ImplItem::Method(m) = impl_item {
+                            if matches!(m.vis, syn::Visibility::Public(_)) && m.sig.receiver().is_some() {
+                                builder.insert(m.sig.ident.to_string(), m.sig.inputs.len() - 1);
+                            }
+                        }
+                    }
+                },
+                syn::Item::Mod(m) => if let Some((_, items)) = &m.content {
+                    collect_items(items, methods);
+                },
+                _ => {}
+            }
+        }
+    }
+
+    /// Every parameter of the tables must be a method of the builder, and every single argument
+    /// method of a builder must be in its table. The layout children are set by `nwg_layout_item`.
+    #[test]
+    fn parameters_match_the_builders() {
+        let sources = Path::new(env!("CARGO_MANIFEST_DIR")).join("../native-windows-gui/src");
+        let mut methods = HashMap::new();
+        collect_builder_methods(&sources, &mut methods);
+
+        let mut errors = Vec::new();
+        for (name, params) in CONTROLS.iter().chain(RESOURCES.iter()).chain(LAYOUTS.iter()) {
+            let builder = match methods.get(&format!("{}Builder", name)) {
+                Some(builder) => builder,
+                None => {
+                    errors.push(format!("{}: no `{}Builder`", name, name));
+                    continue;
+                }
+            };
+
+            for param in params.iter() {
+                if !builder.contains_key(*param) {
+                    errors.push(format!("{}: `{}` is not a method of the builder", name, param));
+                }
+            }
+
+            let skipped = |method: &str| method.starts_with("build") || method.starts_with("child");
+            for (method, &args) in builder.iter() {
+                if args == 1 && !skipped(method) && !params.contains(&method.as_str()) {
+                    errors.push(format!("{}: `{}` is missing from the table", name, method));
+                }
+            }
+        }
+
+        assert!(errors.is_empty(), "\n{}", errors.join("\n"));
+    }
+
+}
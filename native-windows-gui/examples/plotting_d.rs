@@ -55,7 +55,7 @@ pub struct PlottingExample {
     animation_timer: nwg::AnimationTimer,
 
     #[nwg_control(parent: window)]
-    #[nwg_layout_item(layout: layout, col: 0, col: 3)]
+    #[nwg_layout_item(layout: layout, col: 3, row: 0)]
     options_frame: nwg::Frame,
 
     #[nwg_layout(
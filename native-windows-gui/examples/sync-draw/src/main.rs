@@ -85,7 +85,7 @@ pub struct SyncDraw {
 
     // Canvas
     #[nwg_control(ty: ExternCanvas, parent: Some(&data.window) )]
-    #[nwg_layout_item(layout: layout, cell: 0)]
+    #[nwg_layout_item(layout: layout, col: 0, row: 0)]
     #[nwg_events(OnMouseMove: [SyncDraw::paint], MousePressLeftUp: [SyncDraw::update_draw(SELF, EVT)], MousePressLeftDown: [SyncDraw::update_draw(SELF, EVT)])]
     canvas: OpenGlCanvas,
 
@@ -162,7 +162,7 @@ pub struct CanvasTest {
     timer: nwg::AnimationTimer,
 
     #[nwg_control(parent: window)]
-    #[nwg_layout_item(layout: layout, col: 0, col: 3)]
+    #[nwg_layout_item(layout: layout, col: 3, row: 0)]
     options_frame: nwg::Frame,
 
     #[nwg_layout(
@@ -0,0 +1,315 @@
+/*!
+    Two-way bindings between controls and observable values.
+
+    The observable values (`Property`) and the converters (`Converter`) are pure rust and can be tested on any platform.
+    The `bind_*` functions of this module keep a control in sync with a property: the control is updated when the property
+    changes, and the property is updated when the user modifies the control.
+
+    ```rust
+    use native_windows_gui as nwg;
+
+    struct Model {
+        name: nwg::Property<String>,
+        age: nwg::Property<u32>,
+        subscribe: nwg::Property<bool>,
+    }
+
+    fn bind(model: &Model, name: &nwg::TextInput, age: &nwg::TextInput, subscribe: &nwg::CheckBox) -> Vec<nwg::Binding> {
+        let age_converter = nwg::Converter::parse().validator(|age| *age <= 150);
+
+        vec![
+            nwg::bind_text(name, &model.name),
+            nwg::bind_text_with(age, &model.age, age_converter),
+            nwg::bind_checked(subscribe, &model.subscribe),
+        ]
+    }
+    ```
+*/
+mod property;
+
+pub use property::{Property, Subscription, Converter};
+
+use crate::win32::sys::shared::windef::HWND;
+use crate::win32::window_helper as wh;
+use crate::controls::{ControlHandle, TextInput, CheckBox, CheckBoxState};
+use crate::{Event, EventHandler, bind_event_handler, unbind_event_handler};
+use std::cell::Cell;
+use std::rc::Rc;
+use std::ops::Deref;
+
+#[cfg(feature = "trackbar")]
+use crate::controls::TrackBar;
+
+#[cfg(feature = "listbox")]
+use crate::controls::ListBox;
+
+#[cfg(feature = "listbox")]
+use std::fmt::Display;
+
+
+/**
+    A link between a control and a property. The link is removed when the binding is dropped.
+
+    If the control was already destroyed when the binding is dropped, only the property observer is removed.
+*/
+pub struct Binding {
+    handler: Option<EventHandler>,
+    handles: [HWND; 2],
+    _subscription: Subscription,
+}
+
+impl Drop for Binding {
+    fn drop(&mut self) {
+        if let Some(handler) = self.handler.take() {
+            if self.handles.iter().all(|&h| wh::window_valid(h)) {
+                unbind_event_handler(&handler);
+            }
+        }
+    }
+}
+
+
+/// Bind the text of a `TextInput` to a string property. The property is updated on `OnTextInput`.
+pub fn bind_text(input: &TextInput, property: &Property<String>) -> Binding {
+    bind_text_with(input, property, Converter::identity())
+}
+
+/**
+    Bind the text of a `TextInput` to a property using a converter. The property is updated on `OnTextInput`.
+    If the text cannot be converted or is rejected by a validator, the property keeps its value and the text is left as is.
+*/
+pub fn bind_text_with<T>(input: &TextInput, property: &Property<T>, converter: Converter<T, String>) -> Binding
+    where T: Clone + PartialEq + 'static
+{
+    let control = Rc::new(Alias::new(input));
+    let control2 = control.clone();
+    bind(
+        &input.handle,
+        Some(Event::OnTextInput),
+        property,
+        converter,
+        move || control.text(),
+        move |text| control2.set_text(text),
+    )
+}
+
+/// Bind the state of a `CheckBox` to a boolean property. The property is updated on `OnButtonClick`.
+/// An indeterminate checkbox is considered unchecked.
+pub fn bind_checked(check: &CheckBox, property: &Property<bool>) -> Binding {
+    let converter = Converter::new(
+        |checked: &bool| match *checked {
+            true => CheckBoxState::Checked,
+            false => CheckBoxState::Unchecked
+        },
+        |state: &CheckBoxState| Some(*state == CheckBoxState::Checked)
+    );
+
+    bind_checked_with(check, property, converter)
+}
+
+/// Bind the state of a `CheckBox` to a property using a converter. The property is updated on `OnButtonClick`.
+pub fn bind_checked_with<T>(check: &CheckBox, property: &Property<T>, converter: Converter<T, CheckBoxState>) -> Binding
+    where T: Clone + PartialEq + 'static
+{
+    let control = Rc::new(Alias::new(check));
+    let control2 = control.clone();
+    bind(
+        &check.handle,
+        Some(Event::OnButtonClick),
+        property,
+        converter,
+        move || control.check_state(),
+        move |state| control2.set_check_state(*state),
+    )
+}
+
+/// Bind the position of a `TrackBar` to a property. The property is updated on `TrackBarUpdated`.
+#[cfg(feature = "trackbar")]
+pub fn bind_value(track: &TrackBar, property: &Property<usize>) -> Binding {
+    bind_value_with(track, property, Converter::identity())
+}
+
+/// Bind the position of a `TrackBar` to a property using a converter. The property is updated on `TrackBarUpdated`.
+#[cfg(feature = "trackbar")]
+pub fn bind_value_with<T>(track: &TrackBar, property: &Property<T>, converter: Converter<T, usize>) -> Binding
+    where T: Clone + PartialEq + 'static
+{
+    let control = Rc::new(Alias::new(track));
+    let control2 = control.clone();
+    bind(
+        &track.handle,
+        Some(Event::TrackBarUpdated),
+        property,
+        converter,
+        move || control.pos(),
+        move |pos| control2.set_pos(*pos),
+    )
+}
+
+/**
+    Bind the collection of a `ListBox` to a property. This binding only goes one way: the list box collection is replaced
+    every time the property changes. Replacing the collection clears the selection.
+*/
+#[cfg(feature = "listbox")]
+pub fn bind_items<D>(list: &ListBox<D>, property: &Property<Vec<D>>) -> Binding
+    where D: Display + Default + Clone + PartialEq + 'static
+{
+    let control = Alias::new(list);
+    bind(
+        &list.handle,
+        None,
+        property,
+        Converter::identity(),
+        Vec::new,
+        move |items| { control.set_collection(items.clone()); },
+    )
+}
+
+/// Bind the selected index of a single selection `ListBox` to a property. The property is updated on `OnListBoxSelect`.
+#[cfg(feature = "listbox")]
+pub fn bind_selection<D>(list: &ListBox<D>, property: &Property<Option<usize>>) -> Binding
+    where D: Display + Default + 'static
+{
+    let control = Rc::new(Alias::new(list));
+    let control2 = control.clone();
+    bind(
+        &list.handle,
+        Some(Event::OnListBoxSelect),
+        property,
+        Converter::identity(),
+        move || control.selection(),
+        move |index| control2.set_selection(*index),
+    )
+}
+
+
+/**
+    Shared implementation of the bindings. The control is initialized with the property value.
+    `read` and `write` access the control value. If `event` is `None`, the control does not update the property.
+*/
+fn bind<T, V, R, W>(handle: &ControlHandle, event: Option<Event>, property: &Property<T>, converter: Converter<T, V>, read: R, write: W) -> Binding
+    where T: Clone + PartialEq + 'static,
+          V: 'static,
+          R: Fn() -> V + 'static,
+          W: Fn(&V) + 'static,
+{
+    let hwnd = handle.hwnd().expect("Cannot bind a control that is not a window");
+    let parent_hwnd = wh::get_window_parent(hwnd);
+
+    let converter = Rc::new(converter);
+
+    // Set while the binding updates the control or the property to prevent feedback loops
+    let updating = Rc::new(Cell::new(false));
+
+    // Model to control
+    let update_control = {
+        let converter = converter.clone();
+        let updating = updating.clone();
+        move |value: &T| {
+            // The property can outlive the control
+            if updating.get() || !wh::window_valid(hwnd) {
+                return;
+            }
+
+            updating.set(true);
+            write(&converter.to_control(value));
+            updating.set(false);
+        }
+    };
+
+    update_control(&property.get());
+    let subscription = property.subscribe(update_control);
+
+    // Control to model
+    let handler = event.map(|event| {
+        let control = *handle;
+        let property = property.clone();
+        bind_event_handler(handle, &ControlHandle::Hwnd(parent_hwnd), move |evt, _evt_data, evt_handle| {
+            if evt != event || evt_handle != control || updating.get() {
+                return;
+            }
+
+            if let Some(value) = converter.from_control(&read()) {
+                updating.set(true);
+                property.set(value);
+                updating.set(false);
+            }
+        })
+    });
+
+    Binding {
+        handler,
+        handles: [hwnd, parent_hwnd],
+        _subscription: subscription,
+    }
+}
+
+
+/// Controls that can be aliased by a binding
+trait Aliased {
+    /// Return a second instance of the control using the same handle
+    fn alias(&self) -> Self;
+
+    /// Clear the handle so that the control is not destroyed when the alias is dropped
+    fn release(&mut self);
+}
+
+/// A copy of a control kept by the binding callbacks. Dropping the alias does not destroy the control.
+struct Alias<C: Aliased>(C);
+
+impl<C: Aliased> Alias<C> {
+    fn new(control: &C) -> Alias<C> {
+        Alias(control.alias())
+    }
+}
+
+impl<C: Aliased> Deref for Alias<C> {
+    type Target = C;
+    fn deref(&self) -> &C { &self.0 }
+}
+
+impl<C: Aliased> Drop for Alias<C> {
+    fn drop(&mut self) {
+        self.0.release();
+    }
+}
+
+impl Aliased for TextInput {
+    fn alias(&self) -> Self {
+        let mut alias = TextInput::default();
+        alias.handle = self.handle;
+        alias
+    }
+
+    fn release(&mut self) { self.handle = ControlHandle::NoHandle; }
+}
+
+impl Aliased for CheckBox {
+    fn alias(&self) -> Self {
+        let mut alias = CheckBox::default();
+        alias.handle = self.handle;
+        alias
+    }
+
+    fn release(&mut self) { self.handle = ControlHandle::NoHandle; }
+}
+
+#[cfg(feature = "trackbar")]
+impl Aliased for TrackBar {
+    fn alias(&self) -> Self {
+        let mut alias = TrackBar::default();
+        alias.handle = self.handle;
+        alias
+    }
+
+    fn release(&mut self) { self.handle = ControlHandle::NoHandle; }
+}
+
+#[cfg(feature = "listbox")]
+impl<D: Display + Default> Aliased for ListBox<D> {
+    fn alias(&self) -> Self {
+        ListBox::alias(self)
+    }
+
+    fn release(&mut self) { self.handle = ControlHandle::NoHandle; }
+}
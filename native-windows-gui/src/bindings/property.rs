@@ -0,0 +1,351 @@
+/*!
+    Observable values and converters. This module does not depend on the windowing system.
+*/
+use std::cell::{Cell, RefCell};
+use std::rc::{Rc, Weak};
+use std::fmt;
+use std::str::FromStr;
+
+
+type Observer<T> = Rc<dyn Fn(&T)>;
+type FromControl<T, C> = Box<dyn Fn(&C) -> Option<T>>;
+type Validator<T> = Box<dyn Fn(&T) -> bool>;
+
+struct PropertyInner<T> {
+    value: RefCell<T>,
+    observers: RefCell<Vec<(usize, Observer<T>)>>,
+    next_id: Cell<usize>,
+}
+
+/// Removes an observer from a property without knowing the property type
+trait Observable {
+    fn unsubscribe(&self, id: usize);
+}
+
+impl<T> Observable for PropertyInner<T> {
+    fn unsubscribe(&self, id: usize) {
+        self.observers.borrow_mut().retain(|(i, _)| *i != id);
+    }
+}
+
+/**
+    An observable value. Cloning a property returns a new reference to the same value.
+
+    Observers registered with `subscribe` are called every time the value changes. Observers
+    receive a copy of the new value, so they are free to read or set the property again.
+
+    ```rust
+    use native_windows_gui as nwg;
+
+    let name = nwg::Property::new(String::from("Hello"));
+    let _sub = name.subscribe(|value| println!("Name changed to {}", value));
+
+    name.set("World".into());
+    assert_eq!(name.get(), "World");
+    ```
+*/
+pub struct Property<T> {
+    inner: Rc<PropertyInner<T>>
+}
+
+impl<T: Clone + 'static> Property<T> {
+
+    /// Create a new property with an initial value
+    pub fn new(value: T) -> Property<T> {
+        Property {
+            inner: Rc::new(PropertyInner {
+                value: RefCell::new(value),
+                observers: RefCell::new(Vec::new()),
+                next_id: Cell::new(0),
+            })
+        }
+    }
+
+    /// Return a copy of the value
+    pub fn get(&self) -> T {
+        self.inner.value.borrow().clone()
+    }
+
+    /// Execute `f` with a reference to the value. The property must not be modified in `f`.
+    pub fn with<R, F: FnOnce(&T) -> R>(&self, f: F) -> R {
+        f(&self.inner.value.borrow())
+    }
+
+    /// Set the value and notify the observers. Nothing happens if the new value is equal to the current one.
+    /// Return `true` if the value was changed.
+    pub fn set(&self, value: T) -> bool where T: PartialEq {
+        {
+            let mut current = self.inner.value.borrow_mut();
+            if *current == value {
+                return false;
+            }
+
+            *current = value;
+        }
+
+        self.notify();
+        true
+    }
+
+    /// Modify the value in place and notify the observers. The observers are always notified.
+    pub fn update<F: FnOnce(&mut T)>(&self, f: F) {
+        f(&mut self.inner.value.borrow_mut());
+        self.notify();
+    }
+
+    /**
+        Call `f` every time the value changes. The observer is removed when the returned subscription is dropped.
+        `f` is not called with the current value.
+    */
+    pub fn subscribe<F: Fn(&T) + 'static>(&self, f: F) -> Subscription {
+        let id = self.inner.next_id.get();
+        self.inner.next_id.set(id + 1);
+        self.inner.observers.borrow_mut().push((id, Rc::new(f)));
+
+        let target: Rc<dyn Observable> = self.inner.clone();
+        Subscription { target: Rc::downgrade(&target), id }
+    }
+
+    /// Return the number of observers of the property
+    pub fn observers_len(&self) -> usize {
+        self.inner.observers.borrow().len()
+    }
+
+    fn notify(&self) {
+        // Both lists are copied so that the observers can set the property or (un)subscribe.
+        let value = self.get();
+        let observers: Vec<Observer<T>> = self.inner.observers.borrow().iter().map(|(_, o)| o.clone()).collect();
+        for observer in observers {
+            observer(&value);
+        }
+    }
+
+}
+
+impl<T> Clone for Property<T> {
+    fn clone(&self) -> Self {
+        Property { inner: self.inner.clone() }
+    }
+}
+
+impl<T: Clone + Default + 'static> Default for Property<T> {
+    fn default() -> Self {
+        Property::new(T::default())
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Property<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Property").field(&self.inner.value.borrow()).finish()
+    }
+}
+
+
+/**
+    An observer registered on a `Property`. The observer is removed when the subscription is dropped.
+*/
+pub struct Subscription {
+    target: Weak<dyn Observable>,
+    id: usize,
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        if let Some(target) = self.target.upgrade() {
+            target.unsubscribe(self.id);
+        }
+    }
+}
+
+impl fmt::Debug for Subscription {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Subscription").field("id", &self.id).finish()
+    }
+}
+
+
+/**
+    Converts a model value of type `T` to the value displayed by a control (`C`) and back.
+
+    `from_control` returns `None` if the control value cannot be converted. Validators added with `validator`
+    are applied to the converted value; if any of them fails, the value is rejected and the model is not updated.
+
+    ```rust
+    use native_windows_gui as nwg;
+
+    let age = nwg::Converter::<u32, String>::parse()
+        .validator(|age| *age <= 150);
+
+    assert_eq!(age.to_control(&32), "32");
+    assert_eq!(age.from_control(&"40".to_string()), Some(40));
+    assert_eq!(age.from_control(&"200".to_string()), None);
+    assert_eq!(age.from_control(&"abc".to_string()), None);
+    ```
+*/
+pub struct Converter<T, C> {
+    to_control: Box<dyn Fn(&T) -> C>,
+    from_control: FromControl<T, C>,
+    validators: Vec<Validator<T>>,
+}
+
+impl<T, C> Converter<T, C> {
+
+    /// Create a converter from two conversion functions
+    pub fn new<F1, F2>(to_control: F1, from_control: F2) -> Converter<T, C>
+        where F1: Fn(&T) -> C + 'static,
+              F2: Fn(&C) -> Option<T> + 'static
+    {
+        Converter {
+            to_control: Box::new(to_control),
+            from_control: Box::new(from_control),
+            validators: Vec::new(),
+        }
+    }
+
+    /// Add a validator. Values coming from the control are only accepted if every validator returns `true`.
+    pub fn validator<F: Fn(&T) -> bool + 'static>(mut self, f: F) -> Converter<T, C> {
+        self.validators.push(Box::new(f));
+        self
+    }
+
+    /// Convert a model value into a control value
+    pub fn to_control(&self, value: &T) -> C {
+        (self.to_control)(value)
+    }
+
+    /// Convert a control value into a model value. Return `None` if the conversion or the validation failed.
+    pub fn from_control(&self, value: &C) -> Option<T> {
+        let value = (self.from_control)(value)?;
+        match self.validators.iter().all(|v| v(&value)) {
+            true => Some(value),
+            false => None
+        }
+    }
+
+}
+
+impl<T: Clone + 'static> Converter<T, T> {
+
+    /// A converter that does not change the value
+    pub fn identity() -> Converter<T, T> {
+        Converter::new(|v: &T| v.clone(), |v: &T| Some(v.clone()))
+    }
+
+}
+
+impl<T: ToString + FromStr + 'static> Converter<T, String> {
+
+    /// A converter that uses `ToString` and `FromStr`. Leading and trailing whitespaces are ignored when parsing.
+    pub fn parse() -> Converter<T, String> {
+        Converter::new(|v: &T| v.to_string(), |v: &String| v.trim().parse().ok())
+    }
+
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recorder<T: Clone + 'static>(property: &Property<T>) -> (Rc<RefCell<Vec<T>>>, Subscription) {
+        let values = Rc::new(RefCell::new(Vec::new()));
+        let values2 = values.clone();
+        let sub = property.subscribe(move |v: &T| values2.borrow_mut().push(v.clone()));
+        (values, sub)
+    }
+
+    #[test]
+    fn set_notifies_on_change() {
+        let p = Property::new(1);
+        let (values, _sub) = recorder(&p);
+
+        assert!(p.set(2));
+        assert!(!p.set(2));
+        assert!(p.set(3));
+
+        assert_eq!(p.get(), 3);
+        assert_eq!(*values.borrow(), vec![2, 3]);
+    }
+
+    #[test]
+    fn update_always_notifies() {
+        let p = Property::new(vec![1]);
+        let (values, _sub) = recorder(&p);
+
+        p.update(|v| v.push(2));
+        p.update(|_| {});
+
+        assert_eq!(*values.borrow(), vec![vec![1, 2], vec![1, 2]]);
+        assert_eq!(p.with(|v| v.len()), 2);
+    }
+
+    #[test]
+    fn clones_share_the_value() {
+        let p = Property::new(String::from("a"));
+        let p2 = p.clone();
+        let (values, _sub) = recorder(&p);
+
+        p2.set("b".into());
+
+        assert_eq!(p.get(), "b");
+        assert_eq!(*values.borrow(), vec!["b".to_string()]);
+    }
+
+    #[test]
+    fn dropping_the_subscription_removes_the_observer() {
+        let p = Property::new(0);
+        let (values, sub) = recorder(&p);
+        let (values2, _sub2) = recorder(&p);
+        assert_eq!(p.observers_len(), 2);
+
+        p.set(1);
+        drop(sub);
+        p.set(2);
+
+        assert_eq!(p.observers_len(), 1);
+        assert_eq!(*values.borrow(), vec![1]);
+        assert_eq!(*values2.borrow(), vec![1, 2]);
+
+        // The subscription can outlive the property
+        let (_, sub3) = recorder(&p);
+        drop(p);
+        drop(sub3);
+    }
+
+    #[test]
+    fn observers_can_set_the_property() {
+        let p = Property::new(0);
+        let p2 = p.clone();
+        let _clamp = p.subscribe(move |v| { if *v > 10 { p2.set(10); } });
+        let (values, _sub) = recorder(&p);
+
+        p.set(50);
+
+        assert_eq!(p.get(), 10);
+        assert_eq!(*values.borrow(), vec![10, 50]);
+    }
+
+    #[test]
+    fn converters() {
+        let id = Converter::<String, String>::identity();
+        assert_eq!(id.to_control(&"a".to_string()), "a");
+        assert_eq!(id.from_control(&"b".to_string()), Some("b".to_string()));
+
+        let num = Converter::<i32, String>::parse();
+        assert_eq!(num.to_control(&-4), "-4");
+        assert_eq!(num.from_control(&" 12 ".to_string()), Some(12));
+        assert_eq!(num.from_control(&"12a".to_string()), None);
+
+        let even = Converter::<i32, String>::parse()
+            .validator(|v| *v >= 0)
+            .validator(|v| v % 2 == 0);
+        assert_eq!(even.from_control(&"4".to_string()), Some(4));
+        assert_eq!(even.from_control(&"5".to_string()), None);
+        assert_eq!(even.from_control(&"-2".to_string()), None);
+
+        let flag = Converter::new(|v: &u8| *v != 0, |v: &bool| Some(*v as u8));
+        assert!(flag.to_control(&3));
+        assert_eq!(flag.from_control(&false), Some(0));
+    }
+
+}
@@ -0,0 +1,330 @@
+/*!
+    Encoding of the clipboard formats that have a binary layout: "HTML Format", `CF_HDROP` file lists and `CF_DIB` images.
+    This module does not depend on the windowing system.
+*/
+use std::convert::TryInto;
+
+
+const START_FRAGMENT: &str = "<!--StartFragment-->";
+const END_FRAGMENT: &str = "<!--EndFragment-->";
+
+/// Size of the `DROPFILES` header: the offset of the file list, a `POINT` and two `BOOL`
+const DROPFILES_SIZE: usize = 20;
+
+/// Size of a `BITMAPFILEHEADER`
+const BMP_FILE_HEADER_SIZE: usize = 14;
+
+/// The `BI_BITFIELDS` compression of a DIB. The color masks follow a `BITMAPINFOHEADER`.
+const BI_BITFIELDS: u32 = 3;
+
+
+/// The content of the "HTML Format" clipboard format
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ClipboardHtml {
+    /// The html document that contains the fragment
+    pub html: String,
+
+    /// The html that was copied
+    pub fragment: String,
+
+    /// The address of the document the fragment was copied from
+    pub source_url: Option<String>,
+}
+
+/**
+    Wraps an html fragment in a document and prefixes the header of the "HTML Format" clipboard format.
+    The offsets in the header are in bytes from the start of the data.
+*/
+pub(crate) fn encode_html(fragment: &str, source_url: Option<&str>) -> String {
+    const HEADER: &str = "Version:0.9\r\nStartHTML:0000000000\r\nEndHTML:0000000000\r\nStartFragment:0000000000\r\nEndFragment:0000000000\r\n";
+
+    let source = source_url.map(|url| format!("SourceURL:{}\r\n", url)).unwrap_or_default();
+    let prefix = format!("<html>\r\n<body>\r\n{}", START_FRAGMENT);
+    let suffix = format!("{}\r\n</body>\r\n</html>", END_FRAGMENT);
+
+    let start_html = HEADER.len() + source.len();
+    let start_fragment = start_html + prefix.len();
+    let end_fragment = start_fragment + fragment.len();
+    let end_html = end_fragment + suffix.len();
+
+    format!(
+        "Version:0.9\r\nStartHTML:{:010}\r\nEndHTML:{:010}\r\nStartFragment:{:010}\r\nEndFragment:{:010}\r\n{}{}{}{}",
+        start_html, end_html, start_fragment, end_fragment,
+        source, prefix, fragment, suffix
+    )
+}
+
+/**
+    Reads the data of the "HTML Format" clipboard format. Returns `None` if the header is missing or if its offsets are invalid.
+
+    Some applications write `-1` for the optional `StartHTML` and `EndHTML` offsets. The document then starts after the header.
+*/
+pub(crate) fn decode_html(data: &[u8]) -> Option<ClipboardHtml> {
+    let data = match data.iter().position(|&b| b == 0) {
+        Some(end) => &data[..end],
+        None => data
+    };
+
+    let mut start_html = None;
+    let mut end_html = None;
+    let mut start_fragment = None;
+    let mut end_fragment = None;
+    let mut source_url = None;
+    let mut header_end = 0;
+
+    for line in data.split(|&b| b == b'\n') {
+        let text = match std::str::from_utf8(line) {
+            Ok(text) => text.trim_end_matches('\r'),
+            Err(_) => break
+        };
+
+        let (key, value) = match text.find(':') {
+            Some(i) if !text.starts_with('<') => (&text[..i], &text[i+1..]),
+            _ => break
+        };
+
+        let offset = || value.trim().parse::<i64>().ok().and_then(|v| v.try_into().ok());
+        match key {
+            "StartHTML" => { start_html = offset(); },
+            "EndHTML" => { end_html = offset(); },
+            "StartFragment" => { start_fragment = offset(); },
+            "EndFragment" => { end_fragment = offset(); },
+            "SourceURL" => { source_url = Some(value.to_string()); },
+            _ => {}
+        }
+
+        header_end += line.len() + 1;
+    }
+
+    let start_fragment: usize = start_fragment?;
+    let end_fragment: usize = end_fragment?;
+    let start_html = start_html.unwrap_or(header_end);
+    let end_html = end_html.unwrap_or(data.len());
+
+    if start_fragment > end_fragment || end_fragment > data.len() || start_html > end_html || end_html > data.len() {
+        return None;
+    }
+
+    let text = |start: usize, end: usize| String::from_utf8_lossy(&data[start..end]).into_owned();
+
+    Some(ClipboardHtml {
+        html: text(start_html, end_html),
+        fragment: text(start_fragment, end_fragment),
+        source_url,
+    })
+}
+
+/// Encodes text as a null terminated wide string
+pub(crate) fn encode_text(text: &str) -> Vec<u8> {
+    text.encode_utf16()
+        .chain(Some(0))
+        .flat_map(|c| c.to_le_bytes().to_vec())
+        .collect()
+}
+
+/// Reads a null terminated wide string. The data after the null character is ignored.
+pub(crate) fn decode_text(data: &[u8]) -> String {
+    let chars: Vec<u16> = data.chunks_exact(2)
+        .map(|c| u16::from_le_bytes([c[0], c[1]]))
+        .take_while(|&c| c != 0)
+        .collect();
+
+    String::from_utf16_lossy(&chars)
+}
+
+/// Appends the null byte expected by the text formats
+pub(crate) fn null_terminated(text: &str) -> Vec<u8> {
+    let mut data = Vec::with_capacity(text.len() + 1);
+    data.extend_from_slice(text.as_bytes());
+    data.push(0);
+    data
+}
+
+/// Encodes a list of paths as a `DROPFILES` structure followed by a double null terminated list of wide strings
+pub(crate) fn encode_file_list<S: AsRef<str>>(files: &[S]) -> Vec<u8> {
+    let mut data = Vec::with_capacity(DROPFILES_SIZE);
+    data.extend_from_slice(&(DROPFILES_SIZE as u32).to_le_bytes());   // pFiles
+    data.extend_from_slice(&[0; 8]);                                   // pt
+    data.extend_from_slice(&0u32.to_le_bytes());                       // fNC
+    data.extend_from_slice(&1u32.to_le_bytes());                       // fWide
+
+    for file in files {
+        for c in file.as_ref().encode_utf16().chain(Some(0)) {
+            data.extend_from_slice(&c.to_le_bytes());
+        }
+    }
+
+    data.extend_from_slice(&[0, 0]);
+    data
+}
+
+/// Reads the list of paths of a `DROPFILES` structure. Returns `None` if the structure is truncated.
+pub(crate) fn decode_file_list(data: &[u8]) -> Option<Vec<String>> {
+    let read_u32 = |offset: usize| data.get(offset..offset+4).map(|b| u32::from_le_bytes(b.try_into().unwrap()));
+
+    let files_offset = read_u32(0)? as usize;
+    let wide = read_u32(16)? != 0;
+    let list = data.get(files_offset..)?;
+
+    let mut files = Vec::new();
+    if wide {
+        let chars: Vec<u16> = list.chunks_exact(2).map(|c| u16::from_le_bytes([c[0], c[1]])).collect();
+        for file in chars.split(|&c| c == 0).take_while(|f| !f.is_empty()) {
+            files.push(String::from_utf16_lossy(file));
+        }
+    } else {
+        for file in list.split(|&c| c == 0).take_while(|f| !f.is_empty()) {
+            files.push(String::from_utf8_lossy(file).into_owned());
+        }
+    }
+
+    Some(files)
+}
+
+/**
+    Prefixes the `CF_DIB` data (a `BITMAPINFO` followed by the pixels) with a `BITMAPFILEHEADER` so that it can be
+    read by the image decoders. Returns `None` if the bitmap header is truncated.
+*/
+pub(crate) fn dib_to_bmp(dib: &[u8]) -> Option<Vec<u8>> {
+    let read_u32 = |offset: usize| dib.get(offset..offset+4).map(|b| u32::from_le_bytes(b.try_into().unwrap()));
+    let read_u16 = |offset: usize| dib.get(offset..offset+2).map(|b| u16::from_le_bytes(b.try_into().unwrap()));
+
+    let header_size = read_u32(0)? as usize;
+    let bit_count = read_u16(14)?;
+    let compression = read_u32(16)?;
+    let colors_used = read_u32(32)? as usize;
+
+    let colors = match (colors_used, bit_count) {
+        (0, 1) | (0, 4) | (0, 8) => 1 << bit_count,
+        (count, _) => count
+    };
+
+    // A `BITMAPINFOHEADER` is followed by the color masks. They are part of the larger headers.
+    let masks = match (header_size, compression) {
+        (40, BI_BITFIELDS) => 12,
+        _ => 0
+    };
+
+    let pixels_offset = BMP_FILE_HEADER_SIZE + header_size + masks + colors * 4;
+    if dib.len() < header_size {
+        return None;
+    }
+
+    let file_size = BMP_FILE_HEADER_SIZE + dib.len();
+    let mut bmp = Vec::with_capacity(file_size);
+    bmp.extend_from_slice(b"BM");
+    bmp.extend_from_slice(&(file_size as u32).to_le_bytes());
+    bmp.extend_from_slice(&[0; 4]);
+    bmp.extend_from_slice(&(pixels_offset as u32).to_le_bytes());
+    bmp.extend_from_slice(dib);
+
+    Some(bmp)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header_value(data: &str, key: &str) -> usize {
+        let line = data.lines().find(|l| l.starts_with(key)).unwrap();
+        line[key.len()+1..].parse().unwrap()
+    }
+
+    #[test]
+    fn html_offsets() {
+        let data = encode_html("<b>Héllo</b>", Some("https://example.com"));
+
+        let start = header_value(&data, "StartFragment");
+        let end = header_value(&data, "EndFragment");
+        assert_eq!(&data[start..end], "<b>Héllo</b>");
+
+        let start = header_value(&data, "StartHTML");
+        let end = header_value(&data, "EndHTML");
+        assert!(data[start..end].starts_with("<html>"));
+        assert_eq!(end, data.len());
+
+        let decoded = decode_html(data.as_bytes()).unwrap();
+        assert_eq!(decoded.fragment, "<b>Héllo</b>");
+        assert_eq!(decoded.source_url.as_deref(), Some("https://example.com"));
+        assert_eq!(decoded.html, &data[start..end]);
+    }
+
+    #[test]
+    fn html_decode() {
+        let mut data = encode_html("<i>a</i>", None).into_bytes();
+        data.push(0);
+        data.extend_from_slice(b"garbage");
+        let decoded = decode_html(&data).unwrap();
+        assert_eq!(decoded.fragment, "<i>a</i>");
+        assert_eq!(decoded.source_url, None);
+
+        // Optional offsets
+        let data = "Version:1.0\r\nStartHTML:-1\r\nEndHTML:-1\r\nStartFragment:0000000092\r\nEndFragment:0000000095\r\n<p>abc</p>";
+        let decoded = decode_html(data.as_bytes()).unwrap();
+        assert_eq!(decoded.fragment, "abc");
+        assert_eq!(decoded.html, "<p>abc</p>");
+
+        assert_eq!(decode_html(b"<p>abc</p>"), None);
+        assert_eq!(decode_html(b"Version:0.9\r\nStartFragment:10\r\nEndFragment:5000\r\n"), None);
+        assert_eq!(decode_html(b""), None);
+    }
+
+    #[test]
+    fn text() {
+        let data = encode_text("aé");
+        assert_eq!(data, [b'a', 0, 0xE9, 0, 0, 0]);
+        assert_eq!(decode_text(&data), "aé");
+        assert_eq!(decode_text(&[b'a', 0, 0, 0, b'b', 0]), "a");
+        assert_eq!(decode_text(&[b'a', 0, b'b']), "a");
+        assert_eq!(null_terminated("{\\rtf1}"), b"{\\rtf1}\0");
+    }
+
+    #[test]
+    fn file_list() {
+        let files = ["C:\\a.txt", "C:\\dossier\\é.png"];
+        let data = encode_file_list(&files);
+        assert_eq!(&data[..4], &[20, 0, 0, 0]);
+        assert_eq!(decode_file_list(&data).unwrap(), files);
+
+        let empty: &[&str] = &[];
+        assert_eq!(decode_file_list(&encode_file_list(empty)).unwrap(), Vec::<String>::new());
+
+        let mut ansi = encode_file_list(empty);
+        ansi[16] = 0;
+        ansi.truncate(DROPFILES_SIZE);
+        ansi.extend_from_slice(b"C:\\a\0C:\\b\0\0");
+        assert_eq!(decode_file_list(&ansi).unwrap(), ["C:\\a", "C:\\b"]);
+
+        assert_eq!(decode_file_list(&[20, 0, 0]), None);
+    }
+
+    #[test]
+    fn dib_file_header() {
+        // A 2x1 24 bits image
+        let mut dib = vec![0u8; 40];
+        dib[0] = 40;
+        dib[4] = 2;
+        dib[8] = 1;
+        dib[12] = 1;
+        dib[14] = 24;
+        dib.extend_from_slice(&[255, 0, 0, 0, 255, 0, 0, 0]);
+
+        let bmp = dib_to_bmp(&dib).unwrap();
+        assert_eq!(&bmp[..2], b"BM");
+        assert_eq!(u32::from_le_bytes(bmp[2..6].try_into().unwrap()) as usize, bmp.len());
+        assert_eq!(u32::from_le_bytes(bmp[10..14].try_into().unwrap()), 54);
+        assert_eq!(&bmp[14..], &dib[..]);
+
+        // 8 bits with a full palette and 32 bits with color masks
+        dib[14] = 8;
+        assert_eq!(u32::from_le_bytes(dib_to_bmp(&dib).unwrap()[10..14].try_into().unwrap()), 54 + 1024);
+
+        dib[14] = 32;
+        dib[16] = BI_BITFIELDS as u8;
+        assert_eq!(u32::from_le_bytes(dib_to_bmp(&dib).unwrap()[10..14].try_into().unwrap()), 54 + 12);
+
+        assert_eq!(dib_to_bmp(&dib[..20]), None);
+    }
+
+}
@@ -0,0 +1,202 @@
+use winapi::shared::windef::HACCEL;
+use super::control_handle::ControlHandle;
+use super::shortcut::Shortcut;
+use crate::win32::accelerator::{create_table, destroy_table};
+use crate::NwgError;
+use std::{ptr, collections::HashMap};
+
+#[cfg(feature = "menu")]
+use crate::MenuItem;
+
+
+/**
+A table of keyboard shortcuts of a top level window. The shortcuts are handled by `dispatch_thread_events`
+before the message is sent to the focused control, so they work from any control of the window.
+
+A shortcut either raises `OnAccelerator` with the command id of the shortcut (`EventData::OnAccelerator`)
+or selects a menu item, in which case `OnMenuItemSelected` is raised on the menu item. Shortcuts bound to a disabled
+item of the menu bar are ignored. By default, the text of the shortcut is displayed next to the text of the menu items.
+
+Shortcuts are written as text ("Ctrl+Shift+S", see `Shortcut`) or built from a `Shortcut` value.
+An invalid shortcut text is reported by `build`.
+
+The table is destroyed when it is dropped. A window can have more than one accelerator table.
+
+Requires the `accelerator` feature.
+
+**Builder parameters:**
+  * `parent`:       **Required.** The window of the shortcuts. If the parent is a child control, its top level window is used.
+  * `accelerator`:  A shortcut text and the command id raised by `OnAccelerator`
+  * `accelerators`: A list of shortcut texts and command ids
+  * `shortcut`:     A `Shortcut` and the command id raised by `OnAccelerator`
+  * `menu_item`:    A shortcut text and the menu item selected by the shortcut (requires the `menu` feature)
+  * `show_in_menu`: Display the shortcuts next to the text of the menu items. Defaults to `true`.
+
+**Control events:**
+  * `OnAccelerator`: When a shortcut with a command id is pressed
+
+```rust
+use native_windows_gui as nwg;
+
+const SAVE: u16 = 1;
+
+fn build_accelerators(table: &mut nwg::AcceleratorTable, window: &nwg::Window, open: &nwg::MenuItem) -> Result<(), nwg::NwgError> {
+    nwg::AcceleratorTable::builder()
+        .parent(window)
+        .accelerator("Ctrl+S", SAVE)
+        .menu_item("Ctrl+O", open)
+        .build(table)
+}
+
+fn accelerator(data: &nwg::EventData) {
+    if data.on_accelerator() == SAVE {
+        println!("Save");
+    }
+}
+```
+*/
+#[derive(PartialEq, Eq)]
+pub struct AcceleratorTable {
+    pub handle: ControlHandle,
+    table: HACCEL,
+    shortcuts: Vec<(Shortcut, u16)>,
+}
+
+impl AcceleratorTable {
+
+    pub fn builder() -> AcceleratorTableBuilder {
+        AcceleratorTableBuilder {
+            parent: None,
+            entries: Vec::new(),
+            show_in_menu: true,
+        }
+    }
+
+    /// Returns the shortcuts of the table and their command id
+    pub fn shortcuts(&self) -> &[(Shortcut, u16)] {
+        &self.shortcuts
+    }
+
+    /// Returns the first shortcut bound to a command id. The command id of a menu item is its id.
+    pub fn shortcut(&self, id: u16) -> Option<Shortcut> {
+        self.shortcuts.iter().find(|(_, cmd)| *cmd == id).map(|(shortcut, _)| *shortcut)
+    }
+
+}
+
+impl Default for AcceleratorTable {
+    fn default() -> AcceleratorTable {
+        AcceleratorTable {
+            handle: ControlHandle::NoHandle,
+            table: ptr::null_mut(),
+            shortcuts: Vec::new(),
+        }
+    }
+}
+
+impl Drop for AcceleratorTable {
+    fn drop(&mut self) {
+        destroy_table(self.table);
+    }
+}
+
+
+/// The command of a shortcut
+enum AcceleratorTarget {
+    Command(u16),
+    #[cfg(feature = "menu")]
+    MenuItem(ControlHandle),
+}
+
+pub struct AcceleratorTableBuilder {
+    parent: Option<ControlHandle>,
+    entries: Vec<(Result<Shortcut, NwgError>, AcceleratorTarget)>,
+    show_in_menu: bool,
+}
+
+impl AcceleratorTableBuilder {
+
+    pub fn parent<C: Into<ControlHandle>>(mut self, p: C) -> AcceleratorTableBuilder {
+        self.parent = Some(p.into());
+        self
+    }
+
+    pub fn accelerator(mut self, shortcut: &str, id: u16) -> AcceleratorTableBuilder {
+        self.entries.push((shortcut.parse(), AcceleratorTarget::Command(id)));
+        self
+    }
+
+    pub fn accelerators<S: AsRef<str>>(mut self, accelerators: &[(S, u16)]) -> AcceleratorTableBuilder {
+        for (shortcut, id) in accelerators {
+            self.entries.push((shortcut.as_ref().parse(), AcceleratorTarget::Command(*id)));
+        }
+        self
+    }
+
+    pub fn shortcut(mut self, shortcut: Shortcut, id: u16) -> AcceleratorTableBuilder {
+        self.entries.push((Ok(shortcut), AcceleratorTarget::Command(id)));
+        self
+    }
+
+    #[cfg(feature = "menu")]
+    pub fn menu_item(mut self, shortcut: &str, item: &MenuItem) -> AcceleratorTableBuilder {
+        self.entries.push((shortcut.parse(), AcceleratorTarget::MenuItem(item.handle)));
+        self
+    }
+
+    pub fn show_in_menu(mut self, show: bool) -> AcceleratorTableBuilder {
+        self.show_in_menu = show;
+        self
+    }
+
+    pub fn build(self, out: &mut AcceleratorTable) -> Result<(), NwgError> {
+        use winapi::um::winuser::{GetAncestor, GA_ROOT};
+
+        let parent = match self.parent {
+            Some(p) => match p.hwnd() {
+                Some(handle) => Ok(unsafe { GetAncestor(handle, GA_ROOT) }),
+                None => Err(NwgError::control_create("Wrong parent type"))
+            },
+            None => Err(NwgError::no_parent("AcceleratorTable"))
+        }?;
+
+        let mut shortcuts = Vec::with_capacity(self.entries.len());
+        #[cfg_attr(not(feature = "menu"), allow(unused_mut))]
+        let mut menu_items = HashMap::new();
+        for (shortcut, target) in self.entries {
+            let shortcut = shortcut?;
+            match target {
+                AcceleratorTarget::Command(id) => {
+                    shortcuts.push((shortcut, id));
+                },
+                #[cfg(feature = "menu")]
+                AcceleratorTarget::MenuItem(handle) => {
+                    let (menu, id) = match handle.hmenu_item() {
+                        Some(item) => item,
+                        None => return Err(NwgError::control_create("AcceleratorTable menu item is not bound to a menu item"))
+                    };
+
+                    if id > u16::max_value() as u32 {
+                        return Err(NwgError::control_create("AcceleratorTable menu item id does not fit in a command id"));
+                    }
+
+                    if self.show_in_menu {
+                        unsafe { crate::win32::menu::set_menu_item_shortcut(menu, id, &shortcut.to_string()); }
+                    }
+
+                    shortcuts.push((shortcut, id as u16));
+                    menu_items.insert(id as u16, handle);
+                }
+            }
+        }
+
+        *out = Default::default();
+
+        out.table = create_table(parent, &shortcuts, menu_items)?;
+        out.shortcuts = shortcuts;
+        out.handle = ControlHandle::Hwnd(parent);
+
+        Ok(())
+    }
+
+}
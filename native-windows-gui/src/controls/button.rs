@@ -1,4 +1,4 @@
-use winapi::um::winuser::{WS_DISABLED, BS_ICON, BS_BITMAP, BS_NOTIFY, WS_VISIBLE, WS_TABSTOP, WS_CHILD};
+use crate::win32::sys::um::winuser::{WS_DISABLED, BS_ICON, BS_BITMAP, BS_NOTIFY, WS_VISIBLE, WS_TABSTOP, WS_CHILD};
 use crate::win32::{
     base_helper::check_hwnd,  
     window_helper as wh,
@@ -98,7 +98,7 @@ impl Button {
 
     /// Simulate a user click
     pub fn click(&self) {
-        use winapi::um::winuser::BM_CLICK;
+        use crate::win32::sys::um::winuser::BM_CLICK;
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
         wh::send_message(handle, BM_CLICK, 0, 0);
     }
@@ -106,8 +106,8 @@ impl Button {
     /// Sets the bitmap image of the button. Replace the current bitmap or icon.
     /// Set `image` to `None` to remove the image
     pub fn set_bitmap<'a>(&self, image: Option<&'a Bitmap>) {
-        use winapi::um::winuser::{BM_SETIMAGE, IMAGE_BITMAP};
-        use winapi::shared::minwindef::{WPARAM, LPARAM};
+        use crate::win32::sys::um::winuser::{BM_SETIMAGE, IMAGE_BITMAP};
+        use crate::win32::sys::shared::minwindef::{WPARAM, LPARAM};
 
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
 
@@ -118,8 +118,8 @@ impl Button {
     /// Sets the bitmap image of the button. Replace the current bitmap or icon.
     /// Set `image` to `None` to remove the image
     pub fn set_icon<'a>(&self, image: Option<&'a Icon>) {
-        use winapi::um::winuser::{BM_SETIMAGE, IMAGE_ICON};
-        use winapi::shared::minwindef::{WPARAM, LPARAM};
+        use crate::win32::sys::um::winuser::{BM_SETIMAGE, IMAGE_ICON};
+        use crate::win32::sys::shared::minwindef::{WPARAM, LPARAM};
 
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
 
@@ -131,10 +131,10 @@ impl Button {
     /// If the button has a bitmap, the value will be returned in `bitmap`
     /// If the button has a icon, the value will be returned in `icon`
     pub fn image<'a>(&self, bitmap: &mut Option<Bitmap>, icon: &mut Option<Icon>) {
-        use winapi::um::winuser::{BM_GETIMAGE, IMAGE_BITMAP, IMAGE_ICON};
-        use winapi::shared::minwindef::WPARAM;
-        use winapi::shared::windef::HBITMAP;
-        use winapi::um::winnt::HANDLE;
+        use crate::win32::sys::um::winuser::{BM_GETIMAGE, IMAGE_BITMAP, IMAGE_ICON};
+        use crate::win32::sys::shared::minwindef::WPARAM;
+        use crate::win32::sys::shared::windef::HBITMAP;
+        use crate::win32::sys::um::winnt::HANDLE;
 
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
 
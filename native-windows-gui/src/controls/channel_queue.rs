@@ -0,0 +1,167 @@
+/*!
+    The message queue of a `UiReceiver` and the coalescing of its messages.
+    This module does not depend on the windowing system.
+*/
+use std::any::Any;
+use std::rc::Rc;
+use std::sync::Mutex;
+
+
+/**
+    How the pending messages of a `UiReceiver` are delivered when the GUI thread is notified.
+
+    Senders never notify the GUI thread more than once between two deliveries, whatever the mode.
+
+    * All: Every message is delivered, one by one. The payload of `OnNotice` is a `T`.
+    * Latest: Only the last message is delivered. Older pending messages are dropped. The payload of `OnNotice` is a `T`.
+    * Batched: The pending messages are delivered together. The payload of `OnNotice` is a `Vec<T>`.
+*/
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum UiChannelMode {
+    #[default]
+    All,
+    Latest,
+    Batched,
+}
+
+struct QueueState<T> {
+    pending: Vec<T>,
+
+    /// `true` if the GUI thread was notified and did not take the pending messages yet
+    notified: bool,
+
+    /// `true` once the receiver is dropped
+    closed: bool,
+}
+
+/// The pending messages of a channel, shared between the senders and the receiver
+pub(crate) struct ChannelQueue<T> {
+    mode: UiChannelMode,
+    state: Mutex<QueueState<T>>,
+}
+
+impl<T> ChannelQueue<T> {
+
+    pub(crate) fn new(mode: UiChannelMode) -> ChannelQueue<T> {
+        ChannelQueue {
+            mode,
+            state: Mutex::new(QueueState { pending: Vec::new(), notified: false, closed: false }),
+        }
+    }
+
+    pub(crate) fn mode(&self) -> UiChannelMode {
+        self.mode
+    }
+
+    /**
+        Queues a message. Returns `Ok(true)` if the GUI thread must be notified, `Ok(false)` if a notification is already pending,
+        or the message if the receiver was dropped.
+    */
+    pub(crate) fn push(&self, value: T) -> Result<bool, T> {
+        let mut state = self.state.lock().unwrap();
+        if state.closed {
+            return Err(value);
+        }
+
+        if self.mode == UiChannelMode::Latest {
+            state.pending.clear();
+        }
+
+        state.pending.push(value);
+
+        let notify = !state.notified;
+        state.notified = true;
+        Ok(notify)
+    }
+
+    /// Takes the pending messages. The next message sent will notify the GUI thread again.
+    pub(crate) fn take(&self) -> Vec<T> {
+        let mut state = self.state.lock().unwrap();
+        state.notified = false;
+        ::std::mem::take(&mut state.pending)
+    }
+
+    /// Drops the pending messages and rejects the new ones
+    pub(crate) fn close(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.closed = true;
+        state.pending.clear();
+    }
+
+}
+
+impl<T: Any> ChannelQueue<T> {
+
+    /// Takes the pending messages and packs them into the payloads of the `OnNotice` events, according to the mode
+    pub(crate) fn take_payloads(&self) -> Vec<Rc<dyn Any>> {
+        let messages = self.take();
+        if messages.is_empty() {
+            return Vec::new();
+        }
+
+        match self.mode {
+            UiChannelMode::All | UiChannelMode::Latest => messages.into_iter().map(|m| Rc::new(m) as Rc<dyn Any>).collect(),
+            UiChannelMode::Batched => vec![Rc::new(messages)],
+        }
+    }
+
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn notify_once_until_taken() {
+        let queue = ChannelQueue::new(UiChannelMode::All);
+        assert_eq!(queue.push(1), Ok(true));
+        assert_eq!(queue.push(2), Ok(false));
+        assert_eq!(queue.push(3), Ok(false));
+
+        assert_eq!(queue.take(), vec![1, 2, 3]);
+        assert_eq!(queue.take(), Vec::<i32>::new());
+        assert_eq!(queue.push(4), Ok(true));
+    }
+
+    #[test]
+    fn latest_drops_older_messages() {
+        let queue = ChannelQueue::new(UiChannelMode::Latest);
+        assert_eq!(queue.push(1), Ok(true));
+        assert_eq!(queue.push(2), Ok(false));
+
+        let payloads = queue.take_payloads();
+        assert_eq!(payloads.len(), 1);
+        assert_eq!(payloads[0].downcast_ref::<i32>(), Some(&2));
+    }
+
+    #[test]
+    fn payloads_by_mode() {
+        let queue = ChannelQueue::new(UiChannelMode::All);
+        queue.push("a").unwrap();
+        queue.push("b").unwrap();
+        let payloads: Vec<&str> = queue.take_payloads().iter().map(|p| *p.downcast_ref::<&str>().unwrap()).collect();
+        assert_eq!(payloads, vec!["a", "b"]);
+
+        let queue = ChannelQueue::new(UiChannelMode::Batched);
+        queue.push(10u32).unwrap();
+        queue.push(20u32).unwrap();
+        let payloads = queue.take_payloads();
+        assert_eq!(payloads.len(), 1);
+        assert_eq!(payloads[0].downcast_ref::<Vec<u32>>(), Some(&vec![10, 20]));
+
+        // A notification that arrives after the messages were taken has no payload
+        assert!(queue.take_payloads().is_empty());
+    }
+
+    #[test]
+    fn closed_queue_rejects_messages() {
+        let queue = ChannelQueue::new(UiChannelMode::All);
+        queue.push(1).unwrap();
+        queue.close();
+
+        assert_eq!(queue.push(2), Err(2));
+        assert_eq!(queue.take(), Vec::<i32>::new());
+    }
+
+}
@@ -1,8 +1,8 @@
-use winapi::um::{
+use crate::win32::sys::um::{
     winuser::{WS_VISIBLE, WS_DISABLED, BS_AUTOCHECKBOX, BS_AUTO3STATE, BS_PUSHLIKE, WS_TABSTOP},
     wingdi::DeleteObject
 };
-use winapi::shared::windef::HBRUSH;
+use crate::win32::sys::shared::windef::HBRUSH;
 use crate::win32::{base_helper::check_hwnd, window_helper as wh};
 use crate::{Font, NwgError, RawEventHandler};
 use super::{ControlBase, ControlHandle};
@@ -116,8 +116,8 @@ impl CheckBox {
 
     /// Sets or unsets the checkbox as tristate
     pub fn set_tristate(&self, tri: bool) {
-        use winapi::um::winuser::{BM_SETSTYLE};
-        use winapi::shared::minwindef::WPARAM;
+        use crate::win32::sys::um::winuser::{BM_SETSTYLE};
+        use crate::win32::sys::shared::minwindef::WPARAM;
 
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
         
@@ -131,7 +131,7 @@ impl CheckBox {
 
     /// Return the check state of the check box
     pub fn check_state(&self) -> CheckBoxState {
-        use winapi::um::winuser::{BM_GETCHECK, BST_CHECKED, BST_INDETERMINATE, BST_UNCHECKED};
+        use crate::win32::sys::um::winuser::{BM_GETCHECK, BST_CHECKED, BST_INDETERMINATE, BST_UNCHECKED};
 
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
 
@@ -145,8 +145,8 @@ impl CheckBox {
 
     /// Sets the check state of the check box
     pub fn set_check_state(&self, state: CheckBoxState) {
-        use winapi::um::winuser::{BM_SETCHECK, BST_CHECKED, BST_INDETERMINATE, BST_UNCHECKED};
-        use winapi::shared::minwindef::WPARAM;
+        use crate::win32::sys::um::winuser::{BM_SETCHECK, BST_CHECKED, BST_INDETERMINATE, BST_UNCHECKED};
+        use crate::win32::sys::shared::minwindef::WPARAM;
 
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
 
@@ -262,17 +262,18 @@ impl CheckBox {
 
     /// Winapi flags required by the control
     pub fn forced_flags(&self) -> u32 {
-        use winapi::um::winuser::{BS_NOTIFY, WS_CHILD};
+        use crate::win32::sys::um::winuser::{BS_NOTIFY, WS_CHILD};
 
         BS_NOTIFY | WS_CHILD
     }
 
     /// Change the checkbox background color.
+    #[cfg(not(feature = "headless"))]
     fn hook_background_color(&mut self, c: [u8; 3]) {
         use crate::bind_raw_event_handler_inner;
-        use winapi::um::winuser::{WM_CTLCOLORSTATIC};
-        use winapi::shared::{basetsd::UINT_PTR, windef::HWND, minwindef::LRESULT};
-        use winapi::um::wingdi::{CreateSolidBrush, RGB};
+        use crate::win32::sys::um::winuser::{WM_CTLCOLORSTATIC};
+        use crate::win32::sys::shared::{basetsd::UINT_PTR, windef::HWND, minwindef::LRESULT};
+        use crate::win32::sys::um::wingdi::{CreateSolidBrush, RGB};
 
         if self.handle.blank() { panic!("{}", NOT_BOUND); }
         let handle = self.handle.hwnd().expect(BAD_HANDLE);
@@ -299,6 +300,9 @@ impl CheckBox {
         *self.handler0.borrow_mut() = Some(handler.unwrap());
     }
 
+    #[cfg(feature = "headless")]
+    fn hook_background_color(&mut self, _c: [u8; 3]) {}
+
 }
 
 impl Drop for CheckBox {
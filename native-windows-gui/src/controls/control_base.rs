@@ -1,11 +1,11 @@
-use winapi::shared::minwindef::DWORD;
-use winapi::shared::windef::{HWND};
+use crate::win32::sys::shared::minwindef::DWORD;
+use crate::win32::sys::shared::windef::{HWND};
 use super::ControlHandle;
 use crate::win32::window::{build_hwnd_control, build_timer, build_notice};
 use crate::{NwgError};
 
 #[cfg(feature = "menu")] use crate::win32::menu::build_hmenu_control;
-#[cfg(feature = "menu")] use winapi::shared::windef::{HMENU};
+#[cfg(feature = "menu")] use crate::win32::sys::shared::windef::{HMENU};
 
 const NOTICE: u32 = 1;
 const TRAY: u32 = 2;
@@ -1,4 +1,4 @@
-use winapi::shared::windef::{HWND, HMENU};
+use crate::win32::sys::shared::windef::{HWND, HMENU};
 use crate::win32::window_helper as wh;
 
 
@@ -0,0 +1,264 @@
+use crate::win32::sys::um::winuser::{WM_COMMAND, IDOK, IDCANCEL, BM_CLICK};
+use crate::win32::sys::shared::minwindef::{LOWORD, WPARAM, LPARAM, LRESULT};
+use crate::win32::window_helper as wh;
+use crate::win32::base_helper::check_hwnd;
+use crate::win32::dispatch_modal_events;
+use crate::{NwgError, NativeUi, bind_raw_event_handler_inner, unbind_raw_event_handler};
+use super::{ControlHandle, Window, Button};
+use std::cell::{Cell, RefCell};
+use std::ops::Deref;
+use std::rc::Rc;
+
+const NOT_BOUND: &str = "Dialog window is not yet bound to a winapi object";
+const BAD_HANDLE: &str = "INTERNAL ERROR: Dialog window handle is not HWND!";
+
+/// Raw event handler id of the Enter/Esc handling of a running dialog
+const DIALOG_HANDLER_ID: usize = 0x7D00_0000;
+
+
+struct DialogState<R> {
+    result: RefCell<Option<R>>,
+    ended: Cell<bool>,
+    running: Cell<bool>,
+    default_button: Cell<ControlHandle>,
+    cancel_button: Cell<ControlHandle>,
+}
+
+impl<R> DialogState<R> {
+
+    /// Handles the `WM_COMMAND` sent by `IsDialogMessage` when the user presses Enter or Esc
+    fn key_command(&self, w: WPARAM, l: LPARAM) -> Option<LRESULT> {
+        // Commands with a control handle are sent by the controls of the window
+        if l != 0 {
+            return None;
+        }
+
+        let (button, cancel) = match LOWORD(w as u32) as i32 {
+            IDOK => (self.default_button.get(), false),
+            IDCANCEL => (self.cancel_button.get(), true),
+            _ => { return None; }
+        };
+
+        match button.hwnd() {
+            Some(button) => { wh::send_message(button, BM_CLICK, 0, 0); },
+            None if cancel => { self.ended.set(true); },
+            None => { return None; }
+        }
+
+        Some(0)
+    }
+
+}
+
+
+/**
+    Shows a window as a modal dialog and returns the result chosen by the user.
+
+    `run_modal` disables the owner window, shows the dialog window, and dispatches the events of the thread until the dialog
+    ends. The dialog ends when an event handler calls `end(result)` or `cancel`, or when the dialog window is closed or destroyed.
+    The owner is then enabled again, the dialog window is hidden and `run_modal` returns the result (`None` if the dialog was cancelled).
+    Unlike a nested `dispatch_thread_events`, the events loop of the application still stops when `stop_thread_dispatch` is called from the dialog.
+
+    While the dialog runs, Enter clicks the default button and Esc clicks the cancel button (see `set_default_button` and `set_cancel_button`).
+    Without a cancel button, Esc cancels the dialog. Without a default button, Enter raises `OnKeyEnter` on the window as usual.
+
+    The dialog window should be created hidden, with the owner as `parent`, so that it stays on top of the owner.
+
+    A `Dialog` is usually a member of a UI struct. See `DialogUi` to show a `NativeUi` as a modal dialog.
+
+    ```rust
+    use native_windows_gui as nwg;
+
+    fn ask_confirmation(owner: &nwg::Window, dialog_window: &nwg::Window, dialog: &nwg::Dialog<bool>) -> bool {
+        dialog.run_modal(dialog_window, Some(owner)).unwrap_or(false)
+    }
+
+    fn yes_clicked(dialog: &nwg::Dialog<bool>) {
+        dialog.end(true);
+    }
+    ```
+*/
+pub struct Dialog<R> {
+    state: Rc<DialogState<R>>,
+}
+
+impl<R: 'static> Dialog<R> {
+
+    pub fn new() -> Dialog<R> {
+        Dialog {
+            state: Rc::new(DialogState {
+                result: RefCell::new(None),
+                ended: Cell::new(false),
+                running: Cell::new(false),
+                default_button: Cell::new(ControlHandle::NoHandle),
+                cancel_button: Cell::new(ControlHandle::NoHandle),
+            })
+        }
+    }
+
+    /**
+        Builds a UI, shows its window as a modal dialog of `owner` and returns the result of the dialog.
+        The UI is dropped once the dialog ends.
+    */
+    pub fn show<D, UI, C>(data: D, owner: Option<C>) -> Result<Option<R>, NwgError>
+        where D: NativeUi<UI> + DialogUi<R>,
+              UI: Deref<Target=D>,
+              C: Into<ControlHandle>
+    {
+        let ui = D::build_ui(data)?;
+
+        let dialog = ui.dialog();
+        dialog.set_default_button(ui.default_button());
+        dialog.set_cancel_button(ui.cancel_button());
+
+        Ok(dialog.run_modal(ui.dialog_window(), owner))
+    }
+
+    /**
+        Shows `window` as a modal dialog of `owner` and dispatches the events of the thread until the dialog ends.
+        Returns the result passed to `end`, or `None` if the dialog was cancelled or closed.
+
+        Panics if the window is not initialized or if the dialog is already running.
+    */
+    pub fn run_modal<C: Into<ControlHandle>>(&self, window: &Window, owner: Option<C>) -> Option<R> {
+        let handle = check_hwnd(&window.handle, NOT_BOUND, BAD_HANDLE);
+        if self.state.running.replace(true) {
+            panic!("The dialog is already running");
+        }
+
+        self.state.ended.set(false);
+        *self.state.result.borrow_mut() = None;
+
+        // An owner that was already disabled is left disabled
+        let owner = owner
+            .and_then(|o| o.into().hwnd())
+            .filter(|&o| unsafe { wh::get_window_enabled(o) });
+
+        if let Some(owner) = owner {
+            unsafe { wh::set_window_enabled(owner, false); }
+        }
+
+        let state = self.state.clone();
+        let handler = bind_raw_event_handler_inner(&window.handle, DIALOG_HANDLER_ID, move |_hwnd, msg, w, l| {
+            match msg == WM_COMMAND {
+                true => state.key_command(w, l),
+                false => None
+            }
+        });
+
+        unsafe { wh::set_window_visibility(handle, true); }
+
+        let state = &self.state;
+        dispatch_modal_events(handle, &|| state.ended.get());
+
+        if let Ok(handler) = handler {
+            drop(unbind_raw_event_handler(&handler));
+        }
+
+        // The owner is enabled before the dialog is hidden so that it gets the activation back
+        if let Some(owner) = owner {
+            unsafe { wh::set_window_enabled(owner, true); }
+        }
+
+        if wh::window_valid(handle) {
+            unsafe { wh::set_window_visibility(handle, false); }
+        }
+
+        self.state.running.set(false);
+        self.state.result.borrow_mut().take()
+    }
+
+    /// Ends the dialog with a result. Does nothing if the dialog is not running.
+    pub fn end(&self, result: R) {
+        if self.state.running.get() {
+            *self.state.result.borrow_mut() = Some(result);
+            self.state.ended.set(true);
+        }
+    }
+
+    /// Ends the dialog without a result. Does nothing if the dialog is not running.
+    pub fn cancel(&self) {
+        if self.state.running.get() {
+            *self.state.result.borrow_mut() = None;
+            self.state.ended.set(true);
+        }
+    }
+
+    /// Returns `true` if the dialog is running
+    pub fn running(&self) -> bool {
+        self.state.running.get()
+    }
+
+    /// Sets the button clicked when the user presses Enter in the dialog. `None` removes the default button.
+    pub fn set_default_button(&self, button: Option<&Button>) {
+        self.state.default_button.set(button.map(|b| b.handle).unwrap_or(ControlHandle::NoHandle));
+    }
+
+    /// Sets the button clicked when the user presses Esc in the dialog. `None` removes the cancel button.
+    pub fn set_cancel_button(&self, button: Option<&Button>) {
+        self.state.cancel_button.set(button.map(|b| b.handle).unwrap_or(ControlHandle::NoHandle));
+    }
+
+}
+
+impl<R: 'static> Default for Dialog<R> {
+    fn default() -> Dialog<R> {
+        Dialog::new()
+    }
+}
+
+
+/**
+    A UI struct that can be shown as a modal dialog with `Dialog::show`.
+    Native-windows-derive builds the UI, this trait tells `Dialog::show` which members make the dialog.
+
+    ```rust
+    use native_windows_gui as nwg;
+
+    #[derive(Default)]
+    pub struct NameDialog {
+        window: nwg::Window,
+        name: nwg::TextInput,
+        ok: nwg::Button,
+        cancel: nwg::Button,
+        dialog: nwg::Dialog<String>,
+    }
+
+    impl NameDialog {
+        // Bound to `OnButtonClick` of `ok`
+        fn ok_clicked(&self) {
+            self.dialog.end(self.name.text());
+        }
+
+        // Bound to `OnButtonClick` of `cancel`
+        fn cancel_clicked(&self) {
+            self.dialog.cancel();
+        }
+    }
+
+    impl nwg::DialogUi<String> for NameDialog {
+        fn dialog(&self) -> &nwg::Dialog<String> { &self.dialog }
+        fn dialog_window(&self) -> &nwg::Window { &self.window }
+        fn default_button(&self) -> Option<&nwg::Button> { Some(&self.ok) }
+        fn cancel_button(&self) -> Option<&nwg::Button> { Some(&self.cancel) }
+    }
+
+    // With `NameDialog` implementing `NativeUi`:
+    // let name = nwg::Dialog::show(NameDialog::default(), Some(&main_window))?;
+    ```
+*/
+pub trait DialogUi<R> {
+
+    /// The dialog that holds the result
+    fn dialog(&self) -> &Dialog<R>;
+
+    /// The window shown as a modal dialog
+    fn dialog_window(&self) -> &Window;
+
+    /// The button clicked when the user presses Enter
+    fn default_button(&self) -> Option<&Button> { None }
+
+    /// The button clicked when the user presses Esc
+    fn cancel_button(&self) -> Option<&Button> { None }
+
+}
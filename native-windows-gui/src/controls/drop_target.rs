@@ -0,0 +1,109 @@
+use super::control_handle::ControlHandle;
+use crate::win32::drag_drop::{register_drop_target, revoke_drop_target};
+use crate::{DropEffect, NwgError};
+
+
+/**
+An invisible component that accepts the data dragged over a window from this application or from other applications.
+
+The drag events are raised on the parent window:
+
+  * `OnDragEnter`: When the mouse enters the window with dragged data
+  * `OnDragOver`: When the mouse moves over the window
+  * `OnDragLeave`: When the mouse leaves the window or when the drag is cancelled
+  * `OnDragDrop`: When the data is dropped on the window
+
+Each event receives a `EventData::OnDragDrop`. The handlers of `OnDragEnter` and `OnDragOver` pick the effect
+shown to the user (`DragDropData::set_effect`). Unlike `OnFileDrop`, any format shared with the clipboard can be dropped.
+
+A window can only have one drop target. The target is unregistered when it is dropped.
+
+Requires the `drag-drop` feature.
+
+**Builder parameters:**
+  * `parent`:         **Required.** The window that accepts the drops.
+  * `default_effect`: The effect picked when the data enters the window. Defaults to `DropEffect::COPY`.
+
+```rust
+use native_windows_gui as nwg;
+
+fn build_drop_target(target: &mut nwg::DropTarget, window: &nwg::Window) {
+    nwg::DropTarget::builder()
+        .parent(window)
+        .build(target);
+}
+
+fn drag_enter(data: &nwg::EventData) {
+    let drag = data.on_drag_drop();
+    if !drag.has_format(nwg::ClipboardFormat::UnicodeText) {
+        drag.set_effect(nwg::DropEffect::NONE);
+    }
+}
+
+fn drop_text(data: &nwg::EventData) {
+    if let Some(text) = data.on_drag_drop().text() {
+        println!("{}", text);
+    }
+}
+```
+*/
+#[derive(Default, PartialEq, Eq)]
+pub struct DropTarget {
+    pub handle: ControlHandle
+}
+
+impl DropTarget {
+
+    pub fn builder() -> DropTargetBuilder {
+        DropTargetBuilder {
+            parent: None,
+            default_effect: DropEffect::COPY,
+        }
+    }
+
+}
+
+impl Drop for DropTarget {
+    fn drop(&mut self) {
+        if let Some(hwnd) = self.handle.hwnd() {
+            revoke_drop_target(hwnd);
+        }
+    }
+}
+
+
+pub struct DropTargetBuilder {
+    parent: Option<ControlHandle>,
+    default_effect: DropEffect,
+}
+
+impl DropTargetBuilder {
+
+    pub fn parent<C: Into<ControlHandle>>(mut self, p: C) -> DropTargetBuilder {
+        self.parent = Some(p.into());
+        self
+    }
+
+    pub fn default_effect(mut self, effect: DropEffect) -> DropTargetBuilder {
+        self.default_effect = effect;
+        self
+    }
+
+    pub fn build(self, out: &mut DropTarget) -> Result<(), NwgError> {
+        let parent = match self.parent {
+            Some(p) => match p.hwnd() {
+                Some(handle) => Ok(handle),
+                None => Err(NwgError::control_create("Wrong parent type"))
+            },
+            None => Err(NwgError::no_parent("DropTarget"))
+        }?;
+
+        *out = Default::default();
+
+        register_drop_target(parent, self.default_effect)?;
+        out.handle = ControlHandle::Hwnd(parent);
+
+        Ok(())
+    }
+
+}
@@ -5,6 +5,28 @@ use std::convert::From;
 use std::fmt::Display;
 
 macro_rules! handles {
+    ($control:ident<$param:ident>) => {
+        impl<$param> From<&$control<$param>> for ControlHandle {
+            fn from(control: &$control<$param>) -> Self { control.handle }
+        }
+
+        impl<$param> From<&mut $control<$param>> for ControlHandle {
+            fn from(control: &mut $control<$param>) -> Self { control.handle }
+        }
+
+        impl<$param> PartialEq<ControlHandle> for $control<$param> {
+            fn eq(&self, other: &ControlHandle) -> bool {
+                self.handle == *other
+            }
+        }
+
+        impl<$param> PartialEq<$control<$param>> for ControlHandle {
+            fn eq(&self, other: &$control<$param>) -> bool {
+                *self == other.handle
+            }
+        }
+    };
+
     ($control:ty) => {
         #[allow(deprecated)]
         impl From<&$control> for ControlHandle {
@@ -188,7 +210,7 @@ handles!(ProgressBar);
 use super::TreeView;
 
 #[cfg(feature = "tree-view")]
-handles!(TreeView);
+handles!(TreeView<T>);
 
 #[cfg(feature = "tray-notification")]
 use super::TrayNotification;
@@ -225,7 +247,7 @@ handles!(Notice);
 use super::ListView;
 
 #[cfg(feature = "list-view")]
-handles!(ListView);
+handles!(ListView<T>);
 
 #[cfg(feature = "extern-canvas")]
 use super::ExternCanvas;
@@ -324,3 +346,27 @@ use super::Pager;
 
 #[cfg(feature = "pager")]
 handles!(Pager);
+
+#[cfg(feature = "splitter")]
+use super::Splitter;
+
+#[cfg(feature = "splitter")]
+handles!(Splitter);
+
+#[cfg(feature = "drag-drop")]
+use super::DropTarget;
+
+#[cfg(feature = "drag-drop")]
+handles!(DropTarget);
+
+#[cfg(feature = "accelerator")]
+use super::AcceleratorTable;
+
+#[cfg(feature = "accelerator")]
+handles!(AcceleratorTable);
+
+#[cfg(feature = "notice")]
+use super::UiReceiver;
+
+#[cfg(feature = "notice")]
+handles!(UiReceiver<T>);
@@ -1,6 +1,6 @@
-use winapi::um::winuser::{WS_VISIBLE, WS_DISABLED};
-use winapi::um::wingdi::DeleteObject;
-use winapi::shared::windef::HBRUSH;
+use crate::win32::sys::um::winuser::{WS_VISIBLE, WS_DISABLED};
+use crate::win32::sys::um::wingdi::DeleteObject;
+use crate::win32::sys::shared::windef::HBRUSH;
 use crate::win32::{
     base_helper::check_hwnd,  
     window_helper as wh,
@@ -77,8 +77,8 @@ impl ImageFrame {
     /// Sets the bitmap image of the image frame. Replace the current bitmap or icon.
     /// Set `image` to `None` to remove the image
     pub fn set_bitmap<'a>(&self, image: Option<&'a Bitmap>) {
-        use winapi::um::winuser::{STM_SETIMAGE, IMAGE_BITMAP};
-        use winapi::shared::minwindef::{WPARAM, LPARAM};
+        use crate::win32::sys::um::winuser::{STM_SETIMAGE, IMAGE_BITMAP};
+        use crate::win32::sys::shared::minwindef::{WPARAM, LPARAM};
 
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
 
@@ -92,8 +92,8 @@ impl ImageFrame {
     /// Sets the bitmap image of the image frame. Replace the current bitmap or icon.
     /// Set `image` to `None` to remove the image
     pub fn set_icon<'a>(&self, image: Option<&'a Icon>) {
-        use winapi::um::winuser::{STM_SETIMAGE, IMAGE_ICON};
-        use winapi::shared::minwindef::{WPARAM, LPARAM};
+        use crate::win32::sys::um::winuser::{STM_SETIMAGE, IMAGE_ICON};
+        use crate::win32::sys::shared::minwindef::{WPARAM, LPARAM};
 
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
 
@@ -108,10 +108,10 @@ impl ImageFrame {
     /// If the image frame has a bitmap, the value will be returned in `bitmap`
     /// If the image frame has a icon, the value will be returned in `icon`
     pub fn image<'a>(&self, bitmap: &mut Option<Bitmap>, icon: &mut Option<Icon>) {
-        use winapi::um::winuser::{STM_GETIMAGE, IMAGE_BITMAP, IMAGE_ICON};
-        use winapi::shared::minwindef::WPARAM;
-        use winapi::shared::windef::HBITMAP;
-        use winapi::um::winnt::HANDLE;
+        use crate::win32::sys::um::winuser::{STM_GETIMAGE, IMAGE_BITMAP, IMAGE_ICON};
+        use crate::win32::sys::shared::minwindef::WPARAM;
+        use crate::win32::sys::shared::windef::HBITMAP;
+        use crate::win32::sys::um::winnt::HANDLE;
 
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
         let bitmap_handle = wh::send_message(handle, STM_GETIMAGE, IMAGE_BITMAP as WPARAM, 0);
@@ -188,18 +188,19 @@ impl ImageFrame {
 
     /// Winapi flags required by the control
     pub fn forced_flags(&self) -> u32 {
-        use winapi::um::winuser::{SS_NOTIFY, WS_CHILD, SS_CENTERIMAGE};
+        use crate::win32::sys::um::winuser::{SS_NOTIFY, WS_CHILD, SS_CENTERIMAGE};
 
         WS_CHILD | SS_NOTIFY | SS_CENTERIMAGE
     }
 
     /// Change the label background color to transparent.
     /// Change the checkbox background color.
+    #[cfg(not(feature = "headless"))]
     fn hook_background_color(&mut self, c: [u8; 3]) {
         use crate::bind_raw_event_handler_inner;
-        use winapi::um::winuser::{WM_CTLCOLORSTATIC};
-        use winapi::shared::{basetsd::UINT_PTR, windef::{HWND}, minwindef::LRESULT};
-        use winapi::um::wingdi::{CreateSolidBrush, RGB};
+        use crate::win32::sys::um::winuser::{WM_CTLCOLORSTATIC};
+        use crate::win32::sys::shared::{basetsd::UINT_PTR, windef::{HWND}, minwindef::LRESULT};
+        use crate::win32::sys::um::wingdi::{CreateSolidBrush, RGB};
 
         if self.handle.blank() { panic!("{}", NOT_BOUND); }
         let handle = self.handle.hwnd().expect(BAD_HANDLE);
@@ -225,6 +226,9 @@ impl ImageFrame {
         *self.handler0.borrow_mut() = Some(handler.unwrap());
     }
 
+    #[cfg(feature = "headless")]
+    fn hook_background_color(&mut self, _c: [u8; 3]) {}
+
 }
 
 impl Drop for ImageFrame {
@@ -296,7 +300,7 @@ impl<'a> ImageFrameBuilder<'a> {
     }
 
     pub fn build(self, out: &mut ImageFrame) -> Result<(), NwgError> {
-        use winapi::um::winuser::{SS_BITMAP, SS_ICON};
+        use crate::win32::sys::um::winuser::{SS_BITMAP, SS_ICON};
 
         let mut flags = self.flags.map(|f| f.bits()).unwrap_or(out.flags());
         if self.icon.is_some() {
@@ -0,0 +1,218 @@
+/*!
+    Storage for the data attached to the items of a `ListView` or a `TreeView`. This module does not depend on the windowing system.
+
+    The items store a key in their `lParam`. The key `0` is never used, so items without data can keep the default `lParam`.
+*/
+use std::cell::{Cell, Ref, RefCell, RefMut};
+use std::collections::HashMap;
+
+
+/**
+    Owns the data of the items of a control. The data is dropped when the item is removed.
+
+    The data is borrowed by the accessors of the controls. If an item is removed while its data is borrowed,
+    the data is dropped the next time the store is modified. If an item is inserted while the data is borrowed,
+    its data is stored once the borrows end. Until then, `get` returns `None` for it.
+*/
+pub(crate) struct ItemDataStore<T> {
+    values: RefCell<HashMap<isize, T>>,
+    next_key: Cell<isize>,
+    released: RefCell<Vec<isize>>,
+    pending: RefCell<Vec<(isize, T)>>,
+}
+
+impl<T> ItemDataStore<T> {
+
+    /// Stores a value and returns its key
+    pub fn insert(&self, value: T) -> isize {
+        let key = self.reserve();
+        match self.values_mut() {
+            Some(mut values) => { values.insert(key, value); },
+            None => { self.pending.borrow_mut().push((key, value)); }
+        }
+
+        key
+    }
+
+    /// Returns a new key that has no value. Used by the items that need a key before having data.
+    pub fn reserve(&self) -> isize {
+        let key = self.next_key.get() + 1;
+        self.next_key.set(key);
+        key
+    }
+
+    /// Returns the value associated with a key
+    pub fn get(&self, key: isize) -> Option<Ref<'_, T>> {
+        drop(self.values_mut());
+
+        if self.released.borrow().contains(&key) {
+            return None;
+        }
+
+        Ref::filter_map(self.values.borrow(), |values| values.get(&key)).ok()
+    }
+
+    /// Drops the value associated with a key. Does nothing if the key is unknown.
+    pub fn remove(&self, key: isize) {
+        if key == 0 {
+            return;
+        }
+
+        match self.values_mut() {
+            Some(mut values) => { values.remove(&key); },
+            None => {
+                self.pending.borrow_mut().retain(|(k, _)| *k != key);
+
+                let mut released = self.released.borrow_mut();
+                if self.values.borrow().contains_key(&key) && !released.contains(&key) {
+                    released.push(key);
+                }
+            }
+        }
+    }
+
+    /// Drops every value
+    pub fn clear(&self) {
+        match self.values_mut() {
+            Some(mut values) => { values.clear(); },
+            None => {
+                self.pending.borrow_mut().clear();
+                *self.released.borrow_mut() = self.values.borrow().keys().copied().collect();
+            }
+        }
+    }
+
+    /// Mutable access to the values, or `None` if the values are borrowed.
+    /// Applies the removals and the insertions made while the store was borrowed.
+    fn values_mut(&self) -> Option<RefMut<'_, HashMap<isize, T>>> {
+        let mut values = self.values.try_borrow_mut().ok()?;
+        for key in self.released.borrow_mut().drain(..) {
+            values.remove(&key);
+        }
+
+        values.extend(self.pending.borrow_mut().drain(..));
+
+        Some(values)
+    }
+
+}
+
+impl<T> Default for ItemDataStore<T> {
+    fn default() -> Self {
+        ItemDataStore {
+            values: RefCell::new(HashMap::new()),
+            next_key: Cell::new(0),
+            released: RefCell::new(Vec::new()),
+            pending: RefCell::new(Vec::new()),
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+
+    #[test]
+    fn insert_get_remove() {
+        let store = ItemDataStore::default();
+        let a = store.insert("a".to_string());
+        let b = store.insert("b".to_string());
+
+        assert_ne!(a, 0);
+        assert_ne!(a, b);
+        assert_eq!(store.get(a).as_deref().map(String::as_str), Some("a"));
+
+        store.remove(a);
+        store.remove(0);
+        store.remove(1000);
+        assert!(store.get(a).is_none());
+        assert_eq!(store.get(b).as_deref().map(String::as_str), Some("b"));
+
+        let c = store.reserve();
+        assert!(c != a && c != b);
+        assert!(store.get(c).is_none());
+        store.remove(c);
+
+        store.clear();
+        assert!(store.get(b).is_none());
+    }
+
+    #[test]
+    fn values_are_dropped() {
+        let value = Rc::new(());
+        let store = ItemDataStore::default();
+        let a = store.insert(value.clone());
+        store.insert(value.clone());
+        assert_eq!(Rc::strong_count(&value), 3);
+
+        store.remove(a);
+        assert_eq!(Rc::strong_count(&value), 2);
+
+        drop(store);
+        assert_eq!(Rc::strong_count(&value), 1);
+    }
+
+    #[test]
+    fn remove_while_borrowed() {
+        let value = Rc::new(());
+        let store = ItemDataStore::default();
+        let a = store.insert(value.clone());
+        let b = store.insert(value.clone());
+
+        {
+            let _borrowed = store.get(a).unwrap();
+            store.remove(a);
+            assert!(store.get(a).is_none());
+            assert_eq!(Rc::strong_count(&value), 3);
+        }
+
+        let c = store.insert(value.clone());
+        assert_eq!(Rc::strong_count(&value), 3);
+        assert!(store.get(a).is_none());
+
+        {
+            let _borrowed = store.get(b).unwrap();
+            store.clear();
+            assert!(store.get(c).is_none());
+        }
+
+        store.insert(value.clone());
+        assert!(store.get(b).is_none() && store.get(c).is_none());
+        assert_eq!(Rc::strong_count(&value), 2);
+    }
+
+    #[test]
+    fn insert_while_borrowed() {
+        let value = Rc::new(());
+        let store = ItemDataStore::default();
+        let a = store.insert(value.clone());
+
+        let (b, c) = {
+            let _borrowed = store.get(a).unwrap();
+            let b = store.insert(value.clone());
+            let c = store.insert(value.clone());
+            assert!(store.get(b).is_none());
+
+            store.remove(c);
+            assert_eq!(Rc::strong_count(&value), 3);
+
+            (b, c)
+        };
+
+        assert!(store.get(b).is_some());
+        assert!(store.get(c).is_none());
+
+        {
+            let _borrowed = store.get(b).unwrap();
+            store.insert(value.clone());
+            store.clear();
+        }
+
+        store.insert(value.clone());
+        assert!(store.get(a).is_none() && store.get(b).is_none());
+        assert_eq!(Rc::strong_count(&value), 2);
+    }
+
+}
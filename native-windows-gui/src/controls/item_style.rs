@@ -0,0 +1,58 @@
+/*!
+    Colors and font of a single item of a `ListView` or a `TreeView`. The styles are applied by the
+    controls when they paint their items (`NM_CUSTOMDRAW`).
+*/
+use winapi::shared::windef::{COLORREF, HFONT};
+use winapi::shared::minwindef::LRESULT;
+use winapi::um::commctrl::NMCUSTOMDRAW;
+
+
+/**
+    The colors and the font of an item. The fields left to `None` use the control defaults.
+
+    `font` is the handle of a `Font`. The style does not own the font: it must stay alive as long as an item uses it.
+    Selected items keep the system selection colors.
+
+    ```rust
+    use native_windows_gui as nwg;
+
+    fn highlight_error(view: &nwg::ListView, row: usize, bold: &nwg::Font) {
+        let style = nwg::ItemStyle {
+            text_color: Some([200, 0, 0]),
+            background_color: Some([255, 230, 230]),
+            font: Some(bold.handle),
+        };
+
+        view.set_item_style(row, Some(style));
+    }
+    ```
+*/
+#[derive(Default, Clone, Debug, PartialEq, Eq)]
+pub struct ItemStyle {
+    pub text_color: Option<[u8; 3]>,
+    pub background_color: Option<[u8; 3]>,
+    pub font: Option<HFONT>,
+}
+
+/// Applies a style to an item in the `CDDS_ITEMPREPAINT` stage of a custom draw notification.
+/// Returns the value expected by the control.
+pub(crate) fn apply_item_style(draw: &NMCUSTOMDRAW, text: &mut COLORREF, background: &mut COLORREF, style: &ItemStyle) -> LRESULT {
+    use winapi::um::wingdi::{RGB, SelectObject};
+    use winapi::um::commctrl::{CDRF_DODEFAULT, CDRF_NEWFONT};
+
+    if let Some([r, g, b]) = style.text_color {
+        *text = RGB(r, g, b);
+    }
+
+    if let Some([r, g, b]) = style.background_color {
+        *background = RGB(r, g, b);
+    }
+
+    match style.font {
+        Some(font) if !font.is_null() => {
+            unsafe { SelectObject(draw.hdc, font as _); }
+            CDRF_NEWFONT
+        },
+        _ => CDRF_DODEFAULT
+    }
+}
@@ -1,9 +1,9 @@
-use winapi::um::{
+use crate::win32::sys::um::{
     winuser::{WS_VISIBLE, WS_DISABLED, SS_WORDELLIPSIS},
     wingdi::DeleteObject
 };
 
-use winapi::shared::windef::HBRUSH;
+use crate::win32::sys::shared::windef::HBRUSH;
 use crate::win32::window_helper as wh;
 use crate::win32::base_helper::check_hwnd;
 use crate::{Font, NwgError, HTextAlign, VTextAlign, RawEventHandler, unbind_raw_event_handler};
@@ -193,27 +193,28 @@ impl Label {
 
     /// Winapi base flags used during window creation
     pub fn flags(&self) -> u32 {
-        use winapi::um::winuser::{SS_NOPREFIX, SS_LEFT};
+        use crate::win32::sys::um::winuser::{SS_NOPREFIX, SS_LEFT};
 
         WS_VISIBLE | SS_NOPREFIX | SS_LEFT
     }
 
     /// Winapi flags required by the control
     pub fn forced_flags(&self) -> u32 {
-        use winapi::um::winuser::{SS_NOTIFY, WS_CHILD};
+        use crate::win32::sys::um::winuser::{SS_NOTIFY, WS_CHILD};
 
         WS_CHILD | SS_NOTIFY
     }
 
     /// Center the text vertically.
+    #[cfg(not(feature = "headless"))]
     fn hook_non_client_size(&mut self, bg: Option<[u8; 3]>, v_align: VTextAlign) {
         use crate::bind_raw_event_handler_inner;
-        use winapi::shared::windef::{HWND, HGDIOBJ, RECT, POINT};
-        use winapi::shared::{basetsd::UINT_PTR, minwindef::LRESULT};
-        use winapi::um::winuser::{WM_CTLCOLORSTATIC, WM_NCCALCSIZE, WM_NCPAINT, WM_SIZE, DT_CALCRECT, DT_LEFT, NCCALCSIZE_PARAMS, COLOR_WINDOW};
-        use winapi::um::winuser::{SWP_NOOWNERZORDER, SWP_NOSIZE, SWP_NOMOVE, SWP_FRAMECHANGED};
-        use winapi::um::winuser::{GetDC, DrawTextW, ReleaseDC, GetClientRect, GetWindowRect, FillRect, ScreenToClient, SetWindowPos, GetWindowTextW, GetWindowTextLengthW};
-        use winapi::um::wingdi::{SelectObject, CreateSolidBrush, RGB};
+        use crate::win32::sys::shared::windef::{HWND, HGDIOBJ, RECT, POINT};
+        use crate::win32::sys::shared::{basetsd::UINT_PTR, minwindef::LRESULT};
+        use crate::win32::sys::um::winuser::{WM_CTLCOLORSTATIC, WM_NCCALCSIZE, WM_NCPAINT, WM_SIZE, DT_CALCRECT, DT_LEFT, NCCALCSIZE_PARAMS, COLOR_WINDOW};
+        use crate::win32::sys::um::winuser::{SWP_NOOWNERZORDER, SWP_NOSIZE, SWP_NOMOVE, SWP_FRAMECHANGED};
+        use crate::win32::sys::um::winuser::{GetDC, DrawTextW, ReleaseDC, GetClientRect, GetWindowRect, FillRect, ScreenToClient, SetWindowPos, GetWindowTextW, GetWindowTextLengthW};
+        use crate::win32::sys::um::wingdi::{SelectObject, CreateSolidBrush, RGB};
         use std::{mem, ptr};
 
         if self.handle.blank() { panic!("{}", NOT_BOUND); }
@@ -358,6 +359,9 @@ impl Label {
         }
     }
 
+    #[cfg(feature = "headless")]
+    fn hook_non_client_size(&mut self, _bg: Option<[u8; 3]>, _v_align: VTextAlign) {}
+
 }
 
 impl PartialEq for Label {
@@ -453,7 +457,7 @@ impl<'a> LabelBuilder<'a> {
     }
 
     pub fn build(self, out: &mut Label) -> Result<(), NwgError> {
-        use winapi::um::winuser::{SS_LEFT, SS_RIGHT, SS_CENTER};
+        use crate::win32::sys::um::winuser::{SS_LEFT, SS_RIGHT, SS_CENTER};
 
         let mut flags = self.flags.map(|f| f.bits()).unwrap_or(out.flags());
 
@@ -1,6 +1,6 @@
-use winapi::shared::windef::HWND;
-use winapi::shared::minwindef::{WPARAM, LPARAM};
-use winapi::um::winuser::{LBS_MULTIPLESEL, LBS_NOSEL, WS_VISIBLE, WS_DISABLED, WS_TABSTOP};
+use crate::win32::sys::shared::windef::HWND;
+use crate::win32::sys::shared::minwindef::{WPARAM, LPARAM};
+use crate::win32::sys::um::winuser::{LBS_MULTIPLESEL, LBS_NOSEL, WS_VISIBLE, WS_DISABLED, WS_TABSTOP};
 use crate::win32::window_helper as wh;
 use crate::win32::base_helper::{to_utf16, from_utf16, check_hwnd};
 use crate::{Font, NwgError};
@@ -8,6 +8,7 @@ use super::{ControlBase, ControlHandle};
 use std::cell::{Ref, RefMut, RefCell};
 use std::fmt::Display;
 use std::ops::Range;
+use std::rc::Rc;
 use std::mem;
 
 const NOT_BOUND: &'static str = "ListBox is not yet bound to a winapi object";
@@ -77,7 +78,7 @@ fn build_listbox(listb: &mut nwg::ListBox<&'static str>, window: &nwg::Window, f
 #[derive(Default)]
 pub struct ListBox<D: Display+Default> {
     pub handle: ControlHandle,
-    collection: RefCell<Vec<D>>
+    collection: Rc<RefCell<Vec<D>>>
 }
 
 impl<D: Display+Default> ListBox<D> {
@@ -100,7 +101,7 @@ impl<D: Display+Default> ListBox<D> {
 
     /// Add a new item to the listbox. Sort the collection if the listbox is sorted.
     pub fn push(&self, item: D) {
-        use winapi::um::winuser::LB_ADDSTRING;
+        use crate::win32::sys::um::winuser::LB_ADDSTRING;
 
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
         let display = format!("{}", item);
@@ -118,7 +119,7 @@ impl<D: Display+Default> ListBox<D> {
     /// SPECIAL behaviour! If index is `std::usize::MAX`, the item is added at the end of the collection.
     /// The method will still panic if `index > len` with every other values.
     pub fn insert(&self, index: usize, item: D) {
-        use winapi::um::winuser::LB_INSERTSTRING;
+        use crate::win32::sys::um::winuser::LB_INSERTSTRING;
 
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
         let display = format!("{}", item);
@@ -140,7 +141,7 @@ impl<D: Display+Default> ListBox<D> {
     /// Remove the item at the selected index and returns it.
     /// Panic of the index is out of bounds
     pub fn remove(&self, index: usize) -> D {
-        use winapi::um::winuser::LB_DELETESTRING;
+        use crate::win32::sys::um::winuser::LB_DELETESTRING;
 
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
         wh::send_message(handle, LB_DELETESTRING, index as WPARAM, 0);
@@ -152,7 +153,7 @@ impl<D: Display+Default> ListBox<D> {
     /// Return the index of the currencty selected item for single value list box.
     /// Return `None` if no item is selected.
     pub fn selection(&self) -> Option<usize> {
-        use winapi::um::winuser::{LB_GETCURSEL, LB_ERR};
+        use crate::win32::sys::um::winuser::{LB_GETCURSEL, LB_ERR};
 
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
         let index = wh::send_message(handle, LB_GETCURSEL , 0, 0);
@@ -164,7 +165,7 @@ impl<D: Display+Default> ListBox<D> {
     /// Return the number of selected item in the list box
     /// Returns 0 for single select list box
     pub fn multi_selection_len(&self) -> usize {
-        use winapi::um::winuser::{LB_GETSELCOUNT, LB_ERR};
+        use crate::win32::sys::um::winuser::{LB_GETSELCOUNT, LB_ERR};
 
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
         match wh::send_message(handle, LB_GETSELCOUNT, 0, 0) {
@@ -176,7 +177,7 @@ impl<D: Display+Default> ListBox<D> {
     /// Return a list index
     /// Returns an empty vector for single select list box.
     pub fn multi_selection(&self) -> Vec<usize> {
-        use winapi::um::winuser::{LB_GETSELCOUNT, LB_GETSELITEMS, LB_ERR};
+        use crate::win32::sys::um::winuser::{LB_GETSELCOUNT, LB_GETSELITEMS, LB_ERR};
 
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
         let select_count = match wh::send_message(handle, LB_GETSELCOUNT, 0, 0) {
@@ -204,8 +205,8 @@ impl<D: Display+Default> ListBox<D> {
     /// Return the display value of the currenctly selected item for single value
     /// Return `None` if no item is selected. This reads the visual value.
     pub fn selection_string(&self) -> Option<String> {
-        use winapi::um::winuser::{LB_GETCURSEL, LB_GETTEXTLEN, LB_GETTEXT, LB_ERR};
-        use winapi::shared::ntdef::WCHAR;
+        use crate::win32::sys::um::winuser::{LB_GETCURSEL, LB_GETTEXTLEN, LB_GETTEXT, LB_ERR};
+        use crate::win32::sys::shared::ntdef::WCHAR;
 
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
         let index = wh::send_message(handle, LB_GETCURSEL, 0, 0);
@@ -228,7 +229,7 @@ impl<D: Display+Default> ListBox<D> {
     /// Does nothing if the index is out of bound
     /// If the value is None, remove the selected value
     pub fn set_selection(&self, index: Option<usize>) {
-        use winapi::um::winuser::LB_SETCURSEL;
+        use crate::win32::sys::um::winuser::LB_SETCURSEL;
 
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
         let index = index.unwrap_or(-1isize as usize);
@@ -237,35 +238,35 @@ impl<D: Display+Default> ListBox<D> {
 
     /// Select the item as index `index` in a multi item list box
     pub fn multi_add_selection(&self, index: usize) {
-        use winapi::um::winuser::LB_SETSEL;
+        use crate::win32::sys::um::winuser::LB_SETSEL;
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
         wh::send_message(handle, LB_SETSEL, 1, index as LPARAM);
     }
 
     /// Unselect the item as index `index` in a multi item list box
     pub fn multi_remove_selection(&self, index: usize) {
-        use winapi::um::winuser::LB_SETSEL;
+        use crate::win32::sys::um::winuser::LB_SETSEL;
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
         wh::send_message(handle, LB_SETSEL, 0, index as LPARAM);
     }
 
     /// Unselect every item in the list box
     pub fn unselect_all(&self) {
-        use winapi::um::winuser::LB_SETSEL;
+        use crate::win32::sys::um::winuser::LB_SETSEL;
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
         wh::send_message(handle, LB_SETSEL, 0, -1);
     }
 
     /// Select every item in the list box
     pub fn select_all(&self) {
-        use winapi::um::winuser::LB_SETSEL;
+        use crate::win32::sys::um::winuser::LB_SETSEL;
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
         wh::send_message(handle, LB_SETSEL, 1, -1);
     }
 
     /// Select a range of items in a multi list box
     pub fn multi_select_range(&self, range: Range<usize>) {
-        use winapi::um::winuser::LB_SELITEMRANGEEX;
+        use crate::win32::sys::um::winuser::LB_SELITEMRANGEEX;
 
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
         let start = range.start as WPARAM;
@@ -275,7 +276,7 @@ impl<D: Display+Default> ListBox<D> {
 
     /// Unselect a range of items in a multi list box
     pub fn multi_unselect_range(&self, range: Range<usize>) {
-        use winapi::um::winuser::LB_SELITEMRANGEEX;
+        use crate::win32::sys::um::winuser::LB_SELITEMRANGEEX;
 
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
         let start = range.start as LPARAM;
@@ -287,7 +288,7 @@ impl<D: Display+Default> ListBox<D> {
     /// The search is not case sensitive, so this string can contain any combination of uppercase and lowercase letters.
     /// Return the index of the selected string or None if the search was not successful
     pub fn set_selection_string(&self, value: &str) -> Option<usize> {
-        use winapi::um::winuser::{LB_SELECTSTRING, LB_ERR};
+        use crate::win32::sys::um::winuser::{LB_SELECTSTRING, LB_ERR};
 
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
         let os_string = to_utf16(value);
@@ -305,7 +306,7 @@ impl<D: Display+Default> ListBox<D> {
     /// Check if the item at `index` is selected by the user
     /// Return `false` if the index is out of range.
     pub fn selected(&self, index: usize) -> bool {
-        use winapi::um::winuser::LB_GETSEL;
+        use crate::win32::sys::um::winuser::LB_GETSEL;
 
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
         wh::send_message(handle, LB_GETSEL, index as WPARAM, 0) > 0
@@ -314,7 +315,7 @@ impl<D: Display+Default> ListBox<D> {
     /// Update the visual of the control with the inner collection.
     /// This rebuild every item in the list box and can take some time on big collections.
     pub fn sync(&self) {
-        use winapi::um::winuser::{LB_ADDSTRING, LB_INITSTORAGE};
+        use crate::win32::sys::um::winuser::{LB_ADDSTRING, LB_INITSTORAGE};
 
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
 
@@ -335,7 +336,7 @@ impl<D: Display+Default> ListBox<D> {
 
     /// Set the item collection of the list box. Return the old collection
     pub fn set_collection(&self, mut col: Vec<D>) -> Vec<D> {
-        use winapi::um::winuser::LB_ADDSTRING;
+        use crate::win32::sys::um::winuser::LB_ADDSTRING;
 
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
 
@@ -363,7 +364,7 @@ impl<D: Display+Default> ListBox<D> {
 
     /// Return the number of items in the control. NOT the inner rust collection
     pub fn len(&self) -> usize {
-        use winapi::um::winuser::LB_GETCOUNT;
+        use crate::win32::sys::um::winuser::LB_GETCOUNT;
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
         wh::send_message(handle, LB_GETCOUNT, 0, 0) as usize
     }
@@ -478,14 +479,21 @@ impl<D: Display+Default> ListBox<D> {
 
     /// Winapi flags required by the control
     pub fn forced_flags(&self) -> u32 {
-        use winapi::um::winuser::{LBS_HASSTRINGS, WS_BORDER, WS_VSCROLL, LBS_NOTIFY, WS_CHILD};
+        use crate::win32::sys::um::winuser::{LBS_HASSTRINGS, WS_BORDER, WS_VSCROLL, LBS_NOTIFY, WS_CHILD};
 
         LBS_HASSTRINGS | LBS_NOTIFY | WS_BORDER  | WS_CHILD | WS_VSCROLL
     }
 
+    /// Return a second list box that shares the handle and the collection of this one. Used by the bindings.
+    /// The handle of the alias must be cleared before it is dropped, otherwise the control is destroyed.
+    #[cfg(feature = "bindings")]
+    pub(crate) fn alias(&self) -> ListBox<D> {
+        ListBox { handle: self.handle, collection: self.collection.clone() }
+    }
+
     /// Remove all value displayed in the control without touching the rust collection
     fn clear_inner(&self, handle: HWND) {
-        use winapi::um::winuser::LB_RESETCONTENT;
+        use crate::win32::sys::um::winuser::LB_RESETCONTENT;
         wh::send_message(handle, LB_RESETCONTENT, 0, 0);
     }
 
@@ -1,5 +1,7 @@
-use winapi::shared::windef::{HBITMAP, HBRUSH};
-use winapi::um::winuser::{WS_VISIBLE, WS_DISABLED, WS_TABSTOP};
+use winapi::shared::windef::{HBITMAP, HBRUSH, HWND};
+use winapi::shared::minwindef::{UINT, WPARAM, LPARAM};
+use winapi::ctypes::c_int;
+use winapi::um::winuser::{WS_VISIBLE, WS_DISABLED, WS_TABSTOP, WM_USER, NMHDR};
 use winapi::um::commctrl::{
     LVS_ICON, LVS_SMALLICON, LVS_LIST, LVS_REPORT, LVS_NOCOLUMNHEADER, LVCOLUMNW, LVCFMT_LEFT, LVCFMT_RIGHT, LVCFMT_CENTER, LVCFMT_JUSTIFYMASK,
     LVCFMT_IMAGE, LVCFMT_BITMAP_ON_RIGHT, LVCFMT_COL_HAS_IMAGES, LVITEMW, LVIF_TEXT, LVCF_WIDTH, LVCF_TEXT, LVS_EX_GRIDLINES, LVS_EX_BORDERSELECT,
@@ -10,15 +12,25 @@ use super::{ControlBase, ControlHandle};
 use crate::win32::window_helper as wh;
 use crate::win32::base_helper::{to_utf16, from_utf16, check_hwnd};
 use crate::{NwgError, RawEventHandler, unbind_raw_event_handler};
-use std::{mem, ptr, rc::Rc, cell::RefCell};
+use super::list_view_sort::{ListViewDateFormat, compare_text, compare_numeric, compare_date};
+use super::item_data::ItemDataStore;
+use super::item_style::{ItemStyle, apply_item_style};
+use std::{mem, ptr, rc::Rc, cell::{Cell, Ref, RefCell}, ops::Range, cmp::Ordering, collections::HashMap};
 
 #[cfg(feature="image-list")]
 use crate::ImageList;
 
+#[cfg(feature="drag-drop")]
+use crate::{DragPayload, DropEffect};
+
 
 const NOT_BOUND: &'static str = "ListView is not yet bound to a winapi object";
 const BAD_HANDLE: &'static str = "INTERNAL ERROR: ListView handle is not HWND!";
 
+// Messages posted to the list view to start and stop editing a cell outside of the notification that triggered the edit
+const NWG_LV_BEGIN_EDIT: UINT = WM_USER + 110;
+const NWG_LV_END_EDIT: UINT = WM_USER + 111;
+
 
 bitflags! {
     /**
@@ -189,12 +201,90 @@ pub struct ListViewColumn {
 }
 
 /// Represents a column sort indicator in a detailed list view
+/// When sorting, `Up` is the ascending order and `Down` is the descending order.
 #[derive(Copy, Clone, Debug)]
 pub enum ListViewColumnSortArrow {
     Up,
     Down,
 }
 
+type CompareFn = Rc<dyn Fn(&str, &str) -> Ordering>;
+
+/// How the text of two cells is compared when the rows of a list view are sorted. See `ListView::sort_by_column`.
+#[derive(Clone, Default)]
+pub enum ListViewComparator {
+    /// Compares the text code point by code point
+    Text,
+
+    /// Compares the text code point by code point, ignoring the case
+    TextNoCase,
+
+    /// Compares the text using the rules of the user locale. Digits are compared as numbers ("2" is before "10"). This is the default.
+    #[default]
+    Locale,
+
+    /// Compares the cells as numbers. The cells that are not numbers are sorted after the numbers.
+    Numeric,
+
+    /// Compares the cells as dates. The cells that are not dates are sorted after the dates.
+    Date(ListViewDateFormat),
+
+    /// Compares the cells with a custom function
+    Custom(CompareFn),
+}
+
+impl ListViewComparator {
+
+    /// Compares the text of two cells
+    pub fn compare(&self, a: &str, b: &str) -> Ordering {
+        use ListViewComparator::*;
+
+        match self {
+            Text => compare_text(a, b, false),
+            TextNoCase => compare_text(a, b, true),
+            Locale => compare_locale(a, b),
+            Numeric => compare_numeric(a, b),
+            Date(format) => compare_date(a, b, *format),
+            Custom(cmp) => cmp(a, b),
+        }
+    }
+
+}
+
+/// The editor used to modify the cells of a list view column. See `ListView::set_column_editor`
+#[derive(Clone, Debug, PartialEq)]
+pub enum ListViewEditor {
+    /// A single line text input
+    Text,
+
+    /// A drop down list with a fixed set of choices
+    ComboBox(Vec<String>),
+
+    /// A click on the cell switches its text between the two values. `checked` is used if the cell has any other text.
+    CheckBox { checked: String, unchecked: String },
+}
+
+/// Data of the `OnListViewBeginEdit` and `OnListViewEndEdit` notifications
+#[repr(C)]
+pub(crate) struct ListViewEditNotify {
+    pub hdr: NMHDR,
+    pub row_index: usize,
+    pub column_index: usize,
+    pub text: String,
+    pub cancelled: bool,
+}
+
+type EditValidator = dyn Fn(usize, usize, &str) -> bool;
+
+/// The cell being edited and its editor window
+struct ActiveEdit {
+    row_index: usize,
+    column_index: usize,
+    editor: HWND,
+    handler: RawEventHandler,
+}
+
+
 
 /// Represents a list view item parameters
 #[derive(Default, Clone, Debug)]
@@ -229,6 +319,65 @@ pub struct ListViewItem {
     pub image: i32,
 }
 
+/**
+    The rows of a virtual list view. See `ListViewBuilder::data_source`.
+
+    The methods are called while the list view is painted, so they should return quickly.
+    They must not modify the list view.
+*/
+pub trait ListViewDataSource {
+    /// Returns the number of rows. The list view only reads this value when `ListView::sync_data_source` is called.
+    fn row_count(&self) -> usize;
+
+    /// Returns the text of the cell at `row` and `column`
+    fn cell_text(&self, row: usize, column: usize) -> String;
+
+    /// Returns the index of the row image in the list view image list. Images are only shown with the `image-list` feature.
+    fn cell_image(&self, _row: usize) -> Option<i32> {
+        None
+    }
+
+    /// Called before the list view displays the rows in `rows`. Can be used to load a range of rows ahead of time.
+    fn cache_hint(&self, _rows: Range<usize>) {}
+
+    /// Returns the colors and the font of a row. See `ListView::set_item_style`.
+    fn row_style(&self, _row: usize) -> Option<ItemStyle> {
+        None
+    }
+
+    /**
+        Returns the first row, starting at `start`, whose first column begins with `text`. Used by the keyboard navigation.
+        If `wrap` is true, the search continues from the first row after reaching the end of the list.
+
+        The default implementation calls `cell_text` on every row and ignores the case.
+    */
+    fn find_item(&self, text: &str, start: usize, wrap: bool) -> Option<usize> {
+        let count = self.row_count();
+        let start = start.min(count);
+        let text = text.to_lowercase();
+
+        let wrapped = match wrap {
+            true => 0..start,
+            false => 0..0
+        };
+
+        (start..count).chain(wrapped)
+            .find(|&row| self.cell_text(row, 0).to_lowercase().starts_with(&text))
+    }
+}
+
+/// State shared between a list view and the hook on its parent window
+#[derive(Default)]
+struct ListViewState {
+    data_source: Option<Rc<dyn ListViewDataSource>>,
+    auto_sort: Cell<bool>,
+    comparators: RefCell<HashMap<usize, ListViewComparator>>,
+    editors: RefCell<HashMap<usize, ListViewEditor>>,
+    validator: RefCell<Option<Rc<EditValidator>>>,
+    edit: RefCell<Option<ActiveEdit>>,
+    styles: RefCell<HashMap<isize, ItemStyle>>,
+}
+
 struct ListViewDoubleBuffer {
     buffer: HBITMAP,
     size: [i32; 2],
@@ -255,6 +404,8 @@ Builder parameters:
   * `item_count`:       Number of item to preallocate
   * `list_style`:       The default style of the listview
   * `focus`:            The control receive focus after being created
+  * `data_source`:      Creates a virtual list view that reads its rows from a `ListViewDataSource`
+  * `auto_sort`:        Sort the rows when the user clicks on a column header. See `ListView::set_auto_sort`
 
 **Control events:**
   * `MousePress(_)`:   Generic mouse press events on the tree view
@@ -273,13 +424,103 @@ Builder parameters:
   * `OnListViewItemChanged`: When an item is selected/unselected in the listview
   * `OnListViewFocus`: When the list view has received focus
   * `OnListViewFocusLost`: When the list view has lost focus
+  * `OnListViewBeginEdit`: When the user starts editing a cell
+  * `OnListViewEndEdit`: When the user has finished editing a cell
+  * `OnListViewBeginDrag`: When the user starts dragging an item with the left mouse button
+
+**Sorting:**
+
+`sort_by_column` sorts the rows using the text of a column and a `ListViewComparator`. The rows are moved by the control,
+so the selection follows the sorted rows. With `set_auto_sort`, the list view sorts itself when a column header is clicked
+and updates the column sort arrows. The comparator of each column can be changed with `set_column_comparator`.
+
+**Item data:**
+
+A `ListView<T>` can attach a value of type `T` to each row with `insert_item_with_data` or `set_item_data`.
+The value is owned by the list view and follows its row when the rows are sorted. It is dropped when the row is removed
+(`remove_item`, `clear`) or when the list view is destroyed. `ListView` without a type parameter does not store any data.
+
+```rust
+use native_windows_gui as nwg;
+
+struct Contact { name: String, email: String }
+
+fn add_contact(view: &nwg::ListView<Contact>, contact: Contact) {
+    view.insert_item_with_data(contact.name.clone(), contact);
+}
+
+fn selected_email(view: &nwg::ListView<Contact>) -> Option<String> {
+    let row = view.selected_item()?;
+    view.item_data(row).map(|contact| contact.email.clone())
+}
+```
+
+**Editing:**
+
+In the `Detailed` style, the cells of a column can be edited in place once the column has an editor (`set_column_editor`).
+A click on a cell opens its editor. `Enter` or a click outside of the editor commits the new text and `Escape` cancels the edit.
+The text is checked by the validator set with `set_edit_validator` before the cell is updated. `OnListViewEndEdit`
+is raised in every case; its `cancelled` flag is set if the edit was cancelled or rejected.
+In virtual mode, the cells are not updated by the list view: the data source must be updated from `OnListViewEndEdit`.
+
+```rust
+use native_windows_gui as nwg;
+
+fn make_editable(view: &nwg::ListView) {
+    view.set_column_editor(0, Some(nwg::ListViewEditor::Text));
+    view.set_column_editor(1, Some(nwg::ListViewEditor::ComboBox(vec!["Low".into(), "High".into()])));
+    view.set_column_editor(2, Some(nwg::ListViewEditor::CheckBox { checked: "Yes".into(), unchecked: "No".into() }));
+
+    // Names cannot be empty
+    view.set_edit_validator(|_row, column, text| column != 0 || !text.trim().is_empty());
+}
+```
+
+**Item styles:**
+
+`set_item_style` changes the text color, the background color and the font of a single row. Like the item data, the style
+follows its row when the rows are sorted and is removed with the row. In virtual mode, the styles are read from
+`ListViewDataSource::row_style`.
+
+**Virtual mode:**
+
+A list view built with a `data_source` does not store its rows (`LVS_OWNERDATA`). The text and the images are requested
+from the data source when the rows are painted, so the control can display millions of rows.
+The number of rows is read from the data source when the list view is created and every time `sync_data_source` is called.
+In virtual mode, the rows cannot be modified with `insert_item`, `update_item` or `remove_item` and cannot store data. The selection methods work as usual.
+
+```rust
+use native_windows_gui as nwg;
+use std::rc::Rc;
+
+struct LogLines(Vec<String>);
+
+impl nwg::ListViewDataSource for LogLines {
+    fn row_count(&self) -> usize { self.0.len() }
+    fn cell_text(&self, row: usize, _col: usize) -> String { self.0[row].clone() }
+}
+
+fn build_log_view(view: &mut nwg::ListView, window: &nwg::Window, lines: Vec<String>) {
+    nwg::ListView::builder()
+        .list_style(nwg::ListViewStyle::Detailed)
+        .data_source(Rc::new(LogLines(lines)))
+        .parent(window)
+        .build(view)
+        .unwrap();
+
+    view.insert_column("Message");
+}
+```
 
 */
-#[derive(Default)]
-pub struct ListView {
+pub struct ListView<T = ()> {
     pub handle: ControlHandle,
     double_buffer: Option<Rc<RefCell<ListViewDoubleBuffer>>>,
+    state: Rc<ListViewState>,
+    data: Rc<ItemDataStore<T>>,
     handler0: Option<RawEventHandler>,
+    handler1: Option<RawEventHandler>,
+    handler2: Option<RawEventHandler>,
 }
 
 impl ListView {
@@ -297,10 +538,16 @@ impl ListView {
             ex_window_flags: 0,
             style: ListViewStyle::Simple,
             parent: None,
-            item_count: 0
+            item_count: 0,
+            data_source: None,
+            auto_sort: false
         }
     }
 
+}
+
+impl<T: 'static> ListView<T> {
+
     /// Sets the image list of the listview
     /// A listview can accept different kinds of image list. See `ListViewImageListType`
     #[cfg(feature="image-list")]
@@ -557,45 +804,135 @@ impl ListView {
     /// Returns column sort indicator
     pub fn column_sort_arrow(&self, column_index: usize) -> Option<ListViewColumnSortArrow> {
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        column_sort_arrow(handle, column_index)
+    }
 
-        let headers = wh::send_message(handle, LVM_GETHEADER, 0, 0);
-        if headers == 0 { return None; }
+    /// Enable or disable column sort indicator. Draws a up-arrow / down-arrow.
+    pub fn set_column_sort_arrow(&self, column_index: usize, sort: Option<ListViewColumnSortArrow>) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        set_column_sort_arrow(handle, column_index, sort);
+    }
 
-        let mut header: HDITEMW = unsafe { mem::zeroed() };
-        header.mask = HDI_FORMAT;
+    /// Sorts the rows using the text of the column at `column_index`. `Up` sorts in ascending order and `Down` in descending order.
+    /// The sort arrows are not modified. The selection, the focus and the item data stay with their row.
+    /// Does nothing in virtual mode, sort the data source and call `sync_data_source` instead.
+    pub fn sort_by_column(&self, column_index: usize, order: ListViewColumnSortArrow, comparator: &ListViewComparator) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        if !self.is_virtual() {
+            sort_rows(handle, column_index, order, comparator, &|| self.data.reserve());
+        }
+    }
 
-        let l = &mut header as *mut HDITEMW as _;
-        wh::send_message(headers as *mut _, HDM_GETITEMW, column_index, l);
+    /// Sets the comparator used when the rows are sorted by clicking on the header of the column at `column_index`
+    pub fn set_column_comparator(&self, column_index: usize, comparator: ListViewComparator) {
+        self.state.comparators.borrow_mut().insert(column_index, comparator);
+    }
+
+    /// Returns the comparator used when the rows are sorted by clicking on the header of the column at `column_index`
+    pub fn column_comparator(&self, column_index: usize) -> ListViewComparator {
+        self.state.comparators.borrow().get(&column_index).cloned().unwrap_or_default()
+    }
+
+    /**
+        Enable or disable the automatic sorting of the rows when the user clicks on a column header.
 
-        match header.fmt & (HDF_SORTUP | HDF_SORTDOWN) {
-            HDF_SORTUP => Some(ListViewColumnSortArrow::Up),
-            HDF_SORTDOWN => Some(ListViewColumnSortArrow::Down),
-            _ => None,
+        The first click on a header sorts the rows in ascending order and the next clicks reverse the order. The sort arrow of
+        the column is updated and the arrows of the other columns are removed. `OnListViewColumnClick` is still raised.
+        Auto sort does nothing in virtual mode.
+    */
+    pub fn set_auto_sort(&self, enabled: bool) {
+        self.state.auto_sort.set(enabled);
+    }
+
+    /// Returns `true` if the rows are sorted when the user clicks on a column header
+    pub fn auto_sort(&self) -> bool {
+        self.state.auto_sort.get()
+    }
+
+    /// Set the editor used to modify the cells of a column. `None` makes the column read only.
+    /// Editing only works with the `Detailed` list style.
+    pub fn set_column_editor(&self, column_index: usize, editor: Option<ListViewEditor>) {
+        let mut editors = self.state.editors.borrow_mut();
+        match editor {
+            Some(editor) => { editors.insert(column_index, editor); },
+            None => { editors.remove(&column_index); }
         }
     }
 
-    /// Enable or disable column sort indicator. Draws a up-arrow / down-arrow.
-    pub fn set_column_sort_arrow(&self, column_index: usize, sort: Option<ListViewColumnSortArrow>) {
+    /// Returns the editor of a column, or `None` if the column is read only
+    pub fn column_editor(&self, column_index: usize) -> Option<ListViewEditor> {
+        self.state.editors.borrow().get(&column_index).cloned()
+    }
+
+    /**
+        Set the function that validates the text entered by the user. The parameters are the row index, the column index
+        and the new text. If the function returns `false`, the cell keeps its old text and the edit is reported as cancelled.
+    */
+    pub fn set_edit_validator<F: Fn(usize, usize, &str) -> bool + 'static>(&self, f: F) {
+        *self.state.validator.borrow_mut() = Some(Rc::new(f));
+    }
+
+    /// Open the editor of a cell, as if the user had clicked on it. Returns `false` if the column has no editor
+    /// or if the cell does not exist.
+    pub fn edit_cell(&self, row_index: usize, column_index: usize) -> bool {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        if row_index >= self.len() || column_index >= self.column_len() {
+            return false;
+        }
+
+        begin_edit(handle, &self.state, row_index, column_index)
+    }
+
+    /// Close the current editor. If `cancel` is false, the text of the editor is validated and saved in the cell.
+    pub fn end_edit(&self, cancel: bool) {
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        end_edit(handle, &self.state, cancel);
+    }
+
+    /// Returns the row and the column of the cell being edited
+    pub fn edited_cell(&self) -> Option<(usize, usize)> {
+        self.state.edit.borrow().as_ref().map(|edit| (edit.row_index, edit.column_index))
+    }
+
+    /// Set the colors and the font of a row. `None` restores the default style.
+    /// Returns `false` if there is no row at `row_index` or if the list view is virtual.
+    pub fn set_item_style(&self, row_index: usize, style: Option<ItemStyle>) -> bool {
+        use winapi::um::commctrl::LVM_REDRAWITEMS;
 
-        let headers = wh::send_message(handle, LVM_GETHEADER, 0, 0);
-        if headers != 0 {
-            let mut header: HDITEMW = unsafe { mem::zeroed() };
-            header.mask = HDI_FORMAT;
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        if self.is_virtual() || !self.has_item(row_index, 0) {
+            return false;
+        }
 
-            let l = &mut header as *mut HDITEMW as _;
-            wh::send_message(headers as *mut _, HDM_GETITEMW, column_index, l);
+        // The style is stored with the key of the row so that it follows the row
+        let mut key = item_param(handle, row_index);
+        if key == 0 {
+            if style.is_none() {
+                return true;
+            }
 
-            header.fmt &= !(HDF_SORTUP | HDF_SORTDOWN);
-            match sort {
-                Some(ListViewColumnSortArrow::Up) => header.fmt |= HDF_SORTUP,
-                Some(ListViewColumnSortArrow::Down) => header.fmt |= HDF_SORTDOWN,
-                _ => {}
-            };
+            key = self.data.reserve();
+            set_item_param(handle, row_index, key);
+        }
 
-            let l = &mut header as *mut HDITEMW as _;
-            wh::send_message(headers as *mut _, HDM_SETITEMW, column_index, l);
+        match style {
+            Some(style) => { self.state.styles.borrow_mut().insert(key, style); },
+            None => { self.state.styles.borrow_mut().remove(&key); }
         }
+
+        wh::send_message(handle, LVM_REDRAWITEMS, row_index, row_index as _);
+        true
+    }
+
+    /// Returns the style of a row, or `None` if the row uses the default style
+    pub fn item_style(&self, row_index: usize) -> Option<ItemStyle> {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        if self.is_virtual() {
+            return None;
+        }
+
+        let key = item_param(handle, row_index);
+        self.state.styles.borrow().get(&key).cloned()
     }
 
     /// Set the width of a column
@@ -616,7 +953,7 @@ impl ListView {
 
     /// Select or unselect an item at `row_index`. Does nothing if the index is out of bounds.
     pub fn select_item(&self, row_index: usize, selected: bool) {
-        use winapi::um::commctrl::{LVM_SETITEMW, LVIF_STATE, LVIS_SELECTED};
+        use winapi::um::commctrl::{LVM_SETITEMSTATE, LVIF_STATE, LVIS_SELECTED};
 
         if !self.has_item(row_index, 0) {
             return;
@@ -630,7 +967,7 @@ impl ListView {
         item.state = match selected { true => LVIS_SELECTED, false => 0 };
         item.stateMask = LVIS_SELECTED;
 
-        wh::send_message(handle, LVM_SETITEMW , 0, &mut item as *mut LVITEMW as _);
+        wh::send_message(handle, LVM_SETITEMSTATE, row_index as _, &mut item as *mut LVITEMW as _);
     }
 
     /// Returns the index of the first selected item.
@@ -666,17 +1003,106 @@ impl ListView {
         indices
     }
 
+    /// Returns the text of the selected rows as a drag payload. The cells are separated by tabs and the rows by new lines.
+    #[cfg(feature="drag-drop")]
+    pub fn selected_items_payload(&self) -> DragPayload {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let columns = self.column_len().max(1);
+        let rows: Vec<String> = self.selected_items().into_iter()
+            .map(|row| (0..columns).map(|col| item_text(handle, row, col)).collect::<Vec<_>>().join("\t"))
+            .collect();
+
+        match rows.is_empty() {
+            true => DragPayload::new(),
+            false => DragPayload::new().text(&rows.join("\r\n"))
+        }
+    }
+
+    /**
+        Starts a drag and drop operation with the text of the selected rows. Usually called from `OnListViewBeginDrag`.
+        Blocks until the data is dropped or the drag is cancelled and returns the effect picked by the drop target.
+        Returns `DropEffect::NONE` if no rows are selected.
+    */
+    #[cfg(feature="drag-drop")]
+    pub fn drag_selected_items(&self, allowed: DropEffect) -> DropEffect {
+        let payload = self.selected_items_payload();
+        match payload.is_empty() {
+            true => DropEffect::NONE,
+            false => payload.start(allowed)
+        }
+    }
+
     /// Inserts a new item into the list view
     pub fn insert_item<I: Into<InsertListViewItem>>(&self, insert: I) {
+        self.insert_row(insert.into());
+    }
+
+    /// Inserts a new item into the list view and attaches `data` to its row. See `set_item_data`.
+    /// If the item could not be inserted, `data` is dropped.
+    pub fn insert_item_with_data<I: Into<InsertListViewItem>>(&self, insert: I, data: T) {
+        if let Some(row_index) = self.insert_row(insert.into()) {
+            self.set_item_data(row_index, data);
+        }
+    }
+
+    /// Attaches `data` to a row, replacing the data that was attached to it.
+    /// Returns `false` and drops `data` if there is no row at `row_index` or if the list view is virtual.
+    pub fn set_item_data(&self, row_index: usize, data: T) -> bool {
+        use winapi::um::commctrl::{LVM_SETITEMW, LVIF_PARAM};
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        if self.is_virtual() || !self.has_item(row_index, 0) {
+            return false;
+        }
+
+        let old_key = item_param(handle, row_index);
+        let key = self.data.insert(data);
+
+        let mut item: LVITEMW = unsafe { mem::zeroed() };
+        item.mask = LVIF_PARAM;
+        item.iItem = row_index as _;
+        item.lParam = key;
+
+        if wh::send_message(handle, LVM_SETITEMW, 0, &mut item as *mut LVITEMW as _) == 0 {
+            self.data.remove(key);
+            return false;
+        }
+
+        self.data.remove(old_key);
+
+        let mut styles = self.state.styles.borrow_mut();
+        if let Some(style) = styles.remove(&old_key) {
+            styles.insert(key, style);
+        }
+
+        true
+    }
+
+    /// Returns the data attached to a row, or `None` if the row does not exist or has no data.
+    ///
+    /// The data must be released before the row is removed: if the row is removed while its data is borrowed,
+    /// the data is only dropped once the borrows end, the next time the data of the rows is accessed.
+    /// Data attached to a row while data is borrowed is not returned until the borrows end.
+    pub fn item_data(&self, row_index: usize) -> Option<Ref<'_, T>> {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        if self.is_virtual() {
+            return None;
+        }
+
+        self.data.get(item_param(handle, row_index))
+    }
+
+    /// Inserts an item and returns the index of its row
+    fn insert_row(&self, insert: InsertListViewItem) -> Option<usize> {
         use winapi::um::commctrl::{LVM_INSERTITEMW, LVM_SETITEMW};
 
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
-        let insert = insert.into();
 
         let row_insert = insert.index.unwrap_or(i32::max_value());
         let column_insert = insert.column_index;
         if column_insert > 0 && !self.has_item(row_insert as _, 0) {
-            self.insert_item(InsertListViewItem { 
+            self.insert_row(InsertListViewItem { 
                 index: Some(row_insert),
                 column_index: 0,
                 text: None,
@@ -699,10 +1125,18 @@ impl ListView {
         item.pszText = text.as_mut_ptr();
         item.cchTextMax = text.len() as i32;
 
-        if column_insert == 0 {
-            wh::send_message(handle, LVM_INSERTITEMW , 0, &mut item as *mut LVITEMW as _);
+        let row_index = if column_insert == 0 {
+            wh::send_message(handle, LVM_INSERTITEMW , 0, &mut item as *mut LVITEMW as _)
         } else {
-            wh::send_message(handle, LVM_SETITEMW , 0, &mut item as *mut LVITEMW as _);
+            match wh::send_message(handle, LVM_SETITEMW , 0, &mut item as *mut LVITEMW as _) {
+                0 => -1,
+                _ => row_insert as _
+            }
+        };
+
+        match row_index {
+            -1 => None,
+            i => Some(i as usize)
         }
     }
 
@@ -793,6 +1227,7 @@ impl ListView {
 
     /// Remove all items on the seleted row. Returns `true` if an item was removed or false otherwise.
     /// To "remove" an item without deleting the row, use `update_item` and set the text to "".
+    /// The data attached to the row is dropped.
     pub fn remove_item(&self, row_index: usize) -> bool {
         use winapi::um::commctrl::LVM_DELETEITEM;
 
@@ -864,11 +1299,36 @@ impl ListView {
 
     /// Preallocate space for n number of item in the whole control.
     /// For example calling this method with n=1000 while the list has 500 items will add space for 500 new items.
+    ///
+    /// In virtual mode, this sets the number of rows displayed by the control without changing the scroll position.
     pub fn set_item_count(&self, n: u32) {
-        use winapi::um::commctrl::LVM_SETITEMCOUNT;
+        use winapi::um::commctrl::{LVM_SETITEMCOUNT, LVSICF_NOSCROLL};
 
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
-        wh::send_message(handle, LVM_SETITEMCOUNT, n as _, 0);
+        let flags = match self.is_virtual() {
+            true => LVSICF_NOSCROLL,
+            false => 0
+        };
+
+        wh::send_message(handle, LVM_SETITEMCOUNT, n as _, flags);
+    }
+
+    /// Returns the data source of a virtual list view, or `None` if the list view stores its rows.
+    pub fn data_source(&self) -> Option<Rc<dyn ListViewDataSource>> {
+        self.state.data_source.clone()
+    }
+
+    /// Returns `true` if the list view was built with a data source
+    pub fn is_virtual(&self) -> bool {
+        self.state.data_source.is_some()
+    }
+
+    /// Reads the number of rows of the data source and redraws the control. Call this after the data source was modified.
+    /// Does nothing if the list view is not in virtual mode.
+    pub fn sync_data_source(&self) {
+        if let Some(source) = self.state.data_source.as_ref() {
+            self.set_item_count(source.row_count() as u32);
+        }
     }
 
     /// Enable or disable the redrawing of the control when a new item is added.
@@ -903,12 +1363,16 @@ impl ListView {
         unsafe { InvalidateRect(handle, ptr::null(), 1); }
     }
 
-    /// Removes all item from the listview
+    /// Removes all item from the listview. The data attached to the rows is dropped.
     pub fn clear(&self) {
         use winapi::um::commctrl::LVM_DELETEALLITEMS;
 
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
         wh::send_message(handle, LVM_DELETEALLITEMS, 0, 0);
+
+        // `LVN_DELETEITEM` is not sent if a handler on the parent returned `TRUE` to `LVN_DELETEALLITEMS`
+        self.data.clear();
+        self.state.styles.borrow_mut().clear();
     }
 
     /// Returns true if the control currently has the keyboard focus
@@ -1072,9 +1536,158 @@ impl ListView {
         self.double_buffer = Some(rc_double_buffer);
     }
 
+    /// Handles the list view notifications sent to the parent window: the virtual mode requests, the auto sort,
+    /// the removal of the item data, the clicks on the editable cells and the item styles
+    fn hook_parent_notifications(&mut self) {
+        use crate::bind_raw_event_handler_inner;
+        use crate::win32::base_helper::from_wide_ptr;
+        use winapi::um::winuser::WM_NOTIFY;
+        use winapi::um::commctrl::{LVN_GETDISPINFOW, LVN_ODCACHEHINT, LVN_ODFINDITEMW, LVN_COLUMNCLICK, LVN_DELETEITEM, NM_CLICK,
+            NMLVDISPINFOW, NMLVCACHEHINT, NMLVFINDITEMW, NMLISTVIEW, NMITEMACTIVATE, LVHITTESTINFO, LVM_SUBITEMHITTEST, LVHT_ONITEM,
+            LVFI_STRING, LVFI_PARTIAL, LVFI_WRAP, NM_CUSTOMDRAW, NMLVCUSTOMDRAW, CDDS_PREPAINT, CDDS_ITEMPREPAINT, CDRF_NOTIFYITEMDRAW};
+        use winapi::shared::minwindef::LRESULT;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        let parent_handle = ControlHandle::Hwnd(wh::get_window_parent(handle));
+        let state = self.state.clone();
+        let data = self.data.clone();
+
+        let handler = bind_raw_event_handler_inner(&parent_handle, handle as usize, move |_hwnd, msg, _w, l| { unsafe {
+            if msg != WM_NOTIFY {
+                return None;
+            }
+
+            let nmhdr: &NMHDR = &*(l as *const NMHDR);
+            if nmhdr.hwndFrom != handle {
+                return None;
+            }
+
+            match (nmhdr.code, state.data_source.as_ref()) {
+                (LVN_GETDISPINFOW, Some(source)) => {
+                    let info: &mut NMLVDISPINFOW = &mut *(l as *mut NMLVDISPINFOW);
+                    fill_virtual_item(&**source, &mut info.item);
+                    None
+                },
+                (LVN_ODCACHEHINT, Some(source)) => {
+                    let hint: &NMLVCACHEHINT = &*(l as *const NMLVCACHEHINT);
+                    source.cache_hint((hint.iFrom as usize)..((hint.iTo + 1) as usize));
+                    None
+                },
+                (LVN_ODFINDITEMW, Some(source)) => {
+                    let find: &NMLVFINDITEMW = &*(l as *const NMLVFINDITEMW);
+                    if find.lvfi.flags & (LVFI_STRING | LVFI_PARTIAL) == 0 || find.lvfi.psz.is_null() {
+                        return Some(-1);
+                    }
+
+                    let text = from_wide_ptr(find.lvfi.psz as *mut u16, None);
+                    let wrap = find.lvfi.flags & LVFI_WRAP == LVFI_WRAP;
+                    let index = source.find_item(&text, find.iStart.max(0) as usize, wrap);
+                    Some(index.map(|i| i as LRESULT).unwrap_or(-1))
+                },
+                (LVN_COLUMNCLICK, None) if state.auto_sort.get() => {
+                    let info: &NMLISTVIEW = &*(l as *const NMLISTVIEW);
+                    let column = info.iSubItem as usize;
+                    let comparator = state.comparators.borrow().get(&column).cloned().unwrap_or_default();
+                    auto_sort_column(handle, column, &comparator, &|| data.reserve());
+                    None
+                },
+                (LVN_DELETEITEM, None) => {
+                    let info: &NMLISTVIEW = &*(l as *const NMLISTVIEW);
+                    data.remove(info.lParam);
+                    state.styles.borrow_mut().remove(&info.lParam);
+                    None
+                },
+                (NM_CUSTOMDRAW, source) if source.is_some() || !state.styles.borrow().is_empty() => {
+                    let draw: &mut NMLVCUSTOMDRAW = &mut *(l as *mut NMLVCUSTOMDRAW);
+                    match draw.nmcd.dwDrawStage {
+                        CDDS_PREPAINT => Some(CDRF_NOTIFYITEMDRAW),
+                        CDDS_ITEMPREPAINT => {
+                            let style = match source {
+                                Some(source) => source.row_style(draw.nmcd.dwItemSpec),
+                                None => state.styles.borrow().get(&draw.nmcd.lItemlParam).cloned()
+                            };
+
+                            style.map(|style| apply_item_style(&draw.nmcd, &mut draw.clrText, &mut draw.clrTextBk, &style))
+                        },
+                        _ => None
+                    }
+                },
+                (NM_CLICK, _) if !state.editors.borrow().is_empty() => {
+                    let info: &NMITEMACTIVATE = &*(l as *const NMITEMACTIVATE);
+                    let mut hit: LVHITTESTINFO = mem::zeroed();
+                    hit.pt = info.ptAction;
+
+                    let row = wh::send_message(handle, LVM_SUBITEMHITTEST, 0, &mut hit as *mut LVHITTESTINFO as _);
+                    if row >= 0 && hit.flags & LVHT_ONITEM != 0 && state.editors.borrow().contains_key(&(hit.iSubItem as usize)) {
+                        wh::post_message(handle, NWG_LV_BEGIN_EDIT, hit.iItem as WPARAM, hit.iSubItem as LPARAM);
+                    }
+
+                    None
+                },
+                _ => None
+            }
+        } });
+
+        self.handler1 = Some(handler.unwrap());
+    }
+
+    /// Handles the messages that open and close the cell editors. The editors are children of the list view,
+    /// so the list view also receives their commands.
+    fn hook_editors(&mut self) {
+        use crate::bind_raw_event_handler_inner;
+        use winapi::um::winuser::{WM_COMMAND, WM_VSCROLL, WM_HSCROLL, WM_MOUSEWHEEL, CBN_SELENDOK};
+        use winapi::shared::minwindef::HIWORD;
+
+        let state = self.state.clone();
+
+        let handler = bind_raw_event_handler_inner(&self.handle, 0x021, move |hwnd, msg, w, l| {
+            match msg {
+                NWG_LV_BEGIN_EDIT => {
+                    begin_edit(hwnd, &state, w, l as usize);
+                    Some(0)
+                },
+                NWG_LV_END_EDIT => {
+                    let current = state.edit.borrow().as_ref().map(|edit| edit.editor);
+                    if current == Some(l as HWND) {
+                        end_edit(hwnd, &state, w != 0);
+                    }
+                    Some(0)
+                },
+                WM_COMMAND if HIWORD(w as u32) == CBN_SELENDOK => {
+                    let current = state.edit.borrow().as_ref().map(|edit| edit.editor);
+                    if current == Some(l as HWND) {
+                        wh::post_message(hwnd, NWG_LV_END_EDIT, 0, l);
+                    }
+                    None
+                },
+                WM_VSCROLL | WM_HSCROLL | WM_MOUSEWHEEL => {
+                    end_edit(hwnd, &state, false);
+                    None
+                },
+                _ => None
+            }
+        });
+
+        self.handler2 = Some(handler.unwrap());
+    }
+
 }
 
-impl Drop for ListView {
+impl<T> Default for ListView<T> {
+    fn default() -> ListView<T> {
+        ListView {
+            handle: Default::default(),
+            double_buffer: None,
+            state: Default::default(),
+            data: Default::default(),
+            handler0: None,
+            handler1: None,
+            handler2: None,
+        }
+    }
+}
+
+impl<T> Drop for ListView<T> {
     fn drop(&mut self) {
         use winapi::um::wingdi::DeleteObject;
 
@@ -1090,6 +1703,18 @@ impl Drop for ListView {
             drop(unbind_raw_event_handler(h));
         }
 
+        if let Some(h) = self.handler1.as_ref() {
+            drop(unbind_raw_event_handler(h));
+        }
+
+        if let Some(h) = self.handler2.as_ref() {
+            drop(unbind_raw_event_handler(h));
+        }
+
+        if let Some(edit) = self.state.edit.borrow_mut().take() {
+            drop(unbind_raw_event_handler(&edit.handler));
+        }
+
         self.handle.destroy();
     }
 }
@@ -1106,6 +1731,8 @@ pub struct ListViewBuilder {
     ex_window_flags: u32,
     style: ListViewStyle,
     item_count: u32,
+    data_source: Option<Rc<dyn ListViewDataSource>>,
+    auto_sort: bool,
     parent: Option<ControlHandle>
 }
 
@@ -1172,10 +1799,26 @@ impl ListViewBuilder {
         self
     }
 
-    pub fn build(self, out: &mut ListView) -> Result<(), NwgError> {
+    pub fn data_source(mut self, source: Rc<dyn ListViewDataSource>) -> ListViewBuilder {
+        self.data_source = Some(source);
+        self
+    }
+
+    pub fn auto_sort(mut self, auto_sort: bool) -> ListViewBuilder {
+        self.auto_sort = auto_sort;
+        self
+    }
+
+    pub fn build<T: 'static>(self, out: &mut ListView<T>) -> Result<(), NwgError> {
+        use winapi::um::commctrl::LVS_OWNERDATA;
+
         let mut flags = self.flags.map(|f| f.bits()).unwrap_or(out.flags());
         flags |= self.style.bits();
 
+        if self.data_source.is_some() {
+            flags |= LVS_OWNERDATA;
+        }
+
         let parent = match self.parent {
             Some(p) => Ok(p),
             None => Err(NwgError::no_parent("ListView"))
@@ -1198,7 +1841,18 @@ impl ListViewBuilder {
             out.set_double_buffered();
         }
 
-        if self.item_count > 0 {
+        out.state = Rc::new(ListViewState {
+            data_source: self.data_source,
+            auto_sort: Cell::new(self.auto_sort),
+            ..Default::default()
+        });
+
+        out.hook_parent_notifications();
+        out.hook_editors();
+
+        if out.is_virtual() {
+            out.sync_data_source();
+        } else if self.item_count > 0 {
             out.set_item_count(self.item_count);
         }
 
@@ -1272,6 +1926,416 @@ impl From<String> for InsertListViewColumn {
     }
 }
 
+fn column_sort_arrow(handle: HWND, column_index: usize) -> Option<ListViewColumnSortArrow> {
+    let headers = wh::send_message(handle, LVM_GETHEADER, 0, 0);
+    if headers == 0 { return None; }
+
+    let mut header: HDITEMW = unsafe { mem::zeroed() };
+    header.mask = HDI_FORMAT;
+
+    let l = &mut header as *mut HDITEMW as _;
+    wh::send_message(headers as *mut _, HDM_GETITEMW, column_index, l);
+
+    match header.fmt & (HDF_SORTUP | HDF_SORTDOWN) {
+        HDF_SORTUP => Some(ListViewColumnSortArrow::Up),
+        HDF_SORTDOWN => Some(ListViewColumnSortArrow::Down),
+        _ => None,
+    }
+}
+
+fn set_column_sort_arrow(handle: HWND, column_index: usize, sort: Option<ListViewColumnSortArrow>) {
+    let headers = wh::send_message(handle, LVM_GETHEADER, 0, 0);
+    if headers != 0 {
+        let mut header: HDITEMW = unsafe { mem::zeroed() };
+        header.mask = HDI_FORMAT;
+
+        let l = &mut header as *mut HDITEMW as _;
+        wh::send_message(headers as *mut _, HDM_GETITEMW, column_index, l);
+
+        header.fmt &= !(HDF_SORTUP | HDF_SORTDOWN);
+        match sort {
+            Some(ListViewColumnSortArrow::Up) => header.fmt |= HDF_SORTUP,
+            Some(ListViewColumnSortArrow::Down) => header.fmt |= HDF_SORTDOWN,
+            _ => {}
+        };
+
+        let l = &mut header as *mut HDITEMW as _;
+        wh::send_message(headers as *mut _, HDM_SETITEMW, column_index, l);
+    }
+}
+
+/// Sorts a column after a click on its header and updates the sort arrows
+fn auto_sort_column(handle: HWND, column_index: usize, comparator: &ListViewComparator, reserve: &dyn Fn() -> LPARAM) {
+    use winapi::um::commctrl::HDM_GETITEMCOUNT;
+
+    let order = match column_sort_arrow(handle, column_index) {
+        Some(ListViewColumnSortArrow::Up) => ListViewColumnSortArrow::Down,
+        _ => ListViewColumnSortArrow::Up
+    };
+
+    let headers = wh::send_message(handle, LVM_GETHEADER, 0, 0);
+    let column_count = match headers {
+        0 => 0,
+        h => wh::send_message(h as _, HDM_GETITEMCOUNT, 0, 0).max(0) as usize
+    };
+
+    for column in 0..column_count {
+        let arrow = match column == column_index {
+            true => Some(order),
+            false => None
+        };
+
+        set_column_sort_arrow(handle, column, arrow);
+    }
+
+    sort_rows(handle, column_index, order, comparator, reserve);
+}
+
+/// The data passed to `compare_rows` during a sort
+struct SortContext<'a> {
+    texts: HashMap<LPARAM, String>,
+    order: ListViewColumnSortArrow,
+    comparator: &'a ListViewComparator,
+}
+
+/// Sorts the rows with the text of a column. The rows without a key get one from `reserve`.
+fn sort_rows(handle: HWND, column_index: usize, order: ListViewColumnSortArrow, comparator: &ListViewComparator, reserve: &dyn Fn() -> LPARAM) {
+    use winapi::um::commctrl::{LVM_SORTITEMS, LVM_GETITEMCOUNT};
+
+    // The position of the rows changes during the sort, so the texts are read once and stored by the key of their row
+    let row_count = wh::send_message(handle, LVM_GETITEMCOUNT, 0, 0) as usize;
+    let mut texts = HashMap::with_capacity(row_count);
+    for row_index in 0..row_count {
+        let mut key = item_param(handle, row_index);
+        if key == 0 {
+            key = reserve();
+            set_item_param(handle, row_index, key);
+        }
+
+        texts.insert(key, item_text(handle, row_index, column_index));
+    }
+
+    let context = SortContext { texts, order, comparator };
+    let compare: unsafe extern "system" fn(LPARAM, LPARAM, LPARAM) -> c_int = compare_rows;
+
+    wh::send_message(handle, LVM_SORTITEMS, &context as *const SortContext as _, compare as usize as _);
+}
+
+/// `LVM_SORTITEMS` callback. The first two parameters are the keys of the rows to compare.
+unsafe extern "system" fn compare_rows(key1: LPARAM, key2: LPARAM, context: LPARAM) -> c_int {
+    let context = &*(context as *const SortContext);
+    let text = |key: LPARAM| context.texts.get(&key).map(String::as_str).unwrap_or("");
+    let (text1, text2) = (text(key1), text(key2));
+
+    let ordering = match context.order {
+        ListViewColumnSortArrow::Up => context.comparator.compare(text1, text2),
+        ListViewColumnSortArrow::Down => context.comparator.compare(text2, text1),
+    };
+
+    match ordering {
+        Ordering::Less => -1,
+        Ordering::Equal => 0,
+        Ordering::Greater => 1,
+    }
+}
+
+/// Returns the `lParam` of a row, or 0 if the row does not exist
+fn item_param(handle: HWND, row_index: usize) -> LPARAM {
+    use winapi::um::commctrl::{LVM_GETITEMW, LVIF_PARAM};
+
+    let mut item: LVITEMW = unsafe { mem::zeroed() };
+    item.mask = LVIF_PARAM;
+    item.iItem = row_index as _;
+
+    match wh::send_message(handle, LVM_GETITEMW, 0, &mut item as *mut LVITEMW as _) {
+        0 => 0,
+        _ => item.lParam
+    }
+}
+
+/// Sets the `lParam` of a row
+fn set_item_param(handle: HWND, row_index: usize, key: LPARAM) {
+    use winapi::um::commctrl::{LVM_SETITEMW, LVIF_PARAM};
+
+    let mut item: LVITEMW = unsafe { mem::zeroed() };
+    item.mask = LVIF_PARAM;
+    item.iItem = row_index as _;
+    item.lParam = key;
+    wh::send_message(handle, LVM_SETITEMW, 0, &mut item as *mut LVITEMW as _);
+}
+
+/// Reads the whole text of a cell
+fn item_text(handle: HWND, row_index: usize, column_index: usize) -> String {
+    use winapi::um::commctrl::LVM_GETITEMTEXTW;
+
+    let mut buffer_size = 256;
+    loop {
+        let mut buffer: Vec<u16> = vec![0; buffer_size];
+
+        let mut item: LVITEMW = unsafe { mem::zeroed() };
+        item.iSubItem = column_index as _;
+        item.pszText = buffer.as_mut_ptr();
+        item.cchTextMax = buffer_size as _;
+
+        let length = wh::send_message(handle, LVM_GETITEMTEXTW, row_index as _, &mut item as *mut LVITEMW as _) as usize;
+        if length + 1 < buffer_size {
+            return from_utf16(&buffer[..length]);
+        }
+
+        buffer_size *= 2;
+    }
+}
+
+/// Opens the editor of a cell. Returns `false` if the column has no editor or if the editor could not be created.
+fn begin_edit(handle: HWND, state: &ListViewState, row_index: usize, column_index: usize) -> bool {
+    use winapi::um::winuser::SetFocus;
+    use winapi::um::commctrl::LVM_ENSUREVISIBLE;
+
+    end_edit(handle, state, false);
+
+    let editor = match state.editors.borrow().get(&column_index).cloned() {
+        Some(editor) => editor,
+        None => return false
+    };
+
+    let text = item_text(handle, row_index, column_index);
+
+    if let ListViewEditor::CheckBox { checked, unchecked } = editor {
+        notify_edit(handle, wh::NWG_LVN_BEGINEDIT, row_index, column_index, &text, false);
+
+        let text = match text == checked {
+            true => unchecked,
+            false => checked
+        };
+
+        finish_edit(handle, state, row_index, column_index, text, false);
+        return true;
+    }
+
+    wh::send_message(handle, LVM_ENSUREVISIBLE, row_index, 0);
+
+    let editor = match create_editor(handle, row_index, column_index, &editor, &text) {
+        Some(editor) => editor,
+        None => return false
+    };
+
+    *state.edit.borrow_mut() = Some(ActiveEdit {
+        row_index,
+        column_index,
+        editor,
+        handler: hook_editor(handle, editor),
+    });
+
+    notify_edit(handle, wh::NWG_LVN_BEGINEDIT, row_index, column_index, &text, false);
+
+    // The edit may have been closed by the `OnListViewBeginEdit` handlers
+    if state.edit.borrow().as_ref().map(|edit| edit.editor) == Some(editor) {
+        unsafe { SetFocus(editor); }
+    }
+
+    true
+}
+
+/// Closes the current editor and saves its text in the cell unless `cancel` is true
+fn end_edit(handle: HWND, state: &ListViewState, cancel: bool) {
+    use winapi::um::winuser::{GetFocus, SetFocus, DestroyWindow};
+
+    let edit = match state.edit.borrow_mut().take() {
+        Some(edit) => edit,
+        None => return
+    };
+
+    let text = unsafe { wh::get_window_text(edit.editor) };
+    drop(unbind_raw_event_handler(&edit.handler));
+
+    unsafe {
+        if GetFocus() == edit.editor {
+            SetFocus(handle);
+        }
+
+        DestroyWindow(edit.editor);
+    }
+
+    finish_edit(handle, state, edit.row_index, edit.column_index, text, cancel);
+}
+
+/// Validates the new text of a cell, updates the cell and raises `OnListViewEndEdit`.
+/// In virtual mode, the row is redrawn after the event so that the data source can be updated by the event handlers.
+fn finish_edit(handle: HWND, state: &ListViewState, row_index: usize, column_index: usize, text: String, cancel: bool) {
+    use winapi::um::commctrl::{LVM_SETITEMTEXTW, LVM_REDRAWITEMS};
+
+    let validator = state.validator.borrow().clone();
+    let accepted = !cancel && validator.map(|v| v(row_index, column_index, &text)).unwrap_or(true);
+    let virtual_mode = state.data_source.is_some();
+
+    if accepted && !virtual_mode {
+        let mut text_os = to_utf16(&text);
+        let mut item: LVITEMW = unsafe { mem::zeroed() };
+        item.iSubItem = column_index as _;
+        item.pszText = text_os.as_mut_ptr();
+
+        wh::send_message(handle, LVM_SETITEMTEXTW, row_index, &mut item as *mut LVITEMW as _);
+    }
+
+    notify_edit(handle, wh::NWG_LVN_ENDEDIT, row_index, column_index, &text, !accepted);
+
+    if accepted && virtual_mode {
+        wh::send_message(handle, LVM_REDRAWITEMS, row_index, row_index as _);
+    }
+}
+
+/// Sends an edit notification to the list view parent
+fn notify_edit(handle: HWND, code: UINT, row_index: usize, column_index: usize, text: &str, cancelled: bool) {
+    use winapi::um::winuser::{GetDlgCtrlID, WM_NOTIFY};
+
+    let id = unsafe { GetDlgCtrlID(handle) };
+    let notify = ListViewEditNotify {
+        hdr: NMHDR { hwndFrom: handle, idFrom: id as _, code },
+        row_index,
+        column_index,
+        text: text.to_string(),
+        cancelled,
+    };
+
+    wh::send_message(wh::get_window_parent(handle), WM_NOTIFY, id as _, &notify as *const ListViewEditNotify as _);
+}
+
+/// Creates the editor of a cell on top of the cell
+fn create_editor(handle: HWND, row_index: usize, column_index: usize, editor: &ListViewEditor, text: &str) -> Option<HWND> {
+    use winapi::um::winuser::{SetWindowPos, WS_CHILD, WS_BORDER, WS_VSCROLL, WS_CLIPCHILDREN, ES_AUTOHSCROLL, CBS_DROPDOWNLIST,
+        EM_SETSEL, CB_ADDSTRING, CB_SETCURSEL, SWP_NOZORDER};
+    use winapi::um::commctrl::{LVM_GETSUBITEMRECT, LVIR_BOUNDS, LVIR_LABEL};
+    use winapi::shared::windef::RECT;
+
+    // The first column bounds cover the whole row
+    let mut rect: RECT = unsafe { mem::zeroed() };
+    rect.top = column_index as _;
+    rect.left = match column_index {
+        0 => LVIR_LABEL,
+        _ => LVIR_BOUNDS
+    };
+
+    if wh::send_message(handle, LVM_GETSUBITEMRECT, row_index, &mut rect as *mut RECT as _) == 0 {
+        return None;
+    }
+
+    // Prevents the list view from painting over its editor
+    wh::set_style(handle, wh::get_style(handle) | WS_CLIPCHILDREN);
+
+    let (class_name, flags) = match editor {
+        ListViewEditor::ComboBox(_) => ("COMBOBOX", CBS_DROPDOWNLIST | WS_VSCROLL),
+        _ => ("EDIT", WS_BORDER | ES_AUTOHSCROLL)
+    };
+
+    let editor_handle = ControlBase::build_hwnd()
+        .class_name(class_name)
+        .flags(WS_CHILD | WS_VISIBLE | flags)
+        .text(text)
+        .parent(Some(ControlHandle::Hwnd(handle)))
+        .build()
+        .ok()?
+        .hwnd()?;
+
+    unsafe { wh::set_window_font(editor_handle, Some(wh::get_window_font(handle)), false); }
+
+    // The height of a combobox includes its drop down list
+    let width = rect.right - rect.left;
+    let height = match editor {
+        ListViewEditor::ComboBox(choices) => (rect.bottom - rect.top) * (choices.len().min(8) as i32 + 2),
+        _ => rect.bottom - rect.top
+    };
+
+    unsafe { SetWindowPos(editor_handle, ptr::null_mut(), rect.left, rect.top, width, height, SWP_NOZORDER); }
+
+    match editor {
+        ListViewEditor::ComboBox(choices) => {
+            for choice in choices {
+                let choice = to_utf16(choice);
+                wh::send_message(editor_handle, CB_ADDSTRING, 0, choice.as_ptr() as _);
+            }
+
+            if let Some(index) = choices.iter().position(|choice| choice == text) {
+                wh::send_message(editor_handle, CB_SETCURSEL, index, 0);
+            }
+        },
+        _ => {
+            wh::send_message(editor_handle, EM_SETSEL as UINT, 0, -1);
+        }
+    }
+
+    Some(editor_handle)
+}
+
+/// Closes the editor on `Enter`, `Escape` or when it loses the focus. The editor asks the list view to close it
+/// because it cannot be destroyed from its own window procedure.
+fn hook_editor(handle: HWND, editor: HWND) -> RawEventHandler {
+    use crate::bind_raw_event_handler_inner;
+    use winapi::um::winuser::{WM_GETDLGCODE, WM_KEYDOWN, WM_CHAR, WM_KILLFOCUS, DLGC_WANTALLKEYS, VK_RETURN, VK_ESCAPE};
+
+    let end = move |cancel: bool| wh::post_message(handle, NWG_LV_END_EDIT, cancel as WPARAM, editor as LPARAM);
+
+    let handler = bind_raw_event_handler_inner(&ControlHandle::Hwnd(editor), handle as usize, move |_hwnd, msg, w, _l| {
+        match msg {
+            WM_GETDLGCODE => Some(DLGC_WANTALLKEYS),
+            WM_KEYDOWN if w as c_int == VK_RETURN => { end(false); Some(0) },
+            WM_KEYDOWN if w as c_int == VK_ESCAPE => { end(true); Some(0) },
+            WM_CHAR if w == 0x0D || w == 0x1B => Some(0),
+            WM_KILLFOCUS => { end(false); None },
+            _ => None
+        }
+    });
+
+    handler.unwrap()
+}
+
+/// Compares two strings with the user locale
+fn compare_locale(a: &str, b: &str) -> Ordering {
+    use winapi::um::stringapiset::CompareStringEx;
+
+    const LINGUISTIC_IGNORECASE: u32 = 0x00000010;
+    const SORT_DIGITSASNUMBERS: u32 = 0x00000008;
+
+    let a_os = to_utf16(a);
+    let b_os = to_utf16(b);
+    let result = unsafe {
+        CompareStringEx(
+            ptr::null(), LINGUISTIC_IGNORECASE | SORT_DIGITSASNUMBERS,
+            a_os.as_ptr(), -1,
+            b_os.as_ptr(), -1,
+            ptr::null_mut(), ptr::null_mut(), 0
+        )
+    };
+
+    // CSTR_LESS_THAN, CSTR_EQUAL, CSTR_GREATER_THAN. 0 means the comparison failed.
+    match result {
+        1 => Ordering::Less,
+        2 => Ordering::Equal,
+        3 => Ordering::Greater,
+        _ => compare_text(a, b, true)
+    }
+}
+
+/// Fills the item requested by a virtual list view
+fn fill_virtual_item(source: &dyn ListViewDataSource, item: &mut LVITEMW) {
+    let row = item.iItem as usize;
+    let column = item.iSubItem as usize;
+
+    if item.mask & LVIF_TEXT == LVIF_TEXT && !item.pszText.is_null() && item.cchTextMax > 0 {
+        let text = to_utf16(&source.cell_text(row, column));
+        let length = text.len().min(item.cchTextMax as usize);
+        unsafe {
+            ptr::copy_nonoverlapping(text.as_ptr(), item.pszText, length);
+            *item.pszText.add(length - 1) = 0;
+        }
+    }
+
+    if item.mask & LVIF_IMAGE == LVIF_IMAGE && column == 0 {
+        if let Some(image) = source.cell_image(row) {
+            item.iImage = image;
+        }
+    }
+}
+
  // Feature check
 
 #[cfg(feature="image-list")]
@@ -0,0 +1,137 @@
+/*!
+    Text comparisons used to sort the rows of a `ListView`. This module does not depend on the windowing system.
+*/
+use std::cmp::Ordering;
+
+
+/// The order of the date components parsed by `ListViewComparator::Date`
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ListViewDateFormat {
+    /// Year, month, day. Ex: `2021-03-15 14:30`
+    YearMonthDay,
+
+    /// Day, month, year. Ex: `15/03/2021 14:30`
+    DayMonthYear,
+
+    /// Month, day, year. Ex: `03/15/2021 2:30 PM`
+    MonthDayYear,
+}
+
+/// Compares two strings code point by code point
+pub(crate) fn compare_text(a: &str, b: &str, ignore_case: bool) -> Ordering {
+    match ignore_case {
+        true => a.chars().flat_map(char::to_lowercase).cmp(b.chars().flat_map(char::to_lowercase)),
+        false => a.cmp(b)
+    }
+}
+
+/// Compares two numbers. Cells that are not numbers are sorted after the numbers.
+pub(crate) fn compare_numeric(a: &str, b: &str) -> Ordering {
+    compare_parsed(a, b, parse_number, |x, y| x.partial_cmp(y).unwrap_or(Ordering::Equal))
+}
+
+/// Compares two dates. Cells that are not dates are sorted after the dates.
+pub(crate) fn compare_date(a: &str, b: &str, format: ListViewDateFormat) -> Ordering {
+    compare_parsed(a, b, |v| parse_date(v, format), Ord::cmp)
+}
+
+fn compare_parsed<T, P, C>(a: &str, b: &str, parse: P, cmp: C) -> Ordering
+    where P: Fn(&str) -> Option<T>,
+          C: Fn(&T, &T) -> Ordering
+{
+    match (parse(a), parse(b)) {
+        (Some(x), Some(y)) => cmp(&x, &y),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => a.cmp(b),
+    }
+}
+
+fn parse_number(value: &str) -> Option<f64> {
+    value.trim().parse::<f64>().ok().filter(|v| !v.is_nan())
+}
+
+/**
+    Parses a date into `[year, month, day, hour, minute, second]`. The components can be separated by any non digit character.
+    The time is optional. A trailing `AM` or `PM` switches the hour to a 12 hour clock.
+*/
+pub(crate) fn parse_date(value: &str, format: ListViewDateFormat) -> Option<[u32; 6]> {
+    use ListViewDateFormat::*;
+
+    let parts: Vec<u32> = value.split(|c: char| !c.is_ascii_digit())
+        .filter(|p| !p.is_empty())
+        .map(|p| p.parse().ok())
+        .collect::<Option<_>>()?;
+
+    if parts.len() < 3 || parts.len() > 6 {
+        return None;
+    }
+
+    let (year, month, day) = match format {
+        YearMonthDay => (parts[0], parts[1], parts[2]),
+        DayMonthYear => (parts[2], parts[1], parts[0]),
+        MonthDayYear => (parts[2], parts[0], parts[1]),
+    };
+
+    let mut hour = parts.get(3).copied().unwrap_or(0);
+    let minute = parts.get(4).copied().unwrap_or(0);
+    let second = parts.get(5).copied().unwrap_or(0);
+
+    let suffix = value.trim_end().to_ascii_lowercase();
+    if suffix.ends_with("pm") && hour < 12 {
+        hour += 12;
+    } else if suffix.ends_with("am") && hour == 12 {
+        hour = 0;
+    }
+
+    let valid = (1..=12).contains(&month) && (1..=31).contains(&day) && hour < 24 && minute < 60 && second < 60;
+    match valid {
+        true => Some([year, month, day, hour, minute, second]),
+        false => None
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cmp::Ordering::*;
+
+    fn sorted<F: Fn(&str, &str) -> Ordering>(values: &[&'static str], cmp: F) -> Vec<&'static str> {
+        let mut values = values.to_vec();
+        values.sort_by(|a, b| cmp(a, b));
+        values
+    }
+
+    #[test]
+    fn text() {
+        assert_eq!(compare_text("a", "B", false), Greater);
+        assert_eq!(compare_text("a", "B", true), Less);
+        assert_eq!(compare_text("ÉTÉ", "été", true), Equal);
+    }
+
+    #[test]
+    fn numeric() {
+        let values = ["10", "n/a", "-2.5", " 3 ", "", "1e3"];
+        assert_eq!(sorted(&values, compare_numeric), vec!["-2.5", " 3 ", "10", "1e3", "", "n/a"]);
+        assert_eq!(compare_numeric("2", "2.0"), Equal);
+    }
+
+    #[test]
+    fn dates() {
+        use ListViewDateFormat::*;
+
+        assert_eq!(parse_date("2021-03-15", YearMonthDay), Some([2021, 3, 15, 0, 0, 0]));
+        assert_eq!(parse_date("15/03/2021 14:30", DayMonthYear), Some([2021, 3, 15, 14, 30, 0]));
+        assert_eq!(parse_date("03/15/2021 2:30:05 PM", MonthDayYear), Some([2021, 3, 15, 14, 30, 5]));
+        assert_eq!(parse_date("03/15/2021 12:10 am", MonthDayYear), Some([2021, 3, 15, 0, 10, 0]));
+        assert_eq!(parse_date("15/03/2021", MonthDayYear), None);
+        assert_eq!(parse_date("2021-03", YearMonthDay), None);
+        assert_eq!(parse_date("tomorrow", YearMonthDay), None);
+
+        let values = ["02/01/2020", "unknown", "31/12/2019 23:59", "01/02/2020"];
+        let cmp = |a: &str, b: &str| compare_date(a, b, DayMonthYear);
+        assert_eq!(sorted(&values, cmp), vec!["31/12/2019 23:59", "02/01/2020", "01/02/2020", "unknown"]);
+    }
+
+}
@@ -266,7 +266,8 @@ impl<'a> MenuBuilder<'a> {
 
     For example, an "Exit" menu item could have both an access key "E" and a shortcut label "Alt+F4" with `text: "&Exit\tAlt+F4"`.
 
-    **note:** This will only add a text label to the menu item, the keyboard handling must be done through other means.
+    **note:** This will only add a text label to the menu item. The keyboard handling is done by an `AcceleratorTable`
+    (`AcceleratorTableBuilder::menu_item`), which can also add the shortcut label.
 
     ```rust
     use native_windows_gui as nwg;
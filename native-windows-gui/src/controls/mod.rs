@@ -1,6 +1,9 @@
 mod control_handle;
 mod control_base;
 mod window;
+mod window_placement;
+mod shortcut;
+mod dialog;
 mod button;
 mod check_box;
 mod radio_button;
@@ -20,6 +23,9 @@ mod rich_label;
 #[cfg(feature = "status-bar")]
 mod status_bar;
 
+#[cfg(feature = "status-bar")]
+mod status_bar_parts;
+
 #[cfg(feature = "tooltip")]
 mod tooltip;
 
@@ -38,6 +44,18 @@ mod animation_timer;
 #[cfg(feature = "notice")]
 mod notice;
 
+#[cfg(feature = "notice")]
+mod channel_queue;
+
+#[cfg(feature = "notice")]
+pub(crate) mod ui_channel;
+
+#[cfg(feature = "drag-drop")]
+mod drop_target;
+
+#[cfg(feature = "accelerator")]
+mod accelerator_table;
+
 #[cfg(feature = "combobox")]
 mod combo_box;
 
@@ -62,6 +80,9 @@ mod treeview;
 #[cfg(all(feature = "tree-view-iterator", feature = "tree-view") )]
 mod treeview_iterator;
 
+#[cfg(feature = "tree-view")]
+mod treeview_check;
+
 #[cfg(feature = "tray-notification")]
 mod tray_notification;
 
@@ -71,6 +92,15 @@ mod message_window;
 #[cfg(feature = "list-view")]
 mod list_view;
 
+#[cfg(feature = "list-view")]
+mod list_view_sort;
+
+#[cfg(any(feature = "list-view", feature = "tree-view"))]
+mod item_data;
+
+#[cfg(any(feature = "list-view", feature = "tree-view"))]
+mod item_style;
+
 #[cfg(feature = "number-select")]
 mod number_select;
 
@@ -80,6 +110,9 @@ mod extern_canvas;
 #[cfg(feature = "frame")]
 mod frame;
 
+#[cfg(feature = "splitter")]
+mod splitter;
+
 #[cfg(feature = "scroll-bar")]
 mod scroll_bar;
 
@@ -115,6 +148,9 @@ mod handle_from_control;
 pub use control_handle::ControlHandle;
 pub use control_base::{ControlBase, HwndBuilder, TimerBuilder as BaseTimerBuilder, OtherBuilder};
 pub use window::{Window, WindowBuilder, WindowFlags};
+pub use window_placement::{WindowPlacement, WindowShowState};
+pub use shortcut::{Shortcut, ShortcutModifiers};
+pub use dialog::{Dialog, DialogUi};
 pub use button::{Button, ButtonBuilder, ButtonFlags};
 pub use check_box::{CheckBox, CheckBoxBuilder, CheckBoxState, CheckBoxFlags};
 pub use radio_button::{RadioButton, RadioButtonBuilder, RadioButtonState, RadioButtonFlags};
@@ -134,6 +170,9 @@ pub use rich_label::*;
 #[cfg(feature = "status-bar")]
 pub use status_bar::{StatusBar, StatusBarBuilder};
 
+#[cfg(feature = "status-bar")]
+pub use status_bar_parts::PartWidth;
+
 #[cfg(feature = "tooltip")]
 pub use tooltip::{Tooltip, TooltipBuilder, TooltipIcon};
 
@@ -157,6 +196,18 @@ pub use animation_timer::{AnimationTimer, AnimationTimerBuilder};
 #[cfg(feature = "notice")]
 pub use notice::{Notice, NoticeSender, NoticeBuilder};
 
+#[cfg(feature = "notice")]
+pub use channel_queue::UiChannelMode;
+
+#[cfg(feature = "notice")]
+pub use ui_channel::{ui_channel, UiReceiver, UiReceiverBuilder, UiSender};
+
+#[cfg(feature = "drag-drop")]
+pub use drop_target::{DropTarget, DropTargetBuilder};
+
+#[cfg(feature = "accelerator")]
+pub use accelerator_table::{AcceleratorTable, AcceleratorTableBuilder};
+
 #[cfg(feature = "combobox")]
 pub use combo_box::{ComboBox, ComboBoxFlags, ComboBoxBuilder};
 
@@ -188,7 +239,16 @@ pub use tray_notification::{TrayNotificationFlags, TrayNotification, TrayNotific
 pub use message_window::{MessageWindow, MessageWindowBuilder};
 
 #[cfg(feature = "list-view")]
-pub use list_view::{ListView, ListViewStyle, ListViewBuilder, ListViewFlags, ListViewExFlags, InsertListViewItem, ListViewItem, InsertListViewColumn, ListViewColumn, ListViewColumnSortArrow, ListViewColumnFlags};
+pub use list_view::{ListView, ListViewStyle, ListViewBuilder, ListViewFlags, ListViewExFlags, InsertListViewItem, ListViewItem, InsertListViewColumn, ListViewColumn, ListViewColumnSortArrow, ListViewColumnFlags, ListViewDataSource, ListViewComparator, ListViewEditor};
+
+#[cfg(feature = "list-view")]
+pub use list_view_sort::ListViewDateFormat;
+
+#[cfg(feature = "list-view")]
+pub(crate) use list_view::ListViewEditNotify;
+
+#[cfg(any(feature = "list-view", feature = "tree-view"))]
+pub use item_style::ItemStyle;
 
 #[cfg(all(feature="list-view", feature="image-list"))]
 pub use list_view::ListViewImageListType;
@@ -202,6 +262,9 @@ pub use extern_canvas::{ExternCanvas, ExternCanvasBuilder, ExternCanvasFlags};
 #[cfg(feature = "frame")]
 pub use frame::{Frame, FrameBuilder, FrameFlags};
 
+#[cfg(feature = "splitter")]
+pub use splitter::{Splitter, SplitterBuilder, SplitterFlags, SplitterOrientation, SplitterPane};
+
 #[cfg(feature = "scroll-bar")]
 pub use scroll_bar::{ScrollBar, ScrollBarBuilder, ScrollBarFlags};
 
@@ -125,6 +125,12 @@ pub struct NoticeSender {
 
 impl NoticeSender {
 
+    /// Creates a sender from the handle of a notice
+    pub(crate) fn from_handle(handle: &ControlHandle) -> Option<NoticeSender> {
+        let (hwnd, id) = handle.notice()?;
+        Some(NoticeSender { hwnd: hwnd as usize, id })
+    }
+
     /// Send a message to the thread of the parent `Notice` 
     pub fn notice(&self) {
         use winapi::um::winuser::SendNotifyMessageW;
@@ -0,0 +1,249 @@
+/*!
+    Keyboard shortcuts of an `AcceleratorTable` and their text form ("Ctrl+Shift+S").
+    This module does not depend on the windowing system.
+*/
+use crate::NwgError;
+use std::{fmt, str::FromStr};
+
+
+bitflags! {
+    /**
+        The modifier keys of a shortcut
+
+        * NONE: No modifier
+        * CTRL: The control key
+        * SHIFT: The shift key
+        * ALT: The alt key
+    */
+    #[derive(Default)]
+    pub struct ShortcutModifiers: u8 {
+        const NONE = 0;
+        const CTRL = 0b001;
+        const SHIFT = 0b010;
+        const ALT = 0b100;
+    }
+}
+
+/**
+    A key combination: zero or more modifiers and a virtual key code (see `nwg::keys`).
+
+    A shortcut can be parsed from its text form. The modifiers (`Ctrl`, `Shift`, `Alt`) and the key are separated by `+`
+    and are case insensitive. The key is a letter, a digit, a function key (`F1` to `F24`), a named key (`Enter`, `Esc`,
+    `Delete`, `PageUp`, `Left`, `Num0`, ...), a punctuation key (`+`, `-`, `,`, `.`) or a virtual key code (`0x2F`).
+
+    Formatting a shortcut (`to_string`) returns the canonical form, with the modifiers in the `Ctrl+Shift+Alt` order.
+
+    ```rust
+    use native_windows_gui as nwg;
+
+    let save: nwg::Shortcut = "ctrl+shift+s".parse().unwrap();
+    assert_eq!(save, nwg::Shortcut::new(nwg::ShortcutModifiers::CTRL | nwg::ShortcutModifiers::SHIFT, nwg::keys::_S));
+    assert_eq!(save.to_string(), "Ctrl+Shift+S");
+    ```
+*/
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Shortcut {
+    pub modifiers: ShortcutModifiers,
+    pub key: u32,
+}
+
+impl Shortcut {
+
+    pub fn new(modifiers: ShortcutModifiers, key: u32) -> Shortcut {
+        Shortcut { modifiers, key }
+    }
+
+}
+
+/// Names of the keys that are not a letter, a digit or a function key. The first name of a key is used when formatting.
+const KEY_NAMES: &[(&str, u32)] = &[
+    ("Backspace", 0x08), ("Back", 0x08),
+    ("Tab", 0x09),
+    ("Enter", 0x0D), ("Return", 0x0D),
+    ("Pause", 0x13),
+    ("Esc", 0x1B), ("Escape", 0x1B),
+    ("Space", 0x20),
+    ("PageUp", 0x21), ("PgUp", 0x21),
+    ("PageDown", 0x22), ("PgDn", 0x22),
+    ("End", 0x23),
+    ("Home", 0x24),
+    ("Left", 0x25),
+    ("Up", 0x26),
+    ("Right", 0x27),
+    ("Down", 0x28),
+    ("Insert", 0x2D), ("Ins", 0x2D),
+    ("Delete", 0x2E), ("Del", 0x2E),
+    ("Num0", 0x60), ("Num1", 0x61), ("Num2", 0x62), ("Num3", 0x63), ("Num4", 0x64),
+    ("Num5", 0x65), ("Num6", 0x66), ("Num7", 0x67), ("Num8", 0x68), ("Num9", 0x69),
+    ("Num*", 0x6A), ("Num+", 0x6B), ("Num-", 0x6D), ("Num.", 0x6E), ("Num/", 0x6F),
+    ("+", 0xBB), ("Plus", 0xBB),
+    (",", 0xBC), ("Comma", 0xBC),
+    ("-", 0xBD), ("Minus", 0xBD),
+    (".", 0xBE), ("Period", 0xBE),
+];
+
+fn parse_error(text: &str, reason: &str) -> NwgError {
+    NwgError::resource_create(format!("invalid shortcut `{}`: {}", text, reason))
+}
+
+fn parse_modifier(name: &str) -> Option<ShortcutModifiers> {
+    match name.to_ascii_lowercase().as_str() {
+        "ctrl" | "control" => Some(ShortcutModifiers::CTRL),
+        "shift" => Some(ShortcutModifiers::SHIFT),
+        "alt" => Some(ShortcutModifiers::ALT),
+        _ => None
+    }
+}
+
+fn parse_key(name: &str) -> Option<u32> {
+    let bytes = name.as_bytes();
+    if bytes.len() == 1 && bytes[0].is_ascii_alphanumeric() {
+        return Some(bytes[0].to_ascii_uppercase() as u32);
+    }
+
+    if let Some(&(_, key)) = KEY_NAMES.iter().find(|(n, _)| n.eq_ignore_ascii_case(name)) {
+        return Some(key);
+    }
+
+    if name.len() > 1 && (bytes[0] == b'F' || bytes[0] == b'f') {
+        return match name[1..].parse::<u32>() {
+            Ok(index) if (1..=24).contains(&index) => Some(0x6F + index),
+            _ => None
+        };
+    }
+
+    if name.len() > 2 && name.get(..2).map(|p| p.eq_ignore_ascii_case("0x")).unwrap_or(false) {
+        return match u32::from_str_radix(&name[2..], 16) {
+            Ok(key) if key > 0 && key < 0xFF => Some(key),
+            _ => None
+        };
+    }
+
+    None
+}
+
+/// Splits a shortcut into the modifiers (each followed by a `+`) and the key. The `+` keys are written "Ctrl++" or "Ctrl+Num+".
+fn split_key(text: &str) -> (&str, &str) {
+    for name in &["Num+", "+"] {
+        let start = text.len().saturating_sub(name.len());
+        if !text.get(start..).map(|k| k.eq_ignore_ascii_case(name)).unwrap_or(false) {
+            continue;
+        }
+
+        let modifiers = text[..start].trim_end();
+        if modifiers.is_empty() || modifiers.ends_with('+') {
+            return (modifiers, &text[start..]);
+        }
+    }
+
+    match text.rfind('+') {
+        Some(i) => (text[..i + 1].trim_end(), text[i + 1..].trim()),
+        None => ("", text)
+    }
+}
+
+impl FromStr for Shortcut {
+    type Err = NwgError;
+
+    fn from_str(text: &str) -> Result<Shortcut, NwgError> {
+        let trimmed = text.trim();
+
+        let (modifiers_text, key_text) = split_key(trimmed);
+        if key_text.is_empty() {
+            return Err(parse_error(text, "missing key"));
+        }
+
+        let mut modifiers = ShortcutModifiers::NONE;
+        if !modifiers_text.is_empty() {
+            // Every modifier is followed by a `+`
+            for name in modifiers_text[..modifiers_text.len() - 1].split('+') {
+                let modifier = match parse_modifier(name.trim()) {
+                    Some(m) => m,
+                    None => return Err(parse_error(text, &format!("unknown modifier `{}`", name.trim())))
+                };
+
+                if modifiers.contains(modifier) {
+                    return Err(parse_error(text, &format!("duplicate modifier `{}`", name.trim())));
+                }
+
+                modifiers |= modifier;
+            }
+        }
+
+        match parse_key(key_text) {
+            Some(key) => Ok(Shortcut { modifiers, key }),
+            None => Err(parse_error(text, &format!("unknown key `{}`", key_text)))
+        }
+    }
+}
+
+impl fmt::Display for Shortcut {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.modifiers.contains(ShortcutModifiers::CTRL) { f.write_str("Ctrl+")?; }
+        if self.modifiers.contains(ShortcutModifiers::SHIFT) { f.write_str("Shift+")?; }
+        if self.modifiers.contains(ShortcutModifiers::ALT) { f.write_str("Alt+")?; }
+
+        match self.key {
+            0x30..=0x39 | 0x41..=0x5A => write!(f, "{}", self.key as u8 as char),
+            0x70..=0x87 => write!(f, "F{}", self.key - 0x6F),
+            key => match KEY_NAMES.iter().find(|(_, k)| *k == key) {
+                Some((name, _)) => f.write_str(name),
+                None => write!(f, "0x{:02X}", key)
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(text: &str) -> Shortcut {
+        text.parse().unwrap()
+    }
+
+    #[test]
+    fn parse_shortcuts() {
+        let ctrl_shift = ShortcutModifiers::CTRL | ShortcutModifiers::SHIFT;
+
+        assert_eq!(parse("Ctrl+Shift+S"), Shortcut::new(ctrl_shift, 0x53));
+        assert_eq!(parse(" shift + control + s "), Shortcut::new(ctrl_shift, 0x53));
+        assert_eq!(parse("Alt+F4"), Shortcut::new(ShortcutModifiers::ALT, 0x73));
+        assert_eq!(parse("F24"), Shortcut::new(ShortcutModifiers::NONE, 0x87));
+        assert_eq!(parse("del"), Shortcut::new(ShortcutModifiers::NONE, 0x2E));
+        assert_eq!(parse("Ctrl+PgDn"), Shortcut::new(ShortcutModifiers::CTRL, 0x22));
+        assert_eq!(parse("Ctrl+1"), Shortcut::new(ShortcutModifiers::CTRL, 0x31));
+        assert_eq!(parse("Ctrl+Num+"), Shortcut::new(ShortcutModifiers::CTRL, 0x6B));
+        assert_eq!(parse("Ctrl++"), Shortcut::new(ShortcutModifiers::CTRL, 0xBB));
+        assert_eq!(parse("+"), Shortcut::new(ShortcutModifiers::NONE, 0xBB));
+        assert_eq!(parse("Ctrl+-"), Shortcut::new(ShortcutModifiers::CTRL, 0xBD));
+        assert_eq!(parse("Ctrl+0x5D"), Shortcut::new(ShortcutModifiers::CTRL, 0x5D));
+    }
+
+    #[test]
+    fn parse_errors() {
+        let error = |text: &str| format!("{}", text.parse::<Shortcut>().unwrap_err());
+
+        assert!(error("").contains("missing key"));
+        assert!(error("Ctrl+").contains("missing key"));
+        assert!(error("Ctrl+Ctrl+S").contains("duplicate modifier `Ctrl`"));
+        assert!(error("Hyper+S").contains("unknown modifier `Hyper`"));
+        assert!(error("Ctrl+Shift").contains("unknown key `Shift`"));
+        assert!(error("F25").contains("unknown key"));
+        assert!(error("Ctrl+SS").contains("unknown key `SS`"));
+        assert!(error("Ctrl++S").contains("unknown modifier ``"));
+        assert!(error("Ctrl+é").contains("unknown key `é`"));
+    }
+
+    #[test]
+    fn format_shortcuts() {
+        for text in &["Ctrl+Shift+Alt+S", "Alt+F4", "Ctrl+Enter", "Shift+Delete", "Ctrl++", "Ctrl+-", "Num5", "Ctrl+0x5D", "F12"] {
+            assert_eq!(&parse(text).to_string(), text);
+        }
+
+        assert_eq!(parse("alt+shift+ctrl+escape").to_string(), "Ctrl+Shift+Alt+Esc");
+        assert_eq!(parse("shift+return").to_string(), "Shift+Enter");
+    }
+
+}
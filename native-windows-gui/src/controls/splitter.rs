@@ -0,0 +1,761 @@
+use crate::win32::sys::um::winuser::{WS_VISIBLE, WS_DISABLED, WS_TABSTOP, WS_CHILD, WS_CLIPCHILDREN, WS_EX_CONTROLPARENT};
+use crate::win32::sys::shared::windef::HWND;
+use crate::win32::window_helper as wh;
+use crate::win32::base_helper::check_hwnd;
+use crate::{NwgError, RawEventHandler, bind_raw_event_handler_inner, unbind_raw_event_handler};
+use super::{ControlBase, ControlHandle};
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::ptr;
+
+const NOT_BOUND: &str = "Splitter is not yet bound to a winapi object";
+const BAD_HANDLE: &str = "INTERNAL ERROR: Splitter handle is not HWND!";
+
+
+bitflags! {
+    /**
+        The splitter flags
+
+        * NONE:     No flags. Equivalent to a invisible splitter.
+        * VISIBLE:  The splitter is immediatly visible after creation
+        * DISABLED: The splitter bar cannot be moved by the user
+        * TAB_STOP: The bar can be selected using tab navigation and then moved with the arrow keys.
+                    Tab navigation does not go into the panes of the splitter.
+    */
+    pub struct SplitterFlags: u32 {
+        const NONE = 0;
+        const VISIBLE = WS_VISIBLE;
+        const DISABLED = WS_DISABLED;
+        const TAB_STOP = WS_TABSTOP;
+    }
+}
+
+/// The direction in which a splitter lays out its panes
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SplitterOrientation {
+    /// The first pane is on the left and the second pane is on the right. The bar is vertical.
+    Horizontal,
+
+    /// The first pane is on the top and the second pane is at the bottom. The bar is horizontal.
+    Vertical,
+}
+
+/// Identifies one of the two panes of a splitter
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SplitterPane {
+    First,
+    Second,
+}
+
+
+/// The position of the bar of a splitter. This is the platform independent part of the control.
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct SplitterState {
+    /// The size of the first pane divided by the space available to both panes
+    ratio: f64,
+
+    /// The thickness of the bar
+    bar_size: u32,
+
+    /// The minimum size of the first and of the second pane
+    min_sizes: [u32; 2],
+
+    /// The pane hidden by the splitter, if any
+    collapsed: Option<SplitterPane>,
+}
+
+impl SplitterState {
+
+    /// Returns the (offset, length) of the first pane, the bar and the second pane along the splitter axis
+    fn layout(&self, total: u32) -> [(u32, u32); 3] {
+        match self.collapsed {
+            Some(SplitterPane::First) => { return [(0, 0), (0, 0), (0, total)]; },
+            Some(SplitterPane::Second) => { return [(0, total), (total, 0), (total, 0)]; },
+            None => {}
+        }
+
+        let bar = self.bar_size.min(total);
+        let available = total - bar;
+        let first = self.clamp((self.ratio * available as f64).round() as u32, available);
+
+        [(0, first), (first, bar), (first + bar, available - first)]
+    }
+
+    /// Keep the first pane length between the min size of the two panes. The first pane min size wins.
+    fn clamp(&self, first: u32, available: u32) -> u32 {
+        let [min_first, min_second] = self.min_sizes;
+        first
+            .min(available.saturating_sub(min_second))
+            .max(min_first.min(available))
+    }
+
+    /// Move the bar so that the first pane has the length `first`
+    fn set_position(&mut self, first: i64, total: u32) {
+        let available = total.saturating_sub(self.bar_size);
+        if available == 0 {
+            return;
+        }
+
+        let first = self.clamp(first.max(0).min(available as i64) as u32, available);
+        self.ratio = first as f64 / available as f64;
+    }
+
+    /// Move the bar by `delta` pixels
+    fn nudge(&mut self, delta: i32, total: u32) {
+        if self.collapsed.is_some() {
+            return;
+        }
+
+        let [(_, first), _, _] = self.layout(total);
+        self.set_position(first as i64 + delta as i64, total);
+    }
+
+}
+
+impl Default for SplitterState {
+    fn default() -> SplitterState {
+        SplitterState {
+            ratio: 0.5,
+            bar_size: 5,
+            min_sizes: [0, 0],
+            collapsed: None,
+        }
+    }
+}
+
+struct SplitterInner {
+    handle: HWND,
+    orientation: SplitterOrientation,
+    state: SplitterState,
+    panes: [HWND; 2],
+    nudge_step: u32,
+
+    /// The position of the mouse in the bar when the user is dragging it
+    drag: Option<i32>,
+}
+
+impl SplitterInner {
+
+    /// The length of the splitter along its axis
+    fn total(&self) -> u32 {
+        let (w, h) = unsafe { wh::get_window_size(self.handle) };
+        match self.orientation {
+            SplitterOrientation::Horizontal => w,
+            SplitterOrientation::Vertical => h,
+        }
+    }
+
+    /// Place the panes in the splitter client area
+    fn update_layout(&self) {
+        let (w, h) = unsafe { wh::get_window_size(self.handle) };
+        let total = self.total();
+        let [first, _, second] = self.state.layout(total);
+
+        let panes = [(self.panes[0], first, SplitterPane::First), (self.panes[1], second, SplitterPane::Second)];
+        for &(pane, (offset, length), side) in panes.iter() {
+            if pane.is_null() {
+                continue;
+            }
+
+            let (x, y, pw, ph) = match self.orientation {
+                SplitterOrientation::Horizontal => (offset as i32, 0, length, h),
+                SplitterOrientation::Vertical => (0, offset as i32, w, length),
+            };
+
+            unsafe {
+                wh::set_window_visibility(pane, self.state.collapsed != Some(side));
+                wh::set_window_position(pane, x, y);
+                wh::set_window_size(pane, pw, ph, false);
+            }
+        }
+    }
+
+    /// Returns true if the coordinate `pos` (along the splitter axis) is on the bar
+    fn on_bar(&self, pos: i32) -> bool {
+        let [_, (offset, length), _] = self.state.layout(self.total());
+        length > 0 && pos >= offset as i32 && pos < (offset + length) as i32
+    }
+
+    /// Extract the logical mouse coordinate along the splitter axis from a mouse message lparam
+    fn mouse_pos(&self, l: isize) -> i32 {
+        use crate::win32::sys::shared::minwindef::{LOWORD, HIWORD};
+
+        let x = LOWORD(l as u32) as i16 as i32;
+        let y = HIWORD(l as u32) as i16 as i32;
+        let (x, y) = unsafe { crate::win32::high_dpi::physical_to_logical(x, y) };
+
+        match self.orientation {
+            SplitterOrientation::Horizontal => x,
+            SplitterOrientation::Vertical => y,
+        }
+    }
+
+    fn resize_cursor(&self) {
+        use crate::win32::sys::um::winuser::{LoadCursorW, SetCursor, IDC_SIZEWE, IDC_SIZENS};
+
+        let cursor = match self.orientation {
+            SplitterOrientation::Horizontal => IDC_SIZEWE,
+            SplitterOrientation::Vertical => IDC_SIZENS,
+        };
+
+        unsafe { SetCursor(LoadCursorW(ptr::null_mut(), cursor)); }
+    }
+
+}
+
+
+/**
+A splitter is a container that shows two panes separated by a bar that the user can drag to resize the panes.
+Splitter is implemented as a custom control. The panes can be any window-like controls (ex: a `Frame`, a `TreeView`),
+they are moved inside the splitter when they are assigned to it.
+
+The position of the bar is saved as a ratio (the size of the first pane divided by the space available to both panes)
+so that it stays the same when the splitter is resized. Use `ratio` and `set_ratio` to save and restore it.
+
+When the splitter has the keyboard focus, the arrow keys move the bar by `nudge_step` pixels. The splitter gets the focus
+when the bar is clicked. Without the `TAB_STOP` flag (the default), tab navigation goes into the panes instead of stopping on the bar.
+
+Requires the `splitter` feature.
+
+**Builder parameters:**
+  * `parent`:      **Required.** The splitter parent container.
+  * `size`:        The splitter size.
+  * `position`:    The splitter position.
+  * `orientation`: The direction of the panes. `SplitterOrientation::Horizontal` (default) puts the panes side by side.
+  * `first`:       The first (left or top) pane
+  * `second`:      The second (right or bottom) pane
+  * `ratio`:       The initial position of the bar, between 0.0 and 1.0. Default to 0.5.
+  * `bar_size`:    The thickness of the bar. Default to 5.
+  * `min_sizes`:   The minimum size of the first and of the second pane. Default to [0, 0]
+  * `collapsed`:   Hide one of the panes. The other pane takes the whole splitter.
+  * `nudge_step`:  The number of pixel the bar moves when an arrow key is pressed. Default to 10.
+  * `enabled`:     If the user can move the bar.
+  * `flags`:       A combination of the SplitterFlags values.
+
+**Control events:**
+  * `MousePress(_)`: Generic mouse press events on the bar
+  * `OnMouseMove`: Generic mouse mouse event
+
+```rust
+use native_windows_gui as nwg;
+fn build_splitter(splitter: &mut nwg::Splitter, window: &nwg::Window, left: &nwg::TextInput, right: &nwg::TextInput) {
+    nwg::Splitter::builder()
+        .parent(window)
+        .first(left)
+        .second(right)
+        .ratio(0.3)
+        .min_sizes([100, 100])
+        .build(splitter);
+}
+```
+*/
+#[derive(Default)]
+pub struct Splitter {
+    pub handle: ControlHandle,
+    inner: Option<Rc<RefCell<SplitterInner>>>,
+    handler: Option<RawEventHandler>,
+}
+
+impl Splitter {
+
+    pub fn builder() -> SplitterBuilder {
+        SplitterBuilder {
+            size: (300, 200),
+            position: (0, 0),
+            enabled: true,
+            flags: None,
+            orientation: SplitterOrientation::Horizontal,
+            first: None,
+            second: None,
+            state: SplitterState::default(),
+            nudge_step: 10,
+            parent: None,
+        }
+    }
+
+    fn inner(&self) -> &Rc<RefCell<SplitterInner>> {
+        check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        self.inner.as_ref().expect(NOT_BOUND)
+    }
+
+    /// Returns the position of the bar: the size of the first pane divided by the space available to both panes
+    pub fn ratio(&self) -> f64 {
+        self.inner().borrow().state.ratio
+    }
+
+    /// Moves the bar. `ratio` is clamped between 0.0 and 1.0. The min size of the panes is still respected.
+    pub fn set_ratio(&self, ratio: f64) {
+        let inner = self.inner();
+        inner.borrow_mut().state.ratio = ratio.clamp(0.0, 1.0);
+        inner.borrow().update_layout();
+    }
+
+    /// Moves the bar by `delta` pixels. Does nothing if a pane is collapsed.
+    pub fn nudge(&self, delta: i32) {
+        let inner = self.inner();
+        {
+            let mut inner = inner.borrow_mut();
+            let total = inner.total();
+            inner.state.nudge(delta, total);
+        }
+
+        inner.borrow().update_layout();
+    }
+
+    /// Returns the collapsed pane, if any
+    pub fn collapsed(&self) -> Option<SplitterPane> {
+        self.inner().borrow().state.collapsed
+    }
+
+    /// Hides one of the panes, the other pane takes the whole splitter. Use `None` to show both panes again.
+    pub fn set_collapsed(&self, pane: Option<SplitterPane>) {
+        let inner = self.inner();
+        inner.borrow_mut().state.collapsed = pane;
+        inner.borrow().update_layout();
+    }
+
+    /// Returns the minimum size of the first and of the second pane
+    pub fn min_sizes(&self) -> [u32; 2] {
+        self.inner().borrow().state.min_sizes
+    }
+
+    /// Sets the minimum size of the first and of the second pane
+    pub fn set_min_sizes(&self, sizes: [u32; 2]) {
+        let inner = self.inner();
+        inner.borrow_mut().state.min_sizes = sizes;
+        inner.borrow().update_layout();
+    }
+
+    /// Returns the thickness of the bar
+    pub fn bar_size(&self) -> u32 {
+        self.inner().borrow().state.bar_size
+    }
+
+    /// Sets the thickness of the bar
+    pub fn set_bar_size(&self, size: u32) {
+        let inner = self.inner();
+        inner.borrow_mut().state.bar_size = size;
+        inner.borrow().update_layout();
+    }
+
+    /// Returns the orientation of the splitter
+    pub fn orientation(&self) -> SplitterOrientation {
+        self.inner().borrow().orientation
+    }
+
+    /// Sets the orientation of the splitter
+    pub fn set_orientation(&self, orientation: SplitterOrientation) {
+        let inner = self.inner();
+        inner.borrow_mut().orientation = orientation;
+        inner.borrow().update_layout();
+    }
+
+    /**
+        Sets the panes of the splitter. The controls are moved inside the splitter.
+        The old panes are not destroyed, but they stay children of the splitter.
+
+        Panic:
+        - If the panes are not window-like controls (HWND handle)
+    */
+    pub fn set_panes<W1: Into<ControlHandle>, W2: Into<ControlHandle>>(&self, first: W1, second: W2) {
+        let first = first.into().hwnd().expect("Splitter pane must be a window-like control (HWND handle)");
+        let second = second.into().hwnd().expect("Splitter pane must be a window-like control (HWND handle)");
+
+        let inner = self.inner();
+        {
+            let mut inner = inner.borrow_mut();
+            wh::set_window_parent(first, inner.handle);
+            wh::set_window_parent(second, inner.handle);
+            inner.panes = [first, second];
+        }
+
+        inner.borrow().update_layout();
+    }
+
+    /// Returns true if the control currently has the keyboard focus
+    pub fn focus(&self) -> bool {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::get_focus(handle) }
+    }
+
+    /// Sets the keyboard focus on the splitter.
+    pub fn set_focus(&self) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::set_focus(handle); }
+    }
+
+    /// Returns true if the user can move the bar, return false otherwise
+    pub fn enabled(&self) -> bool {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::get_window_enabled(handle) }
+    }
+
+    /// Enable or disable the control
+    pub fn set_enabled(&self, v: bool) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::set_window_enabled(handle, v) }
+    }
+
+    /// Returns true if the control is visible to the user. Will return true even if the
+    /// control is outside of the parent client view (ex: at the position (10000, 10000))
+    pub fn visible(&self) -> bool {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::get_window_visibility(handle) }
+    }
+
+    /// Show or hide the control to the user
+    pub fn set_visible(&self, v: bool) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::set_window_visibility(handle, v) }
+    }
+
+    /// Returns the size of the splitter in the parent window
+    pub fn size(&self) -> (u32, u32) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::get_window_size(handle) }
+    }
+
+    /// Sets the size of the splitter in the parent window
+    pub fn set_size(&self, x: u32, y: u32) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::set_window_size(handle, x, y, false) }
+    }
+
+    /// Returns the position of the splitter in the parent window
+    pub fn position(&self) -> (i32, i32) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::get_window_position(handle) }
+    }
+
+    /// Sets the position of the splitter in the parent window
+    pub fn set_position(&self, x: i32, y: i32) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        unsafe { wh::set_window_position(handle, x, y) }
+    }
+
+    /// Winapi class name used during control creation
+    pub fn class_name(&self) -> &'static str {
+        "NativeWindowsGuiWindow"
+    }
+
+    /// Winapi base flags used during window creation
+    pub fn flags(&self) -> u32 {
+        WS_VISIBLE
+    }
+
+    /// Winapi flags required by the control
+    pub fn forced_flags(&self) -> u32 {
+        WS_CHILD | WS_CLIPCHILDREN
+    }
+
+}
+
+impl Drop for Splitter {
+
+    fn drop(&mut self) {
+        if let Some(h) = self.handler.as_ref() {
+            drop(unbind_raw_event_handler(h));
+        }
+
+        self.handle.destroy();
+    }
+
+}
+
+pub struct SplitterBuilder {
+    size: (i32, i32),
+    position: (i32, i32),
+    enabled: bool,
+    flags: Option<SplitterFlags>,
+    orientation: SplitterOrientation,
+    first: Option<ControlHandle>,
+    second: Option<ControlHandle>,
+    state: SplitterState,
+    nudge_step: u32,
+    parent: Option<ControlHandle>
+}
+
+impl SplitterBuilder {
+
+    pub fn flags(mut self, flags: SplitterFlags) -> SplitterBuilder {
+        self.flags = Some(flags);
+        self
+    }
+
+    pub fn size(mut self, size: (i32, i32)) -> SplitterBuilder {
+        self.size = size;
+        self
+    }
+
+    pub fn position(mut self, pos: (i32, i32)) -> SplitterBuilder {
+        self.position = pos;
+        self
+    }
+
+    pub fn enabled(mut self, e: bool) -> SplitterBuilder {
+        self.enabled = e;
+        self
+    }
+
+    pub fn orientation(mut self, orientation: SplitterOrientation) -> SplitterBuilder {
+        self.orientation = orientation;
+        self
+    }
+
+    pub fn first<C: Into<ControlHandle>>(mut self, pane: C) -> SplitterBuilder {
+        self.first = Some(pane.into());
+        self
+    }
+
+    pub fn second<C: Into<ControlHandle>>(mut self, pane: C) -> SplitterBuilder {
+        self.second = Some(pane.into());
+        self
+    }
+
+    pub fn ratio(mut self, ratio: f64) -> SplitterBuilder {
+        self.state.ratio = ratio.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn bar_size(mut self, size: u32) -> SplitterBuilder {
+        self.state.bar_size = size;
+        self
+    }
+
+    pub fn min_sizes(mut self, sizes: [u32; 2]) -> SplitterBuilder {
+        self.state.min_sizes = sizes;
+        self
+    }
+
+    pub fn collapsed(mut self, pane: Option<SplitterPane>) -> SplitterBuilder {
+        self.state.collapsed = pane;
+        self
+    }
+
+    pub fn nudge_step(mut self, step: u32) -> SplitterBuilder {
+        self.nudge_step = step;
+        self
+    }
+
+    pub fn parent<C: Into<ControlHandle>>(mut self, p: C) -> SplitterBuilder {
+        self.parent = Some(p.into());
+        self
+    }
+
+    pub fn build(self, out: &mut Splitter) -> Result<(), NwgError> {
+        let flags = self.flags.map(|f| f.bits()).unwrap_or(out.flags());
+
+        let parent = match self.parent {
+            Some(p) => Ok(p),
+            None => Err(NwgError::no_parent("Splitter"))
+        }?;
+
+        let pane = |p: Option<ControlHandle>| -> Result<HWND, NwgError> {
+            match p {
+                Some(p) => p.hwnd().ok_or(NwgError::control_create("Splitter pane must be a window-like control (HWND handle)")),
+                None => Ok(ptr::null_mut())
+            }
+        };
+
+        let first = pane(self.first)?;
+        let second = pane(self.second)?;
+
+        // The dialog navigation never stops on a window that has `WS_EX_CONTROLPARENT`, it goes into its children instead
+        let ex_flags = match flags & WS_TABSTOP {
+            0 => WS_EX_CONTROLPARENT,
+            _ => 0
+        };
+
+        *out = Default::default();
+
+        out.handle = ControlBase::build_hwnd()
+            .class_name(out.class_name())
+            .forced_flags(out.forced_flags())
+            .flags(flags)
+            .ex_flags(ex_flags)
+            .size(self.size)
+            .position(self.position)
+            .parent(Some(parent))
+            .build()?;
+
+        let handle = out.handle.hwnd().unwrap();
+        for &pane in [first, second].iter() {
+            if !pane.is_null() {
+                wh::set_window_parent(pane, handle);
+            }
+        }
+
+        let inner = Rc::new(RefCell::new(SplitterInner {
+            handle,
+            orientation: self.orientation,
+            state: self.state,
+            panes: [first, second],
+            nudge_step: self.nudge_step,
+            drag: None,
+        }));
+
+        inner.borrow().update_layout();
+
+        let handler_inner = inner.clone();
+        let handler = bind_raw_event_handler_inner(&out.handle, 0x5E11, move |hwnd, msg, w, l| {
+            use crate::win32::sys::um::winuser::{WM_SIZE, WM_LBUTTONDOWN, WM_LBUTTONUP, WM_MOUSEMOVE, WM_CAPTURECHANGED,
+                WM_SETCURSOR, WM_KEYDOWN, WM_GETDLGCODE, DLGC_WANTARROWS, HTCLIENT, VK_LEFT, VK_UP, VK_RIGHT, VK_DOWN, SetCapture, ReleaseCapture};
+            use crate::win32::sys::shared::minwindef::{LOWORD, LRESULT};
+
+            match msg {
+                WM_SIZE => {
+                    handler_inner.borrow().update_layout();
+                    None
+                },
+                WM_SETCURSOR => {
+                    let inner = handler_inner.borrow();
+                    if w as HWND == hwnd && LOWORD(l as u32) as LRESULT == HTCLIENT && inner.state.collapsed.is_none() && unsafe { wh::get_window_enabled(hwnd) } {
+                        inner.resize_cursor();
+                        Some(1)
+                    } else {
+                        None
+                    }
+                },
+                WM_LBUTTONDOWN => {
+                    let mut inner = handler_inner.borrow_mut();
+                    let pos = inner.mouse_pos(l);
+                    if inner.state.collapsed.is_none() && inner.on_bar(pos) {
+                        let [_, (offset, _), _] = inner.state.layout(inner.total());
+                        inner.drag = Some(pos - offset as i32);
+                        unsafe {
+                            SetCapture(hwnd);
+                            wh::set_focus(hwnd);
+                        }
+                    }
+                    None
+                },
+                WM_MOUSEMOVE => {
+                    let grab = handler_inner.borrow().drag;
+                    if let Some(grab) = grab {
+                        {
+                            let mut inner = handler_inner.borrow_mut();
+                            let pos = inner.mouse_pos(l);
+                            let total = inner.total();
+                            inner.state.set_position((pos - grab) as i64, total);
+                            inner.resize_cursor();
+                        }
+                        handler_inner.borrow().update_layout();
+                    }
+                    None
+                },
+                WM_LBUTTONUP => {
+                    if handler_inner.borrow_mut().drag.take().is_some() {
+                        unsafe { ReleaseCapture(); }
+                    }
+                    None
+                },
+                WM_CAPTURECHANGED => {
+                    handler_inner.borrow_mut().drag = None;
+                    None
+                },
+                // Without this, the dialog navigation uses the arrow keys and `WM_KEYDOWN` is never received
+                WM_GETDLGCODE => Some(DLGC_WANTARROWS),
+                WM_KEYDOWN => {
+                    let step = handler_inner.borrow().nudge_step as i32;
+                    let delta = match (handler_inner.borrow().orientation, w as i32) {
+                        (SplitterOrientation::Horizontal, VK_LEFT) | (SplitterOrientation::Vertical, VK_UP) => -step,
+                        (SplitterOrientation::Horizontal, VK_RIGHT) | (SplitterOrientation::Vertical, VK_DOWN) => step,
+                        _ => { return None; }
+                    };
+
+                    {
+                        let mut inner = handler_inner.borrow_mut();
+                        let total = inner.total();
+                        inner.state.nudge(delta, total);
+                    }
+                    handler_inner.borrow().update_layout();
+                    Some(0)
+                },
+                _ => None
+            }
+        });
+
+        out.inner = Some(inner);
+        out.handler = Some(handler.unwrap());
+
+        if !self.enabled {
+            out.set_enabled(self.enabled);
+        }
+
+        Ok(())
+    }
+
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(ratio: f64, bar_size: u32, min_sizes: [u32; 2]) -> SplitterState {
+        SplitterState { ratio, bar_size, min_sizes, collapsed: None }
+    }
+
+    #[test]
+    fn layout() {
+        assert_eq!(state(0.5, 10, [0, 0]).layout(110), [(0, 50), (50, 10), (60, 50)]);
+        assert_eq!(state(0.25, 0, [0, 0]).layout(100), [(0, 25), (25, 0), (25, 75)]);
+        assert_eq!(state(1.0, 4, [0, 0]).layout(104), [(0, 100), (100, 4), (104, 0)]);
+    }
+
+    #[test]
+    fn min_sizes() {
+        assert_eq!(state(0.1, 0, [30, 0]).layout(100)[0], (0, 30));
+        assert_eq!(state(0.9, 0, [0, 30]).layout(100)[0], (0, 70));
+
+        // Both min sizes cannot be respected, the first pane wins
+        assert_eq!(state(0.5, 0, [80, 80]).layout(100)[0], (0, 80));
+        assert_eq!(state(0.5, 0, [80, 80]).layout(50)[0], (0, 50));
+    }
+
+    #[test]
+    fn small_splitter() {
+        assert_eq!(state(0.5, 10, [0, 0]).layout(6), [(0, 0), (0, 6), (6, 0)]);
+        assert_eq!(state(0.5, 10, [0, 0]).layout(0), [(0, 0), (0, 0), (0, 0)]);
+    }
+
+    #[test]
+    fn collapsed() {
+        let mut s = state(0.5, 10, [20, 20]);
+        s.collapsed = Some(SplitterPane::First);
+        assert_eq!(s.layout(100), [(0, 0), (0, 0), (0, 100)]);
+
+        s.collapsed = Some(SplitterPane::Second);
+        assert_eq!(s.layout(100), [(0, 100), (100, 0), (100, 0)]);
+
+        // The bar cannot be moved while a pane is collapsed
+        s.nudge(10, 100);
+        assert_eq!(s.ratio, 0.5);
+    }
+
+    #[test]
+    fn set_position_and_nudge() {
+        let mut s = state(0.5, 10, [10, 20]);
+        s.set_position(30, 110);
+        assert_eq!(s.ratio, 0.3);
+
+        s.nudge(-25, 110);
+        assert_eq!(s.layout(110)[0], (0, 10));
+
+        s.set_position(1000, 110);
+        assert_eq!(s.layout(110)[0], (0, 80));
+        assert_eq!(s.ratio, 0.8);
+
+        // Nothing to move in an empty splitter
+        s.set_position(5, 10);
+        assert_eq!(s.ratio, 0.8);
+    }
+
+    #[test]
+    fn ratio_is_kept_on_resize() {
+        let mut s = state(0.5, 4, [0, 0]);
+        s.set_position(25, 104);
+        assert_eq!(s.layout(204)[0], (0, 50));
+        assert_eq!(s.layout(404)[0], (0, 100));
+    }
+}
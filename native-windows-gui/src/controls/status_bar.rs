@@ -1,9 +1,11 @@
 use winapi::shared::minwindef::{WPARAM, LPARAM};
+use winapi::shared::windef::HWND;
 use crate::win32::window_helper as wh;
 use crate::win32::base_helper::check_hwnd;
-use crate::{Font, NwgError, RawEventHandler, unbind_raw_event_handler};
+use crate::{Font, Icon, NwgError, RawEventHandler, unbind_raw_event_handler};
 use super::{ControlHandle, ControlBase};
-use std::cell::RefCell;
+use super::status_bar_parts::{PartWidth, part_edges};
+use std::{cell::RefCell, rc::Rc};
 
 const NOT_BOUND: &'static str = "StatusBar is not yet bound to a winapi object";
 const BAD_HANDLE: &'static str = "INTERNAL ERROR: StatusBar handle is not HWND!";
@@ -12,17 +14,23 @@ const BAD_HANDLE: &'static str = "INTERNAL ERROR: StatusBar handle is not HWND!"
 A status bar is a horizontal window at the bottom of a parent window in which an application can display various kinds of status information.
 Status bar cannot stack, so there must be only one per window.
 
+A status bar can be split in parts (`set_parts`). Each part has its own text, icon and tooltip, and can host a control,
+such as a `ProgressBar`. The parts are laid out again when the parent window is resized (see `hook_parent_resize`).
+
 Requires the `status-bar` feature. 
 
 **Builder parameters:**
   * `parent`:   **Required.** The status bar parent container.
   * `text`:     The status bar text.
   * `font`:     The font used for the status bar text
+  * `parts`:    The width of the parts of the status bar. Defaults to a single part.
 
 **Control events:**
   * `MousePress(_)`: Generic mouse press events on the status bar
   * `OnMouseMove`: Generic mouse mouse event
   * `OnMouseWheel`: Generic mouse wheel event
+  * `OnStatusBarClick`: When a part of the status bar is clicked. The part index is in `EventData::OnStatusBarPart`
+  * `OnStatusBarDoubleClick`: When a part of the status bar is double clicked. The part index is in `EventData::OnStatusBarPart`
 
 ```rust
 use native_windows_gui as nwg;
@@ -35,11 +43,81 @@ fn build_status(status: &mut nwg::StatusBar, window: &nwg::Window, font: &nwg::F
 }
 ```
 
+```rust
+use native_windows_gui as nwg;
+use nwg::PartWidth::{Fixed, Fill};
+
+fn build_status(status: &mut nwg::StatusBar, progress: &mut nwg::ProgressBar, window: &nwg::Window) -> Result<(), nwg::NwgError> {
+    nwg::StatusBar::builder()
+        .parts(&[Fixed(120), Fill, Fixed(150)])
+        .parent(window)
+        .build(status)?;
+
+    nwg::ProgressBar::builder()
+        .parent(&*status)
+        .build(progress)?;
+
+    status.set_text(0, "Ready");
+    status.set_tooltip(0, "The state of the application");
+    status.host_control(2, progress);
+
+    Ok(())
+}
+```
 */
 #[derive(Default)]
 pub struct StatusBar {
     pub handle: ControlHandle,
     handler0: RefCell<Option<RawEventHandler>>,
+    parts: Rc<RefCell<StatusBarParts>>,
+}
+
+/// The parts of a status bar, shared with the resize handler
+#[derive(Default)]
+struct StatusBarParts {
+    widths: Vec<PartWidth>,
+
+    /// Controls displayed over a part
+    hosted: Vec<(u8, HWND)>,
+}
+
+impl StatusBarParts {
+
+    /// Sends the part edges to the status bar and moves the hosted controls over their part
+    fn layout(&self, handle: HWND) {
+        use winapi::um::commctrl::{SB_SETPARTS, SB_GETRECT};
+        use winapi::um::winuser::{GetClientRect, SetWindowPos, SWP_NOZORDER, SWP_NOACTIVATE, SWP_NOOWNERZORDER};
+        use winapi::shared::windef::RECT;
+        use crate::win32::high_dpi::logical_to_physical;
+
+        if !self.widths.is_empty() {
+            let mut client: RECT = unsafe { ::std::mem::zeroed() };
+            unsafe { GetClientRect(handle, &mut client); }
+
+            let widths: Vec<PartWidth> = self.widths.iter()
+                .map(|w| match *w {
+                    PartWidth::Fixed(w) => PartWidth::Fixed(unsafe { logical_to_physical(w as i32, 0).0.max(0) as u32 }),
+                    PartWidth::Fill => PartWidth::Fill
+                })
+                .collect();
+
+            let edges = part_edges(&widths, client.right - client.left);
+            wh::send_message(handle, SB_SETPARTS, edges.len() as WPARAM, edges.as_ptr() as LPARAM);
+        }
+
+        for &(part, control) in self.hosted.iter() {
+            let mut rect: RECT = unsafe { ::std::mem::zeroed() };
+            if wh::send_message(handle, SB_GETRECT, part as WPARAM, &mut rect as *mut RECT as LPARAM) == 0 {
+                continue;
+            }
+
+            let flags = SWP_NOZORDER | SWP_NOACTIVATE | SWP_NOOWNERZORDER;
+            unsafe {
+                SetWindowPos(control, ::std::ptr::null_mut(), rect.left + 1, rect.top + 1, rect.right - rect.left - 2, rect.bottom - rect.top - 2, flags);
+            }
+        }
+    }
+
 }
 
 
@@ -49,10 +127,96 @@ impl StatusBar {
         StatusBarBuilder {
             text: "",
             font: None,
+            parts: None,
             parent: None
         }
     }
 
+    /// Split the status bar into parts. The parts are laid out again when the parent window is resized.
+    /// An empty list sets a single part.
+    pub fn set_parts(&self, parts: &[PartWidth]) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let mut state = self.parts.borrow_mut();
+        state.widths = match parts.is_empty() {
+            true => vec![PartWidth::Fill],
+            false => parts.to_vec()
+        };
+
+        state.layout(handle);
+    }
+
+    /// Return the width of the parts set by `set_parts`
+    pub fn parts(&self) -> Vec<PartWidth> {
+        self.parts.borrow().widths.clone()
+    }
+
+    /// Return the number of parts in the status bar
+    pub fn part_count(&self) -> usize {
+        use winapi::um::commctrl::SB_GETPARTS;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        wh::send_message(handle, SB_GETPARTS, 0, 0) as usize
+    }
+
+    /// Set the icon displayed in one of the part of the status bar. `None` removes the icon.
+    /// The icon must live as long as it is displayed.
+    pub fn set_icon(&self, index: u8, icon: Option<&Icon>) {
+        use winapi::um::commctrl::SB_SETICON;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        let icon_handle = icon.map(|i| i.handle).unwrap_or(::std::ptr::null_mut());
+        wh::send_message(handle, SB_SETICON, index as WPARAM, icon_handle as LPARAM);
+    }
+
+    /// Return the tooltip text of one of the part of the status bar
+    pub fn tooltip(&self, index: u8) -> String {
+        use winapi::um::commctrl::SB_GETTIPTEXTW;
+        use winapi::shared::minwindef::MAKELONG;
+        use crate::win32::base_helper::from_utf16;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let mut buffer: Vec<u16> = vec![0; 1024];
+        let w = MAKELONG(index as u16, buffer.len() as u16);
+        wh::send_message(handle, SB_GETTIPTEXTW, w as WPARAM, buffer.as_mut_ptr() as LPARAM);
+
+        from_utf16(&buffer)
+    }
+
+    /// Set the tooltip text of one of the part of the status bar. The tooltip is only displayed
+    /// if the text of the part is truncated or if the part only displays an icon.
+    pub fn set_tooltip(&self, index: u8, text: &str) {
+        use winapi::um::commctrl::SB_SETTIPTEXTW;
+        use crate::win32::base_helper::to_utf16;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        let text = to_utf16(text);
+        wh::send_message(handle, SB_SETTIPTEXTW, index as WPARAM, text.as_ptr() as LPARAM);
+    }
+
+    /**
+        Display a control, such as a `ProgressBar`, over one of the part of the status bar. The control becomes a child
+        of the status bar and it follows the part when the status bar is resized. A part hosts a single control.
+    */
+    pub fn host_control<C: Into<ControlHandle>>(&self, index: u8, control: C) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        let control = control.into().hwnd().expect("Hosted control must be a window control");
+
+        wh::set_window_parent(control, handle);
+
+        let mut state = self.parts.borrow_mut();
+        state.hosted.retain(|&(part, c)| part != index && c != control);
+        state.hosted.push((index, control));
+        state.layout(handle);
+    }
+
+    /// Stop moving a control over a part of the status bar. The control is not destroyed.
+    pub fn remove_hosted_control<C: Into<ControlHandle>>(&self, control: C) {
+        let control = control.into().hwnd();
+        self.parts.borrow_mut().hosted.retain(|&(_, c)| Some(c) != control);
+    }
+
     /// Set the minimum height of the statusbar (in pixels)
     pub fn set_min_height(&self, height: u32) {
         use winapi::um::commctrl::SB_SETMINHEIGHT;
@@ -121,12 +285,13 @@ impl StatusBar {
     /// Winapi flags required by the control
     pub fn forced_flags(&self) -> u32 {
         use winapi::um::winuser::{WS_CHILD};
+        use winapi::um::commctrl::SBARS_TOOLTIPS;
 
-        WS_CHILD
+        WS_CHILD | SBARS_TOOLTIPS
     }
 
     /// Status bar do not resize automatically. Instead, a resize message must be
-    /// manually sent by the parent window to trigger the resize action.
+    /// manually sent by the parent window to trigger the resize action. The parts are laid out after the resize.
     pub fn hook_parent_resize(&self) {
         use winapi::um::winuser::WM_SIZE;
         use crate::bind_raw_event_handler_inner;
@@ -135,9 +300,11 @@ impl StatusBar {
         let handle = self.handle.hwnd().expect(BAD_HANDLE);
 
         let parent_handle = ControlHandle::Hwnd(wh::get_window_parent(handle));
+        let parts = self.parts.clone();
         let handler = bind_raw_event_handler_inner(&parent_handle, handle as usize, move |_hwnd, msg, _w, _l| {
             if msg == WM_SIZE {
                 wh::send_message(handle, WM_SIZE, 0, 0);
+                parts.borrow().layout(handle);
             }
 
             None
@@ -161,6 +328,7 @@ impl Drop for StatusBar {
 pub struct StatusBarBuilder<'a> {
     text: &'a str,
     font: Option<&'a Font>,
+    parts: Option<&'a [PartWidth]>,
     parent: Option<ControlHandle>
 }
 
@@ -176,6 +344,11 @@ impl<'a> StatusBarBuilder<'a> {
         self
     }
 
+    pub fn parts(mut self, parts: &'a [PartWidth]) -> StatusBarBuilder<'a> {
+        self.parts = Some(parts);
+        self
+    }
+
     pub fn parent<C: Into<ControlHandle>>(mut self, p: C) -> StatusBarBuilder<'a> {
         self.parent = Some(p.into());
         self
@@ -202,6 +375,10 @@ impl<'a> StatusBarBuilder<'a> {
             out.set_font(Font::global_default().as_ref());
         }
 
+        if let Some(parts) = self.parts {
+            out.set_parts(parts);
+        }
+
         out.set_text(0, self.text);
         out.hook_parent_resize();
 
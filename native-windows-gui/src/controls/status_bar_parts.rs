@@ -0,0 +1,80 @@
+/*!
+    The layout of the parts of a `StatusBar`.
+    This module does not depend on the windowing system.
+*/
+
+
+/**
+    The width of a part of a `StatusBar`.
+
+    * Fixed: A width in logical pixels
+    * Fill: A share of the space left by the fixed parts. The space is split evenly between the `Fill` parts.
+*/
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PartWidth {
+    Fixed(u32),
+    Fill,
+}
+
+/**
+    Computes the right edge of each part (the `SB_SETPARTS` format) in a status bar `total` pixels wide.
+    The fixed widths must already be scaled to physical pixels. A last `Fill` part extends to the right border (`-1`).
+*/
+pub(crate) fn part_edges(parts: &[PartWidth], total: i32) -> Vec<i32> {
+    let fixed: i64 = parts.iter()
+        .map(|p| match p { PartWidth::Fixed(w) => *w as i64, PartWidth::Fill => 0 })
+        .sum();
+
+    let fill_count = parts.iter().filter(|p| **p == PartWidth::Fill).count() as i64;
+    let free = (total as i64 - fixed).max(0);
+
+    let mut edges = Vec::with_capacity(parts.len());
+    let mut edge = 0i64;
+    let mut fill_index = 0i64;
+    for part in parts {
+        edge += match part {
+            PartWidth::Fixed(w) => *w as i64,
+            PartWidth::Fill => {
+                // The rounding error goes to the last fill part
+                fill_index += 1;
+                (free * fill_index / fill_count) - (free * (fill_index - 1) / fill_count)
+            }
+        };
+
+        edges.push(edge.min(i32::MAX as i64) as i32);
+    }
+
+    if parts.last() == Some(&PartWidth::Fill) {
+        *edges.last_mut().unwrap() = -1;
+    }
+
+    edges
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::PartWidth::*;
+
+    #[test]
+    fn fill_between_fixed_parts() {
+        assert_eq!(part_edges(&[Fixed(120), Fill, Fixed(80)], 500), vec![120, 420, 500]);
+        assert_eq!(part_edges(&[Fixed(120), Fixed(80)], 500), vec![120, 200]);
+        assert_eq!(part_edges(&[Fixed(120), Fill], 500), vec![120, -1]);
+        assert_eq!(part_edges(&[], 500), Vec::<i32>::new());
+    }
+
+    #[test]
+    fn fill_parts_share_the_free_space() {
+        assert_eq!(part_edges(&[Fill, Fill, Fixed(100)], 401), vec![150, 301, 401]);
+        assert_eq!(part_edges(&[Fill, Fixed(100), Fill], 400), vec![150, 250, -1]);
+    }
+
+    #[test]
+    fn fixed_parts_wider_than_the_status_bar() {
+        assert_eq!(part_edges(&[Fixed(300), Fill, Fixed(300)], 500), vec![300, 300, 600]);
+        assert_eq!(part_edges(&[Fill], 0), vec![-1]);
+    }
+
+}
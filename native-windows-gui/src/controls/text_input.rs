@@ -1,13 +1,13 @@
-use winapi::shared::{
+use crate::win32::sys::shared::{
     windef::HBRUSH,
     minwindef::{UINT, WPARAM, LPARAM}
 };
-use winapi::um::{
+use crate::win32::sys::um::{
     winuser::{WS_VISIBLE, WS_DISABLED, ES_NUMBER, ES_LEFT, ES_CENTER, ES_RIGHT, WS_TABSTOP, ES_AUTOHSCROLL},
     wingdi::DeleteObject,
 };
 use crate::win32::window_helper as wh; 
-use crate::win32::base_helper::{check_hwnd, to_utf16};
+use crate::win32::base_helper::{check_hwnd, to_utf16, from_utf16};
 use crate::{Font, NwgError, HTextAlign, RawEventHandler};
 use super::{ControlBase, ControlHandle};
 use std::cell::RefCell;
@@ -124,7 +124,7 @@ impl TextInput {
 
     /// Return the password character displayed by the text input. If the input is not a password, return None.
     pub fn password_char(&self) -> Option<char> {
-        use winapi::um::winuser::EM_GETPASSWORDCHAR;
+        use crate::win32::sys::um::winuser::EM_GETPASSWORDCHAR;
 
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
 
@@ -138,7 +138,7 @@ impl TextInput {
     /// Set or Remove the password character displayed by the text input.
     /// If the input is not a password all character are re-rendered with the new character
     pub fn set_password_char(&self, c: Option<char>) {
-        use winapi::um::winuser::{InvalidateRect, EM_SETPASSWORDCHAR};
+        use crate::win32::sys::um::winuser::{InvalidateRect, EM_SETPASSWORDCHAR};
 
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
         wh::send_message(handle, EM_SETPASSWORDCHAR as u32, c.map(|c| c as usize).unwrap_or(0), 0);
@@ -149,7 +149,7 @@ impl TextInput {
 
     /// Return the number of maximum character allowed in this text input
     pub fn limit(&self) -> u32 {
-        use winapi::um::winuser::EM_GETLIMITTEXT;
+        use crate::win32::sys::um::winuser::EM_GETLIMITTEXT;
 
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
         wh::send_message(handle, EM_GETLIMITTEXT as u32, 0, 0) as u32
@@ -158,7 +158,7 @@ impl TextInput {
     /// Set the number of maximum character allowed in this text input
     /// If `limit` is 0, the text length is set to 0x7FFFFFFE characters 
     pub fn set_limit(&self, limit: usize) {
-        use winapi::um::winuser::EM_SETLIMITTEXT;
+        use crate::win32::sys::um::winuser::EM_SETLIMITTEXT;
 
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
         wh::send_message(handle, EM_SETLIMITTEXT as u32, limit, 0);
@@ -166,7 +166,7 @@ impl TextInput {
 
     /// Check if the content of the text input was modified after it's creation
     pub fn modified(&self) -> bool {
-        use winapi::um::winuser::EM_GETMODIFY;
+        use crate::win32::sys::um::winuser::EM_GETMODIFY;
 
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
         wh::send_message(handle, EM_GETMODIFY as u32, 0, 0) != 0
@@ -174,14 +174,14 @@ impl TextInput {
 
     /// Manually set modified flag of the text input
     pub fn set_modified(&self, e: bool) {
-        use winapi::um::winuser::EM_SETMODIFY;
+        use crate::win32::sys::um::winuser::EM_SETMODIFY;
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
         wh::send_message(handle, EM_SETMODIFY as u32, e as usize, 0);
     }
 
     /// Undo the last action by the user in the control
     pub fn undo(&self) {
-        use winapi::um::winuser::EM_UNDO;
+        use crate::win32::sys::um::winuser::EM_UNDO;
 
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
         wh::send_message(handle, EM_UNDO as u32, 0, 0);
@@ -189,7 +189,7 @@ impl TextInput {
 
     /// Return the selected range of characters by the user in the text input
     pub fn selection(&self) -> Range<u32> {
-        use winapi::um::winuser::EM_GETSEL;
+        use crate::win32::sys::um::winuser::EM_GETSEL;
 
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
 
@@ -204,7 +204,7 @@ impl TextInput {
 
     /// Return the selected range of characters by the user in the text input
     pub fn set_selection(&self, r: Range<u32>) {
-        use winapi::um::winuser::EM_SETSEL;
+        use crate::win32::sys::um::winuser::EM_SETSEL;
 
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
         wh::send_message(handle, EM_SETSEL as u32, r.start as usize, r.end as isize);
@@ -213,7 +213,7 @@ impl TextInput {
     /// Return the length of the user input in the control. This is better than `input.text().len()` as it
     /// does not allocate a string in memory
     pub fn len(&self) -> u32 {
-        use winapi::um::winuser::EM_LINELENGTH;
+        use crate::win32::sys::um::winuser::EM_LINELENGTH;
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
         wh::send_message(handle, EM_LINELENGTH as u32, 0, 0) as u32
     }
@@ -221,7 +221,7 @@ impl TextInput {
     /// Return true if the TextInput value cannot be edited. Retrurn false otherwise.
     /// A user can still copy text from a readonly TextEdit (unlike disabled)
     pub fn readonly(&self) -> bool {
-        use winapi::um::winuser::ES_READONLY;
+        use crate::win32::sys::um::winuser::ES_READONLY;
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
         wh::get_style(handle) & ES_READONLY == ES_READONLY
     }
@@ -229,7 +229,7 @@ impl TextInput {
     /// Set the readonly flag of the text input
     /// A user can still copy text from a readonly TextEdit (unlike disabled)
     pub fn set_readonly(&self, r: bool) {
-        use winapi::um::winuser::EM_SETREADONLY;
+        use crate::win32::sys::um::winuser::EM_SETREADONLY;
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
         wh::send_message(handle, EM_SETREADONLY as u32, r as WPARAM, 0);
     }
@@ -312,24 +312,22 @@ impl TextInput {
     /// as long as the user specified, however it might be longer or shorter than
     /// the actual placeholder text.
     pub fn placeholder_text<'a>(&self, text_length: usize) -> String { 
-        use std::ffi::OsString;
-        use std::os::windows::ffi::OsStringExt;
-        use winapi::shared::ntdef::WCHAR;
-        use winapi::um::commctrl::EM_GETCUEBANNER;
+        use crate::win32::sys::shared::ntdef::WCHAR;
+        use crate::win32::sys::um::commctrl::EM_GETCUEBANNER;
 
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
         let mut placeholder_text: Vec<WCHAR> = Vec::with_capacity(text_length);
         unsafe {
             placeholder_text.set_len(text_length);
             wh::send_message(handle, EM_GETCUEBANNER, placeholder_text.as_mut_ptr() as WPARAM, placeholder_text.len() as LPARAM);
-            OsString::from_wide(&placeholder_text).into_string().unwrap_or("".to_string())
+            from_utf16(&placeholder_text)
         }
     }
 
     /// Set the placeholder text displayed in the TextInput
     /// when it is empty and does not have focus
     pub fn set_placeholder_text<'a>(&self, v: Option<&'a str>) {
-        use winapi::um::commctrl::EM_SETCUEBANNER;
+        use crate::win32::sys::um::commctrl::EM_SETCUEBANNER;
     
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
         let placeholder_text = v.unwrap_or("");
@@ -344,24 +342,25 @@ impl TextInput {
 
     /// Winapi base flags used during window creation
     pub fn flags(&self) -> u32 {
-        ::winapi::um::winuser::WS_VISIBLE
+        crate::win32::sys::um::winuser::WS_VISIBLE
     }
 
     /// Winapi flags required by the control
     pub fn forced_flags(&self) -> u32 {
-        use winapi::um::winuser::{WS_BORDER, WS_CHILD};
+        use crate::win32::sys::um::winuser::{WS_BORDER, WS_CHILD};
         
         WS_BORDER | WS_TABSTOP | ES_AUTOHSCROLL | WS_CHILD
     }
 
     /// Center the text vertically. Can't believe that must be manually hacked in.
+    #[cfg(not(feature = "headless"))]
     fn hook_non_client_size(&mut self, bg: Option<[u8; 3]>) {
         use crate::bind_raw_event_handler_inner;
-        use winapi::shared::windef::{HGDIOBJ, RECT, POINT};
-        use winapi::um::winuser::{WM_NCCALCSIZE, WM_NCPAINT, WM_SIZE, DT_CALCRECT, DT_LEFT, NCCALCSIZE_PARAMS, COLOR_WINDOW,};
-        use winapi::um::winuser::{SWP_NOOWNERZORDER, SWP_NOSIZE, SWP_NOMOVE, SWP_FRAMECHANGED};
-        use winapi::um::winuser::{GetDC, DrawTextW, ReleaseDC, GetClientRect, GetWindowRect, FillRect, ScreenToClient, SetWindowPos};
-        use winapi::um::wingdi::{SelectObject, CreateSolidBrush, RGB};
+        use crate::win32::sys::shared::windef::{HGDIOBJ, RECT, POINT};
+        use crate::win32::sys::um::winuser::{WM_NCCALCSIZE, WM_NCPAINT, WM_SIZE, DT_CALCRECT, DT_LEFT, NCCALCSIZE_PARAMS, COLOR_WINDOW,};
+        use crate::win32::sys::um::winuser::{SWP_NOOWNERZORDER, SWP_NOSIZE, SWP_NOMOVE, SWP_FRAMECHANGED};
+        use crate::win32::sys::um::winuser::{GetDC, DrawTextW, ReleaseDC, GetClientRect, GetWindowRect, FillRect, ScreenToClient, SetWindowPos};
+        use crate::win32::sys::um::wingdi::{SelectObject, CreateSolidBrush, RGB};
         use std::{mem, ptr};
 
         if self.handle.blank() { panic!("{}", NOT_BOUND); }
@@ -458,6 +457,9 @@ impl TextInput {
         }
     }
 
+    #[cfg(feature = "headless")]
+    fn hook_non_client_size(&mut self, _bg: Option<[u8; 3]>) {}
+
 }
 
 impl Drop for TextInput {
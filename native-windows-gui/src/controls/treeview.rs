@@ -2,14 +2,18 @@
 A tree-view control is a window that displays a hierarchical list of items
 */
 
-use winapi::shared::minwindef::{WPARAM, LPARAM};
-use winapi::um::winuser::{WS_VISIBLE, WS_DISABLED, WS_TABSTOP};
-use winapi::um::commctrl::{HTREEITEM, TVIS_EXPANDED, TVIS_SELECTED, TVS_SHOWSELALWAYS, TVITEMW};
+use winapi::shared::minwindef::{UINT, WPARAM, LPARAM};
+use winapi::shared::windef::HWND;
+use winapi::um::winuser::{WS_VISIBLE, WS_DISABLED, WS_TABSTOP, WM_USER};
+use winapi::um::commctrl::{HTREEITEM, TVIS_EXPANDED, TVIS_SELECTED, TVS_SHOWSELALWAYS, TVITEMW, TVIS_STATEIMAGEMASK};
 use crate::win32::window_helper as wh;
 use crate::win32::base_helper::{check_hwnd, to_utf16, from_utf16};
-use crate::{Font, NwgError};
-use super::{ControlBase, ControlHandle};
-use std::{mem, ptr};
+use crate::{Font, NwgError, RawEventHandler, unbind_raw_event_handler};
+use super::{ControlBase, ControlHandle, CheckBoxState};
+use super::item_data::ItemDataStore;
+use super::item_style::{ItemStyle, apply_item_style};
+use super::treeview_check::{CheckTree, propagate, toggled};
+use std::{mem, ptr, rc::Rc, cell::{Cell, Ref, RefCell}, collections::{HashMap, HashSet}};
 
 #[cfg(feature="image-list")]
 use winapi::um::commctrl::HIMAGELIST;
@@ -17,8 +21,15 @@ use winapi::um::commctrl::HIMAGELIST;
 #[cfg(feature="image-list")]
 use crate::ImageList;
 
-const NOT_BOUND: &'static str = "TreeView is not yet bound to a winapi object";
-const BAD_HANDLE: &'static str = "INTERNAL ERROR: TreeView handle is not HWND!";
+#[cfg(feature="drag-drop")]
+use crate::{DragPayload, DropEffect};
+
+const NOT_BOUND: &str = "TreeView is not yet bound to a winapi object";
+const BAD_HANDLE: &str = "INTERNAL ERROR: TreeView handle is not HWND!";
+
+// Messages posted to the tree view to finish handling a notification after the event handlers
+const NWG_TV_LOADED: UINT = WM_USER + 110;
+const NWG_TV_CHECK: UINT = WM_USER + 111;
 
 
 bitflags! {
@@ -109,6 +120,23 @@ impl TreeItem {
     }
 }
 
+/// State shared between a tree view and its hooks
+#[derive(Default)]
+struct TreeViewState {
+    styles: RefCell<HashMap<isize, ItemStyle>>,
+
+    /// The lazy items whose children were not loaded yet
+    lazy: RefCell<HashSet<isize>>,
+
+    tri_state: Cell<bool>,
+
+    /// The items clicked by the user whose check state is not updated yet
+    pending_checks: RefCell<Vec<isize>>,
+
+    /// Set while the tree view updates the check state of its items
+    updating: Cell<bool>,
+}
+
 /**
 A tree-view control is a window that displays a hierarchical list of items.
 
@@ -127,6 +155,8 @@ Requires the `tree-view` feature
   * `font`:       The font used for the treeview text
   * `parent`:     The treeview parent container.
   * `image_list`: Image list containing the icon to use in the tree-view
+  * `checkboxes`: Display a checkbox next to each item
+  * `tri_state`:  Display checkboxes and propagate their state between parents and children. See `TreeView::set_item_check_state`
 
 **Control events:**
   * `MousePress(_)`: Generic mouse press events on the tree view
@@ -139,13 +169,73 @@ Requires the `tree-view` feature
   * `OnTreeFocus`: When the control has acquired the input focus
   * `OnTreeItemDelete`: Just before an item is deleted. Also sent for all the children.
   * `OnTreeItemExpanded`: After an item was expanded or collapsed. Sends a `EventData::OnTreeItemUpdate`.
+  * `OnTreeItemExpanding`: Before an item is expanded or collapsed. Sends a `EventData::OnTreeItemUpdate`.
   * `OnTreeItemChanged`: After the state of an item was changed. Sends a `EventData::OnTreeItemUpdate`.
   * `OnTreeItemSelectionChanged`: After the current selection was changed. Sends a `EventData::OnTreeItemChanged`.
+  * `OnTreeViewBeginDrag`: When the user starts dragging an item with the left mouse button. Sends a `EventData::OnTreeViewBeginDrag`.
+
+**Item data:**
+
+A `TreeView<T>` can attach a value of type `T` to each item with `insert_item_with_data` or `set_item_data`.
+The value is owned by the tree view and is dropped when the item is deleted (`remove_item`, `clear`, `OnTreeItemDelete`)
+or when the tree view is destroyed. The data is stored in the item `lParam`, so `insert_item_with_param`
+must not be used on a tree view that stores data. `TreeView` without a type parameter does not store any data.
+
+```rust
+use native_windows_gui as nwg;
+use std::path::PathBuf;
+
+fn add_file(tree: &nwg::TreeView<PathBuf>, parent: &nwg::TreeItem, path: PathBuf) -> nwg::TreeItem {
+    let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    tree.insert_item_with_data(&name, Some(parent), nwg::TreeInsert::Last, path)
+}
+
+fn selected_path(tree: &nwg::TreeView<PathBuf>) -> Option<PathBuf> {
+    let item = tree.selected_item()?;
+    tree.item_data(&item).map(|path| path.clone())
+}
+```
+
+**Item styles:**
+
+`set_item_style` changes the text color, the background color and the font of a single item. The style is removed with the item.
+The width of an item is computed with the tree view font, so a larger font can cut the end of the item text.
+
+**Checkboxes and lazy loading:**
+
+A tree view built with `checkboxes` or `tri_state` displays a checkbox next to each item (`item_checked`, `set_item_checked`).
+With `tri_state`, checking an item checks all its descendants and the parents become indeterminate when only some of
+their children are checked. Inserting or removing an item does not update the state of its parent.
+
+A lazy item (`set_item_lazy`) shows an expand button before it has any children. Its children are inserted by the
+`OnTreeItemExpanding` handlers the first time the item is expanded.
+
+```rust
+use native_windows_gui as nwg;
+
+fn add_folder(tree: &nwg::TreeView, parent: Option<&nwg::TreeItem>, name: &str) -> nwg::TreeItem {
+    let item = tree.insert_item(name, parent, nwg::TreeInsert::Last);
+    tree.set_item_lazy(&item, true);
+    item
+}
+
+fn on_expanding(tree: &nwg::TreeView, data: &nwg::EventData) {
+    let (item, _action) = data.on_tree_item_update();
+    if tree.item_is_lazy(item) {
+        for name in ["a", "b", "c"].iter() {
+            add_folder(tree, Some(item), name);
+        }
+    }
+}
+```
 */
-#[derive(Default, PartialEq, Eq)]
-pub struct TreeView {
-    pub handle: ControlHandle
-} 
+pub struct TreeView<T = ()> {
+    pub handle: ControlHandle,
+    data: Rc<ItemDataStore<T>>,
+    state: Rc<TreeViewState>,
+    handler0: Option<RawEventHandler>,
+    handler1: Option<RawEventHandler>,
+}
 
 
 impl TreeView {
@@ -160,12 +250,18 @@ impl TreeView {
             ex_flags: 0,
             font: None,
             parent: None,
+            checkboxes: false,
+            tri_state: false,
 
             #[cfg(feature="image-list")]
             image_list: None,
         }
     }
 
+}
+
+impl<T: 'static> TreeView<T> {
+
     /// Sets the image list of the treeview
     #[cfg(feature="image-list")]
     pub fn set_image_list(&self, list: Option<&ImageList>) {
@@ -345,6 +441,33 @@ impl TreeView {
         items
     }
 
+    /// Returns the text of the selected items as a drag payload. The items are separated by new lines.
+    #[cfg(feature="drag-drop")]
+    pub fn selected_items_payload(&self) -> DragPayload {
+        let items: Vec<String> = self.selected_items().iter()
+            .filter_map(|item| self.item_text(item))
+            .collect();
+
+        match items.is_empty() {
+            true => DragPayload::new(),
+            false => DragPayload::new().text(&items.join("\r\n"))
+        }
+    }
+
+    /**
+        Starts a drag and drop operation with the text of the selected items. Usually called from `OnTreeViewBeginDrag`.
+        Blocks until the data is dropped or the drag is cancelled and returns the effect picked by the drop target.
+        Returns `DropEffect::NONE` if no items are selected.
+    */
+    #[cfg(feature="drag-drop")]
+    pub fn drag_selected_items(&self, allowed: DropEffect) -> DropEffect {
+        let payload = self.selected_items_payload();
+        match payload.is_empty() {
+            true => DropEffect::NONE,
+            false => payload.start(allowed)
+        }
+    }
+
     /// Returns the number of selected item in the tree view
     pub fn selected_item_count(&self) -> usize {
         use winapi::um::commctrl::{TVM_GETNEXTITEM, TVGN_NEXTSELECTED};
@@ -396,11 +519,166 @@ impl TreeView {
         let ptr = &new_item as *const TVINSERTSTRUCTW;
         let handle = wh::send_message(handle, TVM_INSERTITEMW, 0, ptr as LPARAM) as HTREEITEM;
 
+        self.loaded(parent);
         self.invalidate();
 
         TreeItem { handle }
     }
 
+    /// Insert a new item into the TreeView, attach `data` to it and return a reference to new newly added item. See `set_item_data`.
+    /// If the item could not be inserted, `data` is dropped.
+    pub fn insert_item_with_data(&self, new: &str, parent: Option<&TreeItem>, position: TreeInsert, data: T) -> TreeItem {
+        let key = self.data.insert(data);
+        let item = self.insert_item_with_param(new, parent, position, key);
+        if item.is_null() {
+            self.data.remove(key);
+        }
+
+        item
+    }
+
+    /// Attach `data` to an item, replacing the data that was attached to it.
+    /// Returns `false` and drops `data` if the item is not in the tree view.
+    pub fn set_item_data(&self, tree_item: &TreeItem, data: T) -> bool {
+        use winapi::um::commctrl::{TVM_SETITEMW, TVIF_PARAM, TVIF_HANDLE};
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        let old_key = match self.item_param(tree_item) {
+            Some(key) => key,
+            None => { return false; }
+        };
+
+        let key = self.data.insert(data);
+
+        let mut item: TVITEMW = blank_item();
+        item.mask = TVIF_HANDLE | TVIF_PARAM;
+        item.hItem = tree_item.handle;
+        item.lParam = key;
+
+        if wh::send_message(handle, TVM_SETITEMW, 0, &mut item as *mut TVITEMW as LPARAM) == 0 {
+            self.data.remove(key);
+            return false;
+        }
+
+        self.data.remove(old_key);
+        true
+    }
+
+    /// Returns the data attached to an item, or `None` if the item is not in the tree view or has no data.
+    ///
+    /// The data must be released before the item is deleted: if the item is deleted while its data is borrowed,
+    /// the data is only dropped once the borrows end, the next time the data of the items is accessed.
+    /// Data attached to an item while data is borrowed is not returned until the borrows end.
+    pub fn item_data(&self, tree_item: &TreeItem) -> Option<Ref<'_, T>> {
+        self.item_param(tree_item).and_then(|key| self.data.get(key))
+    }
+
+    /// Set the colors and the font of an item. `None` restores the default style.
+    pub fn set_item_style(&self, tree_item: &TreeItem, style: Option<ItemStyle>) {
+        use winapi::um::commctrl::TVM_GETITEMRECT;
+        use winapi::um::winuser::InvalidateRect;
+        use winapi::shared::windef::RECT;
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let key = tree_item.handle as isize;
+        match style {
+            Some(style) => { self.state.styles.borrow_mut().insert(key, style); },
+            None => { self.state.styles.borrow_mut().remove(&key); }
+        }
+
+        // `TVM_GETITEMRECT` reads the item handle from the rect
+        let mut rect: RECT = unsafe { mem::zeroed() };
+        unsafe { *(&mut rect as *mut RECT as *mut HTREEITEM) = tree_item.handle; }
+
+        if wh::send_message(handle, TVM_GETITEMRECT, 0, &mut rect as *mut RECT as LPARAM) != 0 {
+            unsafe { InvalidateRect(handle, &rect, 1); }
+        }
+    }
+
+    /// Returns the style of an item, or `None` if the item uses the default style
+    pub fn item_style(&self, tree_item: &TreeItem) -> Option<ItemStyle> {
+        self.state.styles.borrow().get(&(tree_item.handle as isize)).cloned()
+    }
+
+    /// Returns the check state of an item, or `None` if the item is not in the tree view or if the tree view has no checkboxes
+    pub fn item_check_state(&self, tree_item: &TreeItem) -> Option<CheckBoxState> {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        check_state(handle, tree_item.handle)
+    }
+
+    /// Returns `true` if the checkbox of an item is checked
+    pub fn item_checked(&self, tree_item: &TreeItem) -> bool {
+        self.item_check_state(tree_item) == Some(CheckBoxState::Checked)
+    }
+
+    /**
+        Set the check state of an item. In a tri-state tree view, the state is propagated to the children and the parents
+        of the item. `Indeterminate` is only displayed by a tri-state tree view.
+    */
+    pub fn set_item_check_state(&self, tree_item: &TreeItem, state: CheckBoxState) {
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        match self.state.tri_state.get() {
+            true => update_check_state(handle, &self.state, tree_item.handle, state),
+            false => set_check_state(handle, tree_item.handle, state)
+        }
+    }
+
+    /// Check or uncheck an item. See `set_item_check_state`
+    pub fn set_item_checked(&self, tree_item: &TreeItem, checked: bool) {
+        let state = match checked {
+            true => CheckBoxState::Checked,
+            false => CheckBoxState::Unchecked
+        };
+
+        self.set_item_check_state(tree_item, state);
+    }
+
+    /// Returns `true` if the check states are propagated between the parents and the children
+    pub fn tri_state(&self) -> bool {
+        self.state.tri_state.get()
+    }
+
+    /**
+        Mark the children of an item as not loaded. A lazy item shows an expand button even if it has no children.
+        The item stops being lazy once a child is inserted under it or once it was expanded. If the item still has
+        no children after `OnTreeItemExpanding`, its expand button is removed.
+
+        Use `ExpandState::CollapseReset` and `set_item_lazy` to load the children again on the next expansion.
+    */
+    pub fn set_item_lazy(&self, tree_item: &TreeItem, lazy: bool) {
+        use winapi::um::commctrl::{TVM_SETITEMW, TVIF_CHILDREN, I_CHILDRENCALLBACK};
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+
+        let key = tree_item.handle as isize;
+        match lazy {
+            true => { self.state.lazy.borrow_mut().insert(key); },
+            false => { self.state.lazy.borrow_mut().remove(&key); }
+        }
+
+        // The tree view asks for the children count of the item when it is painted
+        let mut item = blank_item();
+        item.mask = TVIF_CHILDREN;
+        item.hItem = tree_item.handle;
+        item.cChildren = I_CHILDRENCALLBACK;
+
+        wh::send_message(handle, TVM_SETITEMW, 0, &mut item as *mut TVITEMW as LPARAM);
+    }
+
+    /// Returns `true` if the children of a lazy item were not loaded yet
+    pub fn item_is_lazy(&self, tree_item: &TreeItem) -> bool {
+        self.state.lazy.borrow().contains(&(tree_item.handle as isize))
+    }
+
+    /// Called when a child is inserted under `parent`
+    fn loaded(&self, parent: Option<&TreeItem>) {
+        if let Some(parent) = parent {
+            self.state.lazy.borrow_mut().remove(&(parent.handle as isize));
+        }
+    }
+
     /// Insert a new item into the TreeView with associated lParam and return a reference to new newly added item
     pub fn insert_item_with_param<'a>(&self, new: &'a str, parent: Option<&TreeItem>, position: TreeInsert, data: isize) -> TreeItem {
         use winapi::um::commctrl::{TVM_INSERTITEMW, TVINSERTSTRUCTW, TVI_FIRST, TVI_LAST, TVI_ROOT, TVI_SORT, TVIF_TEXT, TVIF_PARAM};
@@ -437,12 +715,13 @@ impl TreeView {
         let ptr = &new_item as *const TVINSERTSTRUCTW;
         let handle = wh::send_message(handle, TVM_INSERTITEMW, 0, ptr as LPARAM) as HTREEITEM;
 
+        self.loaded(parent);
         self.invalidate();
 
         TreeItem { handle }
     }
 
-    /// Remove an item and its children from the tree view
+    /// Remove an item and its children from the tree view. The data attached to the items is dropped.
     pub fn remove_item(&self, item: &TreeItem) {
         use winapi::um::commctrl::{TVM_DELETEITEM};
 
@@ -480,14 +759,14 @@ impl TreeView {
 
     /// Creates an iterator over the tree view items
     #[cfg(feature="tree-view-iterator")]
-    pub fn iter<'a>(&'a self) -> crate::TreeViewIterator<'a> {
+    pub fn iter<'a>(&'a self) -> crate::TreeViewIterator<'a, T> {
         check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
         crate::TreeViewIterator::new(self, ptr::null_mut())
     }
 
     /// Creates an iterator over the children of an item. This does not include the item itself.
     #[cfg(feature="tree-view-iterator")]
-    pub fn iter_item<'a>(&'a self, item: &TreeItem) -> crate::TreeViewIterator<'a> {
+    pub fn iter_item<'a>(&'a self, item: &TreeItem) -> crate::TreeViewIterator<'a, T> {
         check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
         crate::TreeViewIterator::new(self, item.handle)
     }
@@ -553,7 +832,7 @@ impl TreeView {
 
     /// Returns `true` if the tree view item has children. Returns `None` if the item is not in the tree view.
     pub fn item_has_children(&self, tree_item: &TreeItem) -> Option<bool> {
-        use winapi::um::commctrl::{TVM_GETITEMW, TVIF_CHILDREN, TVIF_HANDLE};
+        use winapi::um::commctrl::{TVM_GETITEMW, TVIF_CHILDREN, TVIF_HANDLE, I_CHILDRENCALLBACK};
 
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
 
@@ -566,7 +845,10 @@ impl TreeView {
             return None;
         }
 
-        Some(item.cChildren != 0)
+        match item.cChildren {
+            I_CHILDRENCALLBACK => Some(self.item_is_lazy(tree_item) || first_child(handle, tree_item.handle).is_some()),
+            children => Some(children != 0)
+        }
     }
 
     /// Returns the item state in the tree view or `None` if the item is not in the tree view
@@ -613,7 +895,7 @@ impl TreeView {
         wh::send_message(handle, TVM_ENSUREVISIBLE, 0, item.handle as LPARAM);
     }
 
-    /// Remove every item from the treeview by removing the root item
+    /// Remove every item from the treeview by removing the root item. The data attached to the items is dropped.
     pub fn clear(&self) {
         use winapi::um::commctrl::{TVM_DELETEITEM, TVI_ROOT};
 
@@ -776,10 +1058,158 @@ impl TreeView {
     
         wh::send_message(handle,  TVM_ENDEDITLABELNOW, f_cancel as WPARAM, 0) != 0
     }
+
+    /// Handles the tree view notifications sent to the parent window: the removal of the item data, the item styles,
+    /// the lazy items and the checkbox clicks
+    fn hook_parent_notifications(&mut self) {
+        use crate::bind_raw_event_handler_inner;
+        use winapi::um::winuser::{WM_NOTIFY, NMHDR};
+        use winapi::um::commctrl::{TVN_DELETEITEMW, TVN_GETDISPINFOW, TVN_ITEMEXPANDINGW, TVN_ITEMCHANGINGW, NMTREEVIEWW, NMTVDISPINFOW,
+            NMTVITEMCHANGE, TVIF_CHILDREN, TVIF_STATE, NM_CUSTOMDRAW, NMTVCUSTOMDRAW, CDDS_PREPAINT, CDDS_ITEMPREPAINT, CDRF_NOTIFYITEMDRAW};
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        let parent_handle = ControlHandle::Hwnd(wh::get_window_parent(handle));
+        let data = self.data.clone();
+        let state = self.state.clone();
+
+        let handler = bind_raw_event_handler_inner(&parent_handle, handle as usize, move |_hwnd, msg, _w, l| { unsafe {
+            if msg != WM_NOTIFY {
+                return None;
+            }
+
+            let nmhdr: &NMHDR = &*(l as *const NMHDR);
+            if nmhdr.hwndFrom != handle {
+                return None;
+            }
+
+            match nmhdr.code {
+                TVN_DELETEITEMW => {
+                    let info: &NMTREEVIEWW = &*(l as *const NMTREEVIEWW);
+                    let key = info.itemOld.hItem as isize;
+                    data.remove(info.itemOld.lParam);
+                    state.styles.borrow_mut().remove(&key);
+                    state.lazy.borrow_mut().remove(&key);
+                    state.pending_checks.borrow_mut().retain(|&k| k != key);
+                    None
+                },
+                TVN_GETDISPINFOW => {
+                    let info: &mut NMTVDISPINFOW = &mut *(l as *mut NMTVDISPINFOW);
+                    if info.item.mask & TVIF_CHILDREN == TVIF_CHILDREN {
+                        let lazy = state.lazy.borrow().contains(&(info.item.hItem as isize));
+                        info.item.cChildren = (lazy || first_child(handle, info.item.hItem).is_some()) as _;
+                    }
+                    None
+                },
+                TVN_ITEMEXPANDINGW => {
+                    // The lazy flag is removed after the `OnTreeItemExpanding` handlers
+                    let info: &NMTREEVIEWW = &*(l as *const NMTREEVIEWW);
+                    if state.lazy.borrow().contains(&(info.itemNew.hItem as isize)) {
+                        wh::post_message(handle, NWG_TV_LOADED, 0, info.itemNew.hItem as LPARAM);
+                    }
+                    None
+                },
+                TVN_ITEMCHANGINGW if state.tri_state.get() && !state.updating.get() => {
+                    // A click on a checkbox cycles through the state images, including the indeterminate one.
+                    // The change is cancelled and replaced by `toggled` once the notification is handled.
+                    let info: &NMTVITEMCHANGE = &*(l as *const NMTVITEMCHANGE);
+                    let check_changed = (info.uStateNew ^ info.uStateOld) & TVIS_STATEIMAGEMASK != 0;
+                    if info.uChanged & TVIF_STATE == TVIF_STATE && check_changed {
+                        state.pending_checks.borrow_mut().push(info.hItem as isize);
+                        wh::post_message(handle, NWG_TV_CHECK, 0, info.hItem as LPARAM);
+                        return Some(1);
+                    }
+                    None
+                },
+                NM_CUSTOMDRAW if !state.styles.borrow().is_empty() => {
+                    let draw: &mut NMTVCUSTOMDRAW = &mut *(l as *mut NMTVCUSTOMDRAW);
+                    match draw.nmcd.dwDrawStage {
+                        CDDS_PREPAINT => Some(CDRF_NOTIFYITEMDRAW),
+                        CDDS_ITEMPREPAINT => {
+                            let style = state.styles.borrow().get(&(draw.nmcd.dwItemSpec as isize)).cloned();
+                            style.map(|style| apply_item_style(&draw.nmcd, &mut draw.clrText, &mut draw.clrTextBk, &style))
+                        },
+                        _ => None
+                    }
+                },
+                _ => None
+            }
+        } });
+
+        self.handler0 = Some(handler.unwrap());
+    }
+
+    /// Handles the messages posted by the parent hook
+    fn hook_tree_messages(&mut self) {
+        use crate::bind_raw_event_handler_inner;
+        use winapi::um::commctrl::{TVM_SETITEMW, TVIF_CHILDREN, I_CHILDRENCALLBACK};
+
+        let state = self.state.clone();
+
+        let handler = bind_raw_event_handler_inner(&self.handle, 0x020, move |hwnd, msg, _w, l| {
+            match msg {
+                NWG_TV_LOADED => {
+                    let key = l as isize;
+                    if state.lazy.borrow_mut().remove(&key) {
+                        // Removes the expand button if no children were inserted
+                        let mut item = blank_item();
+                        item.mask = TVIF_CHILDREN;
+                        item.hItem = l as HTREEITEM;
+                        item.cChildren = I_CHILDRENCALLBACK;
+                        wh::send_message(hwnd, TVM_SETITEMW, 0, &mut item as *mut TVITEMW as LPARAM);
+                    }
+                    Some(0)
+                },
+                NWG_TV_CHECK => {
+                    let key = l as isize;
+                    let pending = {
+                        let mut pending_checks = state.pending_checks.borrow_mut();
+                        let index = pending_checks.iter().position(|&k| k == key);
+                        index.map(|i| pending_checks.remove(i)).is_some()
+                    };
+
+                    if let (true, Some(current)) = (pending, check_state(hwnd, l as HTREEITEM)) {
+                        update_check_state(hwnd, &state, l as HTREEITEM, toggled(current));
+                    }
+                    Some(0)
+                },
+                _ => None
+            }
+        });
+
+        self.handler1 = Some(handler.unwrap());
+    }
+}
+
+impl<T> Default for TreeView<T> {
+    fn default() -> TreeView<T> {
+        TreeView {
+            handle: Default::default(),
+            data: Default::default(),
+            state: Default::default(),
+            handler0: None,
+            handler1: None,
+        }
+    }
 }
 
-impl Drop for TreeView {
+impl<T> PartialEq for TreeView<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.handle == other.handle
+    }
+}
+
+impl<T> Eq for TreeView<T> {}
+
+impl<T> Drop for TreeView<T> {
     fn drop(&mut self) {
+        if let Some(h) = self.handler0.as_ref() {
+            drop(unbind_raw_event_handler(h));
+        }
+
+        if let Some(h) = self.handler1.as_ref() {
+            drop(unbind_raw_event_handler(h));
+        }
+
         self.handle.destroy();
     }
 }
@@ -795,6 +1225,8 @@ pub struct TreeViewBuilder<'a> {
     ex_flags: u32,
     font: Option<&'a Font>,
     parent: Option<ControlHandle>,
+    checkboxes: bool,
+    tri_state: bool,
 
     #[cfg(feature="image-list")]
     image_list: Option<&'a ImageList>,
@@ -843,13 +1275,23 @@ impl<'a> TreeViewBuilder<'a> {
         self
     }
 
+    pub fn checkboxes(mut self, checkboxes: bool) -> TreeViewBuilder<'a> {
+        self.checkboxes = checkboxes;
+        self
+    }
+
+    pub fn tri_state(mut self, tri_state: bool) -> TreeViewBuilder<'a> {
+        self.tri_state = tri_state;
+        self
+    }
+
     #[cfg(feature="image-list")]
     pub fn image_list(mut self, list: Option<&'a ImageList>) -> TreeViewBuilder<'a> {
         self.image_list = list;
         self
     }
 
-    pub fn build(self, out: &mut TreeView) -> Result<(), NwgError> {
+    pub fn build<T: 'static>(self, out: &mut TreeView<T>) -> Result<(), NwgError> {
         let flags = self.flags.map(|f| f.bits()).unwrap_or(out.flags());
 
         let parent = match self.parent {
@@ -875,6 +1317,22 @@ impl<'a> TreeViewBuilder<'a> {
             out.set_font(Font::global_default().as_ref());
         }
 
+        if self.checkboxes || self.tri_state {
+            use winapi::um::commctrl::{TVS_CHECKBOXES, TVM_SETEXTENDEDSTYLE, TVS_EX_PARTIALCHECKBOXES};
+
+            // `TVS_CHECKBOXES` must be set after the tree view is created
+            let handle = out.handle.hwnd().unwrap();
+            wh::set_style(handle, wh::get_style(handle) | TVS_CHECKBOXES);
+
+            if self.tri_state {
+                wh::send_message(handle, TVM_SETEXTENDEDSTYLE, TVS_EX_PARTIALCHECKBOXES as _, TVS_EX_PARTIALCHECKBOXES as _);
+                out.state.tri_state.set(true);
+            }
+        }
+
+        out.hook_parent_notifications();
+        out.hook_tree_messages();
+
         builder_set_image_list(&self, out);
 
         if self.focus {
@@ -913,14 +1371,94 @@ fn next_treeview_item(handle: &ControlHandle, action: usize, item: HTREEITEM) ->
 }
 
 #[cfg(feature="image-list")]
-fn builder_set_image_list(builder: &TreeViewBuilder, out: &TreeView) {
+fn builder_set_image_list<T: 'static>(builder: &TreeViewBuilder, out: &TreeView<T>) {
     if builder.image_list.is_some() {
         out.set_image_list(builder.image_list);
     }
 }
 
 #[cfg(not(feature="image-list"))]
-fn builder_set_image_list(_builder: &TreeViewBuilder, _out: &TreeView) {
+fn builder_set_image_list<T>(_builder: &TreeViewBuilder, _out: &TreeView<T>) {
+}
+
+fn first_child(handle: HWND, item: HTREEITEM) -> Option<HTREEITEM> {
+    use winapi::um::commctrl::{TVM_GETNEXTITEM, TVGN_CHILD};
+
+    let child = wh::send_message(handle, TVM_GETNEXTITEM, TVGN_CHILD, item as LPARAM) as HTREEITEM;
+    if child.is_null() { None } else { Some(child) }
+}
+
+fn check_state(handle: HWND, item: HTREEITEM) -> Option<CheckBoxState> {
+    use winapi::um::commctrl::TVM_GETITEMSTATE;
+
+    let state = wh::send_message(handle, TVM_GETITEMSTATE, item as WPARAM, TVIS_STATEIMAGEMASK as LPARAM) as UINT;
+    match (state & TVIS_STATEIMAGEMASK) >> 12 {
+        1 => Some(CheckBoxState::Unchecked),
+        2 => Some(CheckBoxState::Checked),
+        3 => Some(CheckBoxState::Indeterminate),
+        _ => None
+    }
+}
+
+fn set_check_state(handle: HWND, item: HTREEITEM, state: CheckBoxState) {
+    use winapi::um::commctrl::{TVM_SETITEMW, TVIF_STATE, INDEXTOSTATEIMAGEMASK};
+
+    let index = match state {
+        CheckBoxState::Unchecked => 1,
+        CheckBoxState::Checked => 2,
+        CheckBoxState::Indeterminate => 3,
+    };
+
+    let mut tree_item = blank_item();
+    tree_item.mask = TVIF_STATE;
+    tree_item.hItem = item;
+    tree_item.state = INDEXTOSTATEIMAGEMASK(index);
+    tree_item.stateMask = TVIS_STATEIMAGEMASK;
+
+    wh::send_message(handle, TVM_SETITEMW, 0, &mut tree_item as *mut TVITEMW as LPARAM);
+}
+
+/// Sets the check state of an item and updates its descendants and ancestors
+fn update_check_state(handle: HWND, state: &TreeViewState, item: HTREEITEM, check: CheckBoxState) {
+    state.updating.set(true);
+    propagate(&TreeCheck(handle), item, check);
+    state.updating.set(false);
+}
+
+/// The items of a tree view, as seen by the check state propagation
+struct TreeCheck(HWND);
+
+impl CheckTree for TreeCheck {
+    type Item = HTREEITEM;
+
+    fn parent(&self, item: HTREEITEM) -> Option<HTREEITEM> {
+        use winapi::um::commctrl::{TVM_GETNEXTITEM, TVGN_PARENT};
+
+        let parent = wh::send_message(self.0, TVM_GETNEXTITEM, TVGN_PARENT, item as LPARAM) as HTREEITEM;
+        if parent.is_null() { None } else { Some(parent) }
+    }
+
+    fn children(&self, item: HTREEITEM) -> Vec<HTREEITEM> {
+        use winapi::um::commctrl::{TVM_GETNEXTITEM, TVGN_NEXT};
+
+        let mut children = Vec::new();
+        let mut next = first_child(self.0, item);
+        while let Some(child) = next {
+            children.push(child);
+            let sibling = wh::send_message(self.0, TVM_GETNEXTITEM, TVGN_NEXT, child as LPARAM) as HTREEITEM;
+            next = if sibling.is_null() { None } else { Some(sibling) };
+        }
+
+        children
+    }
+
+    fn check_state(&self, item: HTREEITEM) -> CheckBoxState {
+        check_state(self.0, item).unwrap_or(CheckBoxState::Unchecked)
+    }
+
+    fn set_check_state(&self, item: HTREEITEM, state: CheckBoxState) {
+        set_check_state(self.0, item, state);
+    }
 }
 
 fn blank_item() -> TVITEMW {
@@ -0,0 +1,147 @@
+/*!
+    Check state propagation of a tri-state `TreeView`. This module does not depend on the windowing system.
+*/
+use super::CheckBoxState;
+
+
+/// Access to the check state of the items of a tree
+pub(crate) trait CheckTree {
+    type Item: Copy;
+
+    fn parent(&self, item: Self::Item) -> Option<Self::Item>;
+    fn children(&self, item: Self::Item) -> Vec<Self::Item>;
+    fn check_state(&self, item: Self::Item) -> CheckBoxState;
+    fn set_check_state(&self, item: Self::Item, state: CheckBoxState);
+}
+
+/// The state of an item after the user clicked on its checkbox. An indeterminate item becomes checked.
+pub(crate) fn toggled(state: CheckBoxState) -> CheckBoxState {
+    match state {
+        CheckBoxState::Checked => CheckBoxState::Unchecked,
+        _ => CheckBoxState::Checked
+    }
+}
+
+/// The state of a parent computed from the state of its children. Returns `None` if there are no children.
+pub(crate) fn combined<I: IntoIterator<Item = CheckBoxState>>(states: I) -> Option<CheckBoxState> {
+    let mut result = None;
+    for state in states {
+        match (result, state) {
+            (_, CheckBoxState::Indeterminate) => return Some(CheckBoxState::Indeterminate),
+            (None, state) => { result = Some(state); },
+            (Some(r), state) if r != state => return Some(CheckBoxState::Indeterminate),
+            _ => {}
+        }
+    }
+
+    result
+}
+
+/**
+    Set the state of an item and propagate it. If the state is `Checked` or `Unchecked`, every descendant of the item
+    receives the same state. The ancestors of the item are then updated from the state of their children.
+*/
+pub(crate) fn propagate<T: CheckTree>(tree: &T, item: T::Item, state: CheckBoxState) {
+    tree.set_check_state(item, state);
+
+    if state != CheckBoxState::Indeterminate {
+        let mut pending = tree.children(item);
+        while let Some(child) = pending.pop() {
+            tree.set_check_state(child, state);
+            pending.extend(tree.children(child));
+        }
+    }
+
+    let mut current = item;
+    while let Some(parent) = tree.parent(current) {
+        let state = match combined(tree.children(parent).into_iter().map(|c| tree.check_state(c))) {
+            Some(state) => state,
+            None => break
+        };
+
+        if tree.check_state(parent) == state {
+            break;
+        }
+
+        tree.set_check_state(parent, state);
+        current = parent;
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::CheckBoxState::*;
+    use std::cell::RefCell;
+
+    /// A tree stored as a list of (parent, state)
+    struct TestTree(RefCell<Vec<(Option<usize>, CheckBoxState)>>);
+
+    impl TestTree {
+        fn new(parents: &[Option<usize>]) -> TestTree {
+            TestTree(RefCell::new(parents.iter().map(|&p| (p, Unchecked)).collect()))
+        }
+
+        fn states(&self) -> Vec<CheckBoxState> {
+            self.0.borrow().iter().map(|(_, s)| *s).collect()
+        }
+    }
+
+    impl CheckTree for TestTree {
+        type Item = usize;
+
+        fn parent(&self, item: usize) -> Option<usize> { self.0.borrow()[item].0 }
+
+        fn children(&self, item: usize) -> Vec<usize> {
+            self.0.borrow().iter().enumerate().filter(|(_, (p, _))| *p == Some(item)).map(|(i, _)| i).collect()
+        }
+
+        fn check_state(&self, item: usize) -> CheckBoxState { self.0.borrow()[item].1 }
+
+        fn set_check_state(&self, item: usize, state: CheckBoxState) { self.0.borrow_mut()[item].1 = state; }
+    }
+
+    #[test]
+    fn combined_states() {
+        assert_eq!(combined(vec![]), None);
+        assert_eq!(combined(vec![Checked, Checked]), Some(Checked));
+        assert_eq!(combined(vec![Unchecked]), Some(Unchecked));
+        assert_eq!(combined(vec![Checked, Unchecked, Checked]), Some(Indeterminate));
+        assert_eq!(combined(vec![Indeterminate]), Some(Indeterminate));
+
+        assert_eq!(toggled(Checked), Unchecked);
+        assert_eq!(toggled(Unchecked), Checked);
+        assert_eq!(toggled(Indeterminate), Checked);
+    }
+
+    #[test]
+    fn propagation() {
+        // 0
+        // ├── 1
+        // │   ├── 3
+        // │   └── 4
+        // └── 2
+        let tree = TestTree::new(&[None, Some(0), Some(0), Some(1), Some(1)]);
+
+        propagate(&tree, 3, Checked);
+        assert_eq!(tree.states(), vec![Indeterminate, Indeterminate, Unchecked, Checked, Unchecked]);
+
+        propagate(&tree, 4, Checked);
+        assert_eq!(tree.states(), vec![Indeterminate, Checked, Unchecked, Checked, Checked]);
+
+        propagate(&tree, 2, Checked);
+        assert_eq!(tree.states(), vec![Checked, Checked, Checked, Checked, Checked]);
+
+        propagate(&tree, 0, Unchecked);
+        assert_eq!(tree.states(), vec![Unchecked; 5]);
+
+        propagate(&tree, 1, Checked);
+        assert_eq!(tree.states(), vec![Indeterminate, Checked, Unchecked, Checked, Checked]);
+
+        // An indeterminate item keeps the state of its children
+        propagate(&tree, 1, Indeterminate);
+        assert_eq!(tree.states(), vec![Indeterminate, Indeterminate, Unchecked, Checked, Checked]);
+    }
+
+}
@@ -27,18 +27,18 @@ fn iter_tree_view(tree: &mut nwg::TreeView) {
 ```
 */
 #[allow(unused)]
-pub struct TreeViewIterator<'a> {
-    tree_view: &'a TreeView,
+pub struct TreeViewIterator<'a, T = ()> {
+    tree_view: &'a TreeView<T>,
     tree_view_handle: HWND,
     base_item: HTREEITEM,
     current_item: HTREEITEM,
     action: NextAction,
 }
 
-impl<'a> TreeViewIterator<'a> {
+impl<'a, T> TreeViewIterator<'a, T> {
 
     /// Use `TreeView.iter` to create a `TreeViewIterator`
-    pub(crate) fn new(tree_view: &'a TreeView, current_item: HTREEITEM) -> TreeViewIterator {
+    pub(crate) fn new(tree_view: &'a TreeView<T>, current_item: HTREEITEM) -> TreeViewIterator<'a, T> {
         let tree_view_handle = tree_view.handle.hwnd().unwrap();
 
         let action = match current_item.is_null() {
@@ -57,7 +57,7 @@ impl<'a> TreeViewIterator<'a> {
 
 }
 
-impl<'a> Iterator for TreeViewIterator<'a> {
+impl<'a, T> Iterator for TreeViewIterator<'a, T> {
     type Item = TreeItem;
 
     fn next(&mut self) -> Option<TreeItem> {
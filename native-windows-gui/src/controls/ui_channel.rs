@@ -0,0 +1,390 @@
+use winapi::shared::windef::HWND;
+use super::control_handle::ControlHandle;
+use super::channel_queue::{ChannelQueue, UiChannelMode};
+use super::notice::NoticeSender;
+use crate::win32::{window_helper as wh, window::{build_notice, create_message_window}};
+use crate::{NwgError, RawEventHandler, bind_raw_event_handler_inner, unbind_raw_event_handler};
+use std::any::Any;
+use std::{cell::RefCell, collections::HashMap, marker::PhantomData, rc::Rc, sync::Arc};
+
+const NOT_BOUND: &str = "UiReceiver is not yet bound to a winapi object";
+
+/// Raw event handler ids of the receivers. The notice id is added to the base.
+const UI_CHANNEL_HANDLER_ID: usize = 0x7C00_0000;
+
+
+/// The UI side of a channel, registered in the thread so that `process_events` can find the payloads of a notice
+trait ChannelDelivery {
+    /// Delivers the pending messages to the callback of the receiver. Returns `false` if the receiver has no callback.
+    fn deliver(&self) -> bool;
+
+    /// Takes the pending messages as `OnNotice` payloads. Returns nothing if the receiver has a callback.
+    fn take_payloads(&self) -> Vec<Rc<dyn Any>>;
+}
+
+type ReceiveCallback<T> = Rc<dyn Fn(T)>;
+
+struct ChannelEntry<T> {
+    queue: Arc<ChannelQueue<T>>,
+    callback: RefCell<Option<ReceiveCallback<T>>>,
+}
+
+impl<T: Any> ChannelDelivery for ChannelEntry<T> {
+
+    fn deliver(&self) -> bool {
+        // The callback is cloned so that it can replace itself
+        let callback = match self.callback.borrow().clone() {
+            Some(callback) => callback,
+            None => { return false; }
+        };
+
+        for message in self.queue.take() {
+            callback(message);
+        }
+
+        true
+    }
+
+    fn take_payloads(&self) -> Vec<Rc<dyn Any>> {
+        match self.callback.borrow().is_some() {
+            true => Vec::new(),
+            false => self.queue.take_payloads()
+        }
+    }
+
+}
+
+/// The payloads of the notice being dispatched to the event handlers of a window
+struct NoticeDispatch {
+    payloads: Rc<Vec<Rc<dyn Any>>>,
+
+    /// The number of `NoticePayloads` alive
+    users: usize,
+}
+
+thread_local! {
+    static CHANNELS: RefCell<HashMap<(usize, u32), Rc<dyn ChannelDelivery>>> = RefCell::new(HashMap::new());
+    static DISPATCHES: RefCell<HashMap<(usize, u32), NoticeDispatch>> = RefCell::new(HashMap::new());
+}
+
+/// The payloads of a notice, shared by the event handlers of a window. See `notice_payloads`.
+pub(crate) struct NoticePayloads {
+    key: (usize, u32),
+    payloads: Rc<Vec<Rc<dyn Any>>>,
+}
+
+impl NoticePayloads {
+
+    pub fn iter(&self) -> impl Iterator<Item=&Rc<dyn Any>> {
+        self.payloads.iter()
+    }
+
+}
+
+impl Drop for NoticePayloads {
+    fn drop(&mut self) {
+        let key = self.key;
+        let dispatch = DISPATCHES.try_with(|dispatches| {
+            let mut dispatches = dispatches.borrow_mut();
+            let done = match dispatches.get_mut(&key) {
+                Some(dispatch) => { dispatch.users -= 1; dispatch.users == 0 },
+                None => false
+            };
+
+            match done {
+                true => dispatches.remove(&key),
+                false => None
+            }
+        });
+
+        drop(dispatch);
+    }
+}
+
+/**
+    Returns the payloads of a notice if it belongs to a `UiReceiver`. Used by `process_events` to raise `OnNotice`.
+
+    Each event handler of the window is a subclass, and the subclasses process the message one inside the other.
+    The first handler takes the pending messages, the next handlers receive the same payloads while the first one is alive.
+*/
+pub(crate) fn notice_payloads(hwnd: HWND, id: u32) -> Option<NoticePayloads> {
+    let key = (hwnd as usize, id);
+    let channel = CHANNELS.with(|channels| channels.borrow().get(&key).cloned())?;
+
+    let payloads = DISPATCHES.with(|dispatches| {
+        let mut dispatches = dispatches.borrow_mut();
+        let dispatch = dispatches.entry(key).or_insert_with(|| NoticeDispatch { payloads: Rc::new(channel.take_payloads()), users: 0 });
+        dispatch.users += 1;
+        dispatch.payloads.clone()
+    });
+
+    Some(NoticePayloads { key, payloads })
+}
+
+
+/**
+Creates a channel that sends values from any thread to the GUI thread.
+
+The receiver has its own message only window, so its messages are delivered with `UiReceiver::on_receive`.
+Use `UiReceiver::builder` to receive the messages as `OnNotice` events of a window or to coalesce the messages.
+
+Requires the `notice` feature.
+
+```rust
+use native_windows_gui as nwg;
+use std::{rc::Rc, thread};
+
+fn download(progress_bar: &Rc<nwg::ProgressBar>) -> Result<nwg::UiReceiver<u32>, nwg::NwgError> {
+    let (sender, receiver) = nwg::ui_channel::<u32>()?;
+
+    let progress_bar = progress_bar.clone();
+    receiver.on_receive(move |pos| progress_bar.set_pos(pos));
+
+    thread::spawn(move || {
+        for pos in 0..100 {
+            if sender.send(pos).is_err() { break; }
+        }
+    });
+
+    Ok(receiver)
+}
+```
+*/
+pub fn ui_channel<T: Send + 'static>() -> Result<(UiSender<T>, UiReceiver<T>), NwgError> {
+    let mut receiver = UiReceiver::default();
+    UiReceiver::builder().build(&mut receiver)?;
+
+    Ok((receiver.sender(), receiver))
+}
+
+
+/**
+The GUI thread side of a channel. Values sent with a `UiSender` from any thread are delivered to the GUI thread
+through the same mechanism as a `Notice`.
+
+The messages are either delivered to a callback (`on_receive`) or raised as `OnNotice` events on the parent window,
+with the message in `EventData::OnNotice` (see `EventData::on_notice`). The payload is a `T`, or a `Vec<T>` with
+the `Batched` mode. If a window has many events handlers, each of them receives every message.
+
+The senders notify the GUI thread once per delivery, however many messages are sent in between. `mode` controls
+how the pending messages are delivered (see `UiChannelMode`). `Latest` and `Batched` are meant for high frequency updates,
+such as the progress of a background task.
+
+When the receiver is dropped, the pending messages are dropped and `UiSender::send` returns an error.
+
+Requires the `notice` feature.
+
+**Builder parameters:**
+  * `parent`: The window that receives the `OnNotice` events. If there is no parent, the receiver creates a message only window.
+  * `mode`:   How the pending messages are delivered. Defaults to `UiChannelMode::All`.
+
+**Control events:**
+  * `OnNotice`: When a message is received, if the receiver has no callback
+
+```rust
+use native_windows_gui as nwg;
+
+fn build_receiver(receiver: &mut nwg::UiReceiver<String>, window: &nwg::Window) {
+    nwg::UiReceiver::builder()
+        .parent(window)
+        .mode(nwg::UiChannelMode::Latest)
+        .build(receiver)
+        .expect("Failed to build the receiver");
+}
+
+fn on_notice(data: &nwg::EventData) {
+    println!("{}", data.on_notice::<String>());
+}
+```
+*/
+pub struct UiReceiver<T> {
+    pub handle: ControlHandle,
+    entry: Option<Rc<ChannelEntry<T>>>,
+    handler: Option<RawEventHandler>,
+
+    /// The message only window created when the receiver has no parent
+    window: ControlHandle,
+}
+
+impl<T: Send + 'static> UiReceiver<T> {
+
+    pub fn builder() -> UiReceiverBuilder<T> {
+        UiReceiverBuilder {
+            parent: None,
+            mode: UiChannelMode::All,
+            _message: PhantomData,
+        }
+    }
+
+    /// Create a new `UiSender` bound to this receiver. Panics if the receiver is not initialized.
+    pub fn sender(&self) -> UiSender<T> {
+        let entry = self.entry.as_ref().expect(NOT_BOUND);
+
+        UiSender {
+            queue: entry.queue.clone(),
+            notice: NoticeSender::from_handle(&self.handle).expect(NOT_BOUND),
+        }
+    }
+
+    /// Delivers the messages to a callback instead of raising `OnNotice`. Replaces the previous callback.
+    /// With the `Batched` mode, the callback is called for each message of the batch. Panics if the receiver is not initialized.
+    pub fn on_receive<F: Fn(T) + 'static>(&self, callback: F) {
+        let entry = self.entry.as_ref().expect(NOT_BOUND);
+        *entry.callback.borrow_mut() = Some(Rc::new(callback));
+    }
+
+    /// Removes the callback of the receiver. The next messages are raised as `OnNotice` events.
+    pub fn clear_callback(&self) {
+        if let Some(entry) = self.entry.as_ref() {
+            *entry.callback.borrow_mut() = None;
+        }
+    }
+
+    /// Takes the pending messages without delivering them
+    pub fn try_receive(&self) -> Vec<T> {
+        match self.entry.as_ref() {
+            Some(entry) => entry.queue.take(),
+            None => Vec::new()
+        }
+    }
+
+    /// Returns the delivery mode of the receiver
+    pub fn mode(&self) -> UiChannelMode {
+        match self.entry.as_ref() {
+            Some(entry) => entry.queue.mode(),
+            None => UiChannelMode::default()
+        }
+    }
+
+}
+
+impl<T> Default for UiReceiver<T> {
+    fn default() -> UiReceiver<T> {
+        UiReceiver {
+            handle: ControlHandle::NoHandle,
+            entry: None,
+            handler: None,
+            window: ControlHandle::NoHandle,
+        }
+    }
+}
+
+impl<T> PartialEq for UiReceiver<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.handle == other.handle
+    }
+}
+
+impl<T> Eq for UiReceiver<T> {}
+
+impl<T> Drop for UiReceiver<T> {
+    fn drop(&mut self) {
+        if let Some(handler) = self.handler.take() {
+            drop(unbind_raw_event_handler(&handler));
+        }
+
+        if let Some((hwnd, id)) = self.handle.notice() {
+            let channel = CHANNELS.try_with(|channels| channels.borrow_mut().remove(&(hwnd as usize, id)));
+            drop(channel);
+        }
+
+        if let Some(entry) = self.entry.take() {
+            entry.queue.close();
+        }
+
+        self.handle = ControlHandle::NoHandle;
+        self.window.destroy();
+    }
+}
+
+
+/// Sends values to a `UiReceiver` from any thread. Senders can be cloned.
+pub struct UiSender<T> {
+    queue: Arc<ChannelQueue<T>>,
+    notice: NoticeSender,
+}
+
+impl<T> UiSender<T> {
+
+    /// Sends a value to the receiver. Returns the value if the receiver was dropped.
+    pub fn send(&self, value: T) -> Result<(), T> {
+        if self.queue.push(value)? {
+            self.notice.notice();
+        }
+
+        Ok(())
+    }
+
+}
+
+impl<T> Clone for UiSender<T> {
+    fn clone(&self) -> UiSender<T> {
+        UiSender {
+            queue: self.queue.clone(),
+            notice: self.notice,
+        }
+    }
+}
+
+
+pub struct UiReceiverBuilder<T> {
+    parent: Option<ControlHandle>,
+    mode: UiChannelMode,
+    _message: PhantomData<T>,
+}
+
+impl<T: Send + 'static> UiReceiverBuilder<T> {
+
+    pub fn parent<C: Into<ControlHandle>>(mut self, p: C) -> UiReceiverBuilder<T> {
+        self.parent = Some(p.into());
+        self
+    }
+
+    pub fn mode(mut self, mode: UiChannelMode) -> UiReceiverBuilder<T> {
+        self.mode = mode;
+        self
+    }
+
+    pub fn build(self, out: &mut UiReceiver<T>) -> Result<(), NwgError> {
+        *out = Default::default();
+
+        let parent = match self.parent {
+            Some(p) => match p.hwnd() {
+                Some(handle) => handle,
+                None => { return Err(NwgError::control_create("Wrong parent type")); }
+            },
+            None => {
+                out.window = create_message_window()?;
+                out.window.hwnd().unwrap()
+            }
+        };
+
+        let handle = build_notice(parent);
+        let (_, id) = handle.notice().unwrap();
+
+        let entry = Rc::new(ChannelEntry {
+            queue: Arc::new(ChannelQueue::new(self.mode)),
+            callback: RefCell::new(None),
+        });
+
+        // The callback receives the messages instead of the events handlers of the window
+        let callback_entry = entry.clone();
+        let handler = bind_raw_event_handler_inner(&ControlHandle::Hwnd(parent), UI_CHANNEL_HANDLER_ID + id as usize, move |_hwnd, msg, w, _l| {
+            if msg == wh::NOTICE_MESSAGE && w as u32 == id && callback_entry.deliver() {
+                Some(0)
+            } else {
+                None
+            }
+        })?;
+
+        let channel: Rc<dyn ChannelDelivery> = entry.clone();
+        CHANNELS.with(|channels| channels.borrow_mut().insert((parent as usize, id), channel));
+
+        out.handle = handle;
+        out.entry = Some(entry);
+        out.handler = Some(handler);
+
+        Ok(())
+    }
+
+}
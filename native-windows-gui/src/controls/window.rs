@@ -1,10 +1,10 @@
-use winapi::um::winuser::{WS_OVERLAPPEDWINDOW, WS_CLIPCHILDREN, WS_VISIBLE, WS_DISABLED, WS_MAXIMIZE, WS_MINIMIZE, WS_CAPTION,
+use crate::win32::sys::um::winuser::{WS_OVERLAPPEDWINDOW, WS_CLIPCHILDREN, WS_VISIBLE, WS_DISABLED, WS_MAXIMIZE, WS_MINIMIZE, WS_CAPTION,
 WS_MINIMIZEBOX, WS_MAXIMIZEBOX, WS_SYSMENU, WS_THICKFRAME, WS_POPUP, WS_EX_TOPMOST, WS_EX_ACCEPTFILES};
 
 use crate::win32::window_helper as wh;
 use crate::win32::base_helper::check_hwnd;
-use crate::{NwgError, Icon};
-use super::{ControlBase, ControlHandle};
+use crate::{NwgError, Icon, Monitor};
+use super::{ControlBase, ControlHandle, WindowPlacement, WindowShowState};
 
 const NOT_BOUND: &'static str = "Window is not yet bound to a winapi object";
 const BAD_HANDLE: &'static str = "INTERNAL ERROR: Window handle is not HWND!";
@@ -79,6 +79,9 @@ bitflags! {
       * `OnWindowMinimize`: When the window is minimized
       * `OnMove`: When the window is moved by the user
       * `OnFileDrop`: When a file is dropped in the window (only raised if accept_file is set)
+      * `OnClipboardUpdate`: When the content of the clipboard changes (only raised if the window is registered with `Clipboard::add_listener`)
+      * `OnDragEnter`, `OnDragOver`, `OnDragLeave`, `OnDragDrop`: When data is dragged over the window (only raised if a `DropTarget` is registered)
+      * `OnAccelerator`: When a shortcut of an `AcceleratorTable` of the window is pressed
       * `OnMinMaxInfo`: When the size or position of the window is about to change and the size of the windows must be restricted
 
 */
@@ -124,9 +127,46 @@ impl Window {
         wh::restore_window(handle);
     }
 
+    /// Returns the size, position and state of the window. Save it to reopen the window at the same place in the next session.
+    pub fn placement(&self) -> WindowPlacement {
+        use crate::win32::sys::um::winuser::{SW_SHOWMINIMIZED, SW_SHOWMAXIMIZED};
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        let (rect, show_cmd) = unsafe { wh::get_window_placement(handle) };
+
+        let state = match show_cmd as i32 {
+            SW_SHOWMINIMIZED => WindowShowState::Minimized,
+            SW_SHOWMAXIMIZED => WindowShowState::Maximized,
+            _ => WindowShowState::Normal
+        };
+
+        WindowPlacement { rect, state }
+    }
+
+    /**
+        Restores the size, position and state of the window from a placement returned by `placement`. The window is shown.
+
+        The rectangle is first moved into the work area of one of the current monitors (see `WindowPlacement::clamp_to`),
+        so a window that was saved on a monitor that is no longer connected reopens on a visible monitor.
+    */
+    pub fn set_placement(&self, placement: &WindowPlacement) {
+        use crate::win32::sys::um::winuser::{SW_SHOWNORMAL, SW_SHOWMINIMIZED, SW_SHOWMAXIMIZED};
+
+        let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
+        let placement = placement.clamp_to(&Monitor::work_areas());
+
+        let show_cmd = match placement.state {
+            WindowShowState::Normal => SW_SHOWNORMAL,
+            WindowShowState::Minimized => SW_SHOWMINIMIZED,
+            WindowShowState::Maximized => SW_SHOWMAXIMIZED,
+        };
+
+        unsafe { wh::set_window_placement(handle, placement.rect, show_cmd as u32); }
+    }
+
     /// Force the window to refraw iteself and all its children
     pub fn invalidate(&self) {
-        use winapi::um::winuser::InvalidateRect;
+        use crate::win32::sys::um::winuser::InvalidateRect;
 
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
         unsafe { InvalidateRect(handle, ::std::ptr::null(), 1); }
@@ -134,7 +174,7 @@ impl Window {
 
     /// Close the window as if the user clicked the X button.
     pub fn close(&self) {
-        use winapi::um::winuser::WM_CLOSE;
+        use crate::win32::sys::um::winuser::WM_CLOSE;
 
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
         wh::post_message(handle, WM_CLOSE, 0, 0);
@@ -142,8 +182,8 @@ impl Window {
 
     /// Return the icon of the window
     pub fn icon(&self) -> Option<Icon> {
-        use winapi::um::winuser::WM_GETICON;
-        use winapi::um::winnt::HANDLE;
+        use crate::win32::sys::um::winuser::WM_GETICON;
+        use crate::win32::sys::um::winnt::HANDLE;
 
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
         let handle = wh::send_message(handle, WM_GETICON, 0, 0);
@@ -157,7 +197,7 @@ impl Window {
     /// Set the icon in the window
     /// - icon: The new icon. If None, the icon is removed
     pub fn set_icon(&self, icon: Option<&Icon>) {
-        use winapi::um::winuser::WM_SETICON;
+        use crate::win32::sys::um::winuser::WM_SETICON;
         use std::{mem, ptr};
 
         let handle = check_hwnd(&self.handle, NOT_BOUND, BAD_HANDLE);
@@ -269,7 +309,7 @@ use raw_window_handle::{HasRawWindowHandle, RawWindowHandle, windows::WindowsHan
 #[cfg(feature = "raw-win-handle")]
 unsafe impl HasRawWindowHandle for Window {
     fn raw_window_handle(&self) -> RawWindowHandle {
-        use winapi::um::winuser::GWL_HINSTANCE;
+        use crate::win32::sys::um::winuser::GWL_HINSTANCE;
         match self.handle {
             ControlHandle::Hwnd(hwnd) => {
                 let hinstance = wh::get_window_long(hwnd, GWL_HINSTANCE);
@@ -0,0 +1,241 @@
+/*!
+    The placement of a `Window`, its text format, and the clamping of a saved placement to the monitors.
+    This module does not depend on the windowing system.
+*/
+use crate::NwgError;
+use std::fmt;
+use std::str::FromStr;
+
+
+/**
+    The state of a window in a `WindowPlacement`.
+*/
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum WindowShowState {
+    Normal,
+    Minimized,
+    Maximized,
+}
+
+impl WindowShowState {
+
+    fn name(self) -> &'static str {
+        match self {
+            WindowShowState::Normal => "normal",
+            WindowShowState::Minimized => "minimized",
+            WindowShowState::Maximized => "maximized",
+        }
+    }
+
+}
+
+
+/**
+    The size, position and state of a window. Returned by `Window::placement` and restored with `Window::set_placement`.
+
+    `rect` is the [left, top, right, bottom] rectangle of the window when it is neither minimized nor maximized,
+    in screen pixels (not scaled by the DPI). When the window is maximized, `rect` is where the window goes when it is restored.
+
+    A placement can be saved between sessions as a string. `to_string` writes the state and the rectangle (ex: `"maximized 100 80 900 680"`)
+    and `parse` reads it back. With the `serde` feature, the placement is serialized as the same string.
+
+    ```rust
+    use native_windows_gui as nwg;
+
+    fn save_placement(window: &nwg::Window) -> String {
+        window.placement().to_string()
+    }
+
+    fn restore_placement(window: &nwg::Window, saved: &str) {
+        if let Ok(placement) = saved.parse::<nwg::WindowPlacement>() {
+            window.set_placement(&placement);
+        }
+    }
+    ```
+*/
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct WindowPlacement {
+    pub rect: [i32; 4],
+    pub state: WindowShowState,
+}
+
+impl WindowPlacement {
+
+    /**
+        Returns the placement with its rectangle moved into one of the `work_areas` ([left, top, right, bottom], see `Monitor::work_areas`).
+
+        The rectangle goes to the work area that contains the largest part of it, or to the nearest work area if it is
+        outside of every monitor (ex: it was saved on a monitor that was disconnected since). The rectangle is shrunk
+        if it is larger than the work area. If there are no work areas, the placement is returned unchanged.
+    */
+    pub fn clamp_to(&self, work_areas: &[[i32; 4]]) -> WindowPlacement {
+        let rect = match best_area(self.rect, work_areas) {
+            Some(index) => clamp_rect(self.rect, work_areas[index]),
+            None => self.rect
+        };
+
+        WindowPlacement { rect, state: self.state }
+    }
+
+}
+
+impl fmt::Display for WindowPlacement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [left, top, right, bottom] = self.rect;
+        write!(f, "{} {} {} {} {}", self.state.name(), left, top, right, bottom)
+    }
+}
+
+impl FromStr for WindowPlacement {
+    type Err = NwgError;
+
+    fn from_str(s: &str) -> Result<WindowPlacement, NwgError> {
+        let mut values = s.split_whitespace();
+
+        let state = match values.next() {
+            Some("normal") => WindowShowState::Normal,
+            Some("minimized") => WindowShowState::Minimized,
+            Some("maximized") => WindowShowState::Maximized,
+            Some(state) => { return Err(NwgError::window_placement(format!("unknown window state `{}`", state))); },
+            None => { return Err(NwgError::window_placement("empty placement")); }
+        };
+
+        let mut rect = [0; 4];
+        for edge in rect.iter_mut() {
+            let value = values.next().ok_or_else(|| NwgError::window_placement("expected 4 coordinates after the state"))?;
+            *edge = value.parse().map_err(|_| NwgError::window_placement(format!("`{}` is not a coordinate", value)))?;
+        }
+
+        if values.next().is_some() {
+            return Err(NwgError::window_placement("expected 4 coordinates after the state"));
+        }
+
+        Ok(WindowPlacement { rect, state })
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for WindowPlacement {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for WindowPlacement {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<WindowPlacement, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        text.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+
+/// Area of the intersection of two rectangles
+fn overlap(a: [i32; 4], b: [i32; 4]) -> i64 {
+    let width = (a[2].min(b[2]) as i64 - a[0].max(b[0]) as i64).max(0);
+    let height = (a[3].min(b[3]) as i64 - a[1].max(b[1]) as i64).max(0);
+    width * height
+}
+
+/// Squared distance between the closest points of two rectangles
+fn distance(a: [i32; 4], b: [i32; 4]) -> i64 {
+    let dx = (b[0] as i64 - a[2] as i64).max(a[0] as i64 - b[2] as i64).max(0);
+    let dy = (b[1] as i64 - a[3] as i64).max(a[1] as i64 - b[3] as i64).max(0);
+    dx * dx + dy * dy
+}
+
+/// Index of the area that contains the largest part of `rect`, or of the nearest area if `rect` does not intersect any of them
+pub(crate) fn best_area(rect: [i32; 4], areas: &[[i32; 4]]) -> Option<usize> {
+    let largest = (0..areas.len())
+        .map(|i| (i, overlap(rect, areas[i])))
+        .filter(|&(_, area)| area > 0)
+        .fold(None, |best: Option<(usize, i64)>, (i, area)| match best {
+            Some((_, largest)) if largest >= area => best,
+            _ => Some((i, area))
+        });
+
+    match largest {
+        Some((index, _)) => Some(index),
+        None => (0..areas.len()).min_by_key(|&i| distance(rect, areas[i]))
+    }
+}
+
+/// Moves `rect` inside `area`, shrinking it if it is larger than the area
+pub(crate) fn clamp_rect(rect: [i32; 4], area: [i32; 4]) -> [i32; 4] {
+    let [left, top, right, bottom] = area;
+
+    let width = (rect[2] as i64 - rect[0] as i64).clamp(0, (right as i64 - left as i64).max(0)) as i32;
+    let height = (rect[3] as i64 - rect[1] as i64).clamp(0, (bottom as i64 - top as i64).max(0)) as i32;
+
+    let x = rect[0].min(right - width).max(left);
+    let y = rect[1].min(bottom - height).max(top);
+
+    [x, y, x + width, y + height]
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PRIMARY: [i32; 4] = [0, 0, 1920, 1040];
+    const SECONDARY: [i32; 4] = [1920, -200, 3200, 824];
+
+    fn placement(rect: [i32; 4]) -> WindowPlacement {
+        WindowPlacement { rect, state: WindowShowState::Normal }
+    }
+
+    #[test]
+    fn visible_rect_is_unchanged() {
+        let areas = [PRIMARY, SECONDARY];
+        assert_eq!(placement([100, 100, 900, 700]).clamp_to(&areas).rect, [100, 100, 900, 700]);
+        assert_eq!(placement([2000, -100, 2600, 500]).clamp_to(&areas).rect, [2000, -100, 2600, 500]);
+        assert_eq!(placement([100, 100, 900, 700]).clamp_to(&[]).rect, [100, 100, 900, 700]);
+    }
+
+    #[test]
+    fn rect_goes_to_the_monitor_with_the_largest_part() {
+        let areas = [PRIMARY, SECONDARY];
+
+        // Mostly on the secondary monitor
+        assert_eq!(best_area([1800, 0, 2600, 600], &areas), Some(1));
+        assert_eq!(placement([1800, 0, 2600, 600]).clamp_to(&areas).rect, [1920, 0, 2720, 600]);
+
+        // Below the taskbar of the primary monitor
+        assert_eq!(placement([100, 800, 900, 1400]).clamp_to(&areas).rect, [100, 440, 900, 1040]);
+    }
+
+    #[test]
+    fn rect_on_a_disconnected_monitor() {
+        // The window was saved on a monitor on the left of the primary monitor
+        assert_eq!(best_area([-1500, 100, -700, 700], &[PRIMARY, SECONDARY]), Some(0));
+        assert_eq!(placement([-1500, 100, -700, 700]).clamp_to(&[PRIMARY, SECONDARY]).rect, [0, 100, 800, 700]);
+
+        // Far on the right, closer to the secondary monitor
+        assert_eq!(placement([5000, 300, 5400, 600]).clamp_to(&[PRIMARY, SECONDARY]).rect, [2800, 300, 3200, 600]);
+    }
+
+    #[test]
+    fn rect_larger_than_the_monitor() {
+        assert_eq!(clamp_rect([-50, -50, 2500, 1500], PRIMARY), PRIMARY);
+        assert_eq!(clamp_rect([100, 100, 50, 50], PRIMARY), [100, 100, 100, 100]);
+
+        let maximized = WindowPlacement { rect: [3000, 0, 5000, 900], state: WindowShowState::Maximized };
+        assert_eq!(maximized.clamp_to(&[SECONDARY]), WindowPlacement { rect: [1920, -76, 3200, 824], state: WindowShowState::Maximized });
+    }
+
+    #[test]
+    fn text_format() {
+        let maximized = WindowPlacement { rect: [-100, 80, 900, 680], state: WindowShowState::Maximized };
+        assert_eq!(maximized.to_string(), "maximized -100 80 900 680");
+        assert_eq!("maximized -100 80 900 680".parse::<WindowPlacement>().unwrap(), maximized);
+        assert_eq!(" normal 1 2\t3 4 ".parse::<WindowPlacement>().unwrap(), placement([1, 2, 3, 4]));
+
+        assert!("".parse::<WindowPlacement>().is_err());
+        assert!("hidden 1 2 3 4".parse::<WindowPlacement>().is_err());
+        assert!("normal 1 2 3".parse::<WindowPlacement>().is_err());
+        assert!("normal 1 2 3 4 5".parse::<WindowPlacement>().is_err());
+        assert!("normal 1 2 x 4".parse::<WindowPlacement>().is_err());
+    }
+
+}
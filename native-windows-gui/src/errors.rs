@@ -29,6 +29,9 @@ pub enum NwgError {
     /// Error raised when an event handler could not be bound
     EventsBinding(String),
 
+    /// Error raised when a saved window placement cannot be read
+    WindowPlacementError(String),
+
     /// Error raised by the FileDialog object
     #[cfg(feature = "file-dialog")]
     FileDialogError(String),
@@ -44,6 +47,10 @@ pub enum NwgError {
     /// Error raised by one of the locale functions
     #[cfg(feature = "plotting")]
     Plotters(PlottersError),
+
+    /// Error raised when a UI description cannot be loaded
+    #[cfg(feature = "ui-loader")]
+    UiLoaderError(String),
 }
 
 impl NwgError {
@@ -72,6 +79,10 @@ impl NwgError {
         NwgError::EventsBinding(e.into())
     }
 
+    pub fn window_placement<S: Into<String>>(e: S) -> NwgError {
+        NwgError::WindowPlacementError(e.into())
+    }
+
     #[cfg(feature = "file-dialog")]
     pub fn file_dialog<S: Into<String>>(e: S) -> NwgError {
         NwgError::FileDialogError(e.into())
@@ -87,6 +98,11 @@ impl NwgError {
         NwgError::ImageDecoderError(code, e.into())
     }
 
+    #[cfg(feature = "ui-loader")]
+    pub fn ui_loader<S: Into<String>>(e: S) -> NwgError {
+        NwgError::UiLoaderError(e.into())
+    }
+
     pub fn no_parent(name: &'static str) -> NwgError {
         NwgError::ControlCreationError(format!("No parent defined for {:?} control", name))
     }
@@ -109,6 +125,7 @@ impl fmt::Display for NwgError {
             ResourceCreationError(reason) => write!(f, "Failed to create a resource: {:?}", reason),
             LayoutCreationError(reason) => write!(f, "Failed to create a layout: {:?}", reason),
             EventsBinding(reason) => write!(f, "Failed to bind events: {:?}", reason),
+            WindowPlacementError(reason) => write!(f, "Failed to read the window placement: {}", reason),
             
             #[cfg(feature = "file-dialog")]
             FileDialogError(reason) => write!(f, "File dialog actions failed: {:?}", reason),
@@ -121,6 +138,9 @@ impl fmt::Display for NwgError {
 
             #[cfg(feature = "plotting")]
             Plotters(reason) => write!(f, "Plotting canvas function failed: {}", reason),
+
+            #[cfg(feature = "ui-loader")]
+            UiLoaderError(reason) => write!(f, "Failed to load the ui description: {}", reason),
         }
         
     }
@@ -133,4 +153,11 @@ impl From<PlottersError> for NwgError {
     }
 }
 
+#[cfg(feature = "ui-loader")]
+impl From<crate::UiParseError> for NwgError {
+    fn from(e: crate::UiParseError) -> Self {
+        NwgError::UiLoaderError(e.to_string())
+    }
+}
+
 impl Error for NwgError {}
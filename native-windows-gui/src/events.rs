@@ -12,6 +12,8 @@ pub enum MousePressEvent {
 
 /// Events are identifiers that are sent by controls on user interaction
 /// Some events also have data that can be further processed by the event loop. See `EventData`
+///
+/// Note: native-windows-derive keeps a list of these names to validate `nwg_events`. Keep it in sync when adding events.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[repr(usize)]
 pub enum Event {
@@ -93,6 +95,21 @@ pub enum Event {
     /// When a file is dropped into a control
     OnFileDrop,
 
+    /// When the content of the clipboard changes. Only sent to the windows registered with `Clipboard::add_listener`.
+    OnClipboardUpdate,
+
+    /// When dragged data enters a window registered with a `DropTarget`. Generates a `EventData::OnDragDrop`
+    OnDragEnter,
+
+    /// When dragged data moves over a window registered with a `DropTarget`. Generates a `EventData::OnDragDrop`
+    OnDragOver,
+
+    /// When dragged data leaves a window registered with a `DropTarget`, or when the drag is cancelled
+    OnDragLeave,
+
+    /// When data is dropped on a window registered with a `DropTarget`. Generates a `EventData::OnDragDrop`
+    OnDragDrop,
+
     /// When a button is clicked. Similar to a MouseUp event, but only for button control
     OnButtonClick,
 
@@ -171,6 +188,10 @@ pub enum Event {
     /// When the user selects on a menu item
     OnMenuItemSelected,
 
+    /// When the user presses a shortcut of an `AcceleratorTable`
+    /// The callback will also receive a `EventData::OnAccelerator` with the command id of the shortcut
+    OnAccelerator,
+
     /// When the user hovers over a callback tooltip
     /// The callback will also receive a `EventData::OnTooltipText`
     OnTooltipText,
@@ -190,6 +211,10 @@ pub enum Event {
     /// When ends the editing of a treeview item's label.
     OnTreeViewEndItemEdit,
 
+    /// When the user starts dragging an item with the left mouse button. See `DragPayload`.
+    /// Generates a `EventData::OnTreeViewBeginDrag`
+    OnTreeViewBeginDrag,
+
     /// When the control has lost the input focus
     OnTreeFocusLost,
 
@@ -202,6 +227,10 @@ pub enum Event {
     /// When an item is expanded. Generates a `EventData::OnTreeItemDelete`
     OnTreeItemExpanded,
 
+    /// Before an item is expanded or collapsed. The children of a lazy item should be inserted here.
+    /// See `TreeView::set_item_lazy`. Generates a `EventData::OnTreeItemUpdate`
+    OnTreeItemExpanding,
+
     /// When the state of a tree item is changed.
     OnTreeItemChanged,
 
@@ -252,6 +281,18 @@ pub enum Event {
     /// When the control has lost the input focus
     OnListViewFocusLost,
 
+    /// When the user starts editing a list view cell. See `ListView::set_column_editor`
+    /// Generates an `EventData::OnListViewEdit`
+    OnListViewBeginEdit,
+
+    /// When the user has finished editing a list view cell
+    /// Generates an `EventData::OnListViewEdit`
+    OnListViewEndEdit,
+
+    /// When the user starts dragging an item with the left mouse button. See `ListView::drag_selected_items`
+    /// Generates an `EventData::ListViewItemIndex`
+    OnListViewBeginDrag,
+
     /// When a TrayNotification info popup (not the tooltip) is shown 
     OnTrayNotificationShow,
 
@@ -299,6 +340,12 @@ pub enum Event {
 
     /// When the pager control scrolls
     OnPagerScroll,
+
+    /// When a part of a status bar is clicked. Use EventData::OnStatusBarPart to get the part index.
+    OnStatusBarClick,
+
+    /// When a part of a status bar is double clicked. Use EventData::OnStatusBarPart to get the part index.
+    OnStatusBarDoubleClick,
 }
 
 
@@ -325,6 +372,9 @@ pub enum EventData {
     /// The windows key code entered by a user. See the `nwg::keys` module
     OnKey(u32),
 
+    /// The command id of the `AcceleratorTable` shortcut pressed by the user
+    OnAccelerator(u16),
+
     /// Hold resources that will most likely be used during painting. 
     OnPaint(PaintData),
 
@@ -351,6 +401,10 @@ pub enum EventData {
     #[cfg(feature="tree-view")]
     OnTreeItemSelectionChanged{ old: crate::TreeItem, new: crate::TreeItem },
 
+    /// The item dragged by the user. The item is not always selected.
+    #[cfg(feature="tree-view")]
+    OnTreeViewBeginDrag(crate::TreeItem),
+
     /// Row index and column index of the list view item that raised the event
     /// `row_index` `0xFFF...` means the absence of an item
     #[cfg(feature="list-view")]
@@ -360,9 +414,28 @@ pub enum EventData {
     #[cfg(feature="list-view")]
     OnListViewItemChanged { row_index: usize, column_index: usize, selected: bool },
 
+    /// Row index, column index and text of the list view cell being edited.
+    /// In `OnListViewEndEdit`, `text` is the text proposed by the user and `cancelled` is true
+    /// if the edit was cancelled or if the text was rejected by the validator.
+    #[cfg(feature="list-view")]
+    OnListViewEdit { row_index: usize, column_index: usize, text: String, cancelled: bool },
+
     /// Data for a SysLink click event containing the link URL and ID
     #[cfg(feature="syslink")]
     OnSysLinkClick { url: String, id: String },
+
+    /// The dragged data and the effect picked by the drop target
+    #[cfg(feature="drag-drop")]
+    OnDragDrop(crate::DragDropData),
+
+    /// The index of the status bar part that was clicked
+    #[cfg(feature="status-bar")]
+    OnStatusBarPart(usize),
+
+    /// The message received by a `UiReceiver`. A `T`, or a `Vec<T>` with `UiChannelMode::Batched`.
+    /// The message is shared by the event handlers of the window. The method `on_notice` should be used to access the inner data
+    #[cfg(feature="notice")]
+    OnNotice(std::rc::Rc<dyn std::any::Any>),
 }
 
 impl EventData {
@@ -415,6 +488,22 @@ impl EventData {
         }
     }
 
+    /// Unwraps event data into the command id of an `OnAccelerator` event
+    pub fn on_accelerator(&self) -> u16 {
+        match self {
+            EventData::OnAccelerator(id) => *id,
+            d => panic!("Wrong data type: {:?}", d)
+        }
+    }
+
+    /// Unwraps event data into a `&WindowCloseData`. Panics if it's not the right type.
+    pub fn on_window_close(&self) -> &WindowCloseData {
+        match self {
+            EventData::OnWindowClose(d) => d,
+            d => panic!("Wrong data type: {:?}", d)
+        }
+    }
+
     /// unwraps event data into the removed tree item
     #[cfg(feature="tree-view")]
     pub fn on_tree_item_delete(&self) -> &crate::TreeItem {
@@ -471,6 +560,33 @@ impl EventData {
         }
     }
 
+    /// unwraps event data into the edited list view cell (row_index, column_index, text, cancelled)
+    #[cfg(feature="list-view")]
+    pub fn on_list_view_edit(&self) -> (usize, usize, &str, bool) {
+        match self {
+            EventData::OnListViewEdit { row_index, column_index, text, cancelled } => (*row_index, *column_index, text.as_str(), *cancelled),
+            d => panic!("Wrong data type: {:?}", d)
+        }
+    }
+
+    /// Unwraps event data into the item dragged by the user
+    #[cfg(feature="tree-view")]
+    pub fn on_tree_view_begin_drag(&self) -> &crate::TreeItem {
+        match self {
+            EventData::OnTreeViewBeginDrag(item) => item,
+            d => panic!("Wrong data type: {:?}", d)
+        }
+    }
+
+    /// Unwraps event data into a `&DragDropData`. Panics if it's not the right type.
+    #[cfg(feature="drag-drop")]
+    pub fn on_drag_drop(&self) -> &crate::DragDropData {
+        match self {
+            EventData::OnDragDrop(d) => d,
+            d => panic!("Wrong data type: {:?}", d)
+        }
+    }
+
     /// Unwraps event data into the SysLink click info (url, id)
     #[cfg(feature="syslink")]
     pub fn on_syslink_click(&self) -> (&str, &str) {
@@ -480,16 +596,88 @@ impl EventData {
         }
     }
 
+    /// Unwraps event data into the index of a status bar part. Panics if it's not the right type.
+    #[cfg(feature="status-bar")]
+    pub fn on_status_bar_part(&self) -> usize {
+        match self {
+            EventData::OnStatusBarPart(i) => *i,
+            d => panic!("Wrong data type: {:?}", d)
+        }
+    }
+
+    /// Unwraps the message of a `UiReceiver` into a `&T`. Panics if it's not the right type.
+    #[cfg(feature="notice")]
+    pub fn on_notice<T: std::any::Any>(&self) -> &T {
+        match self {
+            EventData::OnNotice(payload) => match payload.downcast_ref::<T>() {
+                Some(message) => message,
+                None => panic!("Wrong notice type: expected {}", std::any::type_name::<T>())
+            },
+            d => panic!("Wrong data type: {:?}", d)
+        }
+    }
+
+    /// Copy the event data so that it can be sent to more than one callback. The copy points to the same
+    /// system data, except for `OnFileDrop` where the copy is empty because the drop handle can only be freed once.
+    pub(crate) fn duplicate(&self) -> EventData {
+        use std::ptr;
+
+        match self {
+            EventData::NoData => EventData::NoData,
+            EventData::OnWindowClose(d) => EventData::OnWindowClose(WindowCloseData { data: d.data, _storage: None }),
+            EventData::OnMinMaxInfo(i) => EventData::OnMinMaxInfo(MinMaxInfo { inner: i.inner }),
+            EventData::OnTooltipText(d) => EventData::OnTooltipText(ToolTipTextData { data: d.data }),
+            EventData::OnChar(c) => EventData::OnChar(*c),
+            EventData::OnKey(k) => EventData::OnKey(*k),
+            EventData::OnAccelerator(id) => EventData::OnAccelerator(*id),
+            EventData::OnPaint(p) => EventData::OnPaint(PaintData { hwnd: p.hwnd }),
+            EventData::OnMouseWheel(d) => EventData::OnMouseWheel(*d),
+            EventData::OnFileDrop(_) => EventData::OnFileDrop(DropFiles { drop: ptr::null_mut() }),
+            #[cfg(feature="tree-view")]
+            EventData::OnTreeItemDelete(item) => EventData::OnTreeItemDelete(crate::TreeItem { handle: item.handle }),
+            #[cfg(feature="tree-view")]
+            EventData::OnTreeItemUpdate { item, action } => EventData::OnTreeItemUpdate { item: crate::TreeItem { handle: item.handle }, action: *action },
+            #[cfg(feature="tree-view")]
+            EventData::OnTreeViewEndItemEdit { f_cancel, new_text } => EventData::OnTreeViewEndItemEdit { f_cancel: *f_cancel, new_text: new_text.clone() },
+            #[cfg(feature="tree-view")]
+            EventData::OnTreeItemSelectionChanged { old, new } => EventData::OnTreeItemSelectionChanged {
+                old: crate::TreeItem { handle: old.handle },
+                new: crate::TreeItem { handle: new.handle }
+            },
+            #[cfg(feature="tree-view")]
+            EventData::OnTreeViewBeginDrag(item) => EventData::OnTreeViewBeginDrag(crate::TreeItem { handle: item.handle }),
+            #[cfg(feature="list-view")]
+            &EventData::OnListViewItemIndex { row_index, column_index } => EventData::OnListViewItemIndex { row_index, column_index },
+            #[cfg(feature="list-view")]
+            &EventData::OnListViewItemChanged { row_index, column_index, selected } => EventData::OnListViewItemChanged { row_index, column_index, selected },
+            #[cfg(feature="list-view")]
+            EventData::OnListViewEdit { row_index, column_index, text, cancelled } => EventData::OnListViewEdit {
+                row_index: *row_index,
+                column_index: *column_index,
+                text: text.clone(),
+                cancelled: *cancelled
+            },
+            #[cfg(feature="syslink")]
+            EventData::OnSysLinkClick { url, id } => EventData::OnSysLinkClick { url: url.clone(), id: id.clone() },
+            #[cfg(feature="drag-drop")]
+            EventData::OnDragDrop(d) => EventData::OnDragDrop(crate::DragDropData { inner: d.inner }),
+            #[cfg(feature="status-bar")]
+            EventData::OnStatusBarPart(i) => EventData::OnStatusBarPart(*i),
+            #[cfg(feature="notice")]
+            EventData::OnNotice(payload) => EventData::OnNotice(payload.clone()),
+        }
+    }
+
 }
 
 //
 // Events data structures
 //
 
-use winapi::um::commctrl::NMTTDISPINFOW;
-use winapi::um::winuser::{PAINTSTRUCT, MINMAXINFO, BeginPaint, EndPaint};
-use winapi::um::shellapi::{HDROP, DragFinish};
-use winapi::shared::windef::{HWND, POINT};
+use crate::win32::sys::um::commctrl::NMTTDISPINFOW;
+use crate::win32::sys::um::winuser::{PAINTSTRUCT, MINMAXINFO, BeginPaint, EndPaint};
+use crate::win32::sys::um::shellapi::{HDROP, DragFinish};
+use crate::win32::sys::shared::windef::{HWND, POINT};
 use std::fmt;
 
 /// A wrapper structure that sets the tooltip text on an `OnTooltipText` callback
@@ -502,7 +690,7 @@ impl ToolTipTextData {
     /// Tells the application to save the text value of the callback
     /// The `OnTooltipText` will not be called a second time for the associated control
     pub fn keep(&self, keep: bool) {
-        use ::winapi::um::commctrl::TTF_DI_SETITEM;
+        use crate::win32::sys::um::commctrl::TTF_DI_SETITEM;
         
         let data = unsafe { &mut *self.data };
         match keep {
@@ -532,7 +720,7 @@ impl ToolTipTextData {
     }
 
     fn clear(&self) {
-        use winapi::um::winnt::WCHAR;
+        use crate::win32::sys::um::winnt::WCHAR;
         use std::{ptr, mem};
         
         unsafe {
@@ -552,11 +740,23 @@ impl fmt::Debug for ToolTipTextData {
 
 /// Opaque type that manages if a window should be closed after an OnClose event
 pub struct WindowCloseData {
-    pub(crate) data: *mut bool
+    pub(crate) data: *mut bool,
+
+    /// Storage of `data` when the value was created by `WindowCloseData::new`
+    pub(crate) _storage: Option<Box<bool>>,
 }
 
 impl WindowCloseData {
 
+    /// Create a standalone close data. Use it with `simulate_event` to simulate an `OnWindowClose` event.
+    pub fn new(closing: bool) -> WindowCloseData {
+        let mut storage = Box::new(closing);
+        WindowCloseData {
+            data: &mut *storage as *mut bool,
+            _storage: Some(storage)
+        }
+    }
+
     /// Sets if the window should close after the event
     pub fn close(&self, value: bool) {
         unsafe{ *self.data = value; }
@@ -612,7 +812,7 @@ impl DropFiles {
     /// Retrieves the position of the mouse pointer at the time a file was dropped during a drag-and-drop operation.
     /// The coordinates are local to the control. Ex: (0, 0) is the top left corner of the control.
     pub fn point(&self) -> [i32; 2] {
-        use winapi::um::shellapi::DragQueryPoint;
+        use crate::win32::sys::um::shellapi::DragQueryPoint;
 
         unsafe {
             let mut pt = POINT { x: 0, y: 0 };
@@ -623,7 +823,7 @@ impl DropFiles {
 
     /// Return the number of files dropped 
     pub fn len(&self) -> usize {
-        use winapi::um::shellapi::DragQueryFileW;
+        use crate::win32::sys::um::shellapi::DragQueryFileW;
         use std::ptr;
 
         unsafe {
@@ -633,7 +833,7 @@ impl DropFiles {
 
     /// Return the files path dropped into the app
     pub fn files(&self) -> Vec<String> {
-        use winapi::um::shellapi::DragQueryFileW;
+        use crate::win32::sys::um::shellapi::DragQueryFileW;
         use crate::win32::base_helper::from_utf16;
         use std::ptr;
 
@@ -748,3 +948,128 @@ impl fmt::Debug for MinMaxInfo {
     }
 }
 
+
+
+//
+// Events simulation
+//
+
+use crate::controls::ControlHandle;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Payload of the message sent by `simulate_event`
+pub(crate) struct SimulatedEvent {
+    pub evt: Event,
+    pub data: EventData,
+    pub handle: ControlHandle,
+
+    /// Ids of the event handlers that already received the event
+    dispatched: Vec<usize>,
+}
+
+impl SimulatedEvent {
+
+    pub fn new(evt: Event, data: EventData, handle: ControlHandle) -> SimulatedEvent {
+        SimulatedEvent { evt, data, handle, dispatched: Vec::new() }
+    }
+
+    /// Send the event to a callback. An event handler bound to many controls only receives the event once.
+    pub fn dispatch(&mut self, handler_id: usize, callback: &dyn Fn(Event, EventData, ControlHandle)) {
+        if self.dispatched.contains(&handler_id) {
+            return;
+        }
+
+        self.dispatched.push(handler_id);
+
+        callback(self.evt, self.data.duplicate(), self.handle);
+    }
+
+}
+
+
+/// An event captured by an `EventRecorder`. `data` is the debug representation of the event data.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RecordedEvent {
+    pub event: Event,
+    pub handle: ControlHandle,
+    pub data: String,
+}
+
+/**
+    Captures the events dispatched through event handlers. Meant to be used in tests along with `simulate_event`.
+
+    The recorder can be bound to a control like any other event handler (`EventRecorder::bind`), or it can wrap an
+    existing callback (`EventRecorder::wrap`). Clones of a recorder share the same events.
+
+    ```rust
+    use native_windows_gui as nwg;
+    fn record(window: &nwg::Window, button: &nwg::Button) {
+        let recorder = nwg::EventRecorder::new();
+        let handler = recorder.bind(&window.handle);
+
+        nwg::simulate_event(&button.handle, nwg::Event::OnButtonClick, nwg::EventData::NoData);
+        assert_eq!(recorder.count(nwg::Event::OnButtonClick), 1);
+
+        nwg::unbind_event_handler(&handler);
+    }
+    ```
+*/
+#[derive(Clone, Default)]
+pub struct EventRecorder {
+    events: Rc<RefCell<Vec<RecordedEvent>>>
+}
+
+impl EventRecorder {
+
+    pub fn new() -> EventRecorder {
+        EventRecorder::default()
+    }
+
+    /// Add an event to the recorder
+    pub fn record(&self, evt: Event, evt_data: &EventData, handle: ControlHandle) {
+        let event = RecordedEvent { event: evt, handle, data: format!("{:?}", evt_data) };
+        self.events.borrow_mut().push(event);
+    }
+
+    /// Bind an event handler that records every event of the control and its children.
+    /// See `full_bind_event_handler`.
+    pub fn bind(&self, handle: &ControlHandle) -> crate::EventHandler {
+        let recorder = self.clone();
+        crate::full_bind_event_handler(handle, move |evt, evt_data, handle| {
+            recorder.record(evt, &evt_data, handle);
+        })
+    }
+
+    /// Wrap an events callback. The events are recorded before being forwarded to `f`.
+    pub fn wrap<F>(&self, f: F) -> impl Fn(Event, EventData, ControlHandle) -> () + 'static
+        where F: Fn(Event, EventData, ControlHandle) -> () + 'static
+    {
+        let recorder = self.clone();
+        move |evt, evt_data, handle| {
+            recorder.record(evt, &evt_data, handle);
+            f(evt, evt_data, handle);
+        }
+    }
+
+    /// Return a copy of the recorded events, in the order they were dispatched
+    pub fn events(&self) -> Vec<RecordedEvent> {
+        self.events.borrow().clone()
+    }
+
+    /// Return the recorded events and clear the recorder
+    pub fn take(&self) -> Vec<RecordedEvent> {
+        self.events.replace(Vec::new())
+    }
+
+    /// Return the number of times `evt` was recorded
+    pub fn count(&self, evt: Event) -> usize {
+        self.events.borrow().iter().filter(|e| e.event == evt).count()
+    }
+
+    /// Remove all the recorded events
+    pub fn clear(&self) {
+        self.events.borrow_mut().clear();
+    }
+
+}
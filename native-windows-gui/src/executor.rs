@@ -0,0 +1,337 @@
+/*!
+    A single threaded executor driven by the message loop of the GUI thread.
+
+    The tasks of the executor never leave the thread that spawned them. Their wakers are `Send` and can be woken
+    from any thread, the same way as a `NoticeSender`. Waking a task puts it in the ready queue of the executor and
+    signals the GUI thread if the queue was empty. The GUI thread then polls every ready task (see `win32::executor`).
+
+    The executor itself does not depend on the windowing system.
+*/
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::task::{Context, Wake, Waker};
+
+
+/// The ready queue shared between the executor and the wakers of its tasks
+struct ReadyQueue {
+    ready: Mutex<Vec<usize>>,
+
+    /// Called when a task is queued in an empty queue
+    signal: Box<dyn Fn() + Send + Sync>,
+}
+
+impl ReadyQueue {
+
+    fn push(&self, id: usize) {
+        let was_empty = {
+            let mut ready = self.ready.lock().unwrap();
+            let was_empty = ready.is_empty();
+            ready.push(id);
+            was_empty
+        };
+
+        // The signal is sent outside of the lock because it may run the executor on the current thread
+        if was_empty {
+            (self.signal)();
+        }
+    }
+
+    fn take(&self) -> Vec<usize> {
+        ::std::mem::take(&mut *self.ready.lock().unwrap())
+    }
+
+}
+
+struct TaskWaker {
+    id: usize,
+
+    /// `true` if the task is in the ready queue. A task is only queued once between two polls.
+    queued: AtomicBool,
+    queue: Arc<ReadyQueue>,
+}
+
+impl Wake for TaskWaker {
+
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        if !self.queued.swap(true, Ordering::SeqCst) {
+            self.queue.push(self.id);
+        }
+    }
+
+}
+
+struct Task {
+    future: Pin<Box<dyn Future<Output=()>>>,
+    waker: Arc<TaskWaker>,
+}
+
+/**
+    The executor of a GUI thread. `signal` is called from the thread that wakes a task, when the executor
+    must run again. It must arrange for `run` to be called on the executor thread.
+*/
+pub(crate) struct LocalExecutor {
+    tasks: RefCell<HashMap<usize, Task>>,
+    next_id: Cell<usize>,
+    running: Cell<bool>,
+    queue: Arc<ReadyQueue>,
+}
+
+impl LocalExecutor {
+
+    pub(crate) fn new<F>(signal: F) -> LocalExecutor
+        where F: Fn() + Send + Sync + 'static
+    {
+        LocalExecutor {
+            tasks: RefCell::new(HashMap::new()),
+            next_id: Cell::new(0),
+            running: Cell::new(false),
+            queue: Arc::new(ReadyQueue { ready: Mutex::new(Vec::new()), signal: Box::new(signal) }),
+        }
+    }
+
+    /// Adds a task to the executor. The task is polled the next time the executor runs.
+    pub(crate) fn spawn<F>(&self, future: F)
+        where F: Future<Output=()> + 'static
+    {
+        let id = self.next_id.get();
+        self.next_id.set(id.wrapping_add(1));
+
+        let waker = Arc::new(TaskWaker { id, queued: AtomicBool::new(false), queue: self.queue.clone() });
+        self.tasks.borrow_mut().insert(id, Task { future: Box::pin(future), waker: waker.clone() });
+
+        waker.wake_by_ref();
+    }
+
+    /// Returns the number of tasks that are not completed
+    #[cfg(test)]
+    pub(crate) fn task_count(&self) -> usize {
+        self.tasks.borrow().len()
+    }
+
+    /**
+        Polls the ready tasks until the ready queue is empty. The tasks woken while running are polled in the same call.
+
+        Does nothing if the executor is already running. This happens when a task runs a modal loop (ex: a message box).
+        The tasks woken in the modal loop are polled once the current task returns.
+    */
+    pub(crate) fn run(&self) {
+        if self.running.replace(true) {
+            return;
+        }
+
+        loop {
+            let ready = self.queue.take();
+            if ready.is_empty() {
+                break;
+            }
+
+            for id in ready {
+                // The task is removed while it is polled so that it can spawn new tasks
+                let mut task = match self.tasks.borrow_mut().remove(&id) {
+                    Some(task) => task,
+                    None => continue
+                };
+
+                task.waker.queued.store(false, Ordering::SeqCst);
+
+                let waker = Waker::from(task.waker.clone());
+                let mut context = Context::from_waker(&waker);
+                if task.future.as_mut().poll(&mut context).is_pending() {
+                    self.tasks.borrow_mut().insert(id, task);
+                }
+            }
+        }
+
+        self.running.set(false);
+    }
+
+}
+
+
+/**
+    Spawns a future on the executor of the current thread. The future is polled by the message loop
+    (`dispatch_thread_events`), so it can update the controls of the thread when it resumes.
+
+    The future is first polled after the current event handler returns. It is woken through the same mechanism
+    as a `NoticeSender`, so it can `await` values sent from other threads (ex: a oneshot channel).
+
+    Futures are never polled if the thread does not dispatch its events.
+
+    Requires the `executor` feature.
+
+    ```rust
+    use native_windows_gui as nwg;
+    use std::rc::Rc;
+
+    fn load_clicked(label: &Rc<nwg::Label>) {
+        let label = label.clone();
+        nwg::spawn_local(async move {
+            let text = async { "Loaded".to_string() }.await;
+            label.set_text(&text);
+        });
+    }
+    ```
+*/
+pub fn spawn_local<F>(future: F)
+    where F: Future<Output=()> + 'static
+{
+    crate::win32::executor::with_executor(|executor| executor.spawn(future));
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+    use std::task::Poll;
+    use std::sync::atomic::AtomicUsize;
+
+    /// A future that is pending until the flag is set
+    struct Flag {
+        set: Arc<AtomicBool>,
+        waker: Arc<Mutex<Option<Waker>>>,
+    }
+
+    impl Future for Flag {
+        type Output = ();
+
+        fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<()> {
+            match self.set.load(Ordering::SeqCst) {
+                true => Poll::Ready(()),
+                false => {
+                    *self.waker.lock().unwrap() = Some(cx.waker().clone());
+                    Poll::Pending
+                }
+            }
+        }
+    }
+
+    fn counting_executor() -> (LocalExecutor, Arc<AtomicUsize>) {
+        let signals = Arc::new(AtomicUsize::new(0));
+        let signals2 = signals.clone();
+        let executor = LocalExecutor::new(move || { signals2.fetch_add(1, Ordering::SeqCst); });
+        (executor, signals)
+    }
+
+    #[test]
+    fn spawned_tasks_run_on_the_next_run() {
+        let (executor, signals) = counting_executor();
+        let done = Rc::new(Cell::new(0));
+
+        for _ in 0..3 {
+            let done = done.clone();
+            executor.spawn(async move { done.set(done.get() + 1); });
+        }
+
+        // Only the first task signals, the queue is not empty for the others
+        assert_eq!(signals.load(Ordering::SeqCst), 1);
+        assert_eq!(done.get(), 0);
+
+        executor.run();
+        assert_eq!(done.get(), 3);
+        assert_eq!(executor.task_count(), 0);
+    }
+
+    #[test]
+    fn wake_from_another_thread() {
+        let (executor, signals) = counting_executor();
+        let set = Arc::new(AtomicBool::new(false));
+        let waker = Arc::new(Mutex::new(None));
+        let done = Rc::new(Cell::new(false));
+
+        let flag = Flag { set: set.clone(), waker: waker.clone() };
+        let done2 = done.clone();
+        executor.spawn(async move { flag.await; done2.set(true); });
+        executor.run();
+        assert!(!done.get());
+        assert_eq!(executor.task_count(), 1);
+
+        let waker = waker.lock().unwrap().take().unwrap();
+        std::thread::spawn(move || {
+            set.store(true, Ordering::SeqCst);
+            waker.wake_by_ref();
+            waker.wake();
+        }).join().unwrap();
+
+        // The task was queued once
+        assert_eq!(signals.load(Ordering::SeqCst), 2);
+        executor.run();
+        assert!(done.get());
+        assert_eq!(executor.task_count(), 0);
+    }
+
+    #[test]
+    fn tasks_spawned_by_tasks() {
+        let (executor, _signals) = counting_executor();
+        let executor = Rc::new(executor);
+        let order = Rc::new(RefCell::new(Vec::new()));
+
+        let (executor2, order2) = (executor.clone(), order.clone());
+        executor.spawn(async move {
+            let order3 = order2.clone();
+            executor2.spawn(async move { order3.borrow_mut().push(2); });
+            order2.borrow_mut().push(1);
+        });
+
+        executor.run();
+        assert_eq!(*order.borrow(), vec![1, 2]);
+        assert_eq!(executor.task_count(), 0);
+    }
+
+    #[test]
+    fn nested_run_is_ignored() {
+        let (executor, _signals) = counting_executor();
+        let executor = Rc::new(executor);
+        let done = Rc::new(Cell::new(false));
+
+        let (executor2, done2) = (executor.clone(), done.clone());
+        executor.spawn(async move {
+            let done3 = done2.clone();
+            executor2.spawn(async move { done3.set(true); });
+
+            // Like a modal loop dispatching the wake message of the new task
+            executor2.run();
+            assert!(!done2.get());
+        });
+
+        executor.run();
+        assert!(done.get());
+    }
+
+    #[test]
+    fn stale_wakers_are_ignored() {
+        let (executor, signals) = counting_executor();
+        let waker = Arc::new(Mutex::new(None));
+
+        let waker2 = waker.clone();
+        executor.spawn(async move {
+            let flag = Flag { set: Arc::new(AtomicBool::new(false)), waker: waker2 };
+            let _ = poll_once(flag).await;
+        });
+
+        executor.run();
+        assert_eq!(executor.task_count(), 0);
+
+        waker.lock().unwrap().take().unwrap().wake();
+        assert_eq!(signals.load(Ordering::SeqCst), 2);
+        executor.run();
+        assert_eq!(executor.task_count(), 0);
+    }
+
+    /// Polls a future once and returns its result, if any
+    async fn poll_once<F: Future + Unpin>(mut future: F) -> Option<F::Output> {
+        ::std::future::poll_fn(|cx| Poll::Ready(match Pin::new(&mut future).poll(cx) {
+            Poll::Ready(v) => Some(v),
+            Poll::Pending => None
+        })).await
+    }
+
+}
@@ -0,0 +1,29 @@
+use super::sys::shared::windef::HWND;
+use super::tree;
+use crate::ControlHandle;
+
+
+pub fn check_hwnd(handle: &ControlHandle, not_bound: &str, bad_handle: &str) -> HWND {
+    if handle.blank() { panic!("{}", not_bound); }
+    match handle.hwnd() {
+        Some(hwnd) => match tree::exists(hwnd) {
+            false => { panic!("The window handle is no longer valid. This usually means the control was freed by the OS"); },
+            true => hwnd
+        },
+        None => { panic!("{}", bad_handle); }
+    }
+}
+
+pub fn to_utf16<'a>(s: &'a str) -> Vec<u16> {
+    s.encode_utf16()
+      .chain(Some(0u16).into_iter())
+      .collect()
+}
+
+/**
+    Decode a raw utf16 string. Should be null terminated.
+*/
+pub fn from_utf16(s: &[u16]) -> String {
+    let null_index = s.iter().position(|&i| i==0).unwrap_or(s.len());
+    String::from_utf16(&s[0..null_index]).unwrap_or("Decoding error".to_string())
+}
@@ -0,0 +1,461 @@
+/*!
+    Default window procedures of the classes emulated by the headless backend.
+
+    Only the messages sent by the controls that support the headless backend are implemented.
+    Unknown messages return 0, like `DefWindowProcW` does for most of them.
+*/
+use super::sys::shared::windef::HWND;
+use super::sys::shared::ntdef::HANDLE;
+use super::sys::shared::minwindef::{UINT, WORD, WPARAM, LPARAM, LRESULT, MAKELONG};
+use super::sys::um::winuser::*;
+use super::sys::um::commctrl::{EM_SETCUEBANNER, EM_GETCUEBANNER};
+use super::tree::{self, ControlState};
+use super::window::send_message;
+use std::{mem, ptr};
+
+
+/// The widget class, copied out of the tree so that the tree is not borrowed while a message is processed
+#[derive(Copy, Clone)]
+enum Class {
+    Window,
+    Button,
+    Edit,
+    Static,
+    ListBox,
+    Generic
+}
+
+pub(crate) fn default_proc(hwnd: HWND, msg: UINT, w: WPARAM, l: LPARAM) -> LRESULT {
+    let class = tree::with_widget(hwnd, |w| match &w.state {
+        ControlState::Window { .. } => Class::Window,
+        ControlState::Button { .. } => Class::Button,
+        ControlState::Edit { .. } => Class::Edit,
+        ControlState::Static { .. } => Class::Static,
+        ControlState::ListBox { .. } => Class::ListBox,
+        ControlState::Generic => Class::Generic,
+    });
+
+    let class = match class {
+        Some(c) => c,
+        None => { return 0; }
+    };
+
+    match msg {
+        WM_SETTEXT => set_text(hwnd, class, l),
+        WM_SETFONT => {
+            tree::with_widget(hwnd, |widget| widget.font = w as HANDLE);
+            0
+        },
+        WM_GETFONT => tree::with_widget(hwnd, |w| w.font as LRESULT).unwrap_or(0),
+        WM_CLOSE => {
+            match class {
+                Class::Window => { tree::with_widget(hwnd, |w| w.style &= !WS_VISIBLE); },
+                _ => super::window_helper::destroy_window(hwnd)
+            }
+            0
+        },
+        _ => match class {
+            Class::Window => window_proc(hwnd, msg, w, l),
+            Class::Button => button_proc(hwnd, msg, w, l),
+            Class::Edit => edit_proc(hwnd, msg, w, l),
+            Class::Static => static_proc(hwnd, msg, w, l),
+            Class::ListBox => list_box_proc(hwnd, msg, w, l),
+            Class::Generic => 0,
+        }
+    }
+}
+
+/// Read a null terminated wide string
+unsafe fn read_wide(text: *const u16) -> Vec<u16> {
+    if text.is_null() {
+        return Vec::new();
+    }
+
+    let mut length = 0;
+    while *text.add(length) != 0 {
+        length += 1;
+    }
+
+    ::std::slice::from_raw_parts(text, length).to_vec()
+}
+
+/// Copy `text` in a wide string buffer of `length` characters. The copy is null terminated and truncated if needed.
+unsafe fn write_wide(text: &[u16], buffer: *mut u16, length: usize) -> usize {
+    if buffer.is_null() || length == 0 {
+        return 0;
+    }
+
+    let count = text.len().min(length - 1);
+    ptr::copy_nonoverlapping(text.as_ptr(), buffer, count);
+    *buffer.add(count) = 0;
+
+    count
+}
+
+/// Send a `WM_COMMAND` notification to the parent of a control
+fn notify_parent(hwnd: HWND, code: WORD) {
+    let parent = tree::parent(hwnd);
+    if !parent.is_null() {
+        send_message(parent, WM_COMMAND, MAKELONG(0, code) as WPARAM, hwnd as LPARAM);
+    }
+}
+
+fn set_text(hwnd: HWND, class: Class, l: LPARAM) -> LRESULT {
+    let text = unsafe { read_wide(l as *const u16) };
+    let text = String::from_utf16_lossy(&text);
+
+    tree::with_widget(hwnd, |w| {
+        w.text = text;
+
+        if let ControlState::Edit { modified, selection, undo, .. } = &mut w.state {
+            *modified = false;
+            *selection = (0, 0);
+            *undo = None;
+        }
+    });
+
+    if let Class::Edit = class {
+        notify_parent(hwnd, EN_CHANGE);
+    }
+
+    1
+}
+
+fn window_proc(hwnd: HWND, msg: UINT, _w: WPARAM, l: LPARAM) -> LRESULT {
+    tree::with_widget(hwnd, |widget| match (&mut widget.state, msg) {
+        (ControlState::Window { icon }, WM_SETICON) => mem::replace(icon, l as HANDLE) as LRESULT,
+        (ControlState::Window { icon }, WM_GETICON) => *icon as LRESULT,
+        _ => 0
+    }).unwrap_or(0)
+}
+
+fn image_index(image_type: WPARAM) -> Option<usize> {
+    match image_type as UINT {
+        IMAGE_BITMAP => Some(0),
+        IMAGE_ICON => Some(1),
+        IMAGE_CURSOR => Some(2),
+        _ => None
+    }
+}
+
+fn button_proc(hwnd: HWND, msg: UINT, w: WPARAM, l: LPARAM) -> LRESULT {
+    if msg == BM_CLICK {
+        return button_click(hwnd);
+    }
+
+    tree::with_widget(hwnd, |widget| {
+        let style = &mut widget.style;
+        let (check, images) = match &mut widget.state {
+            ControlState::Button { check, images } => (check, images),
+            _ => { return 0; }
+        };
+
+        match msg {
+            BM_GETCHECK => *check as LRESULT,
+            BM_SETCHECK => { *check = w; 0 },
+            BM_SETSTYLE => { *style = (*style & !0xFFFF) | (w as u32 & 0xFFFF); 0 },
+            BM_SETIMAGE => match image_index(w) {
+                Some(i) => mem::replace(&mut images[i], l as HANDLE) as LRESULT,
+                None => 0
+            },
+            BM_GETIMAGE => match image_index(w) {
+                Some(i) => images[i] as LRESULT,
+                None => 0
+            },
+            _ => 0
+        }
+    }).unwrap_or(0)
+}
+
+/// Emulates a user click on a button. The automatic check boxes and radio buttons update their state.
+fn button_click(hwnd: HWND) -> LRESULT {
+    if !tree::enabled(hwnd) {
+        return 0;
+    }
+
+    let style = tree::with_widget(hwnd, |w| {
+        let style = w.style & BS_TYPEMASK;
+        if let ControlState::Button { check, .. } = &mut w.state {
+            *check = match style {
+                BS_AUTOCHECKBOX => if *check == BST_CHECKED { BST_UNCHECKED } else { BST_CHECKED },
+                BS_AUTO3STATE => (*check + 1) % 3,
+                BS_AUTORADIOBUTTON => BST_CHECKED,
+                _ => *check
+            };
+        }
+
+        style
+    });
+
+    if style == Some(BS_AUTORADIOBUTTON) {
+        for sibling in radio_group(hwnd) {
+            tree::with_widget(sibling, |w| {
+                let auto_radio = w.style & BS_TYPEMASK == BS_AUTORADIOBUTTON;
+                if let (true, ControlState::Button { check, .. }) = (auto_radio, &mut w.state) {
+                    *check = BST_UNCHECKED;
+                }
+            });
+        }
+    }
+
+    notify_parent(hwnd, BN_CLICKED);
+
+    0
+}
+
+/// Return the other controls in the same group as `hwnd`. A group starts at a control with the `WS_GROUP` style.
+fn radio_group(hwnd: HWND) -> Vec<HWND> {
+    let siblings = tree::children(tree::parent(hwnd));
+    let index = match siblings.iter().position(|&s| s == hwnd) {
+        Some(i) => i,
+        None => { return Vec::new(); }
+    };
+
+    let is_group_start = |h: HWND| tree::with_widget(h, |w| w.style & WS_GROUP == WS_GROUP).unwrap_or(false);
+
+    let start = (0..=index).rev()
+        .find(|&i| is_group_start(siblings[i]))
+        .unwrap_or(0);
+
+    let end = ((index+1)..siblings.len())
+        .find(|&i| is_group_start(siblings[i]))
+        .unwrap_or(siblings.len());
+
+    siblings[start..end].iter().copied().filter(|&s| s != hwnd).collect()
+}
+
+fn edit_proc(hwnd: HWND, msg: UINT, w: WPARAM, l: LPARAM) -> LRESULT {
+    const GETSEL: UINT = EM_GETSEL as UINT;
+    const SETSEL: UINT = EM_SETSEL as UINT;
+    const GETMODIFY: UINT = EM_GETMODIFY as UINT;
+    const SETMODIFY: UINT = EM_SETMODIFY as UINT;
+    const LINELENGTH: UINT = EM_LINELENGTH as UINT;
+    const SETLIMITTEXT: UINT = EM_SETLIMITTEXT as UINT;
+    const GETLIMITTEXT: UINT = EM_GETLIMITTEXT as UINT;
+    const CANUNDO: UINT = EM_CANUNDO as UINT;
+    const UNDO: UINT = EM_UNDO as UINT;
+    const SETPASSWORDCHAR: UINT = EM_SETPASSWORDCHAR as UINT;
+    const GETPASSWORDCHAR: UINT = EM_GETPASSWORDCHAR as UINT;
+    const SETREADONLY: UINT = EM_SETREADONLY as UINT;
+
+    let mut changed = false;
+
+    let result = tree::with_widget(hwnd, |widget| {
+        let text_length = widget.text.encode_utf16().count() as u32;
+        let text = &mut widget.text;
+        let style = &mut widget.style;
+        let (limit, modified, selection, password, cue_banner, undo) = match &mut widget.state {
+            ControlState::Edit { limit, modified, selection, password, cue_banner, undo } => (limit, modified, selection, password, cue_banner, undo),
+            _ => { return 0; }
+        };
+
+        match msg {
+            GETSEL => unsafe {
+                let (start, end) = *selection;
+                if w != 0 { *(w as *mut u32) = start; }
+                if l != 0 { *(l as *mut u32) = end; }
+                MAKELONG(start as WORD, end as WORD) as LRESULT
+            },
+            SETSEL => {
+                *selection = match (w as isize, l) {
+                    (-1, _) => (selection.0, selection.0),
+                    (start, -1) => ((start as u32).min(text_length), text_length),
+                    (start, end) => {
+                        let (start, end) = ((start as u32).min(text_length), (end as u32).min(text_length));
+                        (start.min(end), start.max(end))
+                    }
+                };
+                0
+            },
+            GETMODIFY => *modified as LRESULT,
+            SETMODIFY => { *modified = w != 0; 0 },
+            LINELENGTH => text_length as LRESULT,
+            SETLIMITTEXT => { *limit = if w == 0 { 0x7FFFFFFE } else { w }; 0 },
+            GETLIMITTEXT => *limit as LRESULT,
+            CANUNDO => undo.is_some() as LRESULT,
+            UNDO => match undo.take() {
+                Some(old_text) => {
+                    *undo = Some(mem::replace(text, old_text));
+                    changed = true;
+                    1
+                },
+                None => 0
+            },
+            SETPASSWORDCHAR => {
+                *password = w as u32;
+                match w {
+                    0 => { *style &= !ES_PASSWORD; },
+                    _ => { *style |= ES_PASSWORD; }
+                }
+                0
+            },
+            GETPASSWORDCHAR => *password as LRESULT,
+            SETREADONLY => {
+                match w {
+                    0 => { *style &= !ES_READONLY; },
+                    _ => { *style |= ES_READONLY; }
+                }
+                1
+            },
+            EM_SETCUEBANNER => {
+                *cue_banner = unsafe { read_wide(l as *const u16) };
+                1
+            },
+            EM_GETCUEBANNER => {
+                unsafe { write_wide(cue_banner, w as *mut u16, l as usize); }
+                1
+            },
+            _ => 0
+        }
+    }).unwrap_or(0);
+
+    if changed {
+        notify_parent(hwnd, EN_CHANGE);
+    }
+
+    result
+}
+
+fn static_proc(hwnd: HWND, msg: UINT, w: WPARAM, l: LPARAM) -> LRESULT {
+    tree::with_widget(hwnd, |widget| {
+        let images = match &mut widget.state {
+            ControlState::Static { images } => images,
+            _ => { return 0; }
+        };
+
+        match (msg, image_index(w)) {
+            (STM_SETIMAGE, Some(i)) => mem::replace(&mut images[i], l as HANDLE) as LRESULT,
+            (STM_GETIMAGE, Some(i)) => images[i] as LRESULT,
+            _ => 0
+        }
+    }).unwrap_or(0)
+}
+
+fn list_box_proc(hwnd: HWND, msg: UINT, w: WPARAM, l: LPARAM) -> LRESULT {
+    tree::with_widget(hwnd, |widget| {
+        let sorted = widget.style & LBS_SORT == LBS_SORT;
+        let (items, selected, multi) = match &mut widget.state {
+            ControlState::ListBox { items, selected, multi } => (items, selected, *multi),
+            _ => { return 0; }
+        };
+
+        let count = items.len();
+        let index = w as isize;
+
+        match msg {
+            LB_ADDSTRING => {
+                let item = String::from_utf16_lossy(&unsafe { read_wide(l as *const u16) });
+                let index = match sorted {
+                    true => items.iter().position(|i| i.to_lowercase() > item.to_lowercase()).unwrap_or(count),
+                    false => count
+                };
+
+                items.insert(index, item);
+                selected.insert(index, false);
+                index as LRESULT
+            },
+            LB_INSERTSTRING => {
+                let index = match index {
+                    -1 => count,
+                    i if i as usize > count => { return LB_ERR; },
+                    i => i as usize
+                };
+
+                let item = String::from_utf16_lossy(&unsafe { read_wide(l as *const u16) });
+                items.insert(index, item);
+                selected.insert(index, false);
+                index as LRESULT
+            },
+            LB_DELETESTRING => match w < count {
+                true => {
+                    items.remove(w);
+                    selected.remove(w);
+                    items.len() as LRESULT
+                },
+                false => LB_ERR
+            },
+            LB_RESETCONTENT => {
+                items.clear();
+                selected.clear();
+                0
+            },
+            LB_INITSTORAGE => 0,
+            LB_GETCOUNT => count as LRESULT,
+            LB_GETTEXTLEN => match items.get(w) {
+                Some(item) => item.encode_utf16().count() as LRESULT,
+                None => LB_ERR
+            },
+            LB_GETTEXT => match items.get(w) {
+                Some(item) => {
+                    let text: Vec<u16> = item.encode_utf16().collect();
+                    unsafe { write_wide(&text, l as *mut u16, text.len() + 1) as LRESULT }
+                },
+                None => LB_ERR
+            },
+            LB_GETCURSEL => match selected.iter().position(|&s| s) {
+                Some(i) => i as LRESULT,
+                None => LB_ERR
+            },
+            LB_SETCURSEL if !multi => {
+                selected.iter_mut().for_each(|s| *s = false);
+                match w < count {
+                    true => { selected[w] = true; w as LRESULT },
+                    false => LB_ERR
+                }
+            },
+            LB_SELECTSTRING if !multi => {
+                let prefix = String::from_utf16_lossy(&unsafe { read_wide(l as *const u16) }).to_lowercase();
+                let start = if index < 0 { 0 } else { (w + 1).min(count) };
+                let found = (start..count).chain(0..start).find(|&i| items[i].to_lowercase().starts_with(&prefix));
+
+                match found {
+                    Some(i) => {
+                        selected.iter_mut().for_each(|s| *s = false);
+                        selected[i] = true;
+                        i as LRESULT
+                    },
+                    None => LB_ERR
+                }
+            },
+            LB_GETSEL => match selected.get(w) {
+                Some(&s) => s as LRESULT,
+                None => LB_ERR
+            },
+            LB_GETSELCOUNT if multi => selected.iter().filter(|&&s| s).count() as LRESULT,
+            LB_GETSELITEMS if multi => {
+                let buffer = l as *mut u32;
+                let indices = selected.iter().enumerate().filter(|(_, &s)| s).map(|(i, _)| i as u32).take(w);
+
+                let mut written = 0;
+                for (offset, i) in indices.enumerate() {
+                    unsafe { *buffer.add(offset) = i; }
+                    written += 1;
+                }
+
+                written as LRESULT
+            },
+            LB_SETSEL if multi => {
+                let select = w != 0;
+                match l {
+                    -1 => { selected.iter_mut().for_each(|s| *s = select); 0 },
+                    i if (i as usize) < count => { selected[i as usize] = select; 0 },
+                    _ => LB_ERR
+                }
+            },
+            LB_SELITEMRANGEEX if multi => {
+                let (start, end, select) = match w <= l as usize {
+                    true => (w, l as usize, true),
+                    false => (l as usize, w, false)
+                };
+
+                for s in selected.iter_mut().take(end + 1).skip(start) {
+                    *s = select;
+                }
+
+                0
+            },
+            LB_SETCURSEL | LB_SELECTSTRING | LB_GETSELCOUNT | LB_GETSELITEMS | LB_SETSEL | LB_SELITEMRANGEEX => LB_ERR,
+            _ => 0
+        }
+    }).unwrap_or(0)
+}
@@ -0,0 +1,28 @@
+/*!
+    Headless executor of the GUI thread. There is no window to wake, the dispatch loops run the executor
+    before they process each message.
+*/
+use crate::executor::LocalExecutor;
+use std::{cell::RefCell, rc::Rc};
+
+
+thread_local! {
+    static EXECUTOR: RefCell<Option<Rc<LocalExecutor>>> = const { RefCell::new(None) };
+}
+
+/// Runs `f` with the executor of the current thread. The executor is created on the first call.
+pub(crate) fn with_executor<R, F: FnOnce(&LocalExecutor) -> R>(f: F) -> R {
+    let executor = EXECUTOR.with(|executor| {
+        executor.borrow_mut().get_or_insert_with(|| Rc::new(LocalExecutor::new(|| {}))).clone()
+    });
+
+    f(&executor)
+}
+
+/// Polls the ready tasks of the current thread, if any
+pub(crate) fn run() {
+    let executor = EXECUTOR.try_with(|executor| executor.borrow().clone()).ok().flatten();
+    if let Some(executor) = executor {
+        executor.run();
+    }
+}
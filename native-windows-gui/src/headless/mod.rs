@@ -0,0 +1,162 @@
+/*!
+    The `headless` backend replaces the win32 windowing system by an in-memory widget tree.
+
+    It is meant to test NWG applications (`cargo test`) on machines without a desktop, including non-Windows CI.
+    Controls are created, moved, resized, parented and destroyed the same way, and the messages sent by the controls
+    are processed by small emulations of the standard window classes. Nothing is ever painted.
+
+    Supported controls: `Window`, `Button`, `CheckBox`, `RadioButton`, `TextInput`, `Label`, `ImageFrame`,
+    `ListBox` (with the `listbox` feature), `Splitter` (with the `splitter` feature), the `GridLayout` and the `DockLayout`.
+    UIs can also be loaded from a description with `DynamicUi` (with the `ui-loader` feature), and controls can be bound to
+    properties (with the `bindings` feature). Futures spawned with `spawn_local` (with the `executor` feature) are polled by
+    the dispatch loops. A modal `Dialog` dispatches the queued events until it ends, like `dispatch_thread_events`.
+    Resources (`Font`, `Icon`, `Bitmap`, `Cursor`) are fake handles.
+
+    The feature must be used without the default features:
+
+    ```toml
+    [dev-dependencies]
+    native-windows-gui = { version = "1", default-features = false, features = ["headless", "listbox"] }
+    ```
+*/
+#[cfg(any(
+    feature = "all", feature = "file-dialog", feature = "color-dialog", feature = "font-dialog", feature = "task-dialog", feature = "datetime-picker",
+    feature = "month-calendar", feature = "progress-bar", feature = "tabs", feature = "tree-view", feature = "fancy-window",
+    feature = "combobox", feature = "tray-notification", feature = "message-window", feature = "timer", feature = "animation-timer",
+    feature = "notice", feature = "list-view", feature = "image-decoder", feature = "number-select", feature = "cursor",
+    feature = "clipboard", feature = "drag-drop", feature = "accelerator", feature = "menu", feature = "trackbar", feature = "extern-canvas", feature = "frame", feature = "tooltip",
+    feature = "status-bar", feature = "winnls", feature = "textbox", feature = "rich-textbox", feature = "image-list",
+    feature = "embed-resource", feature = "scroll-bar", feature = "tree-view-iterator", feature = "toolbar", feature = "rebar",
+    feature = "syslink", feature = "animation", feature = "combobox-ex", feature = "ip-address", feature = "hot-key", feature = "pager",
+    feature = "dynamic_layout", feature = "plotting", feature = "flexbox", feature = "raw-win-handle"
+))]
+compile_error!("The `headless` feature only supports the `listbox`, `splitter`, `ui-loader`, `bindings`, `executor`, `high-dpi` and `no-styling` features. Use `default-features = false`.");
+
+pub(crate) mod sys;
+pub(crate) mod base_helper;
+pub(crate) mod window_helper;
+pub(crate) mod resources_helper;
+pub(crate) mod window;
+
+#[path = "../win32/message_box.rs"]
+pub(crate) mod message_box;
+
+#[path = "../win32/high_dpi.rs"]
+pub(crate) mod high_dpi;
+
+#[path = "../win32/monitor.rs"]
+pub(crate) mod monitor;
+
+#[cfg(feature = "executor")]
+pub(crate) mod executor;
+
+mod tree;
+mod classes;
+
+#[cfg(test)]
+mod tests;
+
+use crate::errors::NwgError;
+
+
+/**
+    Dispatch the queued events of the current thread.
+
+    Headless: returns once the message queue is empty or when `stop_thread_dispatch` is called.
+*/
+pub fn dispatch_thread_events() {
+    run_executor();
+
+    while let Some((hwnd, msg, w, l)) = tree::next_message() {
+        window::send_message(hwnd, msg, w, l);
+
+        if tree::take_quit() {
+            return;
+        }
+
+        run_executor();
+    }
+
+    tree::take_quit();
+}
+
+/**
+    Dispatch system events in the current thread AND execute a callback after each peeking attempt.
+
+    Headless: the loop runs until `stop_thread_dispatch` is called.
+*/
+pub fn dispatch_thread_events_with_callback<F>(mut cb: F)
+    where F: FnMut() -> () + 'static
+{
+    while !tree::take_quit() {
+        run_executor();
+
+        if let Some((hwnd, msg, w, l)) = tree::next_message() {
+            window::send_message(hwnd, msg, w, l);
+        }
+
+        cb();
+    }
+}
+
+/**
+    Dispatch the events of the current thread until `done` returns true or until the window `hwnd` is hidden or destroyed.
+    Used by the modal dialogs.
+
+    If `stop_thread_dispatch` is called while the loop runs, the loop stops and the outer loop stops too.
+
+    Headless: also returns once the message queue is empty.
+*/
+pub(crate) fn dispatch_modal_events(hwnd: sys::shared::windef::HWND, done: &dyn Fn() -> bool) {
+    use sys::um::winuser::WS_VISIBLE;
+
+    // Like `IsWindowVisible`, an owned window is visible even if its owner is hidden
+    let shown = || tree::with_widget(hwnd, |w| w.style & WS_VISIBLE == WS_VISIBLE).unwrap_or(false);
+
+    run_executor();
+
+    while !done() && shown() {
+        let (target, msg, w, l) = match tree::next_message() {
+            Some(message) => message,
+            None => { return; }
+        };
+
+        window::send_message(target, msg, w, l);
+
+        if tree::take_quit() {
+            tree::quit();
+            return;
+        }
+
+        run_executor();
+    }
+}
+
+#[cfg(feature = "executor")]
+fn run_executor() {
+    executor::run();
+}
+
+#[cfg(not(feature = "executor"))]
+fn run_executor() {
+}
+
+/**
+    Break the events loop running on the current thread
+*/
+pub fn stop_thread_dispatch() {
+    tree::quit();
+}
+
+/**
+    Headless: there are no visual styles to enable
+*/
+pub fn enable_visual_styles() {
+}
+
+/**
+    Headless: there are no dll to load. Always succeed.
+*/
+pub fn init_common_controls() -> Result<(), NwgError> {
+    window::init_window_class()
+}
@@ -0,0 +1,98 @@
+/*!
+    Headless resources. Nothing is loaded or rendered: resources are unique fake handles
+    tagged with their type so that the controls can tell a bitmap from an icon.
+*/
+use super::sys::shared::windef::{HFONT, HBITMAP};
+use super::sys::um::winnt::HANDLE;
+use super::sys::um::winuser::{IMAGE_BITMAP, IMAGE_ICON, IMAGE_CURSOR};
+use crate::resources::OemImage;
+use crate::NwgError;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static NEXT_RESOURCE: AtomicUsize = AtomicUsize::new(1);
+
+const FONT_TAG: usize = 0xF;
+
+/// Create a new unique handle. The 4 lower bits store the resource type.
+fn fake_handle(tag: usize) -> HANDLE {
+    let id = NEXT_RESOURCE.fetch_add(1, Ordering::SeqCst);
+    ((id << 4) | (tag & 0xF)) as HANDLE
+}
+
+fn image_tag(image_type: u32) -> usize {
+    match image_type {
+        IMAGE_BITMAP => 1,
+        IMAGE_ICON => 2,
+        IMAGE_CURSOR => 3,
+        _ => 0
+    }
+}
+
+pub fn is_bitmap(handle: HBITMAP) -> bool {
+    !handle.is_null() && (handle as usize & 0xF) == image_tag(IMAGE_BITMAP)
+}
+
+pub fn destroy_icon(_icon: HANDLE) {
+}
+
+pub fn destroy_cursor(_cursor: HANDLE) {
+}
+
+pub fn destroy_obj(_obj: HANDLE) {
+}
+
+pub unsafe fn build_font(
+    _size: i32,
+    _weight: u32,
+    _style: [bool; 3],
+    _family_name: Option<&str>,
+) -> Result<HFONT, NwgError>
+{
+    Ok(fake_handle(FONT_TAG) as HFONT)
+}
+
+/// Same as the win32 version, a missing file is an error only if the loading is strict.
+pub unsafe fn build_image<'a>(
+    source: &'a str,
+    _size: Option<(u32, u32)>,
+    strict: bool,
+    image_type: u32
+) -> Result<HANDLE, NwgError>
+{
+    if strict && !Path::new(source).exists() {
+        return Err( NwgError::resource_create(format!("Failed to create image from source '{}' ", source)));
+    }
+
+    Ok(fake_handle(image_tag(image_type)))
+}
+
+pub unsafe fn build_oem_image(
+    source: OemImage,
+    _size: Option<(u32, u32)>,
+) -> Result<HANDLE, NwgError>
+{
+    let image_type = match source {
+        OemImage::Bitmap(_) => IMAGE_BITMAP,
+        OemImage::Cursor(_) => IMAGE_CURSOR,
+        OemImage::Icon(_) => IMAGE_ICON,
+    };
+
+    Ok(fake_handle(image_tag(image_type)))
+}
+
+pub unsafe fn bitmap_from_memory(source: &[u8]) -> Result<HANDLE, NwgError> {
+    if source.is_empty() {
+        return Err(NwgError::resource_create("Invalid source. The source data is empty."));
+    }
+
+    Ok(fake_handle(image_tag(IMAGE_BITMAP)))
+}
+
+pub unsafe fn icon_from_memory(source: &[u8], _strict: bool, _size: Option<(u32, u32)>) -> Result<HANDLE, NwgError> {
+    if source.is_empty() {
+        return Err(NwgError::resource_create("Invalid source. The source data is empty."));
+    }
+
+    Ok(fake_handle(image_tag(IMAGE_ICON)))
+}
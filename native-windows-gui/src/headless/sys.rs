@@ -0,0 +1,549 @@
+/*!
+    A tiny pure rust stand-in for the parts of `winapi` used by the controls that support the headless backend.
+
+    The module layout mirrors winapi so that `crate::win32::sys::um::winuser::WS_VISIBLE` resolves the same way
+    with or without the `headless` feature. Types and constants keep their winapi values. The few functions that
+    are called directly by the controls are inert: they never touch the widget tree (that is the job of `window_helper`).
+*/
+#![allow(non_snake_case, non_camel_case_types, dead_code, unused_imports, clippy::upper_case_acronyms, clippy::missing_safety_doc)]
+
+pub mod ctypes {
+    pub type c_int = i32;
+    pub type c_uint = u32;
+    pub type c_short = i16;
+    pub type c_ushort = u16;
+    pub type c_long = i32;
+    pub type c_ulong = u32;
+    pub type c_void = std::ffi::c_void;
+}
+
+pub mod shared {
+
+    pub mod basetsd {
+        pub type UINT_PTR = usize;
+        pub type LONG_PTR = isize;
+        pub type DWORD_PTR = usize;
+        pub type ULONG_PTR = usize;
+    }
+
+    pub mod ntdef {
+        use crate::win32::sys::ctypes::c_void;
+
+        pub type WCHAR = u16;
+        pub type LONG = i32;
+        pub type LPCWSTR = *const WCHAR;
+        pub type LPWSTR = *mut WCHAR;
+        pub type HANDLE = *mut c_void;
+        pub type PVOID = *mut c_void;
+    }
+
+    pub mod minwindef {
+        use super::basetsd::{UINT_PTR, LONG_PTR};
+        use super::windef::HINSTANCE__;
+
+        pub type UINT = u32;
+        pub type DWORD = u32;
+        pub type WORD = u16;
+        pub type BYTE = u8;
+        pub type BOOL = i32;
+        pub type WPARAM = UINT_PTR;
+        pub type LPARAM = LONG_PTR;
+        pub type LRESULT = LONG_PTR;
+        pub type HINSTANCE = *mut HINSTANCE__;
+        pub type HMODULE = HINSTANCE;
+
+        #[inline]
+        pub fn LOWORD(l: DWORD) -> WORD { (l & 0xffff) as WORD }
+
+        #[inline]
+        pub fn HIWORD(l: DWORD) -> WORD { ((l >> 16) & 0xffff) as WORD }
+
+        #[inline]
+        pub fn MAKELONG(a: WORD, b: WORD) -> DWORD { (a as DWORD) | ((b as DWORD) << 16) }
+    }
+
+    pub mod windef {
+        use super::ntdef::LONG;
+
+        pub enum HWND__ {}
+        pub enum HMENU__ {}
+        pub enum HFONT__ {}
+        pub enum HBITMAP__ {}
+        pub enum HBRUSH__ {}
+        pub enum HICON__ {}
+        pub enum HDC__ {}
+        pub enum HMONITOR__ {}
+        pub enum HINSTANCE__ {}
+
+        pub type HWND = *mut HWND__;
+        pub type HMENU = *mut HMENU__;
+        pub type HFONT = *mut HFONT__;
+        pub type HBITMAP = *mut HBITMAP__;
+        pub type HBRUSH = *mut HBRUSH__;
+        pub type HICON = *mut HICON__;
+        pub type HCURSOR = HICON;
+        pub type HDC = *mut HDC__;
+        pub type HMONITOR = *mut HMONITOR__;
+        pub type HGDIOBJ = *mut crate::win32::sys::ctypes::c_void;
+
+        #[repr(C)]
+        #[derive(Copy, Clone, Debug, Default)]
+        pub struct RECT {
+            pub left: LONG,
+            pub top: LONG,
+            pub right: LONG,
+            pub bottom: LONG,
+        }
+
+        #[repr(C)]
+        #[derive(Copy, Clone, Debug, Default)]
+        pub struct POINT {
+            pub x: LONG,
+            pub y: LONG,
+        }
+    }
+
+}
+
+pub mod um {
+
+    pub mod winnt {
+        pub use crate::win32::sys::shared::ntdef::{HANDLE, WCHAR, LONG, LPCWSTR, LPWSTR, PVOID};
+    }
+
+    pub mod winuser {
+        use crate::win32::sys::ctypes::c_int;
+        use crate::win32::sys::shared::minwindef::{UINT, DWORD, WORD, BYTE, BOOL, WPARAM, LPARAM, LRESULT, HINSTANCE};
+        use crate::win32::sys::shared::ntdef::{LONG, LPCWSTR};
+        use crate::win32::sys::shared::windef::{HWND, HDC, HBITMAP, HICON, HCURSOR, HMONITOR, RECT, POINT};
+
+        /// Size of the single monitor exposed by the headless backend
+        pub(crate) const HEADLESS_SCREEN: (c_int, c_int) = (1920, 1080);
+
+        pub const WS_OVERLAPPED: DWORD = 0x00000000;
+        pub const WS_POPUP: DWORD = 0x80000000;
+        pub const WS_CHILD: DWORD = 0x40000000;
+        pub const WS_MINIMIZE: DWORD = 0x20000000;
+        pub const WS_VISIBLE: DWORD = 0x10000000;
+        pub const WS_DISABLED: DWORD = 0x08000000;
+        pub const WS_CLIPCHILDREN: DWORD = 0x02000000;
+        pub const WS_MAXIMIZE: DWORD = 0x01000000;
+        pub const WS_CAPTION: DWORD = 0x00C00000;
+        pub const WS_BORDER: DWORD = 0x00800000;
+        pub const WS_VSCROLL: DWORD = 0x00200000;
+        pub const WS_HSCROLL: DWORD = 0x00100000;
+        pub const WS_SYSMENU: DWORD = 0x00080000;
+        pub const WS_THICKFRAME: DWORD = 0x00040000;
+        pub const WS_GROUP: DWORD = 0x00020000;
+        pub const WS_TABSTOP: DWORD = 0x00010000;
+        pub const WS_MINIMIZEBOX: DWORD = 0x00020000;
+        pub const WS_MAXIMIZEBOX: DWORD = 0x00010000;
+        pub const WS_OVERLAPPEDWINDOW: DWORD = WS_OVERLAPPED | WS_CAPTION | WS_SYSMENU | WS_THICKFRAME
+            | WS_MINIMIZEBOX | WS_MAXIMIZEBOX;
+        pub const WS_EX_TOPMOST: DWORD = 0x00000008;
+        pub const WS_EX_ACCEPTFILES: DWORD = 0x00000010;
+        pub const WS_EX_CONTROLPARENT: DWORD = 0x00010000;
+
+        pub const GWL_HINSTANCE: c_int = -6;
+        pub const GWL_STYLE: c_int = -16;
+        pub const GWL_EXSTYLE: c_int = -20;
+        pub const GWL_USERDATA: c_int = -21;
+
+        pub const WM_MOVE: UINT = 0x0003;
+        pub const WM_SIZE: UINT = 0x0005;
+        pub const WM_SETFOCUS: UINT = 0x0007;
+        pub const WM_KILLFOCUS: UINT = 0x0008;
+        pub const WM_SETTEXT: UINT = 0x000C;
+        pub const WM_PAINT: UINT = 0x000F;
+        pub const WM_CLOSE: UINT = 0x0010;
+        pub const WM_QUIT: UINT = 0x0012;
+        pub const WM_SETCURSOR: UINT = 0x0020;
+        pub const WM_SETFONT: UINT = 0x0030;
+        pub const WM_GETFONT: UINT = 0x0031;
+        pub const WM_CONTEXTMENU: UINT = 0x007B;
+        pub const WM_GETICON: UINT = 0x007F;
+        pub const WM_GETDLGCODE: UINT = 0x0087;
+        pub const WM_SETICON: UINT = 0x0080;
+        pub const WM_KEYDOWN: UINT = 0x0100;
+        pub const WM_KEYUP: UINT = 0x0101;
+        pub const WM_CHAR: UINT = 0x0102;
+        pub const WM_SYSKEYDOWN: UINT = 0x0104;
+        pub const WM_SYSKEYUP: UINT = 0x0105;
+        pub const WM_COMMAND: UINT = 0x0111;
+        pub const WM_TIMER: UINT = 0x0113;
+        pub const WM_HSCROLL: UINT = 0x0114;
+        pub const WM_VSCROLL: UINT = 0x0115;
+        pub const WM_CTLCOLORSTATIC: UINT = 0x0138;
+        pub const WM_MOUSEMOVE: UINT = 0x0200;
+        pub const WM_LBUTTONDOWN: UINT = 0x0201;
+        pub const WM_LBUTTONUP: UINT = 0x0202;
+        pub const WM_RBUTTONDOWN: UINT = 0x0204;
+        pub const WM_RBUTTONUP: UINT = 0x0205;
+        pub const WM_MOUSEWHEEL: UINT = 0x020A;
+        pub const WM_CAPTURECHANGED: UINT = 0x0215;
+        pub const WM_ENTERSIZEMOVE: UINT = 0x0231;
+        pub const WM_EXITSIZEMOVE: UINT = 0x0232;
+        pub const WM_USER: UINT = 0x0400;
+
+        pub const SIZE_RESTORED: WPARAM = 0;
+        pub const SIZE_MINIMIZED: WPARAM = 1;
+        pub const SIZE_MAXIMIZED: WPARAM = 2;
+
+        pub const SW_SHOWNORMAL: c_int = 1;
+        pub const SW_SHOWMINIMIZED: c_int = 2;
+        pub const SW_SHOWMAXIMIZED: c_int = 3;
+
+        pub const HTCLIENT: LRESULT = 1;
+
+        pub const DLGC_WANTARROWS: LRESULT = 0x0001;
+
+        pub const VK_LEFT: c_int = 0x25;
+        pub const VK_UP: c_int = 0x26;
+        pub const VK_RIGHT: c_int = 0x27;
+        pub const VK_DOWN: c_int = 0x28;
+
+        pub const IDC_SIZEWE: LPCWSTR = 32644 as LPCWSTR;
+        pub const IDC_SIZENS: LPCWSTR = 32645 as LPCWSTR;
+
+        pub const ICON_SMALL: UINT = 0;
+        pub const ICON_BIG: UINT = 1;
+
+        pub const IMAGE_BITMAP: UINT = 0;
+        pub const IMAGE_ICON: UINT = 1;
+        pub const IMAGE_CURSOR: UINT = 2;
+
+        pub const IDOK: c_int = 1;
+        pub const IDCANCEL: c_int = 2;
+        pub const IDABORT: c_int = 3;
+        pub const IDRETRY: c_int = 4;
+        pub const IDIGNORE: c_int = 5;
+        pub const IDYES: c_int = 6;
+        pub const IDNO: c_int = 7;
+        pub const IDTRYAGAIN: c_int = 10;
+        pub const IDCONTINUE: c_int = 11;
+
+        pub const MB_OK: UINT = 0x00000000;
+        pub const MB_OKCANCEL: UINT = 0x00000001;
+        pub const MB_ABORTRETRYIGNORE: UINT = 0x00000002;
+        pub const MB_YESNOCANCEL: UINT = 0x00000003;
+        pub const MB_YESNO: UINT = 0x00000004;
+        pub const MB_RETRYCANCEL: UINT = 0x00000005;
+        pub const MB_CANCELTRYCONTINUE: UINT = 0x00000006;
+        pub const MB_ICONSTOP: UINT = 0x00000010;
+        pub const MB_ICONQUESTION: UINT = 0x00000020;
+        pub const MB_ICONEXCLAMATION: UINT = 0x00000030;
+        pub const MB_ICONINFORMATION: UINT = 0x00000040;
+
+        pub const BS_CHECKBOX: DWORD = 0x00000002;
+        pub const BS_AUTOCHECKBOX: DWORD = 0x00000003;
+        pub const BS_RADIOBUTTON: DWORD = 0x00000004;
+        pub const BS_3STATE: DWORD = 0x00000005;
+        pub const BS_AUTO3STATE: DWORD = 0x00000006;
+        pub const BS_AUTORADIOBUTTON: DWORD = 0x00000009;
+        pub const BS_TYPEMASK: DWORD = 0x0000000F;
+        pub const BS_ICON: DWORD = 0x00000040;
+        pub const BS_BITMAP: DWORD = 0x00000080;
+        pub const BS_PUSHLIKE: DWORD = 0x00001000;
+        pub const BS_NOTIFY: DWORD = 0x00004000;
+
+        pub const BST_UNCHECKED: WPARAM = 0x0000;
+        pub const BST_CHECKED: WPARAM = 0x0001;
+        pub const BST_INDETERMINATE: WPARAM = 0x0002;
+
+        pub const BM_GETCHECK: UINT = 0x00F0;
+        pub const BM_SETCHECK: UINT = 0x00F1;
+        pub const BM_SETSTYLE: UINT = 0x00F4;
+        pub const BM_CLICK: UINT = 0x00F5;
+        pub const BM_GETIMAGE: UINT = 0x00F6;
+        pub const BM_SETIMAGE: UINT = 0x00F7;
+
+        pub const BN_CLICKED: WORD = 0;
+        pub const BN_DBLCLK: WORD = 5;
+
+        pub const ES_LEFT: DWORD = 0x0000;
+        pub const ES_CENTER: DWORD = 0x0001;
+        pub const ES_RIGHT: DWORD = 0x0002;
+        pub const ES_PASSWORD: DWORD = 0x0020;
+        pub const ES_AUTOHSCROLL: DWORD = 0x0080;
+        pub const ES_READONLY: DWORD = 0x0800;
+        pub const ES_NUMBER: DWORD = 0x2000;
+
+        pub const EM_GETSEL: WORD = 0x00B0;
+        pub const EM_SETSEL: WORD = 0x00B1;
+        pub const EM_GETMODIFY: WORD = 0x00B8;
+        pub const EM_SETMODIFY: WORD = 0x00B9;
+        pub const EM_LINELENGTH: WORD = 0x00C1;
+        pub const EM_LIMITTEXT: WORD = 0x00C5;
+        pub const EM_SETLIMITTEXT: WORD = EM_LIMITTEXT;
+        pub const EM_CANUNDO: WORD = 0x00C6;
+        pub const EM_UNDO: WORD = 0x00C7;
+        pub const EM_SETPASSWORDCHAR: WORD = 0x00CC;
+        pub const EM_SETREADONLY: WORD = 0x00CF;
+        pub const EM_GETPASSWORDCHAR: WORD = 0x00D2;
+        pub const EM_GETLIMITTEXT: WORD = 0x00D5;
+
+        pub const EN_CHANGE: WORD = 0x0300;
+
+        pub const SS_LEFT: DWORD = 0x00000000;
+        pub const SS_CENTER: DWORD = 0x00000001;
+        pub const SS_RIGHT: DWORD = 0x00000002;
+        pub const SS_ICON: DWORD = 0x00000003;
+        pub const SS_BITMAP: DWORD = 0x0000000E;
+        pub const SS_NOPREFIX: DWORD = 0x00000080;
+        pub const SS_NOTIFY: DWORD = 0x00000100;
+        pub const SS_CENTERIMAGE: DWORD = 0x00000200;
+        pub const SS_WORDELLIPSIS: DWORD = 0x0000C000;
+
+        pub const STM_SETIMAGE: UINT = 0x0172;
+        pub const STM_GETIMAGE: UINT = 0x0173;
+
+        pub const STN_CLICKED: WORD = 0;
+        pub const STN_DBLCLK: WORD = 1;
+
+        pub const LBS_NOTIFY: DWORD = 0x0001;
+        pub const LBS_SORT: DWORD = 0x0002;
+        pub const LBS_MULTIPLESEL: DWORD = 0x0008;
+        pub const LBS_HASSTRINGS: DWORD = 0x0040;
+        pub const LBS_EXTENDEDSEL: DWORD = 0x0800;
+        pub const LBS_NOSEL: DWORD = 0x4000;
+
+        pub const LB_ERR: LRESULT = -1;
+        pub const LB_ADDSTRING: UINT = 0x0180;
+        pub const LB_INSERTSTRING: UINT = 0x0181;
+        pub const LB_DELETESTRING: UINT = 0x0182;
+        pub const LB_SELITEMRANGEEX: UINT = 0x0183;
+        pub const LB_RESETCONTENT: UINT = 0x0184;
+        pub const LB_SETSEL: UINT = 0x0185;
+        pub const LB_SETCURSEL: UINT = 0x0186;
+        pub const LB_GETSEL: UINT = 0x0187;
+        pub const LB_GETCURSEL: UINT = 0x0188;
+        pub const LB_GETTEXT: UINT = 0x0189;
+        pub const LB_GETTEXTLEN: UINT = 0x018A;
+        pub const LB_GETCOUNT: UINT = 0x018B;
+        pub const LB_SELECTSTRING: UINT = 0x018C;
+        pub const LB_GETSELCOUNT: UINT = 0x0190;
+        pub const LB_GETSELITEMS: UINT = 0x0191;
+        pub const LB_INITSTORAGE: UINT = 0x01A8;
+
+        pub const LBN_SELCHANGE: WORD = 1;
+        pub const LBN_DBLCLK: WORD = 2;
+
+        pub const SM_CXSCREEN: c_int = 0;
+        pub const SM_CYSCREEN: c_int = 1;
+        pub const SM_CXVIRTUALSCREEN: c_int = 78;
+        pub const SM_CYVIRTUALSCREEN: c_int = 79;
+
+        pub const MONITOR_DEFAULTTONEAREST: DWORD = 0x00000002;
+        pub const USER_DEFAULT_SCREEN_DPI: LONG = 96;
+
+        #[repr(C)]
+        #[derive(Copy, Clone)]
+        pub struct PAINTSTRUCT {
+            pub hdc: HDC,
+            pub fErase: BOOL,
+            pub rcPaint: RECT,
+            pub fRestore: BOOL,
+            pub fIncUpdate: BOOL,
+            pub rgbReserved: [BYTE; 32],
+        }
+
+        #[repr(C)]
+        #[derive(Copy, Clone)]
+        pub struct MINMAXINFO {
+            pub ptReserved: POINT,
+            pub ptMaxSize: POINT,
+            pub ptMaxPosition: POINT,
+            pub ptMinTrackSize: POINT,
+            pub ptMaxTrackSize: POINT,
+        }
+
+        #[repr(C)]
+        #[derive(Copy, Clone)]
+        pub struct ICONINFO {
+            pub fIcon: BOOL,
+            pub xHotspot: DWORD,
+            pub yHotspot: DWORD,
+            pub hbmMask: HBITMAP,
+            pub hbmColor: HBITMAP,
+        }
+
+        #[repr(C)]
+        #[derive(Copy, Clone)]
+        pub struct MONITORINFO {
+            pub cbSize: DWORD,
+            pub rcMonitor: RECT,
+            pub rcWork: RECT,
+            pub dwFlags: DWORD,
+        }
+
+        /// Nothing is ever painted by the headless backend
+        pub unsafe fn InvalidateRect(_hwnd: HWND, _rect: *const RECT, _erase: BOOL) -> BOOL { 1 }
+
+        pub unsafe fn BeginPaint(_hwnd: HWND, _paint: *mut PAINTSTRUCT) -> HDC { std::ptr::null_mut() }
+
+        pub unsafe fn EndPaint(_hwnd: HWND, _paint: *const PAINTSTRUCT) -> BOOL { 1 }
+
+        pub unsafe fn GetDC(_hwnd: HWND) -> HDC { std::ptr::null_mut() }
+
+        /// There is no mouse in the headless backend
+        pub unsafe fn LoadCursorW(_instance: HINSTANCE, _name: LPCWSTR) -> HCURSOR { std::ptr::null_mut() }
+
+        pub unsafe fn SetCursor(_cursor: HCURSOR) -> HCURSOR { std::ptr::null_mut() }
+
+        pub unsafe fn SetCapture(_hwnd: HWND) -> HWND { std::ptr::null_mut() }
+
+        pub unsafe fn ReleaseCapture() -> BOOL { 1 }
+
+        /// The headless bitmaps are opaque handles, so the "icon" simply reuses the color bitmap handle
+        pub unsafe fn CreateIconIndirect(info: *mut ICONINFO) -> HICON { (*info).hbmColor as HICON }
+
+        pub unsafe fn GetSystemMetrics(index: c_int) -> c_int {
+            match index {
+                SM_CXSCREEN | SM_CXVIRTUALSCREEN => HEADLESS_SCREEN.0,
+                SM_CYSCREEN | SM_CYVIRTUALSCREEN => HEADLESS_SCREEN.1,
+                _ => 0
+            }
+        }
+
+        pub unsafe fn MonitorFromWindow(_hwnd: HWND, _flags: DWORD) -> HMONITOR { 1 as HMONITOR }
+
+        pub unsafe fn GetMonitorInfoW(_monitor: HMONITOR, info: *mut MONITORINFO) -> BOOL {
+            let screen = RECT { left: 0, top: 0, right: HEADLESS_SCREEN.0, bottom: HEADLESS_SCREEN.1 };
+            let info = &mut *info;
+            info.rcMonitor = screen;
+            info.rcWork = screen;
+            info.dwFlags = 1;
+            1
+        }
+
+        pub type MONITORENUMPROC = Option<unsafe extern "system" fn(HMONITOR, HDC, *mut RECT, LPARAM) -> BOOL>;
+
+        /// The headless backend has a single monitor
+        pub unsafe fn EnumDisplayMonitors(hdc: HDC, _clip: *const RECT, callback: MONITORENUMPROC, data: LPARAM) -> BOOL {
+            let mut screen = RECT { left: 0, top: 0, right: HEADLESS_SCREEN.0, bottom: HEADLESS_SCREEN.1 };
+            match callback {
+                Some(callback) => callback(1 as HMONITOR, hdc, &mut screen, data),
+                None => 0
+            }
+        }
+
+        pub unsafe fn SetProcessDPIAware() -> BOOL { 1 }
+
+        /// There is nobody to click on the message box. The default (first) button of the box is returned.
+        pub unsafe fn MessageBoxW(_hwnd: HWND, _text: LPCWSTR, _caption: LPCWSTR, ty: UINT) -> c_int {
+            match ty & 0xF {
+                MB_ABORTRETRYIGNORE => IDABORT,
+                MB_YESNO | MB_YESNOCANCEL => IDYES,
+                MB_RETRYCANCEL => IDRETRY,
+                MB_CANCELTRYCONTINUE => IDCANCEL,
+                _ => IDOK
+            }
+        }
+    }
+
+    pub mod wingdi {
+        use crate::win32::sys::ctypes::c_int;
+        use crate::win32::sys::shared::minwindef::{BYTE, BOOL, DWORD, LPARAM};
+        use crate::win32::sys::shared::ntdef::{LONG, WCHAR, LPCWSTR, HANDLE, PVOID};
+        use crate::win32::sys::shared::windef::{HDC, HGDIOBJ};
+
+        pub const DEFAULT_CHARSET: DWORD = 1;
+        pub const LOGPIXELSX: c_int = 88;
+
+        #[repr(C)]
+        #[derive(Copy, Clone)]
+        pub struct LOGFONTW {
+            pub lfHeight: LONG,
+            pub lfWidth: LONG,
+            pub lfEscapement: LONG,
+            pub lfOrientation: LONG,
+            pub lfWeight: LONG,
+            pub lfItalic: BYTE,
+            pub lfUnderline: BYTE,
+            pub lfStrikeOut: BYTE,
+            pub lfCharSet: BYTE,
+            pub lfOutPrecision: BYTE,
+            pub lfClipPrecision: BYTE,
+            pub lfQuality: BYTE,
+            pub lfPitchAndFamily: BYTE,
+            pub lfFaceName: [WCHAR; 32],
+        }
+
+        /// Only ever used behind a pointer
+        pub enum TEXTMETRICW {}
+
+        pub type FONTENUMPROCW = Option<unsafe extern "system" fn(*const LOGFONTW, *const TEXTMETRICW, DWORD, LPARAM) -> c_int>;
+
+        pub unsafe fn DeleteObject(_obj: HGDIOBJ) -> BOOL { 1 }
+
+        pub unsafe fn GetDeviceCaps(_hdc: HDC, index: c_int) -> c_int {
+            match index {
+                LOGPIXELSX => 96,
+                _ => 0
+            }
+        }
+
+        /// The headless backend has no font table. Fonts can never be added.
+        pub unsafe fn AddFontResourceW(_path: LPCWSTR) -> c_int { 0 }
+
+        pub unsafe fn RemoveFontResourceW(_path: LPCWSTR) -> BOOL { 0 }
+
+        pub unsafe fn AddFontMemResourceEx(_data: PVOID, _len: DWORD, _reserved: PVOID, num_fonts: *mut DWORD) -> HANDLE {
+            *num_fonts = 0;
+            std::ptr::null_mut()
+        }
+
+        pub unsafe fn RemoveFontMemResourceEx(_handle: HANDLE) -> BOOL { 0 }
+
+        pub unsafe fn EnumFontFamiliesExW(_hdc: HDC, _font: *mut LOGFONTW, _proc: FONTENUMPROCW, _param: LPARAM, _flags: DWORD) -> c_int { 1 }
+    }
+
+    pub mod commctrl {
+        use crate::win32::sys::shared::basetsd::UINT_PTR;
+        use crate::win32::sys::shared::minwindef::{UINT, LPARAM, HINSTANCE};
+        use crate::win32::sys::shared::ntdef::{WCHAR, LPWSTR};
+        use crate::win32::sys::shared::windef::HWND;
+
+        pub const ECM_FIRST: UINT = 0x1500;
+        pub const EM_SETCUEBANNER: UINT = ECM_FIRST + 1;
+        pub const EM_GETCUEBANNER: UINT = ECM_FIRST + 2;
+
+        pub const TTF_DI_SETITEM: UINT = 0x8000;
+
+        #[repr(C)]
+        #[derive(Copy, Clone)]
+        pub struct NMHDR {
+            pub hwndFrom: HWND,
+            pub idFrom: UINT_PTR,
+            pub code: UINT,
+        }
+
+        #[repr(C)]
+        #[derive(Copy, Clone)]
+        pub struct NMTTDISPINFOW {
+            pub hdr: NMHDR,
+            pub lpszText: LPWSTR,
+            pub szText: [WCHAR; 80],
+            pub hinst: HINSTANCE,
+            pub uFlags: UINT,
+            pub lParam: LPARAM,
+        }
+    }
+
+    pub mod shellapi {
+        use crate::win32::sys::shared::minwindef::{UINT, BOOL};
+        use crate::win32::sys::shared::ntdef::LPWSTR;
+        use crate::win32::sys::shared::windef::POINT;
+
+        pub enum HDROP__ {}
+        pub type HDROP = *mut HDROP__;
+
+        /// Files cannot be dropped on headless windows, so a HDROP is always empty
+        pub unsafe fn DragQueryFileW(_drop: HDROP, _file: UINT, _buffer: LPWSTR, _len: UINT) -> UINT { 0 }
+
+        pub unsafe fn DragQueryPoint(_drop: HDROP, _point: *mut POINT) -> BOOL { 0 }
+
+        pub unsafe fn DragFinish(_drop: HDROP) {}
+    }
+
+}
@@ -0,0 +1,831 @@
+use crate::*;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+
+fn window(title: &str) -> Window {
+    let mut window = Window::default();
+    Window::builder()
+        .size((400, 200))
+        .position((10, 20))
+        .title(title)
+        .build(&mut window)
+        .expect("Failed to build window");
+
+    window
+}
+
+#[test]
+fn window_properties() {
+    let window = window("Hello");
+
+    assert_eq!(window.text(), "Hello");
+    assert_eq!(window.size(), (400, 200));
+    assert_eq!(window.position(), (10, 20));
+    assert!(window.visible());
+    assert!(window.enabled());
+
+    window.set_text("World");
+    window.set_size(300, 100);
+    window.set_position(5, 5);
+    window.set_visible(false);
+    window.set_enabled(false);
+
+    assert_eq!(window.text(), "World");
+    assert_eq!(window.size(), (300, 100));
+    assert_eq!(window.position(), (5, 5));
+    assert!(!window.visible());
+    assert!(!window.enabled());
+}
+
+#[test]
+fn window_placement() {
+    let window = window("Placement");
+    assert_eq!(window.placement(), WindowPlacement { rect: [10, 20, 410, 220], state: WindowShowState::Normal });
+
+    window.maximize();
+    let saved = window.placement().to_string();
+    assert_eq!(saved, "maximized 10 20 410 220");
+
+    let restored = self::window("Restored");
+    restored.set_placement(&saved.parse().unwrap());
+    assert_eq!(restored.placement(), window.placement());
+
+    // A window saved on a monitor that is no longer connected reopens on the headless monitor
+    restored.set_placement(&"normal 5000 900 5400 1200".parse().unwrap());
+    assert_eq!(restored.placement(), WindowPlacement { rect: [1520, 780, 1920, 1080], state: WindowShowState::Normal });
+    assert_eq!(restored.position(), (1520, 780));
+    assert_eq!(restored.size(), (400, 300));
+}
+
+#[test]
+fn children_visibility_and_parenting() {
+    let window = window("Parent");
+    let mut button = Button::default();
+    Button::builder()
+        .text("Click me")
+        .parent(&window)
+        .build(&mut button)
+        .unwrap();
+
+    let parent = ControlHandle::Hwnd(crate::win32::window_helper::get_window_parent(button.handle.hwnd().unwrap()));
+    assert_eq!(parent, window.handle);
+    assert_eq!(button.text(), "Click me");
+    assert!(button.visible());
+
+    window.set_visible(false);
+    assert!(!button.visible());
+
+    window.set_visible(true);
+    button.set_focus();
+    assert!(button.focus());
+    assert!(!window.focus());
+}
+
+#[test]
+fn text_input() {
+    let window = window("Text");
+    let mut input = TextInput::default();
+    TextInput::builder()
+        .text("Hello")
+        .limit(10)
+        .placeholder_text(Some("Name"))
+        .parent(&window)
+        .build(&mut input)
+        .unwrap();
+
+    assert_eq!(input.text(), "Hello");
+    assert_eq!(input.limit(), 10);
+    assert_eq!(input.placeholder_text(10), "Name");
+    assert_eq!(input.len(), 5);
+
+    input.set_selection(1..3);
+    assert_eq!(input.selection(), 1..3);
+
+    input.set_selection(0..100);
+    assert_eq!(input.selection(), 0..5);
+
+    input.set_readonly(true);
+    assert!(input.readonly());
+
+    input.set_password_char(Some('*'));
+    assert_eq!(input.password_char(), Some('*'));
+}
+
+#[cfg(feature = "listbox")]
+#[test]
+fn list_box() {
+    let window = window("List");
+    let mut list: ListBox<&'static str> = ListBox::default();
+    ListBox::builder()
+        .collection(vec!["Alpha", "Beta", "Gamma"])
+        .parent(&window)
+        .build(&mut list)
+        .unwrap();
+
+    assert_eq!(list.len(), 3);
+    assert_eq!(list.selection(), None);
+
+    list.set_selection(Some(1));
+    assert_eq!(list.selection(), Some(1));
+    assert_eq!(list.selection_string(), Some("Beta".to_string()));
+
+    assert_eq!(list.set_selection_string("gam"), Some(2));
+    assert_eq!(list.selection(), Some(2));
+
+    list.push("Delta");
+    assert_eq!(list.len(), 4);
+
+    list.remove(0);
+    assert_eq!(list.len(), 3);
+    assert_eq!(&list.collection()[..], &["Beta", "Gamma", "Delta"]);
+}
+
+#[test]
+fn grid_layout_resize() {
+    let window = window("Grid");
+    let mut left = Button::default();
+    let mut right = Button::default();
+    Button::builder().parent(&window).build(&mut left).unwrap();
+    Button::builder().parent(&window).build(&mut right).unwrap();
+
+    let layout = GridLayout::default();
+    GridLayout::builder()
+        .parent(&window)
+        .margin([0, 0, 0, 0])
+        .spacing(0)
+        .child(0, 0, &left)
+        .child(1, 0, &right)
+        .build(&layout)
+        .unwrap();
+
+    assert_eq!(left.position(), (0, 0));
+    assert_eq!(left.size(), (200, 200));
+    assert_eq!(right.position(), (200, 0));
+
+    window.set_size(600, 100);
+    assert_eq!(left.size(), (300, 100));
+    assert_eq!(right.position(), (300, 0));
+}
+
+#[test]
+fn check_box_and_radio_click() {
+    let window = window("Checks");
+    let mut check = CheckBox::default();
+    let mut radio1 = RadioButton::default();
+    let mut radio2 = RadioButton::default();
+
+    CheckBox::builder().parent(&window).build(&mut check).unwrap();
+    RadioButton::builder().flags(RadioButtonFlags::VISIBLE | RadioButtonFlags::GROUP).parent(&window).build(&mut radio1).unwrap();
+    RadioButton::builder().parent(&window).build(&mut radio2).unwrap();
+
+    let click = |handle: &ControlHandle| {
+        use crate::win32::sys::um::winuser::BM_CLICK;
+        crate::win32::window_helper::send_message(handle.hwnd().unwrap(), BM_CLICK, 0, 0);
+    };
+
+    click(&check.handle);
+    assert_eq!(check.check_state(), CheckBoxState::Checked);
+    click(&check.handle);
+    assert_eq!(check.check_state(), CheckBoxState::Unchecked);
+
+    click(&radio1.handle);
+    assert_eq!(radio1.check_state(), RadioButtonState::Checked);
+
+    click(&radio2.handle);
+    assert_eq!(radio1.check_state(), RadioButtonState::Unchecked);
+    assert_eq!(radio2.check_state(), RadioButtonState::Checked);
+}
+
+#[test]
+fn events_dispatch() {
+    let window = window("Events");
+    let mut button = Button::default();
+    let mut input = TextInput::default();
+    Button::builder().parent(&window).build(&mut button).unwrap();
+    TextInput::builder().parent(&window).build(&mut input).unwrap();
+
+    let events = Rc::new(RefCell::new(Vec::new()));
+    let events2 = events.clone();
+    let handler = full_bind_event_handler(&window.handle, move |evt, _data, handle| {
+        events2.borrow_mut().push((evt, handle));
+    });
+
+    button.click();
+    input.set_text("Changed");
+
+    // Disabled buttons cannot be clicked
+    button.set_enabled(false);
+    button.click();
+
+    assert_eq!(&events.borrow()[..], &[
+        (Event::OnButtonClick, button.handle),
+        (Event::OnTextInput, input.handle),
+    ]);
+
+    unbind_event_handler(&handler);
+}
+
+#[test]
+fn init_and_close_events() {
+    let window = window("Close");
+
+    let events = Rc::new(RefCell::new(Vec::new()));
+    let events2 = events.clone();
+    let handler = full_bind_event_handler(&window.handle, move |evt, data, _handle| {
+        if let (Event::OnWindowClose, EventData::OnWindowClose(data)) = (evt, &data) {
+            data.close(false);
+        }
+        events2.borrow_mut().push(evt);
+    });
+
+    // Initialization messages are queued when the window is created
+    dispatch_thread_events();
+    assert_eq!(&events.borrow()[..], &[Event::OnInit]);
+
+    // Close was cancelled by the handler
+    window.close();
+    dispatch_thread_events();
+    assert_eq!(&events.borrow()[..], &[Event::OnInit, Event::OnWindowClose]);
+    assert!(window.visible());
+
+    unbind_event_handler(&handler);
+
+    window.close();
+    dispatch_thread_events();
+    assert!(!window.visible());
+}
+
+#[test]
+fn destroy_controls() {
+    let window = window("Destroy");
+    let mut button = Button::default();
+    Button::builder().parent(&window).build(&mut button).unwrap();
+
+    let button_handle = button.handle.hwnd().unwrap();
+    drop(button);
+    assert!(!crate::win32::window_helper::window_valid(button_handle));
+
+    let window_handle = window.handle.hwnd().unwrap();
+    drop(window);
+    assert!(!crate::win32::window_helper::window_valid(window_handle));
+}
+
+
+/// A hand written application that uses `NativeUi` and `PartialUi`
+mod application {
+    use crate::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[derive(Default)]
+    pub struct NameForm {
+        pub name: TextInput,
+        pub submit: Button,
+        pub submitted: RefCell<Vec<String>>,
+    }
+
+    impl PartialUi for NameForm {
+        fn build_partial<W: Into<ControlHandle>>(data: &mut NameForm, parent: Option<W>) -> Result<(), NwgError> {
+            let parent = parent.unwrap().into();
+
+            TextInput::builder()
+                .parent(parent)
+                .build(&mut data.name)?;
+
+            Button::builder()
+                .text("Submit")
+                .parent(parent)
+                .build(&mut data.submit)?;
+
+            Ok(())
+        }
+
+        fn process_event(&self, evt: Event, _evt_data: &EventData, handle: ControlHandle) {
+            if evt == Event::OnButtonClick && handle == self.submit {
+                self.submitted.borrow_mut().push(self.name.text());
+            }
+        }
+    }
+
+    #[derive(Default)]
+    pub struct App {
+        pub window: Window,
+        pub form: NameForm,
+    }
+
+    pub struct AppUi {
+        pub inner: Rc<App>,
+        handler: EventHandler,
+    }
+
+    impl NativeUi<AppUi> for App {
+        fn build_ui(mut data: App) -> Result<AppUi, NwgError> {
+            Window::builder()
+                .title("App")
+                .build(&mut data.window)?;
+
+            NameForm::build_partial(&mut data.form, Some(&data.window))?;
+
+            let inner = Rc::new(data);
+            let evt_ui = Rc::downgrade(&inner);
+            let handler = full_bind_event_handler(&inner.window.handle, move |evt, evt_data, handle| {
+                if let Some(ui) = evt_ui.upgrade() {
+                    ui.form.process_event(evt, &evt_data, handle);
+                }
+            });
+
+            Ok(AppUi { inner, handler })
+        }
+    }
+
+    impl Drop for AppUi {
+        fn drop(&mut self) {
+            unbind_event_handler(&self.handler);
+        }
+    }
+}
+
+#[test]
+fn native_ui_and_partial_ui() {
+    use self::application::App;
+
+    let app = App::build_ui(Default::default()).expect("Failed to build UI");
+    let form = &app.inner.form;
+
+    form.name.set_text("Ferris");
+    form.submit.click();
+    form.name.set_text("Gabriel");
+    form.submit.click();
+
+    assert_eq!(&form.submitted.borrow()[..], &["Ferris".to_string(), "Gabriel".to_string()]);
+}
+
+#[test]
+fn simulate_events() {
+    let window = window("Simulate");
+    let mut button = Button::default();
+    Button::builder().parent(&window).build(&mut button).unwrap();
+
+    let recorder = EventRecorder::new();
+    let window_handler = recorder.bind(&window.handle);
+
+    // Bound on both the button and the window, but must only receive the button events once
+    let button_handler = bind_event_handler(&button.handle, &window.handle, recorder.wrap(|_, _, _| {}));
+
+    simulate_event(&button.handle, Event::OnButtonClick, EventData::NoData);
+    simulate_event(&window.handle, Event::OnKeyPress, EventData::OnKey(keys::RETURN));
+
+    assert_eq!(recorder.take(), vec![
+        RecordedEvent { event: Event::OnButtonClick, handle: button.handle, data: "NoData".to_string() },
+        RecordedEvent { event: Event::OnButtonClick, handle: button.handle, data: "NoData".to_string() },
+        RecordedEvent { event: Event::OnKeyPress, handle: window.handle, data: format!("OnKey({})", keys::RETURN) },
+        RecordedEvent { event: Event::OnKeyPress, handle: window.handle, data: format!("OnKey({})", keys::RETURN) },
+    ]);
+
+    unbind_event_handler(&button_handler);
+    unbind_event_handler(&window_handler);
+
+    simulate_event(&button.handle, Event::OnButtonClick, EventData::NoData);
+    assert_eq!(recorder.count(Event::OnButtonClick), 0);
+}
+
+#[test]
+fn simulate_window_close() {
+    let window = window("Close");
+    let handler = full_bind_event_handler(&window.handle, |evt, data, _| {
+        if evt == Event::OnWindowClose {
+            data.on_window_close().close(false);
+        }
+    });
+
+    let data = simulate_event(&window.handle, Event::OnWindowClose, EventData::OnWindowClose(WindowCloseData::new(true)));
+    assert!(!data.on_window_close().closing());
+    assert!(window.visible());
+
+    unbind_event_handler(&handler);
+}
+
+#[cfg(feature = "splitter")]
+#[test]
+fn splitter() {
+    use crate::win32::sys::um::winuser::{WM_LBUTTONDOWN, WM_MOUSEMOVE, WM_LBUTTONUP, WM_KEYDOWN, WM_GETDLGCODE, DLGC_WANTARROWS, VK_RIGHT};
+    use crate::win32::sys::shared::minwindef::{MAKELONG, LPARAM};
+    use crate::win32::window_helper::{send_message, get_window_parent};
+
+    let window = window("Splitter");
+    let mut left = TextInput::default();
+    let mut right = TextInput::default();
+    let mut splitter = Splitter::default();
+    TextInput::builder().parent(&window).build(&mut left).unwrap();
+    TextInput::builder().parent(&window).build(&mut right).unwrap();
+
+    Splitter::builder()
+        .parent(&window)
+        .size((210, 100))
+        .bar_size(10)
+        .min_sizes([20, 50])
+        .first(&left)
+        .second(&right)
+        .build(&mut splitter)
+        .unwrap();
+
+    assert_eq!(ControlHandle::Hwnd(get_window_parent(left.handle.hwnd().unwrap())), splitter.handle);
+    assert_eq!((left.position(), left.size()), ((0, 0), (100, 100)));
+    assert_eq!((right.position(), right.size()), ((110, 0), (100, 100)));
+
+    // Drag the bar from its middle
+    let hwnd = splitter.handle.hwnd().unwrap();
+    let mouse = |x: u16| MAKELONG(x, 50) as LPARAM;
+    send_message(hwnd, WM_LBUTTONDOWN, 0, mouse(105));
+    send_message(hwnd, WM_MOUSEMOVE, 0, mouse(45));
+    send_message(hwnd, WM_LBUTTONUP, 0, mouse(45));
+    assert_eq!(left.size(), (40, 100));
+    assert_eq!(splitter.ratio(), 0.2);
+    assert!(splitter.focus());
+
+    // Moving the mouse after the button is released does nothing
+    send_message(hwnd, WM_MOUSEMOVE, 0, mouse(150));
+    assert_eq!(left.size(), (40, 100));
+
+    // Keyboard nudging respects the min size of the second pane
+    assert_eq!(send_message(hwnd, WM_GETDLGCODE, 0, 0), DLGC_WANTARROWS);
+    for _ in 0..20 {
+        send_message(hwnd, WM_KEYDOWN, VK_RIGHT as usize, 0);
+    }
+    assert_eq!((left.size(), right.size()), ((150, 100), (50, 100)));
+
+    // The ratio is kept when the splitter is resized
+    splitter.set_ratio(0.5);
+    splitter.set_size(410, 100);
+    assert_eq!(left.size(), (200, 100));
+
+    splitter.set_collapsed(Some(SplitterPane::First));
+    assert!(!left.visible());
+    assert_eq!((right.position(), right.size()), ((0, 0), (410, 100)));
+
+    splitter.set_collapsed(None);
+    assert!(left.visible());
+    assert_eq!(right.size(), (200, 100));
+}
+
+#[cfg(feature = "ui-loader")]
+#[test]
+fn dynamic_ui() {
+    use crate::win32::window_helper::get_window_parent;
+
+    let source = r#"
+        window: Window(title: "Loaded", size: (400, 200), position: (10, 20)) {
+            dock: DockLayout
+            grid_panel: Window(flags: "POPUP|VISIBLE") @dock(dock: Fill)
+            status: Label(text: "Ready") @dock(dock: Bottom, size: 20)
+            name: TextInput(text: "Heisenberg", readonly: true) @dock(dock: Top, size: 30)
+        }
+        other: Window(title: "Other", size: (200, 100)) {
+            grid: GridLayout(margin: (0, 0, 0, 0), spacing: 0, column_policies: [Fixed(50)])
+            check: CheckBox(check_state: Checked) @grid(col: 0, row: 0)
+            ok: Button(text: "Ok") @grid(col: 1, row: 0)
+        }
+    "#;
+
+    let ui = DynamicUi::load(source).unwrap();
+    assert_eq!(ui.names().collect::<Vec<_>>(), vec!["window", "grid_panel", "status", "name", "other", "check", "ok", "dock", "grid"]);
+
+    let window = ui.control::<Window>("window").unwrap();
+    assert_eq!(window.text(), "Loaded");
+    assert_eq!(window.size(), (400, 200));
+
+    let name = ui.control::<TextInput>("name").unwrap();
+    assert_eq!(name.text(), "Heisenberg");
+    assert!(name.readonly());
+    assert_eq!((name.position(), name.size()), ((0, 0), (400, 30)));
+
+    let status = ui.control::<Label>("status").unwrap();
+    assert_eq!((status.position(), status.size()), ((0, 180), (400, 20)));
+
+    let panel = ui.handle("grid_panel").unwrap();
+    assert_eq!(ControlHandle::Hwnd(get_window_parent(panel.hwnd().unwrap())), window.handle);
+
+    let check = ui.control::<CheckBox>("check").unwrap();
+    let ok = ui.control::<Button>("ok").unwrap();
+    assert_eq!(check.check_state(), CheckBoxState::Checked);
+    assert_eq!(check.size(), (50, 100));
+    assert_eq!((ok.position(), ok.size()), ((50, 0), (150, 100)));
+
+    assert_eq!(ui.name_of(ok.handle), Some("ok"));
+    assert_eq!(ui.get("grid").map(|c| c.kind()), Some("GridLayout"));
+    assert!(ui.control::<Button>("check").is_none());
+    assert!(ui.handle("grid").is_none());
+    assert!(ui.get("missing").is_none());
+
+    match DynamicUi::load("window: Window { b: Button(text: 1) }") {
+        Err(NwgError::UiLoaderError(reason)) => assert_eq!(reason, "line 1, column 28: parameter `text`: expected a string, found an integer"),
+        _ => panic!("Expected a loader error")
+    }
+}
+
+#[test]
+#[cfg(feature = "bindings")]
+fn bind_text_input() {
+    let window = window("Bindings");
+    let mut name = TextInput::default();
+    let mut age = TextInput::default();
+    TextInput::builder().parent(&window).build(&mut name).unwrap();
+    TextInput::builder().parent(&window).build(&mut age).unwrap();
+
+    let name_prop = Property::new(String::from("Ferris"));
+    let age_prop = Property::new(10u32);
+    let name_binding = bind_text(&name, &name_prop);
+    let _age_binding = bind_text_with(&age, &age_prop, Converter::parse().validator(|age| *age <= 150));
+
+    // Initialized from the model
+    assert_eq!(name.text(), "Ferris");
+    assert_eq!(age.text(), "10");
+
+    // Model to control
+    name_prop.set("Gabriel".into());
+    age_prop.set(20);
+    assert_eq!(name.text(), "Gabriel");
+    assert_eq!(age.text(), "20");
+
+    // Control to model. Invalid values are not written to the model.
+    name.set_text("Bob");
+    assert_eq!(name_prop.get(), "Bob");
+
+    age.set_text(" 42");
+    assert_eq!(age_prop.get(), 42);
+    assert_eq!(age.text(), " 42");
+
+    age.set_text("42a");
+    assert_eq!(age_prop.get(), 42);
+    age.set_text("200");
+    assert_eq!(age_prop.get(), 42);
+
+    // Dropping the binding unlinks the control and the property
+    drop(name_binding);
+    assert_eq!(name_prop.observers_len(), 0);
+    name.set_text("Alice");
+    name_prop.set("Eve".into());
+    assert_eq!(name_prop.get(), "Eve");
+    assert_eq!(name.text(), "Alice");
+
+    // The property can be set and the binding dropped after the control was destroyed
+    let binding = bind_text(&name, &name_prop);
+    drop(name);
+    name_prop.set("Mallory".into());
+    drop(binding);
+}
+
+#[test]
+#[cfg(feature = "bindings")]
+fn bind_check_box() {
+    let window = window("Bindings");
+    let mut check = CheckBox::default();
+    CheckBox::builder().parent(&window).build(&mut check).unwrap();
+
+    let checked = Property::new(true);
+    let _binding = bind_checked(&check, &checked);
+    assert_eq!(check.check_state(), CheckBoxState::Checked);
+
+    {
+        use crate::win32::sys::um::winuser::BM_CLICK;
+        crate::win32::window_helper::send_message(check.handle.hwnd().unwrap(), BM_CLICK, 0, 0);
+    }
+    assert!(!checked.get());
+
+    checked.set(true);
+    assert_eq!(check.check_state(), CheckBoxState::Checked);
+}
+
+#[test]
+#[cfg(all(feature = "bindings", feature = "listbox"))]
+fn bind_list_box() {
+    let window = window("Bindings");
+    let mut list: ListBox<String> = ListBox::default();
+    ListBox::builder().parent(&window).build(&mut list).unwrap();
+
+    let items = Property::new(vec!["Alpha".to_string(), "Beta".to_string()]);
+    let selection = Property::new(None);
+    let _items_binding = bind_items(&list, &items);
+    let _selection_binding = bind_selection(&list, &selection);
+
+    assert_eq!(list.len(), 2);
+    assert_eq!(&list.collection()[..], &["Alpha", "Beta"]);
+
+    items.update(|items| items.push("Gamma".to_string()));
+    assert_eq!(list.len(), 3);
+    assert_eq!(list.collection().len(), 3);
+
+    selection.set(Some(2));
+    assert_eq!(list.selection_string(), Some("Gamma".to_string()));
+
+    // Selection changes from the user
+    list.set_selection(Some(0));
+    simulate_event(&list.handle, Event::OnListBoxSelect, EventData::NoData);
+    assert_eq!(selection.get(), Some(0));
+}
+
+#[test]
+#[cfg(feature = "executor")]
+fn spawn_local_in_handler() {
+    let window = window("Executor");
+    let mut button = Button::default();
+    let mut label = Label::default();
+    Button::builder().parent(&window).build(&mut button).unwrap();
+    Label::builder().parent(&window).text("Idle").build(&mut label).unwrap();
+
+    let label = Rc::new(label);
+    let label2 = label.clone();
+    let handler = full_bind_event_handler(&window.handle, move |evt, _data, _handle| {
+        if evt == Event::OnButtonClick {
+            let label = label2.clone();
+            spawn_local(async move {
+                let text = async { "Loaded".to_string() }.await;
+                label.set_text(&text);
+            });
+        }
+    });
+
+    // The future is polled by the message loop, not by the handler
+    button.click();
+    assert_eq!(label.text(), "Idle");
+
+    dispatch_thread_events();
+    assert_eq!(label.text(), "Loaded");
+
+    unbind_event_handler(&handler);
+}
+
+fn dialog_window(owner: &Window) -> Window {
+    let mut window = Window::default();
+    Window::builder()
+        .flags(WindowFlags::WINDOW)
+        .title("Dialog")
+        .parent(Some(owner))
+        .build(&mut window)
+        .expect("Failed to build dialog window");
+
+    window
+}
+
+/// Queues the command sent by `IsDialogMessage` when the user presses Enter (`IDOK`) or Esc (`IDCANCEL`)
+fn press_dialog_key(window: &ControlHandle, id: i32) {
+    use crate::win32::sys::um::winuser::WM_COMMAND;
+    crate::win32::window_helper::post_message(window.hwnd().unwrap(), WM_COMMAND, id as usize, 0);
+}
+
+#[test]
+fn modal_dialog_result() {
+    use crate::win32::sys::um::winuser::IDOK;
+    use std::cell::Cell;
+
+    let owner = window("Owner");
+    let dialog_window = dialog_window(&owner);
+    let mut ok = Button::default();
+    Button::builder().text("Ok").parent(&dialog_window).build(&mut ok).unwrap();
+
+    let dialog = Rc::new(Dialog::<u32>::new());
+    dialog.set_default_button(Some(&ok));
+
+    let owner_enabled = Rc::new(Cell::new(true));
+    let (dialog2, owner_enabled2, ok_handle, owner_hwnd) = (dialog.clone(), owner_enabled.clone(), ok.handle, owner.handle.hwnd().unwrap());
+    let handler = full_bind_event_handler(&dialog_window.handle, move |evt, _data, handle| {
+        if evt == Event::OnButtonClick && handle == ok_handle {
+            owner_enabled2.set(unsafe { crate::win32::window_helper::get_window_enabled(owner_hwnd) });
+            dialog2.end(7);
+        }
+    });
+
+    // Enter clicks the default button
+    press_dialog_key(&dialog_window.handle, IDOK);
+    assert_eq!(dialog.run_modal(&dialog_window, Some(&owner)), Some(7));
+
+    assert!(!owner_enabled.get());
+    assert!(owner.enabled());
+    assert!(!dialog_window.visible());
+    assert!(!dialog.running());
+
+    // The result is not kept between two runs
+    dialog_window.close();
+    assert_eq!(dialog.run_modal(&dialog_window, Some(&owner)), None);
+
+    unbind_event_handler(&handler);
+}
+
+#[test]
+fn modal_dialog_cancel_and_quit() {
+    use crate::win32::sys::um::winuser::{IDOK, IDCANCEL};
+
+    let owner = window("Owner");
+    let dialog_window = dialog_window(&owner);
+    let dialog = Dialog::<u32>::new();
+
+    let events = Rc::new(RefCell::new(Vec::new()));
+    let events2 = events.clone();
+    let handler = full_bind_event_handler(&dialog_window.handle, move |evt, _data, _handle| {
+        if evt == Event::OnKeyEnter {
+            stop_thread_dispatch();
+        }
+        events2.borrow_mut().push(evt);
+    });
+
+    // Without a cancel button, Esc cancels the dialog
+    press_dialog_key(&dialog_window.handle, IDCANCEL);
+    assert_eq!(dialog.run_modal(&dialog_window, Some(&owner)), None);
+    assert!(!events.borrow().contains(&Event::OnKeyEsc));
+    assert!(!dialog_window.visible());
+
+    // Stopping the dispatch in the dialog also stops the outer loop
+    press_dialog_key(&dialog_window.handle, IDOK);
+    assert_eq!(dialog.run_modal(&dialog_window, Some(&owner)), None);
+    assert!(events.borrow().contains(&Event::OnKeyEnter));
+    assert!(super::tree::take_quit());
+    assert!(owner.enabled());
+
+    unbind_event_handler(&handler);
+}
+
+
+/// A hand written dialog shown with `Dialog::show`
+mod confirm_dialog {
+    use crate::*;
+    use std::rc::Rc;
+    use std::ops::Deref;
+
+    #[derive(Default)]
+    pub struct ConfirmDialog {
+        window: Window,
+        yes: Button,
+        no: Button,
+        dialog: Dialog<bool>,
+    }
+
+    pub struct ConfirmDialogUi {
+        inner: Rc<ConfirmDialog>,
+        handler: EventHandler,
+    }
+
+    impl NativeUi<ConfirmDialogUi> for ConfirmDialog {
+        fn build_ui(mut data: ConfirmDialog) -> Result<ConfirmDialogUi, NwgError> {
+            Window::builder()
+                .flags(WindowFlags::WINDOW)
+                .title("Confirm")
+                .build(&mut data.window)?;
+
+            Button::builder().text("Yes").parent(&data.window).build(&mut data.yes)?;
+            Button::builder().text("No").parent(&data.window).build(&mut data.no)?;
+
+            let inner = Rc::new(data);
+            let evt_ui = Rc::downgrade(&inner);
+            let handler = full_bind_event_handler(&inner.window.handle, move |evt, _evt_data, handle| {
+                let ui = match evt_ui.upgrade() {
+                    Some(ui) => ui,
+                    None => { return; }
+                };
+
+                match evt {
+                    // The user presses Esc once the dialog is shown
+                    Event::OnInit => super::press_dialog_key(&ui.window.handle, crate::win32::sys::um::winuser::IDCANCEL),
+                    Event::OnButtonClick if handle == ui.yes => ui.dialog.end(true),
+                    Event::OnButtonClick if handle == ui.no => ui.dialog.end(false),
+                    _ => {}
+                }
+            });
+
+            Ok(ConfirmDialogUi { inner, handler })
+        }
+    }
+
+    impl DialogUi<bool> for ConfirmDialog {
+        fn dialog(&self) -> &Dialog<bool> { &self.dialog }
+        fn dialog_window(&self) -> &Window { &self.window }
+        fn default_button(&self) -> Option<&Button> { Some(&self.yes) }
+        fn cancel_button(&self) -> Option<&Button> { Some(&self.no) }
+    }
+
+    impl Deref for ConfirmDialogUi {
+        type Target = ConfirmDialog;
+
+        fn deref(&self) -> &ConfirmDialog {
+            &self.inner
+        }
+    }
+
+    impl Drop for ConfirmDialogUi {
+        fn drop(&mut self) {
+            unbind_event_handler(&self.handler);
+        }
+    }
+}
+
+#[test]
+fn show_native_ui_as_dialog() {
+    use self::confirm_dialog::ConfirmDialog;
+
+    let owner = window("Owner");
+
+    // Esc clicks the cancel button
+    let result = Dialog::show(ConfirmDialog::default(), Some(&owner)).expect("Failed to build dialog");
+    assert_eq!(result, Some(false));
+    assert!(owner.enabled());
+}
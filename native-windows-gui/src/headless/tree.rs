@@ -0,0 +1,281 @@
+/*!
+    The in-memory widget tree of the headless backend.
+
+    Every thread has its own tree, just like every thread has its own windows and message queue on Windows.
+    Tests running in parallel each get an isolated "desktop".
+
+    Functions in this module never call user code. The borrow of the tree is always released
+    before a callback is executed (see `window::send_message`).
+*/
+use super::sys::shared::windef::HWND;
+use super::sys::shared::minwindef::{UINT, WPARAM, LPARAM, LRESULT};
+use super::sys::shared::basetsd::UINT_PTR;
+use super::sys::shared::ntdef::HANDLE;
+use super::sys::um::winuser::{WS_VISIBLE, WS_DISABLED, LBS_MULTIPLESEL, LBS_EXTENDEDSEL, BST_UNCHECKED};
+use crate::controls::ControlHandle;
+use crate::{Event, EventData};
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
+use std::rc::Rc;
+use std::ptr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+pub(crate) type Callback = dyn Fn(Event, EventData, ControlHandle) -> ();
+pub(crate) type RawCallback = dyn Fn(HWND, UINT, WPARAM, LPARAM) -> Option<LRESULT>;
+
+/// Handles are never reused, even across threads
+static NEXT_HANDLE: AtomicUsize = AtomicUsize::new(0x10);
+
+/// Class specific state of a widget
+pub(crate) enum ControlState {
+    Generic,
+    Window { icon: HANDLE },
+    Button { check: WPARAM, images: [HANDLE; 3] },
+    Edit { limit: usize, modified: bool, selection: (u32, u32), password: u32, cue_banner: Vec<u16>, undo: Option<String> },
+    Static { images: [HANDLE; 3] },
+    ListBox { items: Vec<String>, selected: Vec<bool>, multi: bool },
+}
+
+pub(crate) struct Widget {
+    pub class_name: String,
+    pub text: String,
+    pub position: (i32, i32),
+    pub size: (i32, i32),
+    pub style: u32,
+    pub ex_style: u32,
+    pub parent: HWND,
+    pub children: Vec<HWND>,
+    pub font: HANDLE,
+    pub longs: HashMap<i32, isize>,
+    pub state: ControlState,
+    pub handlers: Vec<(UINT_PTR, Rc<Callback>)>,
+    pub raw_handlers: Vec<(UINT_PTR, Rc<RawCallback>)>,
+}
+
+impl Widget {
+
+    pub fn new(class_name: &str, text: &str, position: (i32, i32), size: (i32, i32), style: u32, ex_style: u32, parent: HWND) -> Widget {
+        let state = match &class_name.to_ascii_lowercase() as &str {
+            "nativewindowsguiwindow" => ControlState::Window { icon: ptr::null_mut() },
+            "button" => ControlState::Button { check: BST_UNCHECKED, images: [ptr::null_mut(); 3] },
+            "edit" => ControlState::Edit { limit: 32767, modified: false, selection: (0, 0), password: 0, cue_banner: Vec::new(), undo: None },
+            "static" => ControlState::Static { images: [ptr::null_mut(); 3] },
+            "listbox" => ControlState::ListBox { items: Vec::new(), selected: Vec::new(), multi: style & (LBS_MULTIPLESEL|LBS_EXTENDEDSEL) != 0 },
+            _ => ControlState::Generic
+        };
+
+        Widget {
+            class_name: class_name.to_string(),
+            text: text.to_string(),
+            position,
+            size,
+            style,
+            ex_style,
+            parent,
+            children: Vec::new(),
+            font: ptr::null_mut(),
+            longs: HashMap::new(),
+            state,
+            handlers: Vec::new(),
+            raw_handlers: Vec::new(),
+        }
+    }
+
+    /// Checks the class name the same way windows does (case insensitive)
+    pub fn is_class(&self, name: &str) -> bool {
+        self.class_name.eq_ignore_ascii_case(name)
+    }
+
+}
+
+struct Desktop {
+    widgets: HashMap<usize, Widget>,
+    focus: HWND,
+    messages: VecDeque<(HWND, UINT, WPARAM, LPARAM)>,
+    quit: bool,
+}
+
+thread_local! {
+    static DESKTOP: RefCell<Desktop> = RefCell::new(Desktop {
+        widgets: HashMap::new(),
+        focus: ptr::null_mut(),
+        messages: VecDeque::new(),
+        quit: false,
+    });
+}
+
+/// Run `f` with the tree of the current thread. Returns `None` if the thread is shutting down.
+fn desktop<R, F: FnOnce(&mut Desktop) -> R>(f: F) -> Option<R> {
+    DESKTOP.try_with(|d| f(&mut d.borrow_mut())).ok()
+}
+
+/// Add a new widget to the tree and returns its handle
+pub(crate) fn create(widget: Widget) -> HWND {
+    let handle = NEXT_HANDLE.fetch_add(1, Ordering::SeqCst) as HWND;
+    let parent = widget.parent;
+
+    desktop(|d| {
+        if let Some(p) = d.widgets.get_mut(&(parent as usize)) {
+            p.children.push(handle);
+        }
+
+        d.widgets.insert(handle as usize, widget);
+    });
+
+    handle
+}
+
+/// Remove a widget from the tree. The children of the widget are destroyed with it.
+/// Returns the widgets that were removed so that their callbacks are dropped outside of the tree borrow.
+pub(crate) fn destroy(handle: HWND) -> Vec<Widget> {
+    desktop(|d| {
+        let mut removed = Vec::new();
+        let mut stack = vec![handle];
+
+        if let Some(parent) = d.widgets.get(&(handle as usize)).map(|w| w.parent) {
+            if let Some(p) = d.widgets.get_mut(&(parent as usize)) {
+                p.children.retain(|&c| c != handle);
+            }
+        }
+
+        while let Some(h) = stack.pop() {
+            if let Some(w) = d.widgets.remove(&(h as usize)) {
+                stack.extend(w.children.iter().copied());
+                removed.push(w);
+            }
+
+            if d.focus == h {
+                d.focus = ptr::null_mut();
+            }
+        }
+
+        removed
+    }).unwrap_or_default()
+}
+
+/// Check if the handle points to a live widget
+pub(crate) fn exists(handle: HWND) -> bool {
+    desktop(|d| d.widgets.contains_key(&(handle as usize))).unwrap_or(false)
+}
+
+/// Execute `f` with a mutable reference to a widget. Returns `None` if the widget does not exist.
+/// `f` must not call any other function of this module.
+pub(crate) fn with_widget<R, F: FnOnce(&mut Widget) -> R>(handle: HWND, f: F) -> Option<R> {
+    desktop(|d| d.widgets.get_mut(&(handle as usize)).map(f)).flatten()
+}
+
+/// Move a widget under a new parent (or make it a top level window if `parent` is null)
+pub(crate) fn set_parent(handle: HWND, parent: HWND) {
+    desktop(|d| {
+        let old_parent = match d.widgets.get_mut(&(handle as usize)) {
+            Some(w) => ::std::mem::replace(&mut w.parent, parent),
+            None => { return; }
+        };
+
+        if let Some(p) = d.widgets.get_mut(&(old_parent as usize)) {
+            p.children.retain(|&c| c != handle);
+        }
+
+        if let Some(p) = d.widgets.get_mut(&(parent as usize)) {
+            p.children.push(handle);
+        }
+    });
+}
+
+/// Change the position of a widget in its parent z-order. If `after` is `None`, the widget is moved to the top.
+pub(crate) fn set_after(handle: HWND, after: Option<HWND>) {
+    desktop(|d| {
+        let parent = match d.widgets.get(&(handle as usize)) {
+            Some(w) => w.parent,
+            None => { return; }
+        };
+
+        if let Some(p) = d.widgets.get_mut(&(parent as usize)) {
+            p.children.retain(|&c| c != handle);
+            let index = after
+                .and_then(|a| p.children.iter().position(|&c| c == a))
+                .map(|i| i + 1)
+                .unwrap_or(0);
+
+            p.children.insert(index, handle);
+        }
+    });
+}
+
+/// Return the first level children of a widget in z-order
+pub(crate) fn children(handle: HWND) -> Vec<HWND> {
+    with_widget(handle, |w| w.children.clone()).unwrap_or_default()
+}
+
+/// Return all the children of a widget (recursively)
+pub(crate) fn descendants(handle: HWND) -> Vec<HWND> {
+    let mut out = Vec::new();
+    let mut stack = children(handle);
+    while let Some(h) = stack.pop() {
+        stack.extend(children(h));
+        out.push(h);
+    }
+
+    out
+}
+
+/// Return the parent of a widget, or null if the widget is a top level window or does not exist
+pub(crate) fn parent(handle: HWND) -> HWND {
+    with_widget(handle, |w| w.parent).unwrap_or(ptr::null_mut())
+}
+
+/// Same as `IsWindowVisible`. A widget is visible if it and all its parents have the `WS_VISIBLE` style.
+pub(crate) fn visible(handle: HWND) -> bool {
+    let mut current = handle;
+    while !current.is_null() {
+        match with_widget(current, |w| (w.style & WS_VISIBLE == WS_VISIBLE, w.parent)) {
+            Some((true, parent)) => { current = parent; },
+            _ => { return false; }
+        }
+    }
+
+    true
+}
+
+/// Same as `IsWindowEnabled`
+pub(crate) fn enabled(handle: HWND) -> bool {
+    with_widget(handle, |w| w.style & WS_DISABLED == 0).unwrap_or(false)
+}
+
+pub(crate) fn focus() -> HWND {
+    desktop(|d| d.focus).unwrap_or(ptr::null_mut())
+}
+
+pub(crate) fn set_focus(handle: HWND) {
+    desktop(|d| d.focus = handle);
+}
+
+/// Clone the event callbacks bound to a widget, along with the id of their event handler
+pub(crate) fn handlers(handle: HWND) -> Vec<(UINT_PTR, Rc<Callback>)> {
+    with_widget(handle, |w| w.handlers.clone()).unwrap_or_default()
+}
+
+/// Clone the raw event callbacks bound to a widget. The last bound callback comes first, like window subclasses.
+pub(crate) fn raw_handlers(handle: HWND) -> Vec<Rc<RawCallback>> {
+    with_widget(handle, |w| w.raw_handlers.iter().rev().map(|(_, cb)| cb.clone()).collect()).unwrap_or_default()
+}
+
+/// Add a message to the thread message queue
+pub(crate) fn post(handle: HWND, msg: UINT, w: WPARAM, l: LPARAM) {
+    desktop(|d| d.messages.push_back((handle, msg, w, l)));
+}
+
+/// Pop the next message of the thread message queue
+pub(crate) fn next_message() -> Option<(HWND, UINT, WPARAM, LPARAM)> {
+    desktop(|d| d.messages.pop_front()).flatten()
+}
+
+/// Request the current message loop to stop
+pub(crate) fn quit() {
+    desktop(|d| d.quit = true);
+}
+
+/// Check and reset the quit flag of the current message loop
+pub(crate) fn take_quit() -> bool {
+    desktop(|d| ::std::mem::replace(&mut d.quit, false)).unwrap_or(false)
+}
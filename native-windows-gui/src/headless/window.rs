@@ -0,0 +1,477 @@
+/*!
+Headless windowing base. Includes events dispatching and window creation.
+
+Events callbacks are stored on the widgets of the in-memory tree instead of window subclasses.
+Messages are processed in the same order as win32: raw handlers, nwg event handlers, then the class default procedure.
+*/
+use super::sys::shared::minwindef::{UINT, DWORD, WPARAM, LPARAM, LRESULT, HIWORD};
+use super::sys::shared::windef::HWND;
+use super::sys::shared::basetsd::UINT_PTR;
+use super::sys::um::winuser::{WS_OVERLAPPEDWINDOW, WS_VISIBLE, WS_CLIPCHILDREN, IDOK, IDCANCEL};
+use super::window_helper::{NOTICE_MESSAGE, NWG_INIT, NWG_TIMER_TICK, NWG_TIMER_STOP, NWG_SIMULATE};
+use super::tree::{self, Widget, Callback, RawCallback};
+use super::classes;
+use crate::controls::ControlHandle;
+use crate::{Event, EventData, NwgError};
+use std::{ptr, mem};
+use std::rc::Rc;
+use std::sync::atomic::{AtomicU32, AtomicUsize, Ordering};
+
+
+static TIMER_ID: AtomicU32 = AtomicU32::new(1);
+static NOTICE_ID: AtomicU32 = AtomicU32::new(1);
+static EVENT_HANDLER_ID: AtomicUsize = AtomicUsize::new(1);
+
+const NO_DATA: EventData = EventData::NoData;
+
+/**
+    An opaque structure that represent a window subclass hook.
+*/
+pub struct EventHandler {
+    handles: Vec<HWND>,
+    id: UINT_PTR
+}
+
+/**
+    An opaque structure that represent a window subclass hook.
+*/
+pub struct RawEventHandler {
+    handle: HWND,
+    handler_id: UINT_PTR
+}
+
+
+pub fn build_notice(parent: HWND) -> ControlHandle {
+    let id = NOTICE_ID.fetch_add(1, Ordering::SeqCst);
+    ControlHandle::Notice(parent, id)
+}
+
+/// Headless timers never tick by themselves
+pub unsafe fn build_timer(parent: HWND, _interval: u32, _stopped: bool) -> ControlHandle {
+    let id = TIMER_ID.fetch_add(1, Ordering::SeqCst);
+    ControlHandle::Timer(parent, id)
+}
+
+/**
+    Hook the window subclass with the default event dispatcher.
+    The hook is applied to the window and all it's children (recursively).
+
+    Returns a `EventHandler` that can be passed to `unbind_event_handler` to remove the callbacks.
+
+    This function will panic if `handle` is not a window handle.
+*/
+pub fn full_bind_event_handler<F>(handle: &ControlHandle, f: F) -> EventHandler
+    where F: Fn(Event, EventData, ControlHandle) -> () + 'static
+{
+    let hwnd = handle.hwnd().expect("Cannot bind control with an handle of type");
+
+    let callback: Rc<Callback> = Rc::new(f);
+    let id = EVENT_HANDLER_ID.fetch_add(1, Ordering::SeqCst);
+
+    let mut handles = vec![hwnd];
+    handles.extend(tree::descendants(hwnd));
+
+    for &h in handles.iter() {
+        tree::with_widget(h, |w| w.handlers.push((id, callback.clone())));
+    }
+
+    EventHandler { handles, id }
+}
+
+
+/**
+Hook the window subclass with the default event dispatcher.
+The hook is applied to the control and its parent. All common controls send their events to their parent.
+
+Arguments:
+    - handle: Handle to the main control to hook
+    - parent_handle: Parent to the main control.
+    - f: User event callback
+
+Returns a `EventHandler` that can be passed to `unbind_event_handler` to remove the callbacks.
+
+*/
+pub fn bind_event_handler<F>(handle: &ControlHandle, parent_handle: &ControlHandle, f: F) -> EventHandler
+    where F: Fn(Event, EventData, ControlHandle) -> () + 'static
+{
+    let hwnd = handle.hwnd().expect("Cannot bind control with an handle of type");
+    let parent_hwnd = parent_handle.hwnd().expect("Cannot bind control with an handle of type");
+
+    let callback: Rc<Callback> = Rc::new(f);
+    let id = EVENT_HANDLER_ID.fetch_add(1, Ordering::SeqCst);
+    let handles = vec![hwnd, parent_hwnd];
+
+    for &h in handles.iter() {
+        tree::with_widget(h, |w| w.handlers.push((id, callback.clone())));
+    }
+
+    EventHandler { handles, id }
+}
+
+
+/**
+    Free all associated callbacks with the event handler.
+
+    This function will panic if the handler was already freed.
+*/
+pub fn unbind_event_handler(handler: &EventHandler)
+{
+    let id = handler.id;
+
+    for &handle in handler.handles.iter() {
+        let callback = tree::with_widget(handle, |w| {
+            let index = w.handlers.iter().position(|(i, _)| *i == id)?;
+            Some(w.handlers.remove(index))
+        });
+
+        match callback {
+            // Dropped outside of the tree borrow
+            Some(Some(callback)) => mem::drop(callback),
+            _ => panic!("Parent of hander was either freed or is already unbound")
+        }
+    }
+}
+
+pub(crate) fn bind_raw_event_handler_inner<F>(handle: &ControlHandle, handler_id: UINT_PTR, f: F) -> Result<RawEventHandler, NwgError>
+    where F: Fn(HWND, UINT, WPARAM, LPARAM) -> Option<LRESULT> + 'static
+{
+    let handle = match handle {
+        &ControlHandle::Hwnd(h) => {
+            if has_raw_handler(&ControlHandle::Hwnd(h), handler_id) {
+                return Err(NwgError::events_binding(format!("Events id {} is already present on this", handler_id)))
+            }
+
+            let callback: Rc<RawCallback> = Rc::new(f);
+            tree::with_widget(h, |w| w.raw_handlers.push((handler_id, callback)));
+
+            h
+        },
+        htype => panic!("Cannot bind control with an handle of type {:?}.", htype)
+    };
+
+    Ok(RawEventHandler {
+        handle,
+        handler_id
+    })
+}
+
+/**
+
+Set a window subclass the uses the `process_raw_events` function of NWG.
+The subclass is only applied to the control itself and NOT the children.
+
+When assigning multiple callback to the same control, a different `id` must be specified for each call
+or otherwise, the old callback will be replaced by the new one. See `Label::hook_background_color` for example.
+
+Error:
+- If the event handler with the same ID is already bound, this function will return an Error. The `has_raw_handler` method can be used to check this.
+
+Panic:
+- If the `handle` parameter is not a window-like control
+- If the `handler_id` parameter is <= 0xFFFF
+*/
+pub fn bind_raw_event_handler<F>(handle: &ControlHandle, handler_id: UINT_PTR, f: F) -> Result<RawEventHandler, NwgError>
+where F: Fn(HWND, UINT, WPARAM, LPARAM) -> Option<LRESULT> + 'static
+{
+    if handler_id <= 0xFFFF {
+        panic!("handler_id <= 0xFFFF are reserved by NWG");
+    }
+
+    bind_raw_event_handler_inner(handle, handler_id, f)
+}
+
+
+/**
+    Check if a raw handler with the specified handler_id is currently bound on the control.
+    This function will panic if the handle parameter is not a window control.
+*/
+pub fn has_raw_handler(handle: &ControlHandle, handler_id: UINT_PTR) -> bool {
+    let handle = handle.hwnd().expect("This type of control cannot have a raw handler.");
+    tree::with_widget(handle, |w| w.raw_handlers.iter().any(|(id, _)| *id == handler_id)).unwrap_or(false)
+}
+
+/**
+    Remove the raw event handler from the associated window.
+    Calling unbind twice or trying to unbind an handler after destroying its parent will cause the function to panic.
+*/
+pub fn unbind_raw_event_handler(handler: &RawEventHandler) -> Result<(), NwgError>
+{
+    let handler_id = handler.handler_id;
+    let handle = handler.handle;
+
+    let callback = tree::with_widget(handle, |w| {
+        let index = w.raw_handlers.iter().position(|(id, _)| *id == handler_id)?;
+        Some(w.raw_handlers.remove(index))
+    });
+
+    match callback {
+        Some(Some(callback)) => {
+            mem::drop(callback);
+            Ok(())
+        },
+        _ => {
+            let err = format!(concat!(
+                "Could not fetch raw event handler #{:?}.",
+                "This can happen if the control ({:?}) was freed or",
+                "if this raw event handler was already unbound"
+            ), handler_id, handle);
+            Err(NwgError::EventsBinding(err))
+        }
+    }
+}
+
+/**
+    Dispatch an event to the event handlers of a control and of its parents. See the win32 `simulate_event`.
+
+    Headless: the event is dispatched by the widget tree.
+*/
+pub fn simulate_event(handle: &ControlHandle, evt: Event, data: EventData) -> EventData {
+    use crate::events::SimulatedEvent;
+
+    let hwnd = match handle {
+        &ControlHandle::Hwnd(h) | &ControlHandle::PopMenu(h, _) | &ControlHandle::Notice(h, _) |
+        &ControlHandle::Timer(h, _) | &ControlHandle::SystemTray(h) => h,
+        htype => panic!("Cannot simulate events on a control with an handle of type {:?}.", htype)
+    };
+
+    let mut simulated = SimulatedEvent::new(evt, data, *handle);
+    let mut target = hwnd;
+    while !target.is_null() {
+        send_message(target, NWG_SIMULATE, 0, &mut simulated as *mut SimulatedEvent as LPARAM);
+        target = tree::parent(target);
+    }
+
+    simulated.data
+}
+
+/**
+    High level function that handle the creation of custom window control or built in window control
+*/
+pub(crate) unsafe fn build_hwnd_control<'a>(
+    class_name: &'a str,
+    window_title: Option<&'a str>,
+    size: Option<(i32, i32)>,
+    pos: Option<(i32, i32)>,
+    flags: Option<DWORD>,
+    ex_flags: Option<DWORD>,
+    forced_flags: DWORD,
+    parent: Option<HWND>
+) -> Result<ControlHandle, NwgError>
+{
+    let parent = parent.unwrap_or(ptr::null_mut());
+    if !parent.is_null() && !tree::exists(parent) {
+        return Err(NwgError::initialization("Window creation failed"));
+    }
+
+    let flags = flags.unwrap_or(WS_OVERLAPPEDWINDOW | WS_CLIPCHILDREN | WS_VISIBLE) | forced_flags;
+    let widget = Widget::new(
+        class_name,
+        window_title.unwrap_or("New Window"),
+        pos.unwrap_or((0, 0)),
+        size.unwrap_or((500, 500)),
+        flags,
+        ex_flags.unwrap_or(0),
+        parent
+    );
+
+    let is_nwg_window = widget.is_class("NativeWindowsGuiWindow");
+    let handle = tree::create(widget);
+
+    // Same as `blank_window_proc` on WM_CREATE
+    if is_nwg_window {
+        tree::post(handle, NWG_INIT, 0, 0);
+    }
+
+    Ok(ControlHandle::Hwnd(handle))
+}
+
+/// There are no window classes to register in headless mode
+pub(crate) fn init_window_class() -> Result<(), NwgError> {
+    Ok(())
+}
+
+/**
+    Send a message to a widget and return the result. This is the headless `SendMessageW`.
+
+    The message goes through the raw event handlers first (last bound first), then through the nwg events handlers,
+    and finally through the default procedure of the widget class.
+*/
+pub(crate) fn send_message(hwnd: HWND, msg: UINT, w: WPARAM, l: LPARAM) -> LRESULT {
+    if !tree::exists(hwnd) {
+        return 0;
+    }
+
+    for callback in tree::raw_handlers(hwnd) {
+        if let Some(result) = callback(hwnd, msg, w, l) {
+            return result;
+        }
+    }
+
+    if !process_events(hwnd, msg, w, l) {
+        return 0;
+    }
+
+    classes::default_proc(hwnd, msg, w, l)
+}
+
+/**
+    Dispatch the message to the nwg events handlers bound to the widget.
+    Returns `false` if the default processing of the message should be skipped.
+*/
+fn process_events(hwnd: HWND, msg: UINT, w: WPARAM, l: LPARAM) -> bool {
+    use crate::events::*;
+    use super::sys::um::winuser::{WM_CLOSE, WM_COMMAND, WM_TIMER, WM_HSCROLL, WM_VSCROLL, WM_LBUTTONDOWN, WM_LBUTTONUP,
+      WM_RBUTTONDOWN, WM_RBUTTONUP, WM_SIZE, WM_MOVE, WM_MOUSEMOVE, WM_CONTEXTMENU, WM_EXITSIZEMOVE,
+      WM_ENTERSIZEMOVE, SIZE_MAXIMIZED, SIZE_MINIMIZED, WM_KEYDOWN, WM_KEYUP, WM_CHAR, WM_MOUSEWHEEL, WM_SYSKEYDOWN, WM_SYSKEYUP};
+    use std::char;
+
+    let callbacks = tree::handlers(hwnd);
+    if callbacks.is_empty() {
+        return true;
+    }
+
+    let base_handle = ControlHandle::Hwnd(hwnd);
+    let dispatch = |evt: Event, handle: ControlHandle, data: &dyn Fn() -> EventData| {
+        for (_, callback) in callbacks.iter() {
+            callback(evt, data(), handle);
+        }
+    };
+
+    match msg {
+        WM_KEYDOWN | WM_KEYUP | WM_SYSKEYDOWN | WM_SYSKEYUP => {
+            let evt = match msg {
+                WM_SYSKEYDOWN => Event::OnSysKeyPress,
+                WM_SYSKEYUP=> Event::OnSysKeyRelease,
+                WM_KEYDOWN => Event::OnKeyPress,
+                _ /* WM_KEYUP */ => Event::OnKeyRelease,
+            };
+
+            dispatch(evt, base_handle, &|| EventData::OnKey(w as u32));
+        },
+        WM_COMMAND => {
+            let child_handle: HWND = l as HWND;
+            let message = HIWORD(w as u32);
+            let class_name = tree::with_widget(child_handle, |w| w.class_name.to_ascii_lowercase());
+
+            let evt = match class_name.as_ref().map(|c| c as &str) {
+                Some("button") => button_commands(message),
+                Some("edit") => edit_commands(message),
+                Some("static") => static_commands(child_handle, message),
+                Some("listbox") => listbox_commands(message),
+                _ => match w as i32 {
+                    IDOK | IDCANCEL => {
+                        dispatch(no_class_name_commands(w), base_handle, &|| NO_DATA);
+                        return true;
+                    },
+                    _ => Event::Unknown
+                }
+            };
+
+            dispatch(evt, ControlHandle::Hwnd(child_handle), &|| NO_DATA);
+        },
+        WM_CONTEXTMENU => dispatch(Event::OnContextMenu, ControlHandle::Hwnd(w as HWND), &|| NO_DATA),
+        WM_SIZE => {
+            let evt = match w {
+                SIZE_MAXIMIZED => Event::OnWindowMaximize,
+                SIZE_MINIMIZED => Event::OnWindowMinimize,
+                _ => Event::OnResize
+            };
+
+            dispatch(evt, base_handle, &|| NO_DATA);
+        },
+        WM_MOUSEWHEEL => dispatch(Event::OnMouseWheel, base_handle, &|| EventData::OnMouseWheel(HIWORD(w as u32) as i16 as i32)),
+        WM_CHAR => dispatch(Event::OnChar, base_handle, &|| EventData::OnChar(char::from_u32(w as u32).unwrap_or('?'))),
+        WM_EXITSIZEMOVE => dispatch(Event::OnResizeEnd, base_handle, &|| NO_DATA),
+        WM_ENTERSIZEMOVE => dispatch(Event::OnResizeBegin, base_handle, &|| NO_DATA),
+        WM_TIMER => dispatch(Event::OnTimerTick, ControlHandle::Timer(hwnd, w as u32), &|| NO_DATA),
+        WM_MOVE => dispatch(Event::OnMove, base_handle, &|| NO_DATA),
+        WM_HSCROLL => dispatch(Event::OnHorizontalScroll, ControlHandle::Hwnd(l as HWND), &|| NO_DATA),
+        WM_VSCROLL => dispatch(Event::OnVerticalScroll, ControlHandle::Hwnd(l as HWND), &|| NO_DATA),
+        WM_MOUSEMOVE => dispatch(Event::OnMouseMove, base_handle, &|| NO_DATA),
+        WM_LBUTTONUP => dispatch(Event::OnMousePress(MousePressEvent::MousePressLeftUp), base_handle, &|| NO_DATA),
+        WM_LBUTTONDOWN => dispatch(Event::OnMousePress(MousePressEvent::MousePressLeftDown), base_handle, &|| NO_DATA),
+        WM_RBUTTONUP => dispatch(Event::OnMousePress(MousePressEvent::MousePressRightUp), base_handle, &|| NO_DATA),
+        WM_RBUTTONDOWN => dispatch(Event::OnMousePress(MousePressEvent::MousePressRightDown), base_handle, &|| NO_DATA),
+        NOTICE_MESSAGE => dispatch(Event::OnNotice, ControlHandle::Notice(hwnd, w as u32), &|| NO_DATA),
+        NWG_TIMER_STOP => dispatch(Event::OnTimerStop, ControlHandle::Timer(hwnd, w as u32), &|| NO_DATA),
+        NWG_TIMER_TICK => dispatch(Event::OnTimerTick, ControlHandle::Timer(hwnd, w as u32), &|| NO_DATA),
+        NWG_INIT => dispatch(Event::OnInit, base_handle, &|| NO_DATA),
+        NWG_SIMULATE => {
+            let simulated = unsafe { &mut *(l as *mut SimulatedEvent) };
+            for (id, callback) in callbacks.iter() {
+                simulated.dispatch(*id, &**callback);
+            }
+
+            return false;
+        },
+        WM_CLOSE => {
+            let mut should_exit = true;
+            let exit_ptr = &mut should_exit as *mut bool;
+            dispatch(Event::OnWindowClose, base_handle, &|| EventData::OnWindowClose(WindowCloseData { data: exit_ptr, _storage: None }));
+            return should_exit;
+        },
+        _ => {}
+    }
+
+    true
+}
+
+fn button_commands(m: u16) -> Event {
+    use super::sys::um::winuser::{BN_CLICKED, BN_DBLCLK};
+    match m {
+        BN_CLICKED => Event::OnButtonClick,
+        BN_DBLCLK => Event::OnButtonDoubleClick,
+        _ => Event::Unknown
+    }
+}
+
+fn edit_commands(m: u16) -> Event {
+    use super::sys::um::winuser::EN_CHANGE;
+
+    match m {
+        EN_CHANGE => Event::OnTextInput,
+        _ => Event::Unknown
+    }
+}
+
+fn static_commands(handle: HWND, m: u16) -> Event {
+    use super::sys::um::winuser::{STN_CLICKED, STN_DBLCLK};
+    use super::tree::ControlState;
+
+    let has_image = tree::with_widget(handle, |w| match &w.state {
+        ControlState::Static { images } => images.iter().any(|i| !i.is_null()),
+        _ => false
+    });
+
+    if has_image == Some(true) {
+        match m {
+            STN_CLICKED => Event::OnImageFrameClick,
+            STN_DBLCLK => Event::OnImageFrameDoubleClick,
+            _ => Event::Unknown
+        }
+    } else {
+        match m {
+            STN_CLICKED => Event::OnLabelClick,
+            STN_DBLCLK => Event::OnLabelDoubleClick,
+            _ => Event::Unknown
+        }
+    }
+}
+
+fn listbox_commands(m: u16) -> Event {
+    use super::sys::um::winuser::{LBN_SELCHANGE, LBN_DBLCLK};
+
+    match m {
+        LBN_SELCHANGE => Event::OnListBoxSelect,
+        LBN_DBLCLK => Event::OnListBoxDoubleClick,
+        _ => Event::Unknown
+    }
+}
+
+fn no_class_name_commands(m: usize) -> Event {
+    match m as i32 {
+        IDOK => Event::OnKeyEnter,
+        IDCANCEL => Event::OnKeyEsc,
+        _ => Event::Unknown,
+    }
+}
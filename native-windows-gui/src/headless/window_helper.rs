@@ -0,0 +1,261 @@
+/*!
+    Headless implementation of the window helpers. The functions have the same signatures as their win32 counterpart,
+    but they read and write the in-memory widget tree.
+
+    Widgets do not have a non client area, so the window size and the client size are always the same.
+*/
+use super::sys::shared::windef::{HFONT, HWND, HMENU};
+use super::sys::shared::minwindef::{UINT, WPARAM, LPARAM, LRESULT, MAKELONG};
+use super::sys::um::winuser::{WM_USER, WM_SIZE, WM_MOVE, WM_SETTEXT, WM_GETFONT, WM_SETFONT, GWL_STYLE, GWL_EXSTYLE,
+  WS_VISIBLE, WS_DISABLED, WS_MAXIMIZE, WS_MINIMIZE, SIZE_RESTORED, SIZE_MAXIMIZED, SIZE_MINIMIZED};
+use super::sys::ctypes::c_int;
+use super::base_helper::to_utf16;
+use super::{tree, window, high_dpi};
+use std::{ptr, mem};
+
+pub const NOTICE_MESSAGE: UINT = WM_USER+100;
+pub const NWG_INIT: UINT = WM_USER + 101;
+#[allow(unused)]
+pub const NWG_TRAY: UINT = WM_USER + 102;
+pub const NWG_TIMER_TICK: UINT = WM_USER + 103;
+pub const NWG_TIMER_STOP: UINT = WM_USER + 104;
+pub const NWG_SIMULATE: UINT = WM_USER + 105;
+
+
+/// Destroy a widget. Same as the win32 version, the children are removed from the window before it is destroyed
+pub fn destroy_window(hwnd: HWND) {
+    iterate_window_children(hwnd, |child| {
+        unsafe { set_window_visibility(child, false); }
+        tree::set_parent(child, ptr::null_mut());
+    });
+
+    // The callbacks of the widgets may hold rust values, drop them outside of the tree borrow
+    mem::drop(tree::destroy(hwnd));
+}
+
+/// Headless menus are never created
+pub fn destroy_menu_item(_parent: HMENU, _item_id: u32) {
+}
+
+/// Headless menus are never created
+pub fn destroy_menu(_menu: HMENU) {
+}
+
+/// Execute the callback for each first level children of the window
+pub fn iterate_window_children<F>(hwnd_parent: HWND, mut cb: F)
+    where F: FnMut(HWND) -> ()
+{
+    for child in tree::children(hwnd_parent) {
+        cb(child);
+    }
+}
+
+#[allow(unused)]
+pub fn window_valid(hwnd: HWND) -> bool {
+    tree::exists(hwnd)
+}
+
+#[allow(unused)]
+pub fn get_window_parent(hwnd: HWND) -> HWND {
+    tree::parent(hwnd)
+}
+
+#[allow(unused)]
+pub fn set_window_parent(hwnd: HWND, parent: HWND) {
+    tree::set_parent(hwnd, parent);
+}
+
+pub fn get_window_font(handle: HWND) -> HFONT {
+    send_message(handle, WM_GETFONT, 0, 0) as HFONT
+}
+
+pub fn maximize_window(handle: HWND) {
+    show_window_state(handle, WS_MAXIMIZE, SIZE_MAXIMIZED);
+}
+
+pub fn minimize_window(handle: HWND) {
+    show_window_state(handle, WS_MINIMIZE, SIZE_MINIMIZED);
+}
+
+pub fn restore_window(handle: HWND) {
+    show_window_state(handle, 0, SIZE_RESTORED);
+}
+
+/// Emulates `ShowWindow` with `SW_MAXIMIZE`, `SW_MINIMIZE` or `SW_RESTORE`
+fn show_window_state(handle: HWND, state: u32, size_type: WPARAM) {
+    let size = tree::with_widget(handle, |w| {
+        w.style = (w.style & !(WS_MAXIMIZE | WS_MINIMIZE)) | state | WS_VISIBLE;
+        w.size
+    });
+
+    if let Some((w, h)) = size {
+        send_message(handle, WM_SIZE, size_type, MAKELONG(w as u16, h as u16) as LPARAM);
+    }
+}
+
+/// Set the font of a window
+pub unsafe fn set_window_font(handle: HWND, font_handle: Option<HFONT>, redraw: bool) {
+    let font_handle = font_handle.unwrap_or(ptr::null_mut());
+    send_message(handle, WM_SETFONT, font_handle as WPARAM, redraw as LPARAM);
+}
+
+pub fn get_style(handle: HWND) -> UINT {
+    get_window_long(handle, GWL_STYLE) as UINT
+}
+
+#[allow(unused)]
+pub fn set_style(handle: HWND, style: u32) {
+    set_window_long(handle, GWL_STYLE, style as usize);
+}
+
+pub fn send_message(hwnd: HWND, msg: UINT, w: WPARAM, l: LPARAM) -> LRESULT {
+    window::send_message(hwnd, msg, w, l)
+}
+
+pub fn post_message(hwnd: HWND, msg: UINT, w: WPARAM, l: LPARAM) {
+    tree::post(hwnd, msg, w, l);
+}
+
+pub unsafe fn set_focus(handle: HWND) {
+    tree::set_focus(handle);
+}
+
+pub unsafe fn get_focus(handle: HWND) -> bool {
+    tree::focus() == handle
+}
+
+pub unsafe fn get_window_text(handle: HWND) -> String {
+    tree::with_widget(handle, |w| w.text.clone()).unwrap_or_default()
+}
+
+pub unsafe fn set_window_text<'a>(handle: HWND, text: &'a str) {
+    let text = to_utf16(text);
+    send_message(handle, WM_SETTEXT, 0, text.as_ptr() as LPARAM);
+}
+
+pub unsafe fn set_window_position(handle: HWND, x: i32, y: i32) {
+    let (x, y) = high_dpi::logical_to_physical(x, y);
+    let moved = tree::with_widget(handle, |w| mem::replace(&mut w.position, (x, y)) != (x, y));
+
+    if moved == Some(true) {
+        send_message(handle, WM_MOVE, 0, MAKELONG(x as u16, y as u16) as LPARAM);
+    }
+}
+
+pub unsafe fn set_window_after(handle: HWND, after: Option<HWND>) {
+    tree::set_after(handle, after);
+}
+
+pub unsafe fn get_window_position(handle: HWND) -> (i32, i32) {
+    let (x, y) = tree::with_widget(handle, |w| w.position).unwrap_or((0, 0));
+    high_dpi::physical_to_logical(x, y)
+}
+
+/// Returns the [left, top, right, bottom] restored rectangle of a window in screen pixels, and its show command
+pub unsafe fn get_window_placement(handle: HWND) -> ([i32; 4], u32) {
+    use super::sys::um::winuser::{SW_SHOWNORMAL, SW_SHOWMINIMIZED, SW_SHOWMAXIMIZED};
+
+    // Maximizing a headless window does not change its rectangle, so it is also the restored rectangle
+    let (position, size, style) = tree::with_widget(handle, |w| (w.position, w.size, w.style)).unwrap_or(((0, 0), (0, 0), 0));
+    let show_cmd = match style {
+        s if s & WS_MINIMIZE == WS_MINIMIZE => SW_SHOWMINIMIZED,
+        s if s & WS_MAXIMIZE == WS_MAXIMIZE => SW_SHOWMAXIMIZED,
+        _ => SW_SHOWNORMAL
+    };
+
+    ([position.0, position.1, position.0 + size.0, position.1 + size.1], show_cmd as u32)
+}
+
+/// Sets the [left, top, right, bottom] restored rectangle of a window in screen pixels, and shows it with `show_cmd`
+pub unsafe fn set_window_placement(handle: HWND, rect: [i32; 4], show_cmd: u32) {
+    use super::sys::um::winuser::{SW_SHOWMINIMIZED, SW_SHOWMAXIMIZED};
+
+    let [left, top, right, bottom] = rect;
+    tree::with_widget(handle, |w| {
+        w.position = (left, top);
+        w.size = ((right - left).max(0), (bottom - top).max(0));
+    });
+
+    send_message(handle, WM_MOVE, 0, MAKELONG(left as u16, top as u16) as LPARAM);
+
+    match show_cmd as c_int {
+        SW_SHOWMINIMIZED => show_window_state(handle, WS_MINIMIZE, SIZE_MINIMIZED),
+        SW_SHOWMAXIMIZED => show_window_state(handle, WS_MAXIMIZE, SIZE_MAXIMIZED),
+        _ => show_window_state(handle, 0, SIZE_RESTORED)
+    }
+}
+
+pub unsafe fn set_window_size(handle: HWND, w: u32, h: u32, _fix: bool) {
+    let (w, h) = high_dpi::logical_to_physical(w as i32, h as i32);
+    let resized = tree::with_widget(handle, |widget| mem::replace(&mut widget.size, (w, h)) != (w, h));
+
+    if resized == Some(true) {
+        send_message(handle, WM_SIZE, SIZE_RESTORED, MAKELONG(w as u16, h as u16) as LPARAM);
+    }
+}
+
+pub unsafe fn get_window_size(handle: HWND) -> (u32, u32) {
+    get_window_size_impl(handle, false)
+}
+
+#[allow(unused)]
+pub unsafe fn get_window_physical_size(handle: HWND) -> (u32, u32) {
+    get_window_size_impl(handle, true)
+}
+
+unsafe fn get_window_size_impl(handle: HWND, return_physical: bool) -> (u32, u32) {
+    let (w, h) = tree::with_widget(handle, |w| w.size).unwrap_or((0, 0));
+
+    let (w, h) = if return_physical {
+        (w, h)
+    } else {
+        high_dpi::physical_to_logical(w, h)
+    };
+
+    (w as u32, h as u32)
+}
+
+pub unsafe fn set_window_visibility(handle: HWND, visible: bool) {
+    tree::with_widget(handle, |w| match visible {
+        true => { w.style |= WS_VISIBLE; },
+        false => { w.style &= !WS_VISIBLE; }
+    });
+}
+
+pub unsafe fn get_window_visibility(handle: HWND) -> bool {
+    tree::visible(handle)
+}
+
+pub unsafe fn get_window_enabled(handle: HWND) -> bool {
+    tree::enabled(handle)
+}
+
+pub unsafe fn set_window_enabled(handle: HWND, enabled: bool) {
+    let old_style = get_window_long(handle, GWL_STYLE) as usize;
+    if enabled {
+        set_window_long(handle, GWL_STYLE, old_style&(!WS_DISABLED as usize) );
+    } else {
+        set_window_long(handle, GWL_STYLE, old_style|(WS_DISABLED as usize));
+    }
+}
+
+#[allow(unused)]
+pub unsafe fn get_window_class_name(handle: HWND) -> String {
+    tree::with_widget(handle, |w| w.class_name.clone()).unwrap_or_default()
+}
+
+pub fn get_window_long(handle: HWND, index: c_int) -> isize {
+    tree::with_widget(handle, |w| match index {
+        GWL_STYLE => w.style as isize,
+        GWL_EXSTYLE => w.ex_style as isize,
+        i => w.longs.get(&i).copied().unwrap_or(0)
+    }).unwrap_or(0)
+}
+
+pub fn set_window_long(handle: HWND, index: c_int, v: usize) {
+    tree::with_widget(handle, |w| match index {
+        GWL_STYLE => { w.style = v as u32; },
+        GWL_EXSTYLE => { w.ex_style = v as u32; },
+        i => { w.longs.insert(i, v as isize); }
+    });
+}
@@ -0,0 +1,581 @@
+use crate::controls::ControlHandle;
+use crate::win32::window::bind_raw_event_handler_inner;
+use crate::win32::window_helper as wh;
+use crate::NwgError;
+use crate::win32::sys::shared::windef::{HWND};
+use std::rc::Rc;
+use std::cell::RefCell;
+use std::ptr;
+
+
+/// The side of the parent a `DockLayout` child is docked to
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Dock {
+    Top,
+    Bottom,
+    Left,
+    Right,
+    Fill,
+}
+
+impl Dock {
+
+    fn horizontal(self) -> bool {
+        self == Dock::Left || self == Dock::Right
+    }
+
+}
+
+
+/// A control item in a DockLayout
+#[derive(Debug)]
+pub struct DockLayoutItem {
+    /// The handle to the control in the item
+    control: HWND,
+
+    /// The side of the layout the control is docked to
+    pub dock: Dock,
+
+    /// The height of a `Top`/`Bottom` item or the width of a `Left`/`Right` item. Ignored by `Fill` items.
+    /// Defaults to the size of the control when the item is created.
+    pub size: u32,
+
+    /// The item size cannot be reduced under this value when the layout is too small. Default to 0.
+    pub min_size: u32,
+}
+
+impl DockLayoutItem {
+
+    /// Initialize a new dock layout item
+    pub fn new<W: Into<ControlHandle>>(c: W, dock: Dock) -> DockLayoutItem {
+        let control = c.into().hwnd().expect("Child must be a window-like control (HWND handle)");
+        let (w, h) = unsafe { wh::get_window_size(control) };
+        let size = match dock.horizontal() {
+            true => w,
+            false => h
+        };
+
+        DockLayoutItem {
+            control,
+            dock,
+            size,
+            min_size: 0,
+        }
+    }
+
+    /// Set the size of the item
+    pub fn size(mut self, size: u32) -> DockLayoutItem {
+        self.size = size;
+        self
+    }
+
+    /// Set the minimum size of the item
+    pub fn min_size(mut self, size: u32) -> DockLayoutItem {
+        self.min_size = size;
+        self
+    }
+
+}
+
+
+/// A layout that docks widgets to the sides of their parent
+/// This is the inner data shared between the callback and the application
+pub struct DockLayoutInner {
+    /// The control that holds the layout
+    base: HWND,
+
+    /// The children of the control that fit in the layout
+    children: Vec<DockLayoutItem>,
+
+    /// The top, right, bottom, left space around the layout
+    margins: [u32; 4],
+
+    /// The space left after each docked item
+    spacing: u32,
+
+    /// The minimum size of the layout. Used if `base` is smaller than `min_size`.
+    min_size: [u32; 2],
+}
+
+/**
+A layout that docks widgets to the sides of their parent. Use it for the classic
+"toolbar on top, status bar at the bottom, tree on the left, content in the middle" window.
+NWG layouts use interior mutability to manage their controls.
+
+The docked children are placed in the order they were added: each `Top`, `Bottom`, `Left` or `Right` item takes
+a band of the remaining space on its side. The `Fill` items take what is left after every docked item was placed.
+
+If the layout is too small, the last docked items of each direction are shrunk first, down to their `min_size`.
+
+A DockLayout has the following properties:
+* margin - The top, right, bottom, left margins of the layout - (default: [0, 0, 0, 0])
+* spacing - The space left after each docked children. Use it to make room for a splitter. - (default: 0)
+* min_size - The minimum size of the layout - (default: [0, 0])
+
+```rust
+    use native_windows_gui as nwg;
+    fn layout(layout: &nwg::DockLayout, window: &nwg::Window, tree: &nwg::TextInput, status: &nwg::Label, content: &nwg::TextInput) {
+        nwg::DockLayout::builder()
+            .parent(window)
+            .spacing(4)
+            .child_item(nwg::DockLayoutItem::new(status, nwg::Dock::Bottom).size(25))
+            .child_item(nwg::DockLayoutItem::new(tree, nwg::Dock::Left).size(200).min_size(50))
+            .child(nwg::Dock::Fill, content)
+            .build(&layout);
+    }
+```
+*/
+#[derive(Clone)]
+pub struct DockLayout {
+    inner: Rc<RefCell<DockLayoutInner>>
+}
+
+impl DockLayout {
+
+    pub fn builder() -> DockLayoutBuilder {
+        let layout = DockLayoutInner {
+            base: ptr::null_mut(),
+            children: Vec::new(),
+            margins: [0, 0, 0, 0],
+            spacing: 0,
+            min_size: [0, 0],
+        };
+
+        DockLayoutBuilder { layout }
+    }
+
+    /**
+        Add a children control to the dock layout.
+        This is a simplified interface over `add_child_item`
+
+        Panic:
+        - If the layout is not initialized
+        - If the control is not window-like (HWND handle)
+    */
+    pub fn add_child<W: Into<ControlHandle>>(&self, dock: Dock, c: W) {
+        self.add_child_item(DockLayoutItem::new(c, dock));
+    }
+
+    /**
+    Add a children control to the dock layout.
+
+    Panic:
+        - If the layout is not initialized
+        - If the control is not window-like (HWND handle)
+    */
+    pub fn add_child_item(&self, i: DockLayoutItem) {
+        {
+            let mut inner = self.inner.borrow_mut();
+            if inner.base.is_null() {
+                panic!("DockLayout is not initialized");
+            }
+
+            inner.children.push(i);
+        }
+
+        self.fit();
+    }
+
+    /**
+        Remove the children control in the layout.
+        Note that the child control won't be hidden after being removed from the control.
+
+        This method won't do anything if the control is not in the layout.
+
+        Panic:
+        - If the layout is not initialized
+    */
+    pub fn remove_child<W: Into<ControlHandle>>(&self, c: W) {
+        {
+            let mut inner = self.inner.borrow_mut();
+            if inner.base.is_null() {
+                panic!("DockLayout is not initialized");
+            }
+
+            let handle = c.into().hwnd().expect("Control must be window-like (HWND handle)");
+            match inner.children.iter().position(|item| item.control == handle) {
+                Some(i) => { inner.children.remove(i); },
+                None => { return; }
+            }
+        }
+
+        self.fit();
+    }
+
+    /**
+        Change the side and the size of a docked control.
+
+        This method won't do anything if the control is not in the layout.
+
+        Panic:
+        - If the layout is not initialized
+    */
+    pub fn set_child_dock<W: Into<ControlHandle>>(&self, c: W, dock: Dock, size: u32) {
+        {
+            let mut inner = self.inner.borrow_mut();
+            if inner.base.is_null() {
+                panic!("DockLayout is not initialized");
+            }
+
+            let handle = c.into().hwnd().expect("Control must be window-like (HWND handle)");
+            match inner.children.iter_mut().find(|item| item.control == handle) {
+                Some(item) => {
+                    item.dock = dock;
+                    item.size = size;
+                },
+                None => { return; }
+            }
+        }
+
+        self.fit();
+    }
+
+    /**
+        Check if a window control is a children of the layout
+
+        Panic:
+        - If the layout is not initialized
+        - If the child is not a window-like control
+    */
+    pub fn has_child<W: Into<ControlHandle>>(&self, c: W) -> bool {
+        let inner = self.inner.borrow();
+        if inner.base.is_null() {
+            panic!("DockLayout is not initialized");
+        }
+
+        let handle = c.into().hwnd().expect("Children is not a window-like control (HWND handle)");
+        inner.children.iter().any(|c| c.control == handle )
+    }
+
+    /// Resize the layout as if the parent window had the specified size.
+    ///
+    ///  Panic:
+    ///   - The layout must have been successfully built otherwise this function will panic.
+    pub fn resize(&self, w: u32, h: u32) {
+        let inner = self.inner.borrow();
+        if inner.base.is_null() {
+            panic!("Dock layout is not bound to a parent control.")
+        }
+        self.update_layout(w, h);
+    }
+
+    /// Resize the layout to fit the parent window size
+    ///
+    /// Panic:
+    ///   - The layout must have been successfully built otherwise this function will panic.
+    pub fn fit(&self) {
+        let base = self.inner.borrow().base;
+        if base.is_null() {
+            panic!("Dock layout is not bound to a parent control.")
+        }
+
+        let (w, h) = unsafe { wh::get_window_size(base) };
+        self.update_layout(w, h);
+    }
+
+    /// Set the margins of the layout. The four values are in this order: top, right, bottom, left.
+    pub fn margin(&self, m: [u32; 4]) {
+        let mut inner = self.inner.borrow_mut();
+        inner.margins = m;
+    }
+
+    /// Set the size of the space left after each docked children.
+    pub fn spacing(&self, sp: u32) {
+        let mut inner = self.inner.borrow_mut();
+        inner.spacing = sp;
+    }
+
+    /// Sets the minimum size of the layout
+    pub fn min_size(&self, sz: [u32; 2]) {
+        let mut inner = self.inner.borrow_mut();
+        inner.min_size = sz;
+    }
+
+    fn update_layout(&self, width: u32, height: u32) {
+        let inner = self.inner.borrow();
+        if inner.base.is_null() || inner.children.is_empty() {
+            return;
+        }
+
+        let [min_w, min_h] = inner.min_size;
+        let [m_top, m_right, m_bottom, m_left] = inner.margins;
+        let width = width.max(min_w).saturating_sub(m_left + m_right);
+        let height = height.max(min_h).saturating_sub(m_top + m_bottom);
+        let container = (m_left as i32, m_top as i32, width, height);
+
+        let docks: Vec<(Dock, u32, u32)> = inner.children.iter().map(|item| (item.dock, item.size, item.min_size)).collect();
+        let rects = dock_rects(container, inner.spacing, &docks);
+
+        let mut last_handle = None;
+        for (item, (x, y, w, h)) in inner.children.iter().zip(rects) {
+            unsafe {
+                wh::set_window_position(item.control, x, y);
+                wh::set_window_size(item.control, w, h, false);
+                wh::set_window_after(item.control, last_handle)
+            }
+
+            last_handle = Some(item.control);
+        }
+    }
+}
+
+impl Default for DockLayout {
+
+    fn default() -> DockLayout {
+        let inner = DockLayoutInner {
+            base: ptr::null_mut(),
+            children: Vec::new(),
+            margins: [0, 0, 0, 0],
+            spacing: 0,
+            min_size: [0, 0],
+        };
+
+        DockLayout {
+            inner: Rc::new(RefCell::new(inner))
+        }
+    }
+
+}
+
+
+/// Builder for a `DockLayout` struct
+pub struct DockLayoutBuilder {
+    layout: DockLayoutInner
+}
+
+impl DockLayoutBuilder {
+
+    /// Set the layout parent. The handle must be a window object otherwise the function will panic
+    pub fn parent<W: Into<ControlHandle>>(mut self, p: W) -> DockLayoutBuilder {
+        self.layout.base = p.into().hwnd().expect("Parent must be HWND");
+        self
+    }
+
+    /// Add a children to the layout, docked to `dock`.
+    /// This is a shortcut over `child_item` for item that use the current control size.
+    /// The handle must be a window object otherwise the function will panic
+    pub fn child<W: Into<ControlHandle>>(mut self, dock: Dock, c: W) -> DockLayoutBuilder {
+        self.layout.children.push(DockLayoutItem::new(c, dock));
+        self
+    }
+
+    /// Add a children to the layout
+    pub fn child_item(mut self, item: DockLayoutItem) -> DockLayoutBuilder {
+        self.layout.children.push(item);
+        self
+    }
+
+    /// Set the margins of the layout. The four values are in this order: top, right, bottom, left.
+    pub fn margin(mut self, m: [u32; 4]) -> DockLayoutBuilder {
+        self.layout.margins = m;
+        self
+    }
+
+    /// Set the size of the space left after each docked children. Default value is 0.
+    pub fn spacing(mut self, sp: u32) -> DockLayoutBuilder {
+        self.layout.spacing = sp;
+        self
+    }
+
+    /// Sets the minimum size of the layout
+    pub fn min_size(mut self, sz: [u32; 2]) -> DockLayoutBuilder {
+        self.layout.min_size = sz;
+        self
+    }
+
+    /// Build the layout object and bind the callback.
+    pub fn build(self, layout: &DockLayout) -> Result<(), NwgError> {
+        use crate::win32::sys::um::winuser::WM_SIZE;
+        use crate::win32::sys::shared::minwindef::{HIWORD, LOWORD};
+
+        if self.layout.base.is_null() {
+            return Err(NwgError::layout_create("DockLayout does not have a parent."));
+        }
+
+        let (w, h) = unsafe { wh::get_window_size(self.layout.base) };
+        let base_handle = ControlHandle::Hwnd(self.layout.base);
+
+        {
+            let mut layout_inner = layout.inner.borrow_mut();
+            *layout_inner = self.layout;
+        }
+
+        // Initial layout update
+        layout.update_layout(w, h);
+
+        // Bind the event handler
+        let event_layout = layout.clone();
+        let cb = move |_h, msg, _w, l| {
+            if msg == WM_SIZE {
+                let size = l as u32;
+                let width = LOWORD(size) as i32;
+                let height = HIWORD(size) as i32;
+                let (w, h) = unsafe { crate::win32::high_dpi::physical_to_logical(width, height) };
+                DockLayout::update_layout(&event_layout, w as u32, h as u32);
+            }
+            None
+        };
+
+        /// Keep generating ids so that multiple layouts can be applied to the same parent
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        static DOCK_LAYOUT_ID: AtomicUsize = AtomicUsize::new(0xAFFF);
+        bind_raw_event_handler_inner(&base_handle, DOCK_LAYOUT_ID.fetch_add(1, Ordering::SeqCst), cb).unwrap();
+
+        Ok(())
+    }
+
+}
+
+
+/// Computes the size of the docked items on one axis. The last items are shrunk first, down to their min size.
+fn dock_sizes(available: u32, items: &[(u32, u32)]) -> Vec<u32> {
+    let mut sizes: Vec<u32> = items.iter().map(|&(size, min)| size.max(min)).collect();
+    let mut excess = sizes.iter().map(|&s| s as u64).sum::<u64>().saturating_sub(available as u64);
+
+    for (size, &(_, min)) in sizes.iter_mut().zip(items.iter()).rev() {
+        if excess == 0 {
+            break;
+        }
+
+        let shrink = ((*size - min) as u64).min(excess);
+        *size -= shrink as u32;
+        excess -= shrink;
+    }
+
+    sizes
+}
+
+/**
+    Computes the rectangle (x, y, width, height) of every item of a dock layout.
+    `items` are the (dock, size, min_size) of the children, in order.
+*/
+fn dock_rects(container: (i32, i32, u32, u32), spacing: u32, items: &[(Dock, u32, u32)]) -> Vec<(i32, i32, u32, u32)> {
+    let (mut x, mut y, mut width, mut height) = container;
+
+    // Sizes are computed ahead to know which items must be shrunk
+    let axis = |horizontal: bool, available: u32| -> Vec<u32> {
+        let docked: Vec<(u32, u32)> = items.iter()
+            .filter(|(dock, _, _)| *dock != Dock::Fill && dock.horizontal() == horizontal)
+            .map(|&(_, size, min)| (size, min))
+            .collect();
+
+        let spacing_total = (spacing as u64 * docked.len() as u64).min(available as u64) as u32;
+        dock_sizes(available - spacing_total, &docked)
+    };
+
+    let mut widths = axis(true, width).into_iter();
+    let mut heights = axis(false, height).into_iter();
+
+    let mut rects = vec![(0, 0, 0, 0); items.len()];
+    for (i, &(dock, _, _)) in items.iter().enumerate() {
+        let size = match dock {
+            Dock::Fill => { continue; },
+            Dock::Left | Dock::Right => widths.next().unwrap_or(0).min(width),
+            Dock::Top | Dock::Bottom => heights.next().unwrap_or(0).min(height),
+        };
+
+        rects[i] = match dock {
+            Dock::Top => (x, y, width, size),
+            Dock::Bottom => (x, y + (height - size) as i32, width, size),
+            Dock::Left => (x, y, size, height),
+            Dock::Right => (x + (width - size) as i32, y, size, height),
+            Dock::Fill => unreachable!(),
+        };
+
+        let used = size.saturating_add(spacing);
+        match dock {
+            Dock::Top => { y += used.min(height) as i32; height = height.saturating_sub(used); },
+            Dock::Bottom => { height = height.saturating_sub(used); },
+            Dock::Left => { x += used.min(width) as i32; width = width.saturating_sub(used); },
+            Dock::Right => { width = width.saturating_sub(used); },
+            Dock::Fill => {}
+        }
+    }
+
+    for (i, &(dock, _, _)) in items.iter().enumerate() {
+        if dock == Dock::Fill {
+            rects[i] = (x, y, width, height);
+        }
+    }
+
+    rects
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tool_window() {
+        let items = [(Dock::Top, 30, 0), (Dock::Bottom, 20, 0), (Dock::Left, 100, 0), (Dock::Fill, 0, 0)];
+        let rects = dock_rects((0, 0, 400, 300), 0, &items);
+
+        assert_eq!(rects, vec![
+            (0, 0, 400, 30),
+            (0, 280, 400, 20),
+            (0, 30, 100, 250),
+            (100, 30, 300, 250),
+        ]);
+    }
+
+    #[test]
+    fn order_matters() {
+        // The left item is added first so it takes the whole height
+        let items = [(Dock::Left, 100, 0), (Dock::Top, 30, 0), (Dock::Right, 50, 0), (Dock::Fill, 0, 0)];
+        let rects = dock_rects((10, 10, 400, 300), 0, &items);
+
+        assert_eq!(rects, vec![
+            (10, 10, 100, 300),
+            (110, 10, 300, 30),
+            (360, 40, 50, 270),
+            (110, 40, 250, 270),
+        ]);
+    }
+
+    #[test]
+    fn spacing() {
+        let items = [(Dock::Top, 30, 0), (Dock::Right, 100, 0), (Dock::Fill, 0, 0)];
+        let rects = dock_rects((0, 0, 400, 300), 5, &items);
+
+        assert_eq!(rects, vec![
+            (0, 0, 400, 30),
+            (300, 35, 100, 265),
+            (0, 35, 295, 265),
+        ]);
+    }
+
+    #[test]
+    fn shrink_last_items_first() {
+        let items = [(Dock::Left, 100, 0), (Dock::Left, 100, 40), (Dock::Right, 100, 60), (Dock::Fill, 0, 0)];
+        let rects = dock_rects((0, 0, 250, 100), 0, &items);
+        let widths: Vec<u32> = rects.iter().map(|r| r.2).collect();
+        assert_eq!(widths, vec![100, 90, 60, 0]);
+
+        let rects = dock_rects((0, 0, 150, 100), 0, &items);
+        let widths: Vec<u32> = rects.iter().map(|r| r.2).collect();
+        assert_eq!(widths, vec![50, 40, 60, 0]);
+    }
+
+    #[test]
+    fn too_small() {
+        let items = [(Dock::Top, 100, 100), (Dock::Bottom, 100, 100), (Dock::Fill, 0, 0)];
+        let rects = dock_rects((0, 0, 50, 150), 10, &items);
+
+        // Items never go outside of the container
+        for &(x, y, w, h) in rects.iter() {
+            assert!(x >= 0 && y >= 0);
+            assert!(x as u32 + w <= 50 && y as u32 + h <= 150);
+        }
+
+        assert_eq!(rects[2].3, 0);
+    }
+
+    #[test]
+    fn multiple_fill() {
+        let items = [(Dock::Fill, 0, 0), (Dock::Top, 10, 0), (Dock::Fill, 0, 0)];
+        let rects = dock_rects((0, 0, 100, 100), 0, &items);
+        assert_eq!(rects[0], (0, 10, 100, 90));
+        assert_eq!(rects[0], rects[2]);
+    }
+}
@@ -2,7 +2,8 @@ use crate::controls::ControlHandle;
 use crate::win32::window::bind_raw_event_handler_inner;
 use crate::win32::window_helper as wh;
 use crate::NwgError;
-use winapi::shared::windef::{HWND};
+use super::grid_solver::{GridSolver, GridSolverItem, GridRect, GridPolicy, GridAlign};
+use crate::win32::sys::shared::windef::{HWND};
 use std::rc::Rc;
 use std::cell::RefCell;
 use std::ptr;
@@ -24,7 +25,14 @@ pub struct GridLayoutItem {
     pub col_span: u32,
 
     /// The number row this item should span. Should be 1 for single row item.
-    pub row_span: u32
+    pub row_span: u32,
+
+    /// The horizontal alignment of the control in its cell. Defaults to `GridAlign::Fill`.
+    pub align: GridAlign,
+
+    /// The preferred size of the control. Used by the `GridPolicy::Auto` columns/rows and by the alignment.
+    /// Defaults to the size of the control when the item is created.
+    pub size_hint: [u32; 2],
 }
 
 impl GridLayoutItem {
@@ -32,13 +40,36 @@ impl GridLayoutItem {
     /// Initialize a new grid layout item
     pub fn new<W: Into<ControlHandle>>(c: W, col: u32, row: u32, col_span: u32, row_span: u32) -> GridLayoutItem {
         let control = c.into().hwnd().expect("Child must be a window-like control (HWND handle)");
+        let (w, h) = unsafe { wh::get_window_size(control) };
 
         GridLayoutItem {
             control,
             col,
             row,
             col_span,
-            row_span
+            row_span,
+            align: GridAlign::Fill,
+            size_hint: [w, h],
+        }
+    }
+
+    /// Set the horizontal alignment of the control in its cell
+    pub fn align(mut self, align: GridAlign) -> GridLayoutItem {
+        self.align = align;
+        self
+    }
+
+    /// Set the preferred size of the control
+    pub fn size_hint(mut self, size: [u32; 2]) -> GridLayoutItem {
+        self.size_hint = size;
+        self
+    }
+
+    fn solver_item(&self) -> GridSolverItem {
+        GridSolverItem {
+            align: self.align,
+            size_hint: self.size_hint,
+            ..GridSolverItem::new(self.col, self.row, self.col_span, self.row_span)
         }
     }
 
@@ -54,23 +85,8 @@ pub struct GridLayoutInner {
     /// The children of the control that fit in the layout
     children: Vec<GridLayoutItem>,
 
-    /// The top, right, bottom, left space around the layout
-    margins: [u32; 4],
-
-    /// The minimum size of the layout. Used if `base` is smaller than `min_size`.
-    min_size: [u32; 2],
-
-    /// The maximum size of the layout. Used if `base` is bigger than `min_size`.
-    max_size: [u32; 2],
-
-    /// The number of column. If None, compute the value from children.
-    column_count: Option<u32>,
-
-    /// The number of row. If None, compute the value from children.
-    row_count: Option<u32>, 
-
-    /// The spacing between controls
-    spacing: u32
+    /// The cell-size computation of the layout
+    solver: GridSolver,
 }
 
 /** 
@@ -84,6 +100,9 @@ A GridLayouts has the following properties:
 * max_size - The maximum size of the layout - (default: [u32::max_value(), u32::max_value()])
 * max_column - Number of columns - (default: None),
 * max_row - Number of rows - (default: None),
+* column_policy / row_policy - The sizing policy of a column/row: `Fixed(px)`, `Weight(n)` or `Auto` - (default: `Weight(1)`)
+
+The cell sizes are computed by a `GridSolver`. Use `preview` to get the children rectangles without moving them.
 
 ```rust
     use native_windows_gui as nwg;
@@ -94,7 +113,9 @@ A GridLayouts has the following properties:
             .spacing(5)
             .margin([0,0,0,0])
             .child(0, 0, item1)
-            .child_item(nwg::GridLayoutItem::new(item2, 1, 0, 2, 1))
+            .child_item(nwg::GridLayoutItem::new(item2, 1, 0, 2, 1).align(nwg::GridAlign::Center))
+            .column_policy(0, nwg::GridPolicy::Auto)
+            .column_policy(1, nwg::GridPolicy::Weight(3))
             .build(&layout);
     }
 ```
@@ -110,12 +131,7 @@ impl GridLayout {
         let layout = GridLayoutInner {
             base: ptr::null_mut(),
             children: Vec::new(),
-            margins: [5, 5, 5, 5],
-            spacing: 5,
-            min_size: [0, 0],
-            max_size: [u32::max_value(), u32::max_value()],
-            column_count: None,
-            row_count: None
+            solver: GridSolver::default(),
         };
 
         GridLayoutBuilder { layout }
@@ -130,16 +146,7 @@ impl GridLayout {
         - If the control is not window-like (HWND handle)
     */
     pub fn add_child<W: Into<ControlHandle>>(&self, col: u32, row: u32, c: W) {
-        let h = c.into().hwnd().expect("Child must be a window-like control (HWND handle)");
-        let item = GridLayoutItem {
-            control: h,
-            col,
-            row,
-            col_span: 1,
-            row_span: 1,
-        };
-
-        self.add_child_item(item);
+        self.add_child_item(GridLayoutItem::new(c, col, row, 1, 1));
     }
     
     /** 
@@ -352,114 +359,77 @@ impl GridLayout {
     /// Set the margins of the layout. The four values are in this order: top, right, bottom, left.
     pub fn margin(&self, m: [u32; 4]) {
         let mut inner = self.inner.borrow_mut();
-        inner.margins = m;
+        inner.solver.margins = m;
     }
 
     /// Set the size of the space between the children in the layout. Default value is 5.
     pub fn spacing(&self, sp: u32) {
         let mut inner = self.inner.borrow_mut();
-        inner.spacing = sp;
+        inner.solver.spacing = sp;
     }
 
     /// Sets the minimum size of the layout
     pub fn min_size(&self, sz: [u32; 2]) {
         let mut inner = self.inner.borrow_mut();
-        inner.min_size = sz;
+        inner.solver.min_size = sz;
     }
 
     /// Sets the maximum size of the layout
     pub fn max_size(&self, sz: [u32; 2]) {
         let mut inner = self.inner.borrow_mut();
-        inner.max_size = sz;
+        inner.solver.max_size = sz;
     }
 
     /// Set the number of column in the layout
     pub fn max_column(&self, count: Option<u32>) {
         let mut inner = self.inner.borrow_mut();
-        inner.column_count = count;
+        inner.solver.column_count = count;
     }
 
     /// Set the number of row in the layout
     pub fn max_row(&self, count: Option<u32>) {
         let mut inner = self.inner.borrow_mut();
-        inner.row_count = count;
+        inner.solver.row_count = count;
     }
 
-    fn update_layout(&self, mut width: u32, mut height: u32) -> () {
-        let inner = self.inner.borrow();
-        if inner.base.is_null() || inner.children.len() == 0 {
-            return;
-        }
-
-        let [m_top, m_right, m_bottom, m_left] = inner.margins;
-        let sp = inner.spacing;
-
-        let children = &inner.children;
-
-        let [min_w, min_h] = inner.min_size;
-        if width < min_w { width = min_w; }
-        if height < min_h { height = min_h; }
-
-        let [max_w, max_h] = inner.max_size;
-        if width > max_w { width = max_w; }
-        if height > max_h { height = max_h; }
-
-        let column_count = match inner.column_count {
-            Some(c) => c,
-            None => children.iter().map(|item| item.col + item.col_span).max().unwrap_or(1)
-        };
+    /// Set the sizing policy of a column. See `GridPolicy`.
+    pub fn column_policy(&self, col: u32, policy: GridPolicy) {
+        let mut inner = self.inner.borrow_mut();
+        inner.solver.set_column_policy(col, policy);
+    }
 
-        let row_count = match inner.row_count {
-            Some(c) => c,
-            None => children.iter().map(|item| item.row + item.row_span).max().unwrap_or(1)
-        };
+    /// Set the sizing policy of a row. See `GridPolicy`.
+    pub fn row_policy(&self, row: u32, policy: GridPolicy) {
+        let mut inner = self.inner.borrow_mut();
+        inner.solver.set_row_policy(row, policy);
+    }
 
-        if width < (m_right + m_left) + ((sp * 2) * column_count) {
-            return;
-        }
+    /// Returns the rectangles the children would have if the parent window had the specified size,
+    /// in the same order as they were added. Nothing is moved.
+    ///
+    /// Returns `None` if the size is too small to fit the layout margins and spacing.
+    pub fn preview(&self, w: u32, h: u32) -> Option<Vec<GridRect>> {
+        let inner = self.inner.borrow();
+        let items: Vec<GridSolverItem> = inner.children.iter().map(|item| item.solver_item()).collect();
+        inner.solver.solve(GridRect::new(0, 0, w, h), &items)
+    }
 
-        if height < (m_top + m_bottom) + ((sp * 2) * row_count) {
+    fn update_layout(&self, width: u32, height: u32) -> () {
+        let inner = self.inner.borrow();
+        if inner.base.is_null() || inner.children.len() == 0 {
             return;
         }
 
-        // Apply margins
-        width = width - m_right - m_left;
-        height = height - m_top - m_bottom;
-
-        // Apply spacing
-        width = width - ((sp * 2) * column_count);
-        height = height - ((sp * 2) * row_count);
-
-        let item_width = width / column_count;
-        let item_height = height / row_count;
-        let sp2 = sp * 2;
-
-        let mut columns = vec![item_width; column_count as usize];
-        let mut rows = vec![item_height; row_count as usize];
-        let extra_width = width - item_width * column_count;
-        if extra_width > 0 {
-            for x in &mut columns[0..(extra_width as usize)] {
-                *x += 1;
-            }
-        }
-        let extra_height = height - item_height * row_count;
-        if extra_height > 0 {
-            for x in &mut rows[0..(extra_height as usize)] {
-                *x += 1;
-            }
-        }
+        let rects = match self.preview(width, height) {
+            Some(rects) => rects,
+            None => { return; }
+        };
 
         let mut last_handle = None;
-        for item in inner.children.iter() {
-            let x: u32 = m_left + (sp + (sp2 * item.col)) + columns[0..(item.col as usize)].iter().sum::<u32>();
-            let y: u32 = m_top + (sp + (sp2 * item.row)) + rows[0..(item.row as usize)].iter().sum::<u32>();
-
-            let local_width: u32 = &columns[(item.col as usize)..((item.col + item.col_span) as usize)].iter().sum::<u32>() + (sp2 * (item.col_span - 1));
-            let local_height: u32 = &rows[(item.row as usize)..((item.row + item.row_span) as usize)].iter().sum::<u32>() + (sp2 * (item.row_span - 1));
-
+        for (item, rect) in inner.children.iter().zip(rects) {
             unsafe {
-                wh::set_window_position(item.control, x as i32, y as i32);
-                wh::set_window_size(item.control, local_width, local_height, false);
+                wh::set_window_position(item.control, rect.x, rect.y);
+                wh::set_window_size(item.control, rect.width, rect.height, false);
                 wh::set_window_after(item.control, last_handle)
             }
 
@@ -474,12 +444,7 @@ impl Default for GridLayout {
         let inner = GridLayoutInner {
             base: ptr::null_mut(),
             children: Vec::new(),
-            margins: [5, 5, 5, 5],
-            min_size: [0, 0],
-            max_size: [u32::max_value(), u32::max_value()],
-            column_count: None,
-            row_count: None,
-            spacing: 5,
+            solver: GridSolver::default(),
         };
 
         GridLayout {
@@ -507,15 +472,7 @@ impl GridLayoutBuilder {
     /// This is a shortcut over `child_item` for item with default span.
     /// The handle must be a window object otherwise the function will panic
     pub fn child<W: Into<ControlHandle>>(mut self, col: u32, row: u32, c: W) -> GridLayoutBuilder {
-        let h = c.into().hwnd().expect("Child must be HWND");
-        self.layout.children.push(GridLayoutItem {
-            control: h,
-            col,
-            row,
-            col_span: 1,
-            row_span: 1,
-        });
-
+        self.layout.children.push(GridLayoutItem::new(c, col, row, 1, 1));
         self
     }
 
@@ -528,58 +485,82 @@ impl GridLayoutBuilder {
 
     /// Set the margins of the layout. The four values are in this order: top, right, bottom, left.
     pub fn margin(mut self, m: [u32; 4]) -> GridLayoutBuilder {
-        self.layout.margins = m;
+        self.layout.solver.margins = m;
         self
     }
 
     /// Set the size of the space between the children in the layout. Default value is 5.
     pub fn spacing(mut self, sp: u32) -> GridLayoutBuilder {
-        self.layout.spacing = sp;
+        self.layout.solver.spacing = sp;
         self
     }
 
     /// Sets the minimum size of the layout
     pub fn min_size(mut self, sz: [u32; 2]) -> GridLayoutBuilder {
-        self.layout.min_size = sz;
+        self.layout.solver.min_size = sz;
         self
     }
 
     /// Sets the maximum size of the layout
     pub fn max_size(mut self, sz: [u32; 2]) -> GridLayoutBuilder {
-        self.layout.max_size = sz;
+        self.layout.solver.max_size = sz;
         self
     }
 
     /// Set the number of column in the layout
     pub fn max_column(mut self, count: Option<u32>) -> GridLayoutBuilder {
-        self.layout.column_count = count;
+        self.layout.solver.column_count = count;
         self
     }
 
     /// Set the number of row in the layout
     pub fn max_row(mut self, count: Option<u32>) -> GridLayoutBuilder {
-        self.layout.row_count = count;
+        self.layout.solver.row_count = count;
+        self
+    }
+
+    /// Set the sizing policy of a column. See `GridPolicy`.
+    pub fn column_policy(mut self, col: u32, policy: GridPolicy) -> GridLayoutBuilder {
+        self.layout.solver.set_column_policy(col, policy);
+        self
+    }
+
+    /// Set the sizing policy of a row. See `GridPolicy`.
+    pub fn row_policy(mut self, row: u32, policy: GridPolicy) -> GridLayoutBuilder {
+        self.layout.solver.set_row_policy(row, policy);
+        self
+    }
+
+    /// Set the sizing policy of the columns, starting from the first one
+    pub fn column_policies(mut self, policies: &[GridPolicy]) -> GridLayoutBuilder {
+        self.layout.solver.column_policies = policies.to_vec();
+        self
+    }
+
+    /// Set the sizing policy of the rows, starting from the first one
+    pub fn row_policies(mut self, policies: &[GridPolicy]) -> GridLayoutBuilder {
+        self.layout.solver.row_policies = policies.to_vec();
         self
     }
 
     /// Build the layout object and bind the callback.
     /// Children must only contains window object otherwise this method will panic.
     pub fn build(self, layout: &GridLayout) -> Result<(), NwgError> {
-        use winapi::um::winuser::WM_SIZE;
-        use winapi::shared::minwindef::{HIWORD, LOWORD};
+        use crate::win32::sys::um::winuser::WM_SIZE;
+        use crate::win32::sys::shared::minwindef::{HIWORD, LOWORD};
 
         if self.layout.base.is_null() {
             return Err(NwgError::layout_create("Gridlayout does not have a parent."));
         }
 
         // Checks if the layouts cell or row are outside max_column or max_row
-        if let Some(max_row) = self.layout.row_count {
+        if let Some(max_row) = self.layout.solver.row_count {
             if let Some(item) = self.layout.children.iter().find(|c| c.row >= max_row) {
                 return Err(NwgError::layout_create(format!("A layout item row is bigger or equal than the max number of row. {} >= {}", item.row, max_row)));
             }
         }
 
-        if let Some(max_column) = self.layout.column_count {
+        if let Some(max_column) = self.layout.solver.column_count {
             if let Some(item) = self.layout.children.iter().find(|c| c.col >= max_column) {
                 return Err(NwgError::layout_create(format!("A layout item column is bigger or equal than the max number of column. {} >= {}", item.col, max_column)));
             }
@@ -0,0 +1,592 @@
+/*!
+    The platform independent part of the `GridLayout`.
+
+    The solver computes the rectangles of the items of a grid from the size of the container. It does not
+    know anything about windows, so it can be used to preview a layout off-screen or to test it anywhere.
+*/
+
+
+/// A rectangle computed by the `GridSolver`. Also used to describe the container of the grid.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct GridRect {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl GridRect {
+
+    pub fn new(x: i32, y: i32, width: u32, height: u32) -> GridRect {
+        GridRect { x, y, width, height }
+    }
+
+    /// Returns `true` if the rectangle does not cover any surface
+    pub fn is_empty(&self) -> bool {
+        self.width == 0 || self.height == 0
+    }
+
+    /// Returns `true` if the two rectangles share some surface
+    pub fn intersects(&self, other: &GridRect) -> bool {
+        if self.is_empty() || other.is_empty() {
+            return false;
+        }
+
+        let (x1, y1) = (self.x as i64, self.y as i64);
+        let (x2, y2) = (other.x as i64, other.y as i64);
+
+        x1 < x2 + other.width as i64 && x2 < x1 + self.width as i64 &&
+        y1 < y2 + other.height as i64 && y2 < y1 + self.height as i64
+    }
+
+    /// Returns `true` if `other` is fully inside the rectangle
+    pub fn contains(&self, other: &GridRect) -> bool {
+        let (x1, y1) = (self.x as i64, self.y as i64);
+        let (x2, y2) = (other.x as i64, other.y as i64);
+
+        x2 >= x1 && y2 >= y1 &&
+        x2 + other.width as i64 <= x1 + self.width as i64 &&
+        y2 + other.height as i64 <= y1 + self.height as i64
+    }
+
+}
+
+
+/**
+    The sizing policy of a column or a row of a grid layout.
+
+    * Fixed: the column/row has a fixed size in pixels
+    * Weight: the column/row shares the remaining space with the other weighted columns/rows.
+      A column with a weight of 3 is three times as large as a column with a weight of 1.
+    * Auto: the column/row is as large as the biggest `size_hint` of its single-span items
+*/
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum GridPolicy {
+    Fixed(u32),
+    Weight(u32),
+    Auto,
+}
+
+// `#[default]` only supports unit variants
+impl Default for GridPolicy {
+    fn default() -> GridPolicy {
+        GridPolicy::Weight(1)
+    }
+}
+
+
+/**
+    The horizontal alignment of an item inside its cell.
+    With `Fill`, the item takes the whole cell. Otherwise, the item keeps the width of its `size_hint`.
+*/
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum GridAlign {
+    #[default]
+    Fill,
+    Left,
+    Center,
+    Right,
+}
+
+/// The position of an item in a `GridSolver`
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct GridSolverItem {
+    /// The column position of the item
+    pub col: u32,
+
+    /// The row position of the item
+    pub row: u32,
+
+    /// The number of column this item spans. A span of 0 is handled as 1.
+    pub col_span: u32,
+
+    /// The number of row this item spans. A span of 0 is handled as 1.
+    pub row_span: u32,
+
+    /// The horizontal alignment of the item in its cell
+    pub align: GridAlign,
+
+    /// The preferred [width, height] of the item. Used by the `Auto` policy and when the item is not aligned with `Fill`.
+    pub size_hint: [u32; 2],
+}
+
+impl GridSolverItem {
+
+    pub fn new(col: u32, row: u32, col_span: u32, row_span: u32) -> GridSolverItem {
+        GridSolverItem { col, row, col_span, row_span, align: GridAlign::Fill, size_hint: [0, 0] }
+    }
+
+}
+
+
+/**
+    Computes the cells of a grid layout. This is the algorithm used by `GridLayout`.
+
+    The container size is first clamped between `min_size` and `max_size`. The margins are then removed and the
+    remaining space is split between the columns and the rows. Each cell is surrounded by `spacing` on every side.
+
+    Columns and rows use the `Weight(1)` policy unless a policy is set in `column_policies`/`row_policies`.
+    The `Fixed` and `Auto` columns are sized first, in order, and the remaining space is shared between the weighted
+    columns. If the space is too small, the last columns are shrunk. When the space cannot be divided evenly between
+    the weighted columns, the extra pixels go to the first ones. Rows work the same way.
+
+    The number of columns and rows is computed from the items, unless `column_count` or `row_count` is set. Items
+    that are outside of the grid are collapsed to an empty rectangle at the edge of the grid, and spans are cut at
+    the last column/row.
+
+    ```rust
+    use native_windows_gui as nwg;
+
+    let solver = nwg::GridSolver { margins: [0, 0, 0, 0], spacing: 0, ..Default::default() };
+    let items = [nwg::GridSolverItem::new(0, 0, 1, 1), nwg::GridSolverItem::new(1, 0, 1, 1)];
+    let rects = solver.solve(nwg::GridRect::new(0, 0, 200, 100), &items).unwrap();
+
+    assert_eq!(rects[1], nwg::GridRect::new(100, 0, 100, 100));
+    ```
+*/
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GridSolver {
+    /// The top, right, bottom, left space around the layout
+    pub margins: [u32; 4],
+
+    /// The spacing between items
+    pub spacing: u32,
+
+    /// The minimum size of the layout. Used if the container is smaller than `min_size`.
+    pub min_size: [u32; 2],
+
+    /// The maximum size of the layout. Used if the container is bigger than `max_size`.
+    pub max_size: [u32; 2],
+
+    /// The number of column. If None, compute the value from the items.
+    pub column_count: Option<u32>,
+
+    /// The number of row. If None, compute the value from the items.
+    pub row_count: Option<u32>,
+
+    /// The sizing policy of each column, by index. Missing columns use `GridPolicy::Weight(1)`.
+    pub column_policies: Vec<GridPolicy>,
+
+    /// The sizing policy of each row, by index. Missing rows use `GridPolicy::Weight(1)`.
+    pub row_policies: Vec<GridPolicy>,
+}
+
+impl Default for GridSolver {
+
+    fn default() -> GridSolver {
+        GridSolver {
+            margins: [5, 5, 5, 5],
+            spacing: 5,
+            min_size: [0, 0],
+            max_size: [u32::max_value(), u32::max_value()],
+            column_count: None,
+            row_count: None,
+            column_policies: Vec::new(),
+            row_policies: Vec::new(),
+        }
+    }
+
+}
+
+impl GridSolver {
+
+    /// Returns the sizing policy of the column at `index`
+    pub fn column_policy(&self, index: u32) -> GridPolicy {
+        self.column_policies.get(index as usize).copied().unwrap_or_default()
+    }
+
+    /// Returns the sizing policy of the row at `index`
+    pub fn row_policy(&self, index: u32) -> GridPolicy {
+        self.row_policies.get(index as usize).copied().unwrap_or_default()
+    }
+
+    /// Sets the sizing policy of the column at `index`
+    pub fn set_column_policy(&mut self, index: u32, policy: GridPolicy) {
+        set_policy(&mut self.column_policies, index, policy);
+    }
+
+    /// Sets the sizing policy of the row at `index`
+    pub fn set_row_policy(&mut self, index: u32, policy: GridPolicy) {
+        set_policy(&mut self.row_policies, index, policy);
+    }
+
+    /// Returns the number of columns and rows used to lay out `items`
+    pub fn grid_size(&self, items: &[GridSolverItem]) -> (u32, u32) {
+        let column_count = match self.column_count {
+            Some(c) => c,
+            None => items.iter().map(|item| item.col.saturating_add(item.col_span.max(1))).max().unwrap_or(1)
+        };
+
+        let row_count = match self.row_count {
+            Some(c) => c,
+            None => items.iter().map(|item| item.row.saturating_add(item.row_span.max(1))).max().unwrap_or(1)
+        };
+
+        (column_count.max(1), row_count.max(1))
+    }
+
+    /**
+        Compute the rectangle of every item in `items`, in the same order. The rectangles are positioned relative
+        to the container origin (`container.x`, `container.y`).
+
+        Returns `None` if the container is too small to fit the margins and the spacing of the grid.
+    */
+    pub fn solve(&self, container: GridRect, items: &[GridSolverItem]) -> Option<Vec<GridRect>> {
+        let [m_top, m_right, m_bottom, m_left] = self.margins;
+        let [min_w, min_h] = self.min_size;
+        let [max_w, max_h] = self.max_size;
+        let sp = self.spacing as u64;
+        let sp2 = sp * 2;
+
+        let width = (container.width.max(min_w).min(max_w)) as u64;
+        let height = (container.height.max(min_h).min(max_h)) as u64;
+
+        let (column_count, row_count) = self.grid_size(items);
+
+        let column_policies: Vec<GridPolicy> = (0..column_count).map(|i| self.column_policy(i)).collect();
+        let auto_columns = auto_sizes(&column_policies, items, |item| (item.col, item.col_span, item.size_hint[0]));
+        let columns = split(width, m_left as u64 + m_right as u64, sp2, &column_policies, &auto_columns)?;
+
+        let row_policies: Vec<GridPolicy> = (0..row_count).map(|i| self.row_policy(i)).collect();
+        let auto_rows = auto_sizes(&row_policies, items, |item| (item.row, item.row_span, item.size_hint[1]));
+        let rows = split(height, m_top as u64 + m_bottom as u64, sp2, &row_policies, &auto_rows)?;
+
+        let rects = items.iter().map(|item| {
+            let (x, cell_width) = cell_range(&columns, item.col, item.col_span, sp);
+            let (y, height) = cell_range(&rows, item.row, item.row_span, sp);
+            let (x, width) = align(x, cell_width, item.align, item.size_hint[0] as u64);
+
+            GridRect {
+                x: container.x + (m_left as u64 + x) as i32,
+                y: container.y + (m_top as u64 + y) as i32,
+                width: width as u32,
+                height: height as u32,
+            }
+        });
+
+        Some(rects.collect())
+    }
+
+}
+
+fn set_policy(policies: &mut Vec<GridPolicy>, index: u32, policy: GridPolicy) {
+    let index = index as usize;
+    if policies.len() <= index {
+        policies.resize(index + 1, GridPolicy::default());
+    }
+
+    policies[index] = policy;
+}
+
+/// Returns the size of the biggest single-span item of each `Auto` column/row
+fn auto_sizes<F>(policies: &[GridPolicy], items: &[GridSolverItem], cell: F) -> Vec<u64>
+    where F: Fn(&GridSolverItem) -> (u32, u32, u32)
+{
+    let mut sizes = vec![0; policies.len()];
+    for item in items {
+        let (index, span, size) = cell(item);
+        let index = index as usize;
+        if span <= 1 && index < policies.len() && policies[index] == GridPolicy::Auto {
+            sizes[index] = sizes[index].max(size as u64);
+        }
+    }
+
+    sizes
+}
+
+/// Split `size` in cells after removing the margins and the spacing around each cell
+fn split(size: u64, margins: u64, sp2: u64, policies: &[GridPolicy], auto: &[u64]) -> Option<Vec<u64>> {
+    let reserved = margins + sp2 * policies.len() as u64;
+    if size < reserved {
+        return None;
+    }
+
+    let mut available = size - reserved;
+    let mut cells = vec![0; policies.len()];
+
+    for (i, policy) in policies.iter().enumerate() {
+        let cell = match *policy {
+            GridPolicy::Fixed(px) => px as u64,
+            GridPolicy::Auto => auto[i],
+            GridPolicy::Weight(_) => { continue; }
+        };
+
+        cells[i] = cell.min(available);
+        available -= cells[i];
+    }
+
+    let total_weight: u64 = policies.iter().map(|p| match *p { GridPolicy::Weight(w) => w as u64, _ => 0 }).sum();
+    if total_weight == 0 {
+        return Some(cells);
+    }
+
+    let mut extra = available;
+    for (i, policy) in policies.iter().enumerate() {
+        if let GridPolicy::Weight(w) = *policy {
+            cells[i] = available * w as u64 / total_weight;
+            extra -= cells[i];
+        }
+    }
+
+    for (i, policy) in policies.iter().enumerate() {
+        match *policy {
+            GridPolicy::Weight(w) if w > 0 && extra > 0 => {
+                cells[i] += 1;
+                extra -= 1;
+            },
+            _ => {}
+        }
+    }
+
+    Some(cells)
+}
+
+/// Returns the offset and the width of an item in a cell
+fn align(x: u64, cell_width: u64, align: GridAlign, hint: u64) -> (u64, u64) {
+    if hint == 0 {
+        return (x, cell_width);
+    }
+
+    let width = hint.min(cell_width);
+    match align {
+        GridAlign::Fill => (x, cell_width),
+        GridAlign::Left => (x, width),
+        GridAlign::Center => (x + (cell_width - width) / 2, width),
+        GridAlign::Right => (x + cell_width - width, width),
+    }
+}
+
+/// Returns the offset and the length of an item spanning `span` cells from `start`
+fn cell_range(cells: &[u64], start: u32, span: u32, sp: u64) -> (u64, u64) {
+    let count = cells.len();
+    let start = (start as usize).min(count);
+    let end = start.saturating_add(span.max(1) as usize).min(count);
+
+    let offset = cells[0..start].iter().sum::<u64>() + sp * 2 * start as u64;
+    match end - start {
+        0 => (offset, 0),
+        n => (offset + sp, cells[start..end].iter().sum::<u64>() + sp * 2 * (n as u64 - 1))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(col: u32, row: u32, col_span: u32, row_span: u32) -> GridSolverItem {
+        GridSolverItem::new(col, row, col_span, row_span)
+    }
+
+    fn solver(margin: u32, spacing: u32) -> GridSolver {
+        GridSolver { margins: [margin; 4], spacing, ..Default::default() }
+    }
+
+    #[test]
+    fn single_item() {
+        let rects = solver(5, 5).solve(GridRect::new(0, 0, 100, 50), &[item(0, 0, 1, 1)]).unwrap();
+        assert_eq!(rects, vec![GridRect::new(10, 10, 80, 30)]);
+    }
+
+    #[test]
+    fn container_offset() {
+        let rects = solver(0, 0).solve(GridRect::new(-20, 30, 100, 50), &[item(1, 1, 1, 1)]).unwrap();
+        assert_eq!(rects, vec![GridRect::new(30, 55, 50, 25)]);
+    }
+
+    #[test]
+    fn extra_pixels_go_to_first_cells() {
+        let items = [item(0, 0, 1, 1), item(1, 0, 1, 1), item(2, 0, 1, 1)];
+        let rects = solver(0, 0).solve(GridRect::new(0, 0, 101, 10), &items).unwrap();
+
+        let widths: Vec<u32> = rects.iter().map(|r| r.width).collect();
+        let xs: Vec<i32> = rects.iter().map(|r| r.x).collect();
+        assert_eq!(widths, vec![34, 34, 33]);
+        assert_eq!(xs, vec![0, 34, 68]);
+    }
+
+    #[test]
+    fn spans_include_inner_spacing() {
+        let items = [item(0, 0, 2, 1), item(0, 1, 1, 1), item(1, 1, 1, 1)];
+        let rects = solver(0, 5).solve(GridRect::new(0, 0, 200, 100), &items).unwrap();
+
+        // The spanning item covers both cells and the spacing between them
+        assert_eq!(rects[0].x, rects[1].x);
+        assert_eq!(rects[0].width, (rects[2].x + rects[2].width as i32 - rects[1].x) as u32);
+        assert_eq!(rects[0], GridRect::new(5, 5, 190, 40));
+    }
+
+    #[test]
+    fn zero_span_is_single_cell() {
+        let rects = solver(0, 0).solve(GridRect::new(0, 0, 100, 100), &[item(0, 0, 0, 0), item(1, 1, 1, 1)]).unwrap();
+        assert_eq!(rects[0], GridRect::new(0, 0, 50, 50));
+    }
+
+    #[test]
+    fn span_is_cut_at_grid_edge() {
+        let solver = GridSolver { column_count: Some(2), row_count: Some(2), ..solver(0, 0) };
+        let rects = solver.solve(GridRect::new(0, 0, 100, 100), &[item(1, 1, 5, 5)]).unwrap();
+        assert_eq!(rects[0], GridRect::new(50, 50, 50, 50));
+    }
+
+    #[test]
+    fn outside_items_are_empty() {
+        let solver = GridSolver { column_count: Some(2), row_count: Some(1), ..solver(0, 0) };
+        let rects = solver.solve(GridRect::new(0, 0, 100, 100), &[item(3, 0, 1, 1)]).unwrap();
+        assert!(rects[0].is_empty());
+        assert_eq!(rects[0].x, 100);
+    }
+
+    #[test]
+    fn min_and_max_size() {
+        let solver = GridSolver { min_size: [100, 0], max_size: [u32::max_value(), 40], ..solver(0, 0) };
+        let rects = solver.solve(GridRect::new(0, 0, 20, 400), &[item(0, 0, 1, 1)]).unwrap();
+        assert_eq!(rects[0], GridRect::new(0, 0, 100, 40));
+    }
+
+    #[test]
+    fn too_small() {
+        let items = [item(0, 0, 1, 1), item(1, 0, 1, 1)];
+        assert_eq!(solver(5, 5).solve(GridRect::new(0, 0, 29, 100), &items), None);
+        assert!(solver(5, 5).solve(GridRect::new(0, 0, 30, 100), &items).is_some());
+        assert_eq!(GridSolver { column_count: Some(0), ..solver(0, 0) }.grid_size(&items), (1, 1));
+    }
+
+    #[test]
+    fn overlapping_items_share_cells() {
+        let items = [item(0, 0, 2, 2), item(1, 1, 1, 1)];
+        let rects = solver(0, 0).solve(GridRect::new(0, 0, 100, 100), &items).unwrap();
+        assert!(rects[0].contains(&rects[1]));
+        assert!(rects[0].intersects(&rects[1]));
+    }
+
+    /// Exhaustively checks the invariants of the solver on small grids
+    #[test]
+    fn grid_invariants() {
+        for &(margin, spacing) in &[(0, 0), (3, 0), (0, 2), (5, 5)] {
+            let solver = GridSolver { column_count: Some(3), row_count: Some(3), ..solver(margin, spacing) };
+            for size in (0..120).step_by(7) {
+                let container = GridRect::new(0, 0, size, size + 3);
+
+                let mut items = Vec::new();
+                for col in 0..4 { for row in 0..4 { for col_span in 0..4 { for row_span in 0..3 {
+                    items.push(item(col, row, col_span, row_span));
+                }}}}
+
+                let rects = match solver.solve(container, &items) {
+                    Some(rects) => rects,
+                    None => {
+                        assert!(size < margin * 2 + spacing * 6 + 3);
+                        continue;
+                    }
+                };
+
+                assert_eq!(rects.len(), items.len());
+
+                for (i, (a, ia)) in rects.iter().zip(items.iter()).enumerate() {
+                    // Every item fits in the container
+                    assert!(container.contains(a), "{:?} outside of {:?}", a, container);
+
+                    for (b, ib) in rects[i+1..].iter().zip(items[i+1..].iter()) {
+                        let cells = |it: &GridSolverItem, count: u32| (it.col.min(count), (it.col + it.col_span.max(1)).min(count), it.row.min(count), (it.row + it.row_span.max(1)).min(count));
+                        let (ac0, ac1, ar0, ar1) = cells(ia, 3);
+                        let (bc0, bc1, br0, br1) = cells(ib, 3);
+                        let share_cells = ac0 < bc1 && bc0 < ac1 && ar0 < br1 && br0 < ar1;
+
+                        // Items only overlap when they share cells
+                        if a.intersects(b) {
+                            assert!(share_cells, "{:?} {:?} overlap", ia, ib);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn weighted_columns() {
+        let mut solver = solver(0, 0);
+        solver.set_column_policy(1, GridPolicy::Weight(3));
+
+        let items = [item(0, 0, 1, 1), item(1, 0, 1, 1)];
+        let rects = solver.solve(GridRect::new(0, 0, 400, 100), &items).unwrap();
+        assert_eq!(rects[0], GridRect::new(0, 0, 100, 100));
+        assert_eq!(rects[1], GridRect::new(100, 0, 300, 100));
+    }
+
+    #[test]
+    fn fixed_and_auto_policies() {
+        let mut solver = solver(0, 5);
+        solver.set_column_policy(0, GridPolicy::Auto);
+        solver.set_row_policy(0, GridPolicy::Fixed(20));
+
+        let label = GridSolverItem { size_hint: [60, 10], ..item(0, 1, 1, 1) };
+        let wide_label = GridSolverItem { size_hint: [500, 10], ..item(0, 0, 2, 1) };
+        let items = [label, wide_label, item(1, 1, 1, 1)];
+        let rects = solver.solve(GridRect::new(0, 0, 300, 200), &items).unwrap();
+
+        // Spanning items are ignored by `Auto`
+        assert_eq!(rects[0], GridRect::new(5, 35, 60, 160));
+        assert_eq!(rects[1], GridRect::new(5, 5, 290, 20));
+        assert_eq!(rects[2], GridRect::new(75, 35, 220, 160));
+    }
+
+    #[test]
+    fn fixed_columns_are_shrunk() {
+        let mut solver = solver(0, 0);
+        solver.column_policies = vec![GridPolicy::Fixed(80), GridPolicy::Fixed(80), GridPolicy::Weight(1)];
+
+        let items = [item(0, 0, 1, 1), item(1, 0, 1, 1), item(2, 0, 1, 1)];
+        let rects = solver.solve(GridRect::new(0, 0, 100, 10), &items).unwrap();
+        let widths: Vec<u32> = rects.iter().map(|r| r.width).collect();
+        assert_eq!(widths, vec![80, 20, 0]);
+    }
+
+    #[test]
+    fn item_alignment() {
+        let solver = solver(0, 0);
+        let aligned = |align| GridSolverItem { align, size_hint: [40, 10], ..item(0, 0, 1, 1) };
+
+        let rect = |align| solver.solve(GridRect::new(0, 0, 100, 50), &[aligned(align)]).unwrap()[0];
+        assert_eq!(rect(GridAlign::Fill), GridRect::new(0, 0, 100, 50));
+        assert_eq!(rect(GridAlign::Left), GridRect::new(0, 0, 40, 50));
+        assert_eq!(rect(GridAlign::Center), GridRect::new(30, 0, 40, 50));
+        assert_eq!(rect(GridAlign::Right), GridRect::new(60, 0, 40, 50));
+
+        // Items bigger than their cell are cut
+        let big = GridSolverItem { align: GridAlign::Center, size_hint: [400, 10], ..item(0, 0, 1, 1) };
+        assert_eq!(solver.solve(GridRect::new(0, 0, 100, 50), &[big]).unwrap()[0], GridRect::new(0, 0, 100, 50));
+    }
+
+    /// The columns always fill the grid when there is at least one weighted column
+    #[test]
+    fn policies_invariants() {
+        let policies = [GridPolicy::Fixed(0), GridPolicy::Fixed(13), GridPolicy::Weight(0), GridPolicy::Weight(1), GridPolicy::Weight(4), GridPolicy::Auto];
+
+        for &a in &policies { for &b in &policies { for &c in &policies {
+            let mut solver = solver(2, 3);
+            solver.column_policies = vec![a, b, c];
+
+            let items: Vec<GridSolverItem> = (0..3).map(|col| GridSolverItem { size_hint: [7 * col + 5, 0], ..item(col, 0, 1, 1) }).collect();
+            for width in (0..90).step_by(11) {
+                let container = GridRect::new(0, 0, width, 30);
+                let rects = match solver.solve(container, &items) {
+                    Some(rects) => rects,
+                    None => { assert!(width < 22); continue; }
+                };
+
+                let used: u32 = rects.iter().map(|r| r.width + 6).sum::<u32>() + 4;
+                let weighted = solver.column_policies.iter().any(|p| match p { GridPolicy::Weight(w) => *w > 0, _ => false });
+                assert!(used <= width);
+                if weighted {
+                    assert_eq!(used, width, "{:?}", solver.column_policies);
+                }
+
+                for pair in rects.windows(2) {
+                    assert!(!pair[0].intersects(&pair[1]));
+                    assert!(pair[0].x <= pair[1].x);
+                }
+            }
+        }}}
+    }
+}
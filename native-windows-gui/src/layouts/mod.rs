@@ -1,4 +1,6 @@
 mod grid_layout;
+mod grid_solver;
+mod dock_layout;
 
 #[cfg(feature = "flexbox")]
 mod flexbox_layout;
@@ -7,6 +9,8 @@ mod flexbox_layout;
 mod dyn_layout;
 
 pub use self::grid_layout::{GridLayout, GridLayoutInner, GridLayoutBuilder, GridLayoutItem};
+pub use self::grid_solver::{GridSolver, GridSolverItem, GridRect, GridPolicy, GridAlign};
+pub use self::dock_layout::{DockLayout, DockLayoutInner, DockLayoutBuilder, DockLayoutItem, Dock};
 
 #[cfg(feature = "flexbox")]
 pub use self::flexbox_layout::{FlexboxLayout, FlexboxLayoutBuilder, FlexboxLayoutItem, FlexboxLayoutChildrenMut, FlexboxLayoutChildren};
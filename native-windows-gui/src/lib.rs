@@ -22,13 +22,20 @@ pub use events::*;
 mod common_types;
 pub use common_types::*;
 
+#[cfg(not(feature = "headless"))]
 pub(crate) mod win32;
+
+#[cfg(feature = "headless")]
+#[path = "headless/mod.rs"]
+pub(crate) mod win32;
+
 pub use win32::{
  dispatch_thread_events, dispatch_thread_events_with_callback, stop_thread_dispatch, enable_visual_styles, init_common_controls, 
  window::{
      EventHandler, RawEventHandler,
      full_bind_event_handler, bind_event_handler, unbind_event_handler,
-     bind_raw_event_handler, has_raw_handler, unbind_raw_event_handler
+     bind_raw_event_handler, has_raw_handler, unbind_raw_event_handler,
+     simulate_event
  },
  message_box::*
 };
@@ -44,7 +51,14 @@ pub use win32::monitor::Monitor;
 pub use win32::cursor::GlobalCursor;
 
 #[cfg(feature="clipboard")]
-pub use win32::clipboard::{Clipboard, ClipboardFormat, ClipboardData};
+pub use win32::clipboard::{Clipboard, ClipboardFormat, ClipboardData, ClipboardWriter};
+
+#[cfg_attr(not(any(feature = "clipboard", feature = "drag-drop")), allow(dead_code))]
+mod clipboard_formats;
+pub use clipboard_formats::ClipboardHtml;
+
+#[cfg(feature="drag-drop")]
+pub use win32::drag_drop::{DragDropData, DragPayload, DropEffect};
 
 mod resources;
 pub use resources::*;
@@ -61,6 +75,24 @@ mod winnls;
 #[cfg(feature = "winnls")]
 pub use winnls::*;
 
+#[cfg(feature = "ui-loader")]
+mod ui_loader;
+
+#[cfg(feature = "ui-loader")]
+pub use ui_loader::*;
+
+#[cfg(feature = "bindings")]
+mod bindings;
+
+#[cfg(feature = "bindings")]
+pub use bindings::*;
+
+#[cfg(feature = "executor")]
+mod executor;
+
+#[cfg(feature = "executor")]
+pub use executor::spawn_local;
+
 /**
     A structure that implements this trait is considered a GUI structure. The structure will hold GUI components and possibly user data.
 
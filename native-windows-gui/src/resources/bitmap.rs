@@ -1,5 +1,5 @@
-use winapi::um::winnt::HANDLE;
-use winapi::um::winuser::IMAGE_BITMAP;
+use crate::win32::sys::um::winnt::HANDLE;
+use crate::win32::sys::um::winuser::IMAGE_BITMAP;
 use crate::win32::resources_helper as rh;
 use crate::{OemBitmap, OemImage, NwgError};
 use std::ptr;
@@ -157,8 +157,8 @@ impl Bitmap {
         Panics if the bitmap is not initialized
     */
     pub fn copy_as_icon(&self) -> crate::Icon {
-        use winapi::um::winuser::CreateIconIndirect;
-        use winapi::um::winuser::ICONINFO;
+        use crate::win32::sys::um::winuser::CreateIconIndirect;
+        use crate::win32::sys::um::winuser::ICONINFO;
 
         if self.handle.is_null() {
             panic!("Bitmap was not initialized");
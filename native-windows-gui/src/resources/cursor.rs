@@ -1,5 +1,5 @@
-use winapi::um::winnt::HANDLE;
-use winapi::um::winuser::IMAGE_CURSOR;
+use crate::win32::sys::um::winnt::HANDLE;
+use crate::win32::sys::um::winuser::IMAGE_CURSOR;
 use crate::win32::resources_helper as rh;
 use crate::{OemCursor, OemImage, NwgError};
 use std::ptr;
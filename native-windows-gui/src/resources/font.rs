@@ -1,5 +1,5 @@
-use winapi::shared::windef::HFONT;
-use winapi::um::winnt::HANDLE;
+use crate::win32::sys::shared::windef::HFONT;
+use crate::win32::sys::um::winnt::HANDLE;
 use crate::win32::resources_helper as rh;
 use crate::win32::base_helper::{to_utf16, from_utf16};
 use crate::NwgError;
@@ -133,7 +133,7 @@ impl Font {
         - Use `Font::families()` to return the available system font families
     */
     pub fn add_font(path: &str) -> bool {
-        use winapi::um::wingdi::AddFontResourceW;
+        use crate::win32::sys::um::wingdi::AddFontResourceW;
 
         unsafe {
             let path = to_utf16(path);
@@ -143,7 +143,7 @@ impl Font {
 
     /// Remove a font that was previously added by `Font::add_font`
     pub fn remove_font(path: &str) {
-        use winapi::um::wingdi::RemoveFontResourceW;
+        use crate::win32::sys::um::wingdi::RemoveFontResourceW;
 
         unsafe {
             let path = to_utf16(path);
@@ -156,7 +156,7 @@ impl Font {
         Send the handle to `remove_memory_font` at the end of your program to free the font from memory.
     */
     pub fn add_memory_font(bin: &mut [u8]) -> Result<MemFont, ()> {
-        use winapi::um::wingdi::AddFontMemResourceEx;
+        use crate::win32::sys::um::wingdi::AddFontMemResourceEx;
 
         let bin_len = bin.len();
         let mut num_fonts = 0;
@@ -178,7 +178,7 @@ impl Font {
 
     /// Remove a font that was previously added by `Font::add_memory_font`
     pub fn remove_memory_font(font: MemFont) {
-        use winapi::um::wingdi::RemoveFontMemResourceEx;
+        use crate::win32::sys::um::wingdi::RemoveFontMemResourceEx;
 
         unsafe {
             RemoveFontMemResourceEx(font.0);
@@ -188,9 +188,9 @@ impl Font {
     /// Returns all the font families loaded on the OS. 
     /// Probably pretty slow, so cache the value if possible
     pub fn families() -> Vec<String> {
-        use winapi::um::wingdi::{LOGFONTW, TEXTMETRICW, DEFAULT_CHARSET, EnumFontFamiliesExW};
-        use winapi::um::winuser::GetDC;
-        use winapi::shared::minwindef::{DWORD, LPARAM};
+        use crate::win32::sys::um::wingdi::{LOGFONTW, TEXTMETRICW, DEFAULT_CHARSET, EnumFontFamiliesExW};
+        use crate::win32::sys::um::winuser::GetDC;
+        use crate::win32::sys::shared::minwindef::{DWORD, LPARAM};
         use std::mem;
         
         let mut families = Vec::with_capacity(16);
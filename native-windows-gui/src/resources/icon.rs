@@ -1,5 +1,5 @@
-use winapi::um::winnt::HANDLE;
-use winapi::um::winuser::IMAGE_ICON;
+use crate::win32::sys::um::winnt::HANDLE;
+use crate::win32::sys::um::winuser::IMAGE_ICON;
 use crate::win32::resources_helper as rh;
 use crate::{OemImage, OemIcon, NwgError};
 use std::ptr;
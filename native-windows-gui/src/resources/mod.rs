@@ -16,6 +16,9 @@ mod color_dialog;
 #[cfg(feature = "font-dialog")]
 mod font_dialog;
 
+#[cfg(feature = "task-dialog")]
+mod task_dialog;
+
 #[cfg(feature = "image-list")]
 mod image_list;
 
@@ -40,6 +43,9 @@ pub use color_dialog::{ColorDialog, ColorDialogBuilder};
 #[cfg(feature = "font-dialog")]
 pub use font_dialog::{FontDialog, FontDialogBuilder};
 
+#[cfg(feature = "task-dialog")]
+pub use task_dialog::{TaskDialog, TaskDialogBuilder, TaskDialogHandle, TaskDialogButton, TaskDialogButtons, TaskDialogIcon, TaskDialogResult};
+
 #[cfg(feature = "image-list")]
 pub use image_list::{ImageList, ImageListBuilder};
 
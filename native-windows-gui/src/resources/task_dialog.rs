@@ -0,0 +1,657 @@
+use winapi::shared::windef::HWND;
+use winapi::shared::minwindef::{BOOL, UINT, WPARAM, LPARAM};
+use winapi::shared::basetsd::LONG_PTR;
+use winapi::shared::winerror::{HRESULT, S_OK, S_FALSE};
+use winapi::um::commctrl::{TASKDIALOGCONFIG, TASKDIALOG_BUTTON};
+use winapi::ctypes::c_int;
+use crate::win32::base_helper::{to_utf16, from_utf16};
+use crate::win32::window_helper as wh;
+use crate::{ControlHandle, NwgError};
+use std::{fmt, mem, ptr, cell::RefCell};
+
+
+bitflags! {
+    /**
+        The standard buttons of a task dialog
+
+        * OK: Ok button
+        * YES: Yes button
+        * NO: No button
+        * CANCEL: Cancel button
+        * RETRY: Retry button
+        * CLOSE: Close button
+    */
+    pub struct TaskDialogButtons: u32 {
+        const OK = 0x01;
+        const YES = 0x02;
+        const NO = 0x04;
+        const CANCEL = 0x08;
+        const RETRY = 0x10;
+        const CLOSE = 0x20;
+    }
+}
+
+/**
+    A button of a task dialog: a standard button or a custom button with its id.
+    If the user closes the dialog with the X button or ESC, `TaskDialogButton::Cancel` is returned.
+*/
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum TaskDialogButton {
+    Ok,
+    Cancel,
+    Retry,
+    Yes,
+    No,
+    Close,
+    Custom(i32),
+}
+
+impl TaskDialogButton {
+
+    fn from_id(id: i32) -> TaskDialogButton {
+        use winapi::um::winuser::{IDOK, IDCANCEL, IDRETRY, IDYES, IDNO, IDCLOSE};
+
+        match id {
+            IDOK => TaskDialogButton::Ok,
+            IDCANCEL => TaskDialogButton::Cancel,
+            IDRETRY => TaskDialogButton::Retry,
+            IDYES => TaskDialogButton::Yes,
+            IDNO => TaskDialogButton::No,
+            IDCLOSE => TaskDialogButton::Close,
+            id => TaskDialogButton::Custom(id),
+        }
+    }
+
+    fn id(self) -> i32 {
+        use winapi::um::winuser::{IDOK, IDCANCEL, IDRETRY, IDYES, IDNO, IDCLOSE};
+
+        match self {
+            TaskDialogButton::Ok => IDOK,
+            TaskDialogButton::Cancel => IDCANCEL,
+            TaskDialogButton::Retry => IDRETRY,
+            TaskDialogButton::Yes => IDYES,
+            TaskDialogButton::No => IDNO,
+            TaskDialogButton::Close => IDCLOSE,
+            TaskDialogButton::Custom(id) => id,
+        }
+    }
+
+}
+
+/**
+    The system icons of a task dialog
+*/
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum TaskDialogIcon {
+    None,
+    Warning,
+    Error,
+    Information,
+    Shield,
+}
+
+impl TaskDialogIcon {
+
+    fn resource(self) -> *const u16 {
+        use winapi::um::commctrl::{TD_WARNING_ICON, TD_ERROR_ICON, TD_INFORMATION_ICON, TD_SHIELD_ICON};
+
+        match self {
+            TaskDialogIcon::None => ptr::null(),
+            TaskDialogIcon::Warning => TD_WARNING_ICON,
+            TaskDialogIcon::Error => TD_ERROR_ICON,
+            TaskDialogIcon::Information => TD_INFORMATION_ICON,
+            TaskDialogIcon::Shield => TD_SHIELD_ICON,
+        }
+    }
+
+}
+
+/**
+    The choices of the user when a task dialog is closed
+
+    Members:
+    * `button`: The button that closed the dialog
+    * `radio`: The id of the selected radio button, or `None` if the dialog has no radio buttons or none is selected
+    * `verification`: The state of the verification checkbox
+*/
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct TaskDialogResult {
+    pub button: TaskDialogButton,
+    pub radio: Option<i32>,
+    pub verification: bool,
+}
+
+/**
+    A reference to an open task dialog. Passed to the task dialog callbacks to update the dialog while it is displayed.
+*/
+pub struct TaskDialogHandle {
+    hwnd: HWND,
+}
+
+impl TaskDialogHandle {
+
+    /// Returns the handle of the dialog window
+    pub fn handle(&self) -> ControlHandle {
+        ControlHandle::Hwnd(self.hwnd)
+    }
+
+    /// Sets the content text of the dialog
+    pub fn set_content(&self, text: &str) {
+        use winapi::um::commctrl::TDE_CONTENT;
+        self.set_element_text(TDE_CONTENT, text);
+    }
+
+    /// Sets the main instruction of the dialog
+    pub fn set_main_instruction(&self, text: &str) {
+        use winapi::um::commctrl::TDE_MAIN_INSTRUCTION;
+        self.set_element_text(TDE_MAIN_INSTRUCTION, text);
+    }
+
+    /// Sets the footer text of the dialog. Does nothing if the dialog was created without a footer.
+    pub fn set_footer(&self, text: &str) {
+        use winapi::um::commctrl::TDE_FOOTER;
+        self.set_element_text(TDE_FOOTER, text);
+    }
+
+    /// Sets the expanded information of the dialog. Does nothing if the dialog was created without expanded information.
+    pub fn set_expanded_information(&self, text: &str) {
+        use winapi::um::commctrl::TDE_EXPANDED_INFORMATION;
+        self.set_element_text(TDE_EXPANDED_INFORMATION, text);
+    }
+
+    /// Sets the position of the progress bar. The default range is 0 to 100.
+    pub fn set_progress(&self, position: u32) {
+        use winapi::um::commctrl::TDM_SET_PROGRESS_BAR_POS;
+        wh::send_message(self.hwnd, TDM_SET_PROGRESS_BAR_POS, position as WPARAM, 0);
+    }
+
+    /// Sets the range of the progress bar
+    pub fn set_progress_range(&self, min: u16, max: u16) {
+        use winapi::um::commctrl::TDM_SET_PROGRESS_BAR_RANGE;
+        use winapi::shared::minwindef::MAKELONG;
+        wh::send_message(self.hwnd, TDM_SET_PROGRESS_BAR_RANGE, 0, MAKELONG(min, max) as LPARAM);
+    }
+
+    /// Switches the progress bar between a marquee (an animation with no position) and a regular progress bar
+    pub fn set_marquee(&self, marquee: bool) {
+        use winapi::um::commctrl::{TDM_SET_MARQUEE_PROGRESS_BAR, TDM_SET_PROGRESS_BAR_MARQUEE};
+        wh::send_message(self.hwnd, TDM_SET_MARQUEE_PROGRESS_BAR, marquee as WPARAM, 0);
+        wh::send_message(self.hwnd, TDM_SET_PROGRESS_BAR_MARQUEE, marquee as WPARAM, 0);
+    }
+
+    /// Clicks a button of the dialog, as if the user clicked it. `on_button_click` is called.
+    pub fn click_button(&self, button: TaskDialogButton) {
+        use winapi::um::commctrl::TDM_CLICK_BUTTON;
+        wh::send_message(self.hwnd, TDM_CLICK_BUTTON, button.id() as WPARAM, 0);
+    }
+
+    /// Enables or disables a button of the dialog
+    pub fn set_button_enabled(&self, button: TaskDialogButton, enabled: bool) {
+        use winapi::um::commctrl::TDM_ENABLE_BUTTON;
+        wh::send_message(self.hwnd, TDM_ENABLE_BUTTON, button.id() as WPARAM, enabled as LPARAM);
+    }
+
+    fn set_element_text(&self, element: u32, text: &str) {
+        use winapi::um::commctrl::TDM_SET_ELEMENT_TEXT;
+
+        let text = to_utf16(text);
+        wh::send_message(self.hwnd, TDM_SET_ELEMENT_TEXT, element as WPARAM, text.as_ptr() as LPARAM);
+    }
+
+}
+
+impl fmt::Debug for TaskDialogHandle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "TaskDialogHandle({:?})", self.hwnd)
+    }
+}
+
+
+type ButtonCallback = Box<dyn FnMut(&TaskDialogHandle, TaskDialogButton) -> bool>;
+type HyperlinkCallback = Box<dyn FnMut(&TaskDialogHandle, &str)>;
+type TimerCallback = Box<dyn FnMut(&TaskDialogHandle, u32)>;
+
+/// The callbacks of a task dialog and the state they need
+#[derive(Default)]
+struct TaskDialogEvents {
+    marquee: bool,
+    button: Option<ButtonCallback>,
+    hyperlink: Option<HyperlinkCallback>,
+    timer: Option<TimerCallback>,
+}
+
+/**
+    A task dialog: a message box with custom buttons, command links, radio buttons, a verification checkbox,
+    expandable details, a footer, a progress bar and hyperlinks.
+
+    The dialog is displayed with `run`, which blocks until the user closes it and returns the choices of the user.
+    The callbacks are called while the dialog is displayed and receive a `TaskDialogHandle` to update it.
+
+    Task dialogs require version 6 of the common controls. Call `enable_visual_styles` or use a manifest,
+    otherwise `run` returns an error.
+
+    Requires the `task-dialog` feature.
+
+    **Builder parameters:**
+      * `title`:                  The title of the dialog window
+      * `main_instruction`:       The large text at the top of the dialog
+      * `content`:                The text under the main instruction. With `hyperlinks`, `<a href="...">text</a>` links are supported.
+      * `icon`:                   The icon next to the main instruction
+      * `buttons`:                The standard buttons of the dialog. If there are no buttons at all, an Ok button is added.
+      * `button`:                 A custom button and its id. The id must be 100 or greater.
+      * `command_links`:          Display the custom buttons as command links. The text after the first new line is the description of the link.
+      * `default_button`:         The button selected by default
+      * `radio`:                  A radio button and its id
+      * `default_radio`:          The id of the radio button selected by default. By default, the first radio button is selected.
+      * `verification`:           The text of the verification checkbox
+      * `verification_checked`:   The initial state of the verification checkbox
+      * `expanded_information`:   Text that is hidden until the user expands it
+      * `expanded`:               Whether the expanded information is displayed by default
+      * `footer`:                 The text at the bottom of the dialog
+      * `footer_icon`:            The icon next to the footer
+      * `progress_bar`:           Display a progress bar
+      * `marquee`:                Display a marquee progress bar
+      * `hyperlinks`:             Enable the links in `content`, `expanded_information` and `footer`
+      * `cancelable`:             Allow the user to close the dialog with ESC or the X button. Defaults to `true`.
+      * `on_button_click`:        Called when a button is clicked. Return `false` to keep the dialog open.
+      * `on_hyperlink_click`:     Called with the `href` of a clicked link
+      * `on_timer`:               Called about every 200 ms with the time in milliseconds since the dialog was opened
+
+    ```rust
+    use native_windows_gui as nwg;
+
+    const SAVE: i32 = 100;
+    const DISCARD: i32 = 101;
+
+    fn ask_save(window: &nwg::Window) -> Result<bool, nwg::NwgError> {
+        let mut dialog = nwg::TaskDialog::default();
+        nwg::TaskDialog::builder()
+            .title("Editor")
+            .main_instruction("Save the changes?")
+            .content("The document was modified. See <a href=\"help\">help</a>.")
+            .hyperlinks(true)
+            .icon(nwg::TaskDialogIcon::Warning)
+            .button(SAVE, "Save\nThe changes are written to the file")
+            .button(DISCARD, "Discard")
+            .buttons(nwg::TaskDialogButtons::CANCEL)
+            .command_links(true)
+            .verification("Do not ask again")
+            .on_hyperlink_click(|_dialog, href| println!("{}", href))
+            .build(&mut dialog)?;
+
+        let result = dialog.run(Some(window))?;
+        Ok(result.button == nwg::TaskDialogButton::Custom(SAVE))
+    }
+    ```
+*/
+pub struct TaskDialog {
+    title: Option<String>,
+    main_instruction: Option<String>,
+    content: Option<String>,
+    icon: TaskDialogIcon,
+    buttons: TaskDialogButtons,
+    custom_buttons: Vec<(i32, String)>,
+    command_links: bool,
+    default_button: Option<TaskDialogButton>,
+    radios: Vec<(i32, String)>,
+    default_radio: Option<i32>,
+    verification: Option<String>,
+    verification_checked: bool,
+    expanded_information: Option<String>,
+    expanded: bool,
+    footer: Option<String>,
+    footer_icon: TaskDialogIcon,
+    progress_bar: bool,
+    hyperlinks: bool,
+    cancelable: bool,
+    events: RefCell<TaskDialogEvents>,
+}
+
+impl TaskDialog {
+
+    pub fn builder() -> TaskDialogBuilder {
+        TaskDialogBuilder {
+            dialog: TaskDialog::default()
+        }
+    }
+
+    /**
+        Display the dialog and return the choices of the user. Blocks the current thread until the dialog is closed.
+        If a parent is set, the parent window is disabled while the dialog is displayed.
+
+        Returns an error if the parent is not a window control or if task dialogs are not available (see `TaskDialog`).
+    */
+    pub fn run<C: Into<ControlHandle>>(&self, parent: Option<C>) -> Result<TaskDialogResult, NwgError> {
+        use winapi::um::commctrl::{TDF_ENABLE_HYPERLINKS, TDF_ALLOW_DIALOG_CANCELLATION, TDF_USE_COMMAND_LINKS, TDF_EXPANDED_BY_DEFAULT,
+            TDF_VERIFICATION_FLAG_CHECKED, TDF_SHOW_PROGRESS_BAR, TDF_SHOW_MARQUEE_PROGRESS_BAR, TDF_CALLBACK_TIMER,
+            TDF_POSITION_RELATIVE_TO_WINDOW};
+
+        let parent = match parent.map(|p| p.into()) {
+            Some(p) => match p.hwnd() {
+                Some(hwnd) => hwnd,
+                None => return Err(NwgError::resource_create("Task dialog parent must be a window control"))
+            },
+            None => ptr::null_mut()
+        };
+
+        let task_dialog_indirect = match task_dialog_function() {
+            Some(f) => f,
+            None => return Err(NwgError::resource_create("Task dialogs require version 6 of the common controls (see `enable_visual_styles`)"))
+        };
+
+        let wide = |text: &Option<String>| text.as_ref().map(|t| to_utf16(t));
+        let wide_ptr = |text: &Option<Vec<u16>>| text.as_ref().map(|t| t.as_ptr()).unwrap_or(ptr::null());
+
+        let title = wide(&self.title);
+        let main_instruction = wide(&self.main_instruction);
+        let content = wide(&self.content);
+        let verification = wide(&self.verification);
+        let expanded_information = wide(&self.expanded_information);
+        let footer = wide(&self.footer);
+
+        let buttons_text: Vec<Vec<u16>> = self.custom_buttons.iter().map(|(_, text)| to_utf16(text)).collect();
+        let buttons: Vec<TASKDIALOG_BUTTON> = self.custom_buttons.iter().zip(buttons_text.iter())
+            .map(|((id, _), text)| TASKDIALOG_BUTTON { nButtonID: *id, pszButtonText: text.as_ptr() })
+            .collect();
+
+        let radios_text: Vec<Vec<u16>> = self.radios.iter().map(|(_, text)| to_utf16(text)).collect();
+        let radios: Vec<TASKDIALOG_BUTTON> = self.radios.iter().zip(radios_text.iter())
+            .map(|((id, _), text)| TASKDIALOG_BUTTON { nButtonID: *id, pszButtonText: text.as_ptr() })
+            .collect();
+
+        let mut flags = TDF_POSITION_RELATIVE_TO_WINDOW;
+        let events = self.events.borrow();
+        if self.hyperlinks { flags |= TDF_ENABLE_HYPERLINKS; }
+        if self.cancelable { flags |= TDF_ALLOW_DIALOG_CANCELLATION; }
+        if self.command_links && !buttons.is_empty() { flags |= TDF_USE_COMMAND_LINKS; }
+        if self.expanded { flags |= TDF_EXPANDED_BY_DEFAULT; }
+        if self.verification_checked { flags |= TDF_VERIFICATION_FLAG_CHECKED; }
+        if self.progress_bar { flags |= TDF_SHOW_PROGRESS_BAR; }
+        if events.marquee { flags |= TDF_SHOW_MARQUEE_PROGRESS_BAR; }
+        if events.timer.is_some() { flags |= TDF_CALLBACK_TIMER; }
+        drop(events);
+
+        let mut config: TASKDIALOGCONFIG = unsafe { mem::zeroed() };
+        config.cbSize = mem::size_of::<TASKDIALOGCONFIG>() as UINT;
+        config.hwndParent = parent;
+        config.dwFlags = flags;
+        config.dwCommonButtons = self.buttons.bits();
+        config.pszWindowTitle = wide_ptr(&title);
+        config.pszMainInstruction = wide_ptr(&main_instruction);
+        config.pszContent = wide_ptr(&content);
+        config.cButtons = buttons.len() as UINT;
+        config.pButtons = buttons.as_ptr();
+        config.nDefaultButton = self.default_button.map(|b| b.id()).unwrap_or(0);
+        config.cRadioButtons = radios.len() as UINT;
+        config.pRadioButtons = radios.as_ptr();
+        config.nDefaultRadioButton = self.default_radio.unwrap_or(0);
+        config.pszVerificationText = wide_ptr(&verification);
+        config.pszExpandedInformation = wide_ptr(&expanded_information);
+        config.pszFooter = wide_ptr(&footer);
+        config.pfCallback = Some(task_dialog_proc);
+        config.lpCallbackData = &self.events as *const RefCell<TaskDialogEvents> as LONG_PTR;
+        unsafe {
+            *config.u1.pszMainIcon_mut() = self.icon.resource();
+            *config.u2.pszFooterIcon_mut() = self.footer_icon.resource();
+        }
+
+        let mut button: c_int = 0;
+        let mut radio: c_int = 0;
+        let mut verification_checked: BOOL = 0;
+        let result = unsafe { task_dialog_indirect(&config, &mut button, &mut radio, &mut verification_checked) };
+        if result != S_OK {
+            return Err(NwgError::resource_create(format!("Failed to display the task dialog (0x{:08X})", result)));
+        }
+
+        Ok(TaskDialogResult {
+            button: TaskDialogButton::from_id(button),
+            radio: match self.radios.iter().any(|(id, _)| *id == radio) {
+                true => Some(radio),
+                false => None
+            },
+            verification: verification_checked != 0,
+        })
+    }
+
+}
+
+impl Default for TaskDialog {
+    fn default() -> TaskDialog {
+        TaskDialog {
+            title: None,
+            main_instruction: None,
+            content: None,
+            icon: TaskDialogIcon::None,
+            buttons: TaskDialogButtons::empty(),
+            custom_buttons: Vec::new(),
+            command_links: false,
+            default_button: None,
+            radios: Vec::new(),
+            default_radio: None,
+            verification: None,
+            verification_checked: false,
+            expanded_information: None,
+            expanded: false,
+            footer: None,
+            footer_icon: TaskDialogIcon::None,
+            progress_bar: false,
+            hyperlinks: false,
+            cancelable: true,
+            events: RefCell::new(TaskDialogEvents::default()),
+        }
+    }
+}
+
+
+pub struct TaskDialogBuilder {
+    dialog: TaskDialog,
+}
+
+impl TaskDialogBuilder {
+
+    pub fn title<S: Into<String>>(mut self, title: S) -> TaskDialogBuilder {
+        self.dialog.title = Some(title.into());
+        self
+    }
+
+    pub fn main_instruction<S: Into<String>>(mut self, text: S) -> TaskDialogBuilder {
+        self.dialog.main_instruction = Some(text.into());
+        self
+    }
+
+    pub fn content<S: Into<String>>(mut self, text: S) -> TaskDialogBuilder {
+        self.dialog.content = Some(text.into());
+        self
+    }
+
+    pub fn icon(mut self, icon: TaskDialogIcon) -> TaskDialogBuilder {
+        self.dialog.icon = icon;
+        self
+    }
+
+    pub fn buttons(mut self, buttons: TaskDialogButtons) -> TaskDialogBuilder {
+        self.dialog.buttons = buttons;
+        self
+    }
+
+    pub fn button<S: Into<String>>(mut self, id: i32, text: S) -> TaskDialogBuilder {
+        self.dialog.custom_buttons.push((id, text.into()));
+        self
+    }
+
+    pub fn command_links(mut self, command_links: bool) -> TaskDialogBuilder {
+        self.dialog.command_links = command_links;
+        self
+    }
+
+    pub fn default_button(mut self, button: TaskDialogButton) -> TaskDialogBuilder {
+        self.dialog.default_button = Some(button);
+        self
+    }
+
+    pub fn radio<S: Into<String>>(mut self, id: i32, text: S) -> TaskDialogBuilder {
+        self.dialog.radios.push((id, text.into()));
+        self
+    }
+
+    pub fn default_radio(mut self, id: i32) -> TaskDialogBuilder {
+        self.dialog.default_radio = Some(id);
+        self
+    }
+
+    pub fn verification<S: Into<String>>(mut self, text: S) -> TaskDialogBuilder {
+        self.dialog.verification = Some(text.into());
+        self
+    }
+
+    pub fn verification_checked(mut self, checked: bool) -> TaskDialogBuilder {
+        self.dialog.verification_checked = checked;
+        self
+    }
+
+    pub fn expanded_information<S: Into<String>>(mut self, text: S) -> TaskDialogBuilder {
+        self.dialog.expanded_information = Some(text.into());
+        self
+    }
+
+    pub fn expanded(mut self, expanded: bool) -> TaskDialogBuilder {
+        self.dialog.expanded = expanded;
+        self
+    }
+
+    pub fn footer<S: Into<String>>(mut self, text: S) -> TaskDialogBuilder {
+        self.dialog.footer = Some(text.into());
+        self
+    }
+
+    pub fn footer_icon(mut self, icon: TaskDialogIcon) -> TaskDialogBuilder {
+        self.dialog.footer_icon = icon;
+        self
+    }
+
+    pub fn progress_bar(mut self, progress_bar: bool) -> TaskDialogBuilder {
+        self.dialog.progress_bar = progress_bar;
+        self
+    }
+
+    pub fn marquee(mut self, marquee: bool) -> TaskDialogBuilder {
+        self.dialog.events.get_mut().marquee = marquee;
+        self
+    }
+
+    pub fn hyperlinks(mut self, hyperlinks: bool) -> TaskDialogBuilder {
+        self.dialog.hyperlinks = hyperlinks;
+        self
+    }
+
+    pub fn cancelable(mut self, cancelable: bool) -> TaskDialogBuilder {
+        self.dialog.cancelable = cancelable;
+        self
+    }
+
+    pub fn on_button_click<F>(mut self, f: F) -> TaskDialogBuilder
+        where F: FnMut(&TaskDialogHandle, TaskDialogButton) -> bool + 'static
+    {
+        self.dialog.events.get_mut().button = Some(Box::new(f));
+        self
+    }
+
+    pub fn on_hyperlink_click<F>(mut self, f: F) -> TaskDialogBuilder
+        where F: FnMut(&TaskDialogHandle, &str) + 'static
+    {
+        self.dialog.events.get_mut().hyperlink = Some(Box::new(f));
+        self
+    }
+
+    pub fn on_timer<F>(mut self, f: F) -> TaskDialogBuilder
+        where F: FnMut(&TaskDialogHandle, u32) + 'static
+    {
+        self.dialog.events.get_mut().timer = Some(Box::new(f));
+        self
+    }
+
+    pub fn build(self, out: &mut TaskDialog) -> Result<(), NwgError> {
+        if let Some((id, _)) = self.dialog.custom_buttons.iter().find(|(id, _)| *id < 100) {
+            return Err(NwgError::resource_create(format!("Task dialog custom button id {} must be 100 or greater", id)));
+        }
+
+        if let Some(id) = self.dialog.default_radio {
+            if !self.dialog.radios.iter().any(|(r, _)| *r == id) {
+                return Err(NwgError::resource_create(format!("Task dialog default radio {} is not a radio button id", id)));
+            }
+        }
+
+        *out = self.dialog;
+        Ok(())
+    }
+
+}
+
+
+type TaskDialogIndirectFn = unsafe extern "system" fn(*const TASKDIALOGCONFIG, *mut c_int, *mut c_int, *mut BOOL) -> HRESULT;
+
+/// `TaskDialogIndirect` only exists in version 6 of comctl32, so it is loaded at runtime instead of being linked.
+fn task_dialog_function() -> Option<TaskDialogIndirectFn> {
+    use winapi::um::libloaderapi::{LoadLibraryW, GetProcAddress};
+    use winapi::shared::minwindef::FARPROC;
+
+    unsafe {
+        let name = to_utf16("comctl32.dll");
+        let module = LoadLibraryW(name.as_ptr());
+        if module.is_null() {
+            return None;
+        }
+
+        let function = GetProcAddress(module, "TaskDialogIndirect\0".as_ptr() as _);
+        match function.is_null() {
+            true => None,
+            false => Some(mem::transmute::<FARPROC, TaskDialogIndirectFn>(function))
+        }
+    }
+}
+
+unsafe extern "system" fn task_dialog_proc(hwnd: HWND, msg: UINT, w: WPARAM, l: LPARAM, data: LONG_PTR) -> HRESULT {
+    use winapi::um::commctrl::{TDN_CREATED, TDN_BUTTON_CLICKED, TDN_HYPERLINK_CLICKED, TDN_TIMER, TDM_SET_PROGRESS_BAR_MARQUEE};
+
+    let events = &*(data as *const RefCell<TaskDialogEvents>);
+
+    // A callback that displays the same dialog again cannot borrow the events
+    let mut events = match events.try_borrow_mut() {
+        Ok(events) => events,
+        Err(_) => return S_OK
+    };
+
+    let dialog = TaskDialogHandle { hwnd };
+
+    match msg {
+        TDN_CREATED if events.marquee => {
+            wh::send_message(hwnd, TDM_SET_PROGRESS_BAR_MARQUEE, 1, 0);
+        },
+        TDN_BUTTON_CLICKED => {
+            if let Some(callback) = events.button.as_mut() {
+                if !callback(&dialog, TaskDialogButton::from_id(w as i32)) {
+                    return S_FALSE;
+                }
+            }
+        },
+        TDN_HYPERLINK_CLICKED => {
+            if let Some(callback) = events.hyperlink.as_mut() {
+                let href = l as *const u16;
+                let mut length = 0;
+                while *href.add(length) != 0 {
+                    length += 1;
+                }
+
+                callback(&dialog, &from_utf16(std::slice::from_raw_parts(href, length)));
+            }
+        },
+        TDN_TIMER => {
+            if let Some(callback) = events.timer.as_mut() {
+                callback(&dialog, w as u32);
+            }
+        },
+        _ => {}
+    }
+
+    S_OK
+}
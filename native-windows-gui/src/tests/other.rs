@@ -100,4 +100,48 @@ fn other_tests() {
     init().expect("Failed to init Native Windows GUI");
     let _app = OtherTests::build_ui(Default::default()).expect("Failed to build UI");
     //dispatch_thread_events();
-}
\ No newline at end of file
+}
+#[test]
+fn list_view_sort() {
+    init().expect("Failed to init Native Windows GUI");
+
+    let mut window = Window::default();
+    Window::builder()
+        .flags(WindowFlags::WINDOW)
+        .build(&mut window)
+        .expect("Failed to build window");
+
+    let mut list: ListView<u32> = ListView::default();
+    ListView::builder()
+        .parent(&window)
+        .list_style(ListViewStyle::Detailed)
+        .build(&mut list)
+        .expect("Failed to build list view");
+
+    list.insert_column("Price");
+
+    let prices = ["40", "7", "300", "1", "25", "8", "120", "3", "60", "15"];
+    for (i, &price) in prices.iter().enumerate() {
+        list.insert_item_with_data(price, i as u32);
+    }
+
+    // Rows without data are sorted too
+    list.insert_item("2");
+    list.select_item(2, true);
+
+    let texts = |list: &ListView<u32>| (0..list.len()).map(|row| list.item(row, 0, 16).unwrap().text).collect::<Vec<_>>();
+
+    list.sort_by_column(0, ListViewColumnSortArrow::Up, &ListViewComparator::Numeric);
+    assert_eq!(texts(&list), ["1", "2", "3", "7", "8", "15", "25", "40", "60", "120", "300"]);
+
+    list.sort_by_column(0, ListViewColumnSortArrow::Down, &ListViewComparator::Numeric);
+    assert_eq!(texts(&list), ["300", "120", "60", "40", "25", "15", "8", "7", "3", "2", "1"]);
+
+    // The data and the selection follow their row
+    assert_eq!(list.selected_item(), Some(0));
+    for row in 0..list.len() {
+        let text = list.item(row, 0, 16).unwrap().text;
+        let expected = prices.iter().position(|&price| price == text).map(|i| i as u32);
+        assert_eq!(list.item_data(row).map(|data| *data), expected);
+    }
+}
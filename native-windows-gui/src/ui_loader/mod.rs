@@ -0,0 +1,645 @@
+/*!
+    Runtime loader for UI descriptions. The parser and the validation (`parser`, `schema`) are pure rust and
+    can be tested on any platform. This module instantiates a validated description into a `DynamicUi`.
+*/
+mod parser;
+mod schema;
+
+pub use parser::{UiDescription, UiNode, UiParam, UiLayoutItem, UiValue, UiPosition, UiParseError};
+
+use schema::NodeCategory;
+use crate::controls::*;
+use crate::layouts::*;
+use crate::{NwgError, HTextAlign, VTextAlign};
+use std::path::Path;
+
+
+/// A control or a layout created by a `DynamicUi`
+pub enum DynamicControl {
+    Window(Window),
+    Button(Button),
+    CheckBox(CheckBox),
+    RadioButton(RadioButton),
+    TextInput(TextInput),
+    Label(Label),
+
+    #[cfg(feature = "listbox")]
+    ListBox(ListBox<String>),
+
+    GridLayout(GridLayout),
+    DockLayout(DockLayout),
+}
+
+impl DynamicControl {
+
+    /// Return the handle of the control. Layouts do not have a handle.
+    pub fn handle(&self) -> Option<ControlHandle> {
+        use DynamicControl::*;
+
+        match self {
+            Window(c) => Some(c.handle),
+            Button(c) => Some(c.handle),
+            CheckBox(c) => Some(c.handle),
+            RadioButton(c) => Some(c.handle),
+            TextInput(c) => Some(c.handle),
+            Label(c) => Some(c.handle),
+            #[cfg(feature = "listbox")]
+            ListBox(c) => Some(c.handle),
+            GridLayout(_) | DockLayout(_) => None,
+        }
+    }
+
+    /// Return the type of the control as written in the UI description
+    pub fn kind(&self) -> &'static str {
+        use DynamicControl::*;
+
+        match self {
+            Window(_) => "Window",
+            Button(_) => "Button",
+            CheckBox(_) => "CheckBox",
+            RadioButton(_) => "RadioButton",
+            TextInput(_) => "TextInput",
+            Label(_) => "Label",
+            #[cfg(feature = "listbox")]
+            ListBox(_) => "ListBox",
+            GridLayout(_) => "GridLayout",
+            DockLayout(_) => "DockLayout",
+        }
+    }
+
+}
+
+/// The types that can be stored in a `DynamicControl`. Used by `DynamicUi::control`.
+pub trait LoadedControl: Sized {
+    fn from_dynamic(control: &DynamicControl) -> Option<&Self>;
+}
+
+macro_rules! loaded_control {
+    ($ty:ty, $variant:ident) => {
+        impl LoadedControl for $ty {
+            fn from_dynamic(control: &DynamicControl) -> Option<&Self> {
+                match control {
+                    DynamicControl::$variant(c) => Some(c),
+                    _ => None
+                }
+            }
+        }
+    };
+}
+
+loaded_control!(Window, Window);
+loaded_control!(Button, Button);
+loaded_control!(CheckBox, CheckBox);
+loaded_control!(RadioButton, RadioButton);
+loaded_control!(TextInput, TextInput);
+loaded_control!(Label, Label);
+#[cfg(feature = "listbox")]
+loaded_control!(ListBox<String>, ListBox);
+loaded_control!(GridLayout, GridLayout);
+loaded_control!(DockLayout, DockLayout);
+
+
+/**
+A UI loaded at runtime from a text description. Controls are queried by name.
+
+Requires the `ui-loader` feature.
+
+The description format mirrors the `nwg_control`, `nwg_layout` and `nwg_layout_item` attributes of native-windows-derive:
+
+* Each node is written `name: Type(param: value, ...)`. The parameters are the builder parameters and can be omitted.
+* Nodes declared between the brackets `{ }` of a control are its children. Top level nodes must be windows.
+* Layouts are declared in the control they apply to. A control joins a layout declared in the same parent with `@layout(param: value, ...)`.
+* Values are booleans (`true`), integers (`-10`), strings (`"text"`), tuples (`(300, 200)`), lists (`["a", "b"]`)
+  and enum values (`Center`, `Fixed(80)`). Flags are strings: `"WINDOW|VISIBLE"`.
+* Comments start with `//`.
+
+Supported types: `Window`, `Button`, `CheckBox`, `RadioButton`, `TextInput`, `Label`, `ListBox` (with the `listbox` feature),
+`GridLayout` and `DockLayout`. Resources (fonts, icons, bitmaps) cannot be set from a description.
+
+The description is fully validated before any control is created. Errors report the line and the column of the faulty node or parameter.
+
+```rust
+use native_windows_gui as nwg;
+use std::rc::Rc;
+
+const FORM: &str = r#"
+    window: Window(title: "Basic example", size: (300, 115), flags: "WINDOW|VISIBLE") {
+        grid: GridLayout(spacing: 1)
+        name_edit: TextInput(text: "Heisenberg", focus: true) @grid(col: 0, row: 0)
+        hello_button: Button(text: "Say my name") @grid(col: 0, row: 1, row_span: 2)
+    }
+"#;
+
+fn load_form() -> Result<(), nwg::NwgError> {
+    let ui = Rc::new(nwg::DynamicUi::load(FORM)?);
+
+    let events_ui = ui.clone();
+    let window = ui.handle("window").unwrap();
+    nwg::full_bind_event_handler(&window, move |evt, _evt_data, handle| {
+        if evt == nwg::Event::OnButtonClick && events_ui.name_of(handle) == Some("hello_button") {
+            let name = events_ui.control::<nwg::TextInput>("name_edit").unwrap().text();
+            nwg::simple_message("Hello", &format!("Hello {}", name));
+        }
+    });
+
+    Ok(())
+}
+```
+*/
+pub struct DynamicUi {
+    controls: Vec<(String, DynamicControl)>,
+}
+
+impl DynamicUi {
+
+    /// Parse, validate and instantiate a UI description
+    pub fn load(source: &str) -> Result<DynamicUi, NwgError> {
+        let description = UiDescription::parse(source)?;
+        DynamicUi::build(&description)
+    }
+
+    /// Read, parse, validate and instantiate a UI description file
+    pub fn load_file<P: AsRef<Path>>(path: P) -> Result<DynamicUi, NwgError> {
+        let path = path.as_ref();
+        let source = std::fs::read_to_string(path)
+            .map_err(|e| NwgError::ui_loader(format!("{}: {}", path.display(), e)))?;
+
+        DynamicUi::load(&source)
+            .map_err(|e| match e {
+                NwgError::UiLoaderError(reason) => NwgError::ui_loader(format!("{}: {}", path.display(), reason)),
+                e => e
+            })
+    }
+
+    /// Validate and instantiate a parsed UI description.
+    /// The controls are created first, in the declaration order, then the layouts.
+    pub fn build(description: &UiDescription) -> Result<DynamicUi, NwgError> {
+        description.validate()?;
+
+        let mut ui = DynamicUi { controls: Vec::new() };
+        for node in description.nodes.iter() {
+            ui.build_controls(node, None)?;
+        }
+
+        for node in description.nodes.iter() {
+            ui.build_layouts(node)?;
+        }
+
+        Ok(ui)
+    }
+
+    /// Return the control or the layout named `name`
+    pub fn get(&self, name: &str) -> Option<&DynamicControl> {
+        self.controls.iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, c)| c)
+    }
+
+    /// Return the control named `name` if its type is `T`. Ex: `ui.control::<nwg::Button>("ok")`
+    pub fn control<T: LoadedControl>(&self, name: &str) -> Option<&T> {
+        self.get(name).and_then(T::from_dynamic)
+    }
+
+    /// Return the handle of the control named `name`
+    pub fn handle(&self, name: &str) -> Option<ControlHandle> {
+        self.get(name).and_then(|c| c.handle())
+    }
+
+    /// Return the name of the control with the handle `handle`. Use it to identify the source of an event.
+    pub fn name_of(&self, handle: ControlHandle) -> Option<&str> {
+        self.controls.iter()
+            .find(|(_, c)| c.handle() == Some(handle))
+            .map(|(n, _)| n.as_str())
+    }
+
+    /// Return the names of the controls and layouts
+    pub fn names(&self) -> impl Iterator<Item=&str> {
+        self.controls.iter().map(|(n, _)| n.as_str())
+    }
+
+    fn build_controls(&mut self, node: &UiNode, parent: Option<ControlHandle>) -> Result<(), NwgError> {
+        if schema::schema(&node.kind).map(|s| s.category) == Some(NodeCategory::Layout) {
+            return Ok(());
+        }
+
+        let control = build_control(node, parent)?;
+        let handle = control.handle();
+        self.controls.push((node.name.clone(), control));
+
+        for child in node.children.iter() {
+            self.build_controls(child, handle)?;
+        }
+
+        Ok(())
+    }
+
+    fn build_layouts(&mut self, node: &UiNode) -> Result<(), NwgError> {
+        for child in node.children.iter() {
+            match schema::schema(&child.kind).map(|s| s.category) {
+                Some(NodeCategory::Layout) => {
+                    let layout = build_layout(self, child, node)?;
+                    self.controls.push((child.name.clone(), layout));
+                },
+                _ => self.build_layouts(child)?
+            }
+        }
+
+        Ok(())
+    }
+
+}
+
+impl Drop for DynamicUi {
+    fn drop(&mut self) {
+        // Children are stored after their parent. Destroy them first.
+        while self.controls.pop().is_some() {}
+    }
+}
+
+
+macro_rules! flags {
+    ($value:expr, $ty:ident, [$($flag:ident),*]) => {{
+        let mut flags = $ty::empty();
+        for name in $value.as_str().unwrap_or("").split('|') {
+            match name.trim() {
+                $(stringify!($flag) => { flags |= $ty::$flag; },)*
+                _ => {}
+            }
+        }
+
+        flags
+    }};
+}
+
+fn int(value: &UiValue) -> i64 {
+    value.as_int().unwrap_or(0)
+}
+
+fn uint(value: &UiValue) -> u32 {
+    int(value) as u32
+}
+
+fn ints(value: &UiValue) -> Vec<i64> {
+    value.as_slice().unwrap_or(&[]).iter().map(int).collect()
+}
+
+fn pair(value: &UiValue) -> (i32, i32) {
+    match ints(value).as_slice() {
+        [a, b] => (*a as i32, *b as i32),
+        _ => (0, 0)
+    }
+}
+
+fn uint_pair(value: &UiValue) -> [u32; 2] {
+    match ints(value).as_slice() {
+        [a, b] => [*a as u32, *b as u32],
+        _ => [0, 0]
+    }
+}
+
+fn margin(value: &UiValue) -> [u32; 4] {
+    match ints(value).as_slice() {
+        [a, b, c, d] => [*a as u32, *b as u32, *c as u32, *d as u32],
+        _ => [0, 0, 0, 0]
+    }
+}
+
+fn color(value: &UiValue) -> Option<[u8; 3]> {
+    match ints(value).as_slice() {
+        [r, g, b] => Some([*r as u8, *g as u8, *b as u8]),
+        _ => None
+    }
+}
+
+fn ident(value: &UiValue) -> &str {
+    value.as_ident().unwrap_or("")
+}
+
+fn h_align(value: &UiValue) -> HTextAlign {
+    match ident(value) {
+        "Center" => HTextAlign::Center,
+        "Right" => HTextAlign::Right,
+        _ => HTextAlign::Left,
+    }
+}
+
+fn v_align(value: &UiValue) -> VTextAlign {
+    match ident(value) {
+        "Top" => VTextAlign::Top,
+        "Bottom" => VTextAlign::Bottom,
+        _ => VTextAlign::Center,
+    }
+}
+
+fn policy(value: &UiValue) -> GridPolicy {
+    match value {
+        UiValue::Call(name, args) if name == "Fixed" => GridPolicy::Fixed(args.first().map(uint).unwrap_or(0)),
+        UiValue::Call(_, args) => GridPolicy::Weight(args.first().map(uint).unwrap_or(1)),
+        _ => GridPolicy::Auto,
+    }
+}
+
+fn policies(value: &UiValue) -> Vec<GridPolicy> {
+    value.as_slice().unwrap_or(&[]).iter().map(policy).collect()
+}
+
+fn build_control(node: &UiNode, parent: Option<ControlHandle>) -> Result<DynamicControl, NwgError> {
+    match node.kind.as_str() {
+        "Window" => {
+            let mut builder = Window::builder().parent(parent);
+            for p in node.params.iter() {
+                let v = &p.value;
+                builder = match p.name.as_str() {
+                    "title" => builder.title(v.as_str().unwrap_or("")),
+                    "size" => builder.size(pair(v)),
+                    "position" => builder.position(pair(v)),
+                    "flags" => builder.flags(flags!(v, WindowFlags, [MAIN_WINDOW, WINDOW, MINIMIZE_BOX, MAXIMIZE_BOX, SYS_MENU, VISIBLE, DISABLED, MAXIMIZED, MINIMIZED, RESIZABLE, POPUP])),
+                    "ex_flags" => builder.ex_flags(uint(v)),
+                    "accept_files" => builder.accept_files(v.as_bool().unwrap_or(false)),
+                    "topmost" => builder.topmost(v.as_bool().unwrap_or(false)),
+                    "center" => builder.center(v.as_bool().unwrap_or(false)),
+                    "maximized" => builder.maximized(v.as_bool().unwrap_or(false)),
+                    "minimized" => builder.minimized(v.as_bool().unwrap_or(false)),
+                    _ => builder
+                };
+            }
+
+            let mut control = Window::default();
+            builder.build(&mut control)?;
+            Ok(DynamicControl::Window(control))
+        },
+        "Button" => {
+            let mut builder = Button::builder();
+            if let Some(parent) = parent {
+                builder = builder.parent(parent);
+            }
+
+            for p in node.params.iter() {
+                let v = &p.value;
+                builder = match p.name.as_str() {
+                    "text" => builder.text(v.as_str().unwrap_or("")),
+                    "size" => builder.size(pair(v)),
+                    "position" => builder.position(pair(v)),
+                    "flags" => builder.flags(flags!(v, ButtonFlags, [NONE, VISIBLE, DISABLED, ICON, BITMAP, NOTIFY, TAB_STOP])),
+                    "ex_flags" => builder.ex_flags(uint(v)),
+                    "enabled" => builder.enabled(v.as_bool().unwrap_or(true)),
+                    "focus" => builder.focus(v.as_bool().unwrap_or(false)),
+                    _ => builder
+                };
+            }
+
+            let mut control = Button::default();
+            builder.build(&mut control)?;
+            Ok(DynamicControl::Button(control))
+        },
+        "CheckBox" => {
+            let mut builder = CheckBox::builder();
+            if let Some(parent) = parent {
+                builder = builder.parent(parent);
+            }
+
+            for p in node.params.iter() {
+                let v = &p.value;
+                builder = match p.name.as_str() {
+                    "text" => builder.text(v.as_str().unwrap_or("")),
+                    "size" => builder.size(pair(v)),
+                    "position" => builder.position(pair(v)),
+                    "flags" => builder.flags(flags!(v, CheckBoxFlags, [NONE, VISIBLE, DISABLED, TRISTATE, PUSHLIKE, TAB_STOP])),
+                    "ex_flags" => builder.ex_flags(uint(v)),
+                    "enabled" => builder.enabled(v.as_bool().unwrap_or(true)),
+                    "focus" => builder.focus(v.as_bool().unwrap_or(false)),
+                    "background_color" => builder.background_color(color(v)),
+                    "check_state" => builder.check_state(match ident(v) {
+                        "Checked" => CheckBoxState::Checked,
+                        "Indeterminate" => CheckBoxState::Indeterminate,
+                        _ => CheckBoxState::Unchecked,
+                    }),
+                    _ => builder
+                };
+            }
+
+            let mut control = CheckBox::default();
+            builder.build(&mut control)?;
+            Ok(DynamicControl::CheckBox(control))
+        },
+        "RadioButton" => {
+            let mut builder = RadioButton::builder();
+            if let Some(parent) = parent {
+                builder = builder.parent(parent);
+            }
+
+            for p in node.params.iter() {
+                let v = &p.value;
+                builder = match p.name.as_str() {
+                    "text" => builder.text(v.as_str().unwrap_or("")),
+                    "size" => builder.size(pair(v)),
+                    "position" => builder.position(pair(v)),
+                    "flags" => builder.flags(flags!(v, RadioButtonFlags, [VISIBLE, DISABLED, TAB_STOP, GROUP])),
+                    "ex_flags" => builder.ex_flags(uint(v)),
+                    "focus" => builder.focus(v.as_bool().unwrap_or(false)),
+                    "background_color" => builder.background_color(color(v)),
+                    "check_state" => builder.check_state(match ident(v) {
+                        "Checked" => RadioButtonState::Checked,
+                        _ => RadioButtonState::Unchecked,
+                    }),
+                    _ => builder
+                };
+            }
+
+            let mut control = RadioButton::default();
+            builder.build(&mut control)?;
+            Ok(DynamicControl::RadioButton(control))
+        },
+        "TextInput" => {
+            let mut builder = TextInput::builder();
+            if let Some(parent) = parent {
+                builder = builder.parent(parent);
+            }
+
+            for p in node.params.iter() {
+                let v = &p.value;
+                builder = match p.name.as_str() {
+                    "text" => builder.text(v.as_str().unwrap_or("")),
+                    "placeholder_text" => builder.placeholder_text(v.as_str()),
+                    "size" => builder.size(pair(v)),
+                    "position" => builder.position(pair(v)),
+                    "flags" => builder.flags(flags!(v, TextInputFlags, [VISIBLE, DISABLED, NUMBER, AUTO_SCROLL, TAB_STOP])),
+                    "ex_flags" => builder.ex_flags(uint(v)),
+                    "limit" => builder.limit(uint(v) as usize),
+                    "password" => builder.password(v.as_str().and_then(|s| s.chars().next())),
+                    "align" => builder.align(h_align(v)),
+                    "readonly" => builder.readonly(v.as_bool().unwrap_or(false)),
+                    "background_color" => builder.background_color(color(v)),
+                    "focus" => builder.focus(v.as_bool().unwrap_or(false)),
+                    _ => builder
+                };
+            }
+
+            let mut control = TextInput::default();
+            builder.build(&mut control)?;
+            Ok(DynamicControl::TextInput(control))
+        },
+        "Label" => {
+            let mut builder = Label::builder();
+            if let Some(parent) = parent {
+                builder = builder.parent(parent);
+            }
+
+            for p in node.params.iter() {
+                let v = &p.value;
+                builder = match p.name.as_str() {
+                    "text" => builder.text(v.as_str().unwrap_or("")),
+                    "size" => builder.size(pair(v)),
+                    "position" => builder.position(pair(v)),
+                    "flags" => builder.flags(flags!(v, LabelFlags, [NONE, VISIBLE, DISABLED, ELIPSIS])),
+                    "ex_flags" => builder.ex_flags(uint(v)),
+                    "background_color" => builder.background_color(color(v)),
+                    "h_align" => builder.h_align(h_align(v)),
+                    "v_align" => builder.v_align(v_align(v)),
+                    _ => builder
+                };
+            }
+
+            let mut control = Label::default();
+            builder.build(&mut control)?;
+            Ok(DynamicControl::Label(control))
+        },
+        #[cfg(feature = "listbox")]
+        "ListBox" => {
+            let mut builder = ListBox::<String>::builder();
+            if let Some(parent) = parent {
+                builder = builder.parent(parent);
+            }
+
+            for p in node.params.iter() {
+                let v = &p.value;
+                builder = match p.name.as_str() {
+                    "size" => builder.size(pair(v)),
+                    "position" => builder.position(pair(v)),
+                    "flags" => builder.flags(flags!(v, ListBoxFlags, [NONE, VISIBLE, DISABLED, MULTI_SELECT, NO_SELECT, TAB_STOP])),
+                    "ex_flags" => builder.ex_flags(uint(v)),
+                    "collection" => builder.collection(v.as_slice().unwrap_or(&[]).iter().filter_map(|s| s.as_str()).map(String::from).collect()),
+                    "selected_index" => builder.selected_index(Some(uint(v) as usize)),
+                    "multi_selection" => builder.multi_selection(ints(v).into_iter().map(|i| i as usize).collect()),
+                    "enabled" => builder.enabled(v.as_bool().unwrap_or(true)),
+                    "focus" => builder.focus(v.as_bool().unwrap_or(false)),
+                    _ => builder
+                };
+            }
+
+            let mut control = ListBox::default();
+            builder.build(&mut control)?;
+            Ok(DynamicControl::ListBox(control))
+        },
+        #[cfg(not(feature = "listbox"))]
+        "ListBox" => Err(NwgError::ui_loader(format!("`{}`: ListBox requires the `listbox` feature", node.name))),
+        kind => Err(NwgError::ui_loader(format!("`{}`: unknown control type `{}`", node.name, kind))),
+    }
+}
+
+fn build_layout(ui: &DynamicUi, node: &UiNode, parent: &UiNode) -> Result<DynamicControl, NwgError> {
+    let parent_handle = ui.handle(&parent.name)
+        .ok_or_else(|| NwgError::layout_create(format!("`{}`: parent `{}` is not a control", node.name, parent.name)))?;
+
+    let items = parent.children.iter()
+        .filter_map(|c| c.layout_item.as_ref().filter(|i| i.layout == node.name).map(|i| (c, i)));
+
+    match node.kind.as_str() {
+        "GridLayout" => {
+            let mut builder = GridLayout::builder().parent(parent_handle);
+            for p in node.params.iter() {
+                let v = &p.value;
+                builder = match p.name.as_str() {
+                    "margin" => builder.margin(margin(v)),
+                    "spacing" => builder.spacing(uint(v)),
+                    "min_size" => builder.min_size(uint_pair(v)),
+                    "max_size" => builder.max_size(uint_pair(v)),
+                    "max_column" => builder.max_column(Some(uint(v))),
+                    "max_row" => builder.max_row(Some(uint(v))),
+                    "column_policies" => builder.column_policies(&policies(v)),
+                    "row_policies" => builder.row_policies(&policies(v)),
+                    _ => builder
+                };
+            }
+
+            for (control, item) in items {
+                let handle = ui.handle(&control.name)
+                    .ok_or_else(|| NwgError::layout_create(format!("`{}` is not a control", control.name)))?;
+
+                let mut grid_item = GridLayoutItem::new(handle, 0, 0, 1, 1);
+                for p in item.params.iter() {
+                    let v = &p.value;
+                    match p.name.as_str() {
+                        "col" => grid_item.col = uint(v),
+                        "row" => grid_item.row = uint(v),
+                        "col_span" => grid_item.col_span = uint(v),
+                        "row_span" => grid_item.row_span = uint(v),
+                        "align" => grid_item.align = match ident(v) {
+                            "Left" => GridAlign::Left,
+                            "Center" => GridAlign::Center,
+                            "Right" => GridAlign::Right,
+                            _ => GridAlign::Fill,
+                        },
+                        "size_hint" => grid_item.size_hint = uint_pair(v),
+                        _ => {}
+                    }
+                }
+
+                if let Some(p) = item.params.iter().find(|p| p.name == "col_policy") {
+                    builder = builder.column_policy(grid_item.col, policy(&p.value));
+                }
+
+                if let Some(p) = item.params.iter().find(|p| p.name == "row_policy") {
+                    builder = builder.row_policy(grid_item.row, policy(&p.value));
+                }
+
+                builder = builder.child_item(grid_item);
+            }
+
+            let layout = GridLayout::default();
+            builder.build(&layout)?;
+            Ok(DynamicControl::GridLayout(layout))
+        },
+        "DockLayout" => {
+            let mut builder = DockLayout::builder().parent(parent_handle);
+            for p in node.params.iter() {
+                let v = &p.value;
+                builder = match p.name.as_str() {
+                    "margin" => builder.margin(margin(v)),
+                    "spacing" => builder.spacing(uint(v)),
+                    "min_size" => builder.min_size(uint_pair(v)),
+                    _ => builder
+                };
+            }
+
+            for (control, item) in items {
+                let handle = ui.handle(&control.name)
+                    .ok_or_else(|| NwgError::layout_create(format!("`{}` is not a control", control.name)))?;
+
+                let dock = match item.params.iter().find(|p| p.name == "dock").map(|p| ident(&p.value)) {
+                    Some("Top") => Dock::Top,
+                    Some("Bottom") => Dock::Bottom,
+                    Some("Left") => Dock::Left,
+                    Some("Right") => Dock::Right,
+                    _ => Dock::Fill,
+                };
+
+                let mut dock_item = DockLayoutItem::new(handle, dock);
+                for p in item.params.iter() {
+                    match p.name.as_str() {
+                        "size" => dock_item.size = uint(&p.value),
+                        "min_size" => dock_item.min_size = uint(&p.value),
+                        _ => {}
+                    }
+                }
+
+                builder = builder.child_item(dock_item);
+            }
+
+            let layout = DockLayout::default();
+            builder.build(&layout)?;
+            Ok(DynamicControl::DockLayout(layout))
+        },
+        kind => Err(NwgError::ui_loader(format!("`{}`: unknown layout type `{}`", node.name, kind))),
+    }
+}
@@ -0,0 +1,650 @@
+/*!
+    Parser for the UI description format. This module is pure rust and does not depend on the windowing system.
+*/
+use std::fmt;
+use std::error::Error;
+use std::iter::Peekable;
+use std::str::Chars;
+
+
+/// A location in a UI description source. Lines and columns start at 1.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct UiPosition {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// Error raised when a UI description cannot be parsed or fails the validation
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UiParseError {
+    /// Where the error was found in the source
+    pub position: UiPosition,
+
+    /// A description of the error
+    pub message: String,
+}
+
+impl UiParseError {
+
+    pub fn new<S: Into<String>>(position: UiPosition, message: S) -> UiParseError {
+        UiParseError { position, message: message.into() }
+    }
+
+}
+
+impl fmt::Display for UiParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}: {}", self.position.line, self.position.column, self.message)
+    }
+}
+
+impl Error for UiParseError {}
+
+
+/// A parameter value in a UI description
+#[derive(Clone, Debug, PartialEq)]
+pub enum UiValue {
+    /// `true` or `false`
+    Bool(bool),
+
+    /// An integer, ex: `-10`
+    Int(i64),
+
+    /// A string, ex: `"Hello"`. Supports the `\"`, `\\`, `\n`, `\r` and `\t` escapes.
+    Str(String),
+
+    /// An identifier, used for enum values. Ex: `Center`
+    Ident(String),
+
+    /// An identifier followed by arguments, used for enum values with data. Ex: `Fixed(30)`
+    Call(String, Vec<UiValue>),
+
+    /// A fixed size group of values. Ex: `(300, 200)`
+    Tuple(Vec<UiValue>),
+
+    /// A list of values. Ex: `["One", "Two"]`
+    List(Vec<UiValue>),
+}
+
+impl UiValue {
+
+    /// Return the value if this is a `Bool`
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            UiValue::Bool(v) => Some(*v),
+            _ => None
+        }
+    }
+
+    /// Return the value if this is an `Int`
+    pub fn as_int(&self) -> Option<i64> {
+        match self {
+            UiValue::Int(v) => Some(*v),
+            _ => None
+        }
+    }
+
+    /// Return the value if this is a `Str`
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            UiValue::Str(v) => Some(v),
+            _ => None
+        }
+    }
+
+    /// Return the identifier if this is an `Ident`
+    pub fn as_ident(&self) -> Option<&str> {
+        match self {
+            UiValue::Ident(v) => Some(v),
+            _ => None
+        }
+    }
+
+    /// Return the values if this is a `Tuple` or a `List`
+    pub fn as_slice(&self) -> Option<&[UiValue]> {
+        match self {
+            UiValue::Tuple(v) | UiValue::List(v) => Some(v),
+            _ => None
+        }
+    }
+
+    /// A short name of the value type, used in the error messages
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            UiValue::Bool(_) => "a boolean",
+            UiValue::Int(_) => "an integer",
+            UiValue::Str(_) => "a string",
+            UiValue::Ident(_) => "an identifier",
+            UiValue::Call(_, _) => "an identifier with arguments",
+            UiValue::Tuple(_) => "a tuple",
+            UiValue::List(_) => "a list",
+        }
+    }
+
+}
+
+
+/// A `name: value` parameter of a control, a layout or a layout item
+#[derive(Clone, Debug, PartialEq)]
+pub struct UiParam {
+    pub name: String,
+    pub value: UiValue,
+    pub position: UiPosition,
+}
+
+/// The `@layout(...)` part of a control. Equivalent to the `nwg_layout_item` attribute.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UiLayoutItem {
+    /// The name of the layout
+    pub layout: String,
+    pub params: Vec<UiParam>,
+    pub position: UiPosition,
+}
+
+/// A control or a layout in a UI description
+#[derive(Clone, Debug, PartialEq)]
+pub struct UiNode {
+    /// The name used to query the control once the ui is loaded
+    pub name: String,
+
+    /// The control type. Ex: `Button`
+    pub kind: String,
+
+    /// The builder parameters
+    pub params: Vec<UiParam>,
+
+    /// The layout the control is added to
+    pub layout_item: Option<UiLayoutItem>,
+
+    /// The nodes declared between the brackets. Their parent is this node.
+    pub children: Vec<UiNode>,
+
+    pub position: UiPosition,
+}
+
+impl UiNode {
+
+    /// Return the parameter named `name`
+    pub fn param(&self, name: &str) -> Option<&UiParam> {
+        self.params.iter().find(|p| p.name == name)
+    }
+
+    /// Return this node or the first of its descendants named `name`
+    pub fn find(&self, name: &str) -> Option<&UiNode> {
+        if self.name == name {
+            return Some(self);
+        }
+
+        self.children.iter().filter_map(|c| c.find(name)).next()
+    }
+
+}
+
+/**
+    A parsed UI description. See the `ui_loader` module documentation for the format.
+
+    ```rust
+    use native_windows_gui as nwg;
+
+    let source = r#"
+        window: Window(title: "Hello", size: (300, 100)) {
+            hello: Button(text: "Say hello")
+        }
+    "#;
+
+    let description = nwg::UiDescription::parse(source).unwrap();
+    assert!(description.validate().is_ok());
+    assert_eq!(description.find("hello").map(|n| n.kind.as_str()), Some("Button"));
+    ```
+*/
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct UiDescription {
+    /// The top level nodes
+    pub nodes: Vec<UiNode>,
+}
+
+impl UiDescription {
+
+    /// Parse a UI description. This only checks the syntax, use `validate` to check the controls and their parameters.
+    pub fn parse(source: &str) -> Result<UiDescription, UiParseError> {
+        let tokens = tokenize(source)?;
+        let mut parser = Parser { tokens, index: 0 };
+        let nodes = parser.nodes(false)?;
+        Ok(UiDescription { nodes })
+    }
+
+    /// Return the first node named `name`
+    pub fn find(&self, name: &str) -> Option<&UiNode> {
+        self.nodes.iter().filter_map(|n| n.find(name)).next()
+    }
+
+}
+
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Ident(String),
+    Int(i64),
+    Str(String),
+    Punct(char),
+    Eof,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Ident(i) => write!(f, "`{}`", i),
+            Token::Int(i) => write!(f, "`{}`", i),
+            Token::Str(s) => write!(f, "{:?}", s),
+            Token::Punct(c) => write!(f, "`{}`", c),
+            Token::Eof => write!(f, "the end of the file"),
+        }
+    }
+}
+
+struct Lexer<'a> {
+    chars: Peekable<Chars<'a>>,
+    line: usize,
+    column: usize,
+}
+
+impl<'a> Lexer<'a> {
+
+    fn position(&self) -> UiPosition {
+        UiPosition { line: self.line, column: self.column }
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.chars.peek().copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+
+        Some(c)
+    }
+
+    fn skip_whitespace_and_comments(&mut self) -> Result<(), UiParseError> {
+        loop {
+            match self.peek() {
+                Some(c) if c.is_whitespace() => { self.next(); },
+                Some('/') => {
+                    let position = self.position();
+                    self.next();
+                    if self.next() != Some('/') {
+                        return Err(UiParseError::new(position, "unexpected character `/`. Comments start with `//`"));
+                    }
+
+                    while !matches!(self.peek(), None | Some('\n')) {
+                        self.next();
+                    }
+                },
+                _ => return Ok(())
+            }
+        }
+    }
+
+    fn string(&mut self, position: UiPosition) -> Result<Token, UiParseError> {
+        let mut value = String::new();
+        loop {
+            match self.next() {
+                Some('"') => return Ok(Token::Str(value)),
+                Some('\\') => {
+                    let escape_position = self.position();
+                    match self.next() {
+                        Some('"') => value.push('"'),
+                        Some('\\') => value.push('\\'),
+                        Some('n') => value.push('\n'),
+                        Some('r') => value.push('\r'),
+                        Some('t') => value.push('\t'),
+                        Some(c) => return Err(UiParseError::new(escape_position, format!("unknown escape sequence `\\{}`", c))),
+                        None => return Err(UiParseError::new(position, "unterminated string")),
+                    }
+                },
+                Some(c) => value.push(c),
+                None => return Err(UiParseError::new(position, "unterminated string")),
+            }
+        }
+    }
+
+    fn integer(&mut self, position: UiPosition) -> Result<Token, UiParseError> {
+        let mut text = String::new();
+        if self.peek() == Some('-') {
+            self.next();
+            text.push('-');
+        }
+
+        while let Some(c) = self.peek().filter(|c| c.is_ascii_digit()) {
+            self.next();
+            text.push(c);
+        }
+
+        text.parse()
+            .map(Token::Int)
+            .map_err(|_| UiParseError::new(position, format!("invalid integer `{}`", text)))
+    }
+
+    fn ident(&mut self) -> Token {
+        let mut text = String::new();
+        while let Some(c) = self.peek().filter(|c| c.is_alphanumeric() || *c == '_') {
+            self.next();
+            text.push(c);
+        }
+
+        Token::Ident(text)
+    }
+
+}
+
+fn tokenize(source: &str) -> Result<Vec<(Token, UiPosition)>, UiParseError> {
+    let mut lexer = Lexer { chars: source.chars().peekable(), line: 1, column: 1 };
+    let mut tokens = Vec::new();
+
+    loop {
+        lexer.skip_whitespace_and_comments()?;
+
+        let position = lexer.position();
+        let token = match lexer.peek() {
+            None => {
+                tokens.push((Token::Eof, position));
+                return Ok(tokens);
+            },
+            Some('"') => {
+                lexer.next();
+                lexer.string(position)?
+            },
+            Some(c) if c == '-' || c.is_ascii_digit() => lexer.integer(position)?,
+            Some(c) if c.is_alphabetic() || c == '_' => lexer.ident(),
+            Some(c) if ":,(){}[]@".contains(c) => {
+                lexer.next();
+                Token::Punct(c)
+            },
+            Some(c) => {
+                return Err(UiParseError::new(position, format!("unexpected character `{}`", c)));
+            }
+        };
+
+        tokens.push((token, position));
+    }
+}
+
+
+struct Parser {
+    tokens: Vec<(Token, UiPosition)>,
+    index: usize,
+}
+
+impl Parser {
+
+    fn peek(&self) -> &Token {
+        &self.tokens[self.index].0
+    }
+
+    fn position(&self) -> UiPosition {
+        self.tokens[self.index].1
+    }
+
+    fn next(&mut self) -> (Token, UiPosition) {
+        let token = self.tokens[self.index].clone();
+        if self.index + 1 < self.tokens.len() {
+            self.index += 1;
+        }
+
+        token
+    }
+
+    fn unexpected(&self, expected: &str) -> UiParseError {
+        UiParseError::new(self.position(), format!("expected {}, found {}", expected, self.peek()))
+    }
+
+    fn eat(&mut self, punct: char) -> bool {
+        if *self.peek() == Token::Punct(punct) {
+            self.next();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, punct: char, expected: &str) -> Result<(), UiParseError> {
+        match self.eat(punct) {
+            true => Ok(()),
+            false => Err(self.unexpected(expected))
+        }
+    }
+
+    fn ident(&mut self, expected: &str) -> Result<(String, UiPosition), UiParseError> {
+        match self.peek().clone() {
+            Token::Ident(i) => Ok((i, self.next().1)),
+            _ => Err(self.unexpected(expected))
+        }
+    }
+
+    /// node* until the end of the file or, if `block` is true, until the closing `}`
+    fn nodes(&mut self, block: bool) -> Result<Vec<UiNode>, UiParseError> {
+        let mut nodes = Vec::new();
+        loop {
+            match self.peek() {
+                Token::Eof if block => return Err(self.unexpected("`}`")),
+                Token::Eof => return Ok(nodes),
+                Token::Punct('}') if block => {
+                    self.next();
+                    return Ok(nodes);
+                },
+                _ => nodes.push(self.node()?),
+            }
+        }
+    }
+
+    /// name ':' kind params? ('@' layout params?)? ('{' node* '}')?
+    fn node(&mut self) -> Result<UiNode, UiParseError> {
+        let (name, position) = self.ident("a control name")?;
+        self.expect(':', "`:` after the control name")?;
+        let (kind, _) = self.ident("a control type")?;
+
+        let params = match *self.peek() == Token::Punct('(') {
+            true => self.params()?,
+            false => Vec::new()
+        };
+
+        let mut layout_item = None;
+        if *self.peek() == Token::Punct('@') {
+            let (_, item_position) = self.next();
+            let (layout, _) = self.ident("a layout name after `@`")?;
+            let params = match *self.peek() == Token::Punct('(') {
+                true => self.params()?,
+                false => Vec::new()
+            };
+
+            layout_item = Some(UiLayoutItem { layout, params, position: item_position });
+        }
+
+        let children = match self.eat('{') {
+            true => self.nodes(true)?,
+            false => Vec::new()
+        };
+
+        Ok(UiNode { name, kind, params, layout_item, children, position })
+    }
+
+    /// '(' (name ':' value),* ')'
+    fn params(&mut self) -> Result<Vec<UiParam>, UiParseError> {
+        self.expect('(', "`(`")?;
+
+        let mut params = Vec::new();
+        loop {
+            if self.eat(')') {
+                return Ok(params);
+            }
+
+            let (name, position) = self.ident("a parameter name")?;
+            self.expect(':', "`:` after the parameter name")?;
+            let value = self.value()?;
+            params.push(UiParam { name, value, position });
+
+            if !self.eat(',') {
+                self.expect(')', "`,` or `)`")?;
+                return Ok(params);
+            }
+        }
+    }
+
+    /// value,* until `close`
+    fn values(&mut self, close: char) -> Result<Vec<UiValue>, UiParseError> {
+        let mut values = Vec::new();
+        loop {
+            if self.eat(close) {
+                return Ok(values);
+            }
+
+            values.push(self.value()?);
+
+            if !self.eat(',') {
+                self.expect(close, &format!("`,` or `{}`", close))?;
+                return Ok(values);
+            }
+        }
+    }
+
+    fn value(&mut self) -> Result<UiValue, UiParseError> {
+        match self.peek().clone() {
+            Token::Str(s) => {
+                self.next();
+                Ok(UiValue::Str(s))
+            },
+            Token::Int(i) => {
+                self.next();
+                Ok(UiValue::Int(i))
+            },
+            Token::Ident(i) => {
+                self.next();
+                match i.as_str() {
+                    "true" => Ok(UiValue::Bool(true)),
+                    "false" => Ok(UiValue::Bool(false)),
+                    _ if self.eat('(') => Ok(UiValue::Call(i, self.values(')')?)),
+                    _ => Ok(UiValue::Ident(i)),
+                }
+            },
+            Token::Punct('(') => {
+                self.next();
+                Ok(UiValue::Tuple(self.values(')')?))
+            },
+            Token::Punct('[') => {
+                self.next();
+                Ok(UiValue::List(self.values(']')?))
+            },
+            _ => Err(self.unexpected("a value"))
+        }
+    }
+
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pos(line: usize, column: usize) -> UiPosition {
+        UiPosition { line, column }
+    }
+
+    fn error(source: &str) -> UiParseError {
+        UiDescription::parse(source).expect_err("source should not parse")
+    }
+
+    #[test]
+    fn parse_empty() {
+        assert_eq!(UiDescription::parse("").unwrap().nodes, vec![]);
+        assert_eq!(UiDescription::parse("  // only a comment\n").unwrap().nodes, vec![]);
+    }
+
+    #[test]
+    fn parse_tree() {
+        let source = r#"
+            // The main window
+            window: Window(title: "Test", size: (300, -1), flags: "WINDOW|VISIBLE") {
+                grid: GridLayout(spacing: 2, column_policies: [Fixed(80), Auto])
+                name: TextInput(text: "a \"b\"\n") @grid(col: 1, row: 0, align: Center,)
+                ok: Button @grid
+            }
+            other: Window
+        "#;
+
+        let desc = UiDescription::parse(source).unwrap();
+        assert_eq!(desc.nodes.len(), 2);
+
+        let window = &desc.nodes[0];
+        assert_eq!(window.name, "window");
+        assert_eq!(window.kind, "Window");
+        assert_eq!(window.position, pos(3, 13));
+        assert_eq!(window.param("title").unwrap().value, UiValue::Str("Test".into()));
+        assert_eq!(window.param("size").unwrap().value, UiValue::Tuple(vec![UiValue::Int(300), UiValue::Int(-1)]));
+        assert_eq!(window.children.len(), 3);
+
+        let grid = &window.children[0];
+        assert_eq!(grid.param("column_policies").unwrap().value, UiValue::List(vec![
+            UiValue::Call("Fixed".into(), vec![UiValue::Int(80)]),
+            UiValue::Ident("Auto".into()),
+        ]));
+
+        let name = desc.find("name").unwrap();
+        assert_eq!(name.param("text").unwrap().value.as_str(), Some("a \"b\"\n"));
+
+        let item = name.layout_item.as_ref().unwrap();
+        assert_eq!(item.layout, "grid");
+        assert_eq!(item.params.len(), 3);
+        assert_eq!(item.params[2].value.as_ident(), Some("Center"));
+        assert_eq!(item.params[0].position, pos(5, 58));
+
+        let ok = desc.find("ok").unwrap();
+        assert!(ok.params.is_empty());
+        assert_eq!(ok.layout_item.as_ref().map(|i| i.params.len()), Some(0));
+
+        assert!(desc.nodes[1].children.is_empty());
+        assert!(desc.find("missing").is_none());
+    }
+
+    #[test]
+    fn parse_values() {
+        let desc = UiDescription::parse("a: B(x: true, y: false, z: (), w: [[1], (2, \"3\")], v: Weight())").unwrap();
+        let node = &desc.nodes[0];
+        assert_eq!(node.param("x").unwrap().value.as_bool(), Some(true));
+        assert_eq!(node.param("y").unwrap().value.as_bool(), Some(false));
+        assert_eq!(node.param("z").unwrap().value, UiValue::Tuple(vec![]));
+        assert_eq!(node.param("w").unwrap().value, UiValue::List(vec![
+            UiValue::List(vec![UiValue::Int(1)]),
+            UiValue::Tuple(vec![UiValue::Int(2), UiValue::Str("3".into())]),
+        ]));
+        assert_eq!(node.param("v").unwrap().value, UiValue::Call("Weight".into(), vec![]));
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(error("window Window"), UiParseError::new(pos(1, 8), "expected `:` after the control name, found `Window`"));
+        assert_eq!(error("window: 10"), UiParseError::new(pos(1, 9), "expected a control type, found `10`"));
+        assert_eq!(error("w: Window {\n  b: Button\n"), UiParseError::new(pos(3, 1), "expected `}`, found the end of the file"));
+        assert_eq!(error("w: Window(size: (1, 2)"), UiParseError::new(pos(1, 23), "expected `,` or `)`, found the end of the file"));
+        assert_eq!(error("w: Window(size: )"), UiParseError::new(pos(1, 17), "expected a value, found `)`"));
+        assert_eq!(error("w: Window(title: \"abc)"), UiParseError::new(pos(1, 18), "unterminated string"));
+        assert_eq!(error("w: Window(title: \"\\q\")"), UiParseError::new(pos(1, 20), "unknown escape sequence `\\q`"));
+        assert_eq!(error("w: Window(size: --1)"), UiParseError::new(pos(1, 17), "invalid integer `-`"));
+        assert_eq!(error("w: Window(size: 99999999999999999999)"), UiParseError::new(pos(1, 17), "invalid integer `99999999999999999999`"));
+        assert_eq!(error("w: Window / comment"), UiParseError::new(pos(1, 11), "unexpected character `/`. Comments start with `//`"));
+        assert_eq!(error("w: Window;"), UiParseError::new(pos(1, 10), "unexpected character `;`"));
+        assert_eq!(error("w: Button @ (col: 1)"), UiParseError::new(pos(1, 13), "expected a layout name after `@`, found `(`"));
+        assert_eq!(error("}"), UiParseError::new(pos(1, 1), "expected a control name, found `}`"));
+    }
+
+    #[test]
+    fn error_display() {
+        let e = UiParseError::new(pos(3, 14), "something went wrong");
+        assert_eq!(e.to_string(), "line 3, column 14: something went wrong");
+    }
+
+}
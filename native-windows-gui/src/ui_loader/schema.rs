@@ -0,0 +1,475 @@
+/*!
+    Validation of a parsed UI description: the known controls and layouts, their parameters and the type of the values.
+    This module is pure rust and does not depend on the windowing system.
+*/
+use super::parser::{UiDescription, UiNode, UiParam, UiValue, UiParseError};
+use std::collections::HashMap;
+
+
+/// The type of a parameter value
+#[derive(Copy, Clone, Debug)]
+pub(crate) enum ValueKind {
+    /// `true` or `false`
+    Bool,
+
+    /// A positive integer
+    Uint,
+
+    /// A string
+    Str,
+
+    /// A string of a single character
+    Char,
+
+    /// Two integers. Ex: a size or a position
+    Pair,
+
+    /// Two positive integers. Ex: a min size
+    UintPair,
+
+    /// Four positive integers. Ex: a margin
+    Margin,
+
+    /// Three integers between 0 and 255
+    Color,
+
+    /// A string of flag names separated by `|`. Ex: `"VISIBLE|TAB_STOP"`
+    Flags(&'static [&'static str]),
+
+    /// An identifier in the list
+    Enum(&'static [&'static str]),
+
+    /// A `GridPolicy`: `Fixed(n)`, `Weight(n)` or `Auto`
+    Policy,
+
+    /// A list of `GridPolicy`
+    PolicyList,
+
+    /// A list of strings
+    StrList,
+
+    /// A list of positive integers
+    UintList,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum NodeCategory {
+    /// A control. If `container` is true, the control can have children.
+    Control { container: bool },
+
+    /// A layout. Layouts must be the children of a control.
+    Layout,
+}
+
+/// The parameters accepted by a control or a layout
+pub(crate) struct NodeSchema {
+    pub kind: &'static str,
+    pub category: NodeCategory,
+    pub params: &'static [(&'static str, ValueKind)],
+
+    /// Parameters of the layout items. Only used by the layouts
+    pub item_params: &'static [(&'static str, ValueKind)],
+
+    /// Layout item parameters that must be set. Only used by the layouts
+    pub required_item_params: &'static [&'static str],
+}
+
+const WINDOW_FLAGS: &[&str] = &["MAIN_WINDOW", "WINDOW", "MINIMIZE_BOX", "MAXIMIZE_BOX", "SYS_MENU", "VISIBLE", "DISABLED", "MAXIMIZED", "MINIMIZED", "RESIZABLE", "POPUP"];
+const BUTTON_FLAGS: &[&str] = &["NONE", "VISIBLE", "DISABLED", "ICON", "BITMAP", "NOTIFY", "TAB_STOP"];
+const CHECK_BOX_FLAGS: &[&str] = &["NONE", "VISIBLE", "DISABLED", "TRISTATE", "PUSHLIKE", "TAB_STOP"];
+const RADIO_BUTTON_FLAGS: &[&str] = &["VISIBLE", "DISABLED", "TAB_STOP", "GROUP"];
+const TEXT_INPUT_FLAGS: &[&str] = &["VISIBLE", "DISABLED", "NUMBER", "AUTO_SCROLL", "TAB_STOP"];
+const LABEL_FLAGS: &[&str] = &["NONE", "VISIBLE", "DISABLED", "ELIPSIS"];
+const LIST_BOX_FLAGS: &[&str] = &["NONE", "VISIBLE", "DISABLED", "MULTI_SELECT", "NO_SELECT", "TAB_STOP"];
+
+const H_ALIGN: &[&str] = &["Left", "Center", "Right"];
+const V_ALIGN: &[&str] = &["Top", "Center", "Bottom"];
+const GRID_ALIGN: &[&str] = &["Fill", "Left", "Center", "Right"];
+const DOCK: &[&str] = &["Top", "Bottom", "Left", "Right", "Fill"];
+
+use self::ValueKind::*;
+
+/// Every node type supported by the loader
+pub(crate) const SCHEMAS: &[NodeSchema] = &[
+    NodeSchema {
+        kind: "Window",
+        category: NodeCategory::Control { container: true },
+        params: &[
+            ("title", Str), ("size", Pair), ("position", Pair), ("flags", Flags(WINDOW_FLAGS)), ("ex_flags", Uint),
+            ("accept_files", Bool), ("topmost", Bool), ("center", Bool), ("maximized", Bool), ("minimized", Bool),
+        ],
+        item_params: &[],
+        required_item_params: &[],
+    },
+    NodeSchema {
+        kind: "Button",
+        category: NodeCategory::Control { container: false },
+        params: &[
+            ("text", Str), ("size", Pair), ("position", Pair), ("flags", Flags(BUTTON_FLAGS)), ("ex_flags", Uint),
+            ("enabled", Bool), ("focus", Bool),
+        ],
+        item_params: &[],
+        required_item_params: &[],
+    },
+    NodeSchema {
+        kind: "CheckBox",
+        category: NodeCategory::Control { container: false },
+        params: &[
+            ("text", Str), ("size", Pair), ("position", Pair), ("flags", Flags(CHECK_BOX_FLAGS)), ("ex_flags", Uint),
+            ("enabled", Bool), ("focus", Bool), ("check_state", Enum(&["Checked", "Unchecked", "Indeterminate"])),
+            ("background_color", Color),
+        ],
+        item_params: &[],
+        required_item_params: &[],
+    },
+    NodeSchema {
+        kind: "RadioButton",
+        category: NodeCategory::Control { container: false },
+        params: &[
+            ("text", Str), ("size", Pair), ("position", Pair), ("flags", Flags(RADIO_BUTTON_FLAGS)), ("ex_flags", Uint),
+            ("focus", Bool), ("check_state", Enum(&["Checked", "Unchecked"])), ("background_color", Color),
+        ],
+        item_params: &[],
+        required_item_params: &[],
+    },
+    NodeSchema {
+        kind: "TextInput",
+        category: NodeCategory::Control { container: false },
+        params: &[
+            ("text", Str), ("placeholder_text", Str), ("size", Pair), ("position", Pair), ("flags", Flags(TEXT_INPUT_FLAGS)),
+            ("ex_flags", Uint), ("limit", Uint), ("password", Char), ("align", Enum(H_ALIGN)), ("readonly", Bool),
+            ("background_color", Color), ("focus", Bool),
+        ],
+        item_params: &[],
+        required_item_params: &[],
+    },
+    NodeSchema {
+        kind: "Label",
+        category: NodeCategory::Control { container: false },
+        params: &[
+            ("text", Str), ("size", Pair), ("position", Pair), ("flags", Flags(LABEL_FLAGS)), ("ex_flags", Uint),
+            ("background_color", Color), ("h_align", Enum(H_ALIGN)), ("v_align", Enum(V_ALIGN)),
+        ],
+        item_params: &[],
+        required_item_params: &[],
+    },
+    NodeSchema {
+        kind: "ListBox",
+        category: NodeCategory::Control { container: false },
+        params: &[
+            ("size", Pair), ("position", Pair), ("flags", Flags(LIST_BOX_FLAGS)), ("ex_flags", Uint),
+            ("collection", StrList), ("selected_index", Uint), ("multi_selection", UintList), ("enabled", Bool), ("focus", Bool),
+        ],
+        item_params: &[],
+        required_item_params: &[],
+    },
+    NodeSchema {
+        kind: "GridLayout",
+        category: NodeCategory::Layout,
+        params: &[
+            ("margin", Margin), ("spacing", Uint), ("min_size", UintPair), ("max_size", UintPair), ("max_column", Uint),
+            ("max_row", Uint), ("column_policies", PolicyList), ("row_policies", PolicyList),
+        ],
+        item_params: &[
+            ("col", Uint), ("row", Uint), ("col_span", Uint), ("row_span", Uint), ("align", Enum(GRID_ALIGN)),
+            ("size_hint", UintPair), ("col_policy", Policy), ("row_policy", Policy),
+        ],
+        required_item_params: &[],
+    },
+    NodeSchema {
+        kind: "DockLayout",
+        category: NodeCategory::Layout,
+        params: &[
+            ("margin", Margin), ("spacing", Uint), ("min_size", UintPair),
+        ],
+        item_params: &[
+            ("dock", Enum(DOCK)), ("size", Uint), ("min_size", Uint),
+        ],
+        required_item_params: &["dock"],
+    },
+];
+
+/// Return the schema of the node type `kind`
+pub(crate) fn schema(kind: &str) -> Option<&'static NodeSchema> {
+    SCHEMAS.iter().find(|s| s.kind == kind)
+}
+
+
+impl UiDescription {
+
+    /**
+        Check that every node of the description is a known control or layout, that their parameters exist and
+        have the right type, that the names are unique and that the layout items reference a layout declared
+        in the same parent.
+    */
+    pub fn validate(&self) -> Result<(), UiParseError> {
+        let mut names = HashMap::new();
+        for node in self.nodes.iter() {
+            let schema = node_schema(node)?;
+            if schema.kind != "Window" {
+                return Err(UiParseError::new(node.position, format!("`{}` must be declared inside a window", node.name)));
+            }
+
+            if let Some(item) = node.layout_item.as_ref() {
+                return Err(UiParseError::new(item.position, format!("no layout named `{}`", item.layout)));
+            }
+
+            validate_node(node, schema, &mut names)?;
+        }
+
+        Ok(())
+    }
+
+}
+
+fn node_schema(node: &UiNode) -> Result<&'static NodeSchema, UiParseError> {
+    schema(&node.kind)
+        .ok_or_else(|| UiParseError::new(node.position, format!("unknown control type `{}`", node.kind)))
+}
+
+fn validate_node(node: &UiNode, node_schema: &NodeSchema, names: &mut HashMap<String, usize>) -> Result<(), UiParseError> {
+    if let Some(line) = names.insert(node.name.clone(), node.position.line) {
+        return Err(UiParseError::new(node.position, format!("duplicate name `{}`, first declared on line {}", node.name, line)));
+    }
+
+    validate_params(&node.params, node_schema.params, &format!("`{}`", node.kind))?;
+
+    let container = match node_schema.category {
+        NodeCategory::Control { container } => container,
+        NodeCategory::Layout => {
+            if let Some(item) = node.layout_item.as_ref() {
+                return Err(UiParseError::new(item.position, format!("layout `{}` cannot be a layout item", node.name)));
+            }
+
+            false
+        },
+    };
+
+    if !container && !node.children.is_empty() {
+        return Err(UiParseError::new(node.children[0].position, format!("`{}` cannot have children", node.kind)));
+    }
+
+    for child in node.children.iter() {
+        validate_node(child, self::node_schema(child)?, names)?;
+
+        if let Some(item) = child.layout_item.as_ref() {
+            let layout_schema = node.children.iter()
+                .find(|n| n.name == item.layout)
+                .and_then(|n| schema(&n.kind))
+                .filter(|s| s.category == NodeCategory::Layout)
+                .ok_or_else(|| UiParseError::new(item.position, format!("no layout named `{}` in `{}`", item.layout, node.name)))?;
+
+            validate_params(&item.params, layout_schema.item_params, &format!("`{}` items", layout_schema.kind))?;
+
+            for &required in layout_schema.required_item_params {
+                if !item.params.iter().any(|p| p.name == required) {
+                    return Err(UiParseError::new(item.position, format!("missing `{}` parameter in {} item", required, layout_schema.kind)));
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn validate_params(params: &[UiParam], known: &[(&str, ValueKind)], owner: &str) -> Result<(), UiParseError> {
+    for (i, param) in params.iter().enumerate() {
+        let kind = known.iter()
+            .find(|(name, _)| *name == param.name)
+            .map(|(_, kind)| *kind)
+            .ok_or_else(|| UiParseError::new(param.position, format!("unknown parameter `{}` for {}", param.name, owner)))?;
+
+        if params[..i].iter().any(|p| p.name == param.name) {
+            return Err(UiParseError::new(param.position, format!("parameter `{}` is set more than once", param.name)));
+        }
+
+        validate_value(&param.value, kind)
+            .map_err(|message| UiParseError::new(param.position, format!("parameter `{}`: {}", param.name, message)))?;
+    }
+
+    Ok(())
+}
+
+fn validate_uint(value: &UiValue) -> Result<(), String> {
+    match value {
+        UiValue::Int(i) if *i >= 0 && *i <= u32::MAX as i64 => Ok(()),
+        UiValue::Int(i) => Err(format!("{} is not a positive 32 bits integer", i)),
+        v => Err(format!("expected a positive integer, found {}", v.type_name())),
+    }
+}
+
+fn validate_int(value: &UiValue) -> Result<(), String> {
+    match value {
+        UiValue::Int(i) if *i >= i32::MIN as i64 && *i <= i32::MAX as i64 => Ok(()),
+        UiValue::Int(i) => Err(format!("{} is not a 32 bits integer", i)),
+        v => Err(format!("expected an integer, found {}", v.type_name())),
+    }
+}
+
+fn validate_tuple<F>(value: &UiValue, len: usize, item: F) -> Result<(), String>
+    where F: Fn(&UiValue) -> Result<(), String>
+{
+    match value {
+        UiValue::Tuple(values) if values.len() == len => values.iter().try_for_each(item),
+        _ => Err(format!("expected a tuple of {} integers", len)),
+    }
+}
+
+fn validate_list<F>(value: &UiValue, item: F) -> Result<(), String>
+    where F: Fn(&UiValue) -> Result<(), String>
+{
+    match value {
+        UiValue::List(values) => values.iter().try_for_each(item),
+        v => Err(format!("expected a list, found {}", v.type_name())),
+    }
+}
+
+fn validate_policy(value: &UiValue) -> Result<(), String> {
+    match value {
+        UiValue::Ident(i) if i == "Auto" => Ok(()),
+        UiValue::Call(name, args) if name == "Fixed" || name == "Weight" => match args.as_slice() {
+            [arg] => validate_uint(arg),
+            _ => Err(format!("`{}` takes a single positive integer", name)),
+        },
+        _ => Err("expected `Fixed(n)`, `Weight(n)` or `Auto`".to_string()),
+    }
+}
+
+fn validate_value(value: &UiValue, kind: ValueKind) -> Result<(), String> {
+    match kind {
+        Bool => match value {
+            UiValue::Bool(_) => Ok(()),
+            v => Err(format!("expected `true` or `false`, found {}", v.type_name())),
+        },
+        Uint => validate_uint(value),
+        Str => match value {
+            UiValue::Str(_) => Ok(()),
+            v => Err(format!("expected a string, found {}", v.type_name())),
+        },
+        Char => match value.as_str() {
+            Some(s) if s.chars().count() == 1 => Ok(()),
+            _ => Err("expected a string of a single character".to_string()),
+        },
+        Pair => validate_tuple(value, 2, validate_int),
+        UintPair => validate_tuple(value, 2, validate_uint),
+        Margin => validate_tuple(value, 4, validate_uint),
+        Color => validate_tuple(value, 3, |v| match v {
+            UiValue::Int(i) if *i >= 0 && *i <= 255 => Ok(()),
+            _ => Err("color components must be integers between 0 and 255".to_string()),
+        }),
+        Flags(names) => match value {
+            UiValue::Str(s) => s.split('|').map(|f| f.trim()).try_for_each(|f| match names.contains(&f) {
+                true => Ok(()),
+                false => Err(format!("unknown flag `{}`. Expected one of: {}", f, names.join(", "))),
+            }),
+            v => Err(format!("expected a string of flags, found {}", v.type_name())),
+        },
+        Enum(names) => match value.as_ident() {
+            Some(i) if names.contains(&i) => Ok(()),
+            _ => Err(format!("expected one of: {}", names.join(", "))),
+        },
+        Policy => validate_policy(value),
+        PolicyList => validate_list(value, validate_policy),
+        StrList => validate_list(value, |v| match v {
+            UiValue::Str(_) => Ok(()),
+            v => Err(format!("expected a list of strings, found {}", v.type_name())),
+        }),
+        UintList => validate_list(value, validate_uint),
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::parser::UiPosition;
+
+    fn error(source: &str) -> UiParseError {
+        UiDescription::parse(source)
+            .expect("source should parse")
+            .validate()
+            .expect_err("source should not validate")
+    }
+
+    fn message(source: &str) -> String {
+        error(source).message
+    }
+
+    #[test]
+    fn validate_ok() {
+        let source = r#"
+            window: Window(title: "Test", size: (300, 200), position: (-10, 10), flags: "WINDOW | VISIBLE", center: true) {
+                grid: GridLayout(spacing: 2, margin: (1, 2, 3, 4), max_column: 2, column_policies: [Fixed(80), Weight(2), Auto])
+                dock: DockLayout(min_size: (100, 100))
+                name: TextInput(text: "x", password: "*", align: Right, background_color: (255, 0, 10)) @grid(col: 1, row: 0, align: Center, row_policy: Fixed(20))
+                status: Label(h_align: Left, v_align: Bottom) @dock(dock: Bottom, size: 20)
+                check: CheckBox(check_state: Indeterminate, flags: "VISIBLE|TRISTATE")
+                list: ListBox(collection: ["a", "b"], multi_selection: [0, 1])
+                popup: Window(flags: "POPUP") {
+                    radio: RadioButton(check_state: Checked)
+                    ok: Button(text: "Ok", enabled: false)
+                }
+            }
+        "#;
+
+        assert_eq!(UiDescription::parse(source).unwrap().validate(), Ok(()));
+    }
+
+    #[test]
+    fn validate_structure() {
+        assert_eq!(message("w: Window { b: Buton }"), "unknown control type `Buton`");
+        assert_eq!(message("b: Button"), "`b` must be declared inside a window");
+        assert_eq!(message("g: GridLayout"), "`g` must be declared inside a window");
+        assert_eq!(message("w: Window { b: Button { c: Button } }"), "`Button` cannot have children");
+        assert_eq!(message("w: Window { g: GridLayout { c: Button } }"), "`GridLayout` cannot have children");
+        assert_eq!(message("w: Window { b: Button \n b: Label }"), "duplicate name `b`, first declared on line 1");
+        assert_eq!(message("w: Window { w2: Window { w: Button } }"), "duplicate name `w`, first declared on line 1");
+
+        let e = error("w: Window {\n  b: Button\n  c: Button @grid\n}");
+        assert_eq!(e, UiParseError::new(UiPosition { line: 3, column: 13 }, "no layout named `grid` in `w`"));
+    }
+
+    #[test]
+    fn validate_layout_items() {
+        assert_eq!(message("w: Window { b: Button @b }"), "no layout named `b` in `w`");
+        assert_eq!(message("w: Window { g: GridLayout \n w2: Window { b: Button @g } }"), "no layout named `g` in `w2`");
+        assert_eq!(message("w: Window { g: GridLayout @g }"), "layout `g` cannot be a layout item");
+        assert_eq!(message("w: Window @g"), "no layout named `g`");
+        assert_eq!(message("w: Window { d: DockLayout \n b: Button @d(size: 10) }"), "missing `dock` parameter in DockLayout item");
+        assert_eq!(message("w: Window { d: DockLayout \n b: Button @d(dock: Center) }"), "parameter `dock`: expected one of: Top, Bottom, Left, Right, Fill");
+        assert_eq!(message("w: Window { g: GridLayout \n b: Button @g(dock: Top) }"), "unknown parameter `dock` for `GridLayout` items");
+    }
+
+    #[test]
+    fn validate_params() {
+        assert_eq!(message("w: Window(titel: \"a\")"), "unknown parameter `titel` for `Window`");
+        assert_eq!(message("w: Window(title: \"a\", title: \"b\")"), "parameter `title` is set more than once");
+        assert_eq!(message("w: Window(title: 10)"), "parameter `title`: expected a string, found an integer");
+        assert_eq!(message("w: Window(center: \"yes\")"), "parameter `center`: expected `true` or `false`, found a string");
+        assert_eq!(message("w: Window(size: (1, 2, 3))"), "parameter `size`: expected a tuple of 2 integers");
+        assert_eq!(message("w: Window(size: (1, 99999999999))"), "parameter `size`: 99999999999 is not a 32 bits integer");
+        assert_eq!(message("w: Window(ex_flags: -1)"), "parameter `ex_flags`: -1 is not a positive 32 bits integer");
+        assert_eq!(message("w: Window(flags: \"WINDOW|VISBLE\")"), "parameter `flags`: unknown flag `VISBLE`. Expected one of: MAIN_WINDOW, WINDOW, MINIMIZE_BOX, MAXIMIZE_BOX, SYS_MENU, VISIBLE, DISABLED, MAXIMIZED, MINIMIZED, RESIZABLE, POPUP");
+        assert_eq!(message("w: Window { t: TextInput(password: \"ab\") }"), "parameter `password`: expected a string of a single character");
+        assert_eq!(message("w: Window { l: Label(background_color: (0, 0, 256)) }"), "parameter `background_color`: color components must be integers between 0 and 255");
+        assert_eq!(message("w: Window { l: ListBox(collection: [\"a\", 1]) }"), "parameter `collection`: expected a list of strings, found an integer");
+        assert_eq!(message("w: Window { g: GridLayout(row_policies: [Fixed(1, 2)]) }"), "parameter `row_policies`: `Fixed` takes a single positive integer");
+        assert_eq!(message("w: Window { g: GridLayout(row_policies: [Stretch]) }"), "parameter `row_policies`: expected `Fixed(n)`, `Weight(n)` or `Auto`");
+        assert_eq!(message("w: Window { g: GridLayout(margin: 5) }"), "parameter `margin`: expected a tuple of 4 integers");
+
+        let e = error("w: Window(\n  title: 1\n)");
+        assert_eq!(e.position, UiPosition { line: 2, column: 3 });
+    }
+
+    #[test]
+    fn schema_lookup() {
+        assert!(schema("Window").is_some());
+        assert_eq!(schema("GridLayout").map(|s| s.category), Some(NodeCategory::Layout));
+        assert!(schema("window").is_none());
+
+        for s in SCHEMAS {
+            assert_eq!(schema(s.kind).map(|s2| s2.kind), Some(s.kind), "duplicate schema for {}", s.kind);
+        }
+    }
+
+}
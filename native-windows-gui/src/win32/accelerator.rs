@@ -0,0 +1,101 @@
+/*!
+    Accelerator tables of the current thread. The tables are translated by the dispatch loops before `IsDialogMessage`.
+*/
+use winapi::shared::windef::{HACCEL, HWND};
+use winapi::um::winuser::{MSG, ACCEL, FVIRTKEY, FCONTROL, FSHIFT, FALT, GetAncestor, GA_ROOT};
+use crate::controls::ControlHandle;
+use crate::{NwgError, Shortcut, ShortcutModifiers};
+use std::{cell::RefCell, collections::HashMap};
+
+
+struct RegisteredTable {
+    window: HWND,
+    table: HACCEL,
+
+    /// Commands that raise `OnMenuItemSelected` on a menu item instead of `OnAccelerator`
+    menu_items: HashMap<u16, ControlHandle>,
+}
+
+thread_local! {
+    static TABLES: RefCell<Vec<RegisteredTable>> = const { RefCell::new(Vec::new()) };
+}
+
+
+/// Creates an accelerator table for a top level window and registers it in the current thread
+pub(crate) fn create_table(window: HWND, entries: &[(Shortcut, u16)], menu_items: HashMap<u16, ControlHandle>) -> Result<HACCEL, NwgError> {
+    use winapi::um::winuser::CreateAcceleratorTableW;
+
+    let mut accels: Vec<ACCEL> = entries.iter()
+        .map(|(shortcut, cmd)| {
+            let mut virt = FVIRTKEY;
+            if shortcut.modifiers.contains(ShortcutModifiers::CTRL) { virt |= FCONTROL; }
+            if shortcut.modifiers.contains(ShortcutModifiers::SHIFT) { virt |= FSHIFT; }
+            if shortcut.modifiers.contains(ShortcutModifiers::ALT) { virt |= FALT; }
+            ACCEL { fVirt: virt, key: shortcut.key as u16, cmd: *cmd }
+        })
+        .collect();
+
+    if accels.is_empty() {
+        return Err(NwgError::resource_create("An accelerator table must have at least one shortcut"));
+    }
+
+    let table = unsafe { CreateAcceleratorTableW(accels.as_mut_ptr(), accels.len() as _) };
+    if table.is_null() {
+        return Err(NwgError::resource_create("Failed to create the accelerator table"));
+    }
+
+    TABLES.with(|tables| tables.borrow_mut().push(RegisteredTable { window, table, menu_items }));
+
+    Ok(table)
+}
+
+/// Unregisters and destroys an accelerator table
+pub(crate) fn destroy_table(table: HACCEL) {
+    use winapi::um::winuser::DestroyAcceleratorTable;
+
+    if table.is_null() {
+        return;
+    }
+
+    TABLES.with(|tables| tables.borrow_mut().retain(|t| t.table != table));
+    unsafe { DestroyAcceleratorTable(table); }
+}
+
+/// Returns the menu item bound to an accelerator command of a window
+pub(crate) fn menu_item_command(window: HWND, cmd: u16) -> Option<ControlHandle> {
+    TABLES.with(|tables| {
+        tables.borrow().iter()
+            .filter(|t| t.window == window)
+            .find_map(|t| t.menu_items.get(&cmd).copied())
+    })
+}
+
+/**
+    Translates a keyboard message using the accelerator tables of the top level window of the message.
+    Returns `true` if the message was translated into a `WM_COMMAND` and must not be dispatched.
+*/
+pub(crate) unsafe fn translate(msg: &mut MSG) -> bool {
+    use winapi::um::winuser::TranslateAcceleratorW;
+
+    if msg.hwnd.is_null() {
+        return false;
+    }
+
+    if TABLES.with(|tables| tables.borrow().is_empty()) {
+        return false;
+    }
+
+    // The tables are copied because the `WM_COMMAND` handlers may create or destroy tables
+    let window = GetAncestor(msg.hwnd, GA_ROOT);
+    let tables: Vec<HACCEL> = TABLES.with(|tables| {
+        tables.borrow().iter().filter(|t| t.window == window).map(|t| t.table).collect()
+    });
+
+    for table in tables {
+        if TranslateAcceleratorW(window, table, msg) != 0 {
+            return true;
+        }
+    }
+
+    false
+}
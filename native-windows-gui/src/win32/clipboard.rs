@@ -1,8 +1,16 @@
 use crate::controls::ControlHandle;
+use crate::Bitmap;
 use super::base_helper::{to_utf16};
-use winapi::um::winuser::{CF_BITMAP, CF_TEXT, CF_UNICODETEXT};
+use crate::clipboard_formats::{self as formats, ClipboardHtml};
+use winapi::um::winuser::{CF_BITMAP, CF_TEXT, CF_UNICODETEXT, CF_HDROP, CF_DIB};
 use winapi::um::winnt::HANDLE;
 
+#[cfg(feature = "image-decoder")]
+use crate::{ImageDecoder, ImageData};
+
+#[cfg(feature = "clipboard-json")]
+use crate::NwgError;
+
 
 #[derive(Copy, Clone)]
 pub enum ClipboardFormat {
@@ -15,6 +23,21 @@ pub enum ClipboardFormat {
     /// A bitmap file
     Bitmap,
 
+    /// A device independent bitmap. The system converts it from and to `Bitmap`.
+    Dib,
+
+    /// A png image, as written by the browsers and the image editors
+    Png,
+
+    /// An html fragment with a header that locates the fragment in its document. See `Clipboard::data_html`.
+    Html,
+
+    /// Rich Text Format
+    Rtf,
+
+    /// A list of files, as copied by the Windows explorer
+    FileList,
+
     /// Global clipboard format to share data between applications
     /// The format name comparison is case-insensitive.
     Global(&'static str)
@@ -23,7 +46,7 @@ pub enum ClipboardFormat {
 
 impl ClipboardFormat {
 
-    fn into_raw(&self) -> u32 {
+    pub(crate) fn into_raw(&self) -> u32 {
         use ClipboardFormat::*;
         use winapi::um::winuser::RegisterClipboardFormatW;
 
@@ -31,6 +54,11 @@ impl ClipboardFormat {
             Text => CF_TEXT,
             UnicodeText => CF_UNICODETEXT,
             Bitmap => CF_BITMAP,
+            Dib => CF_DIB,
+            Png => ClipboardFormat::Global("PNG").into_raw(),
+            Html => ClipboardFormat::Global("HTML Format").into_raw(),
+            Rtf => ClipboardFormat::Global("Rich Text Format").into_raw(),
+            FileList => CF_HDROP,
             Global(v) => unsafe {
                 let v = to_utf16(v);
                 RegisterClipboardFormatW(v.as_ptr())
@@ -141,6 +169,33 @@ fn read_custom_data_handle(window: &nwg::Window) -> Option<Hello> {
 }
 
 ```
+
+Rich formats
+
+The html, rtf, file list and image formats have typed readers and writers. Many applications write the same data in more
+than one format. `Clipboard::writer` writes multiple formats in a single clipboard operation.
+
+```rust
+use native_windows_gui as nwg;
+
+fn copy_link(window: &nwg::Window) {
+    nwg::Clipboard::writer(window)
+        .text("NWG")
+        .html("<a href=\"https://github.com/gabdube/native-windows-gui\">NWG</a>", None);
+}
+
+fn paste_files(window: &nwg::Window) -> Vec<String> {
+    match nwg::Clipboard::data_files(window) {
+        Some(files) => files,
+        None => match nwg::Clipboard::data_html(window) {
+            Some(html) => vec![html.fragment],
+            None => Vec::new()
+        }
+    }
+}
+```
+
+A window registered with `Clipboard::add_listener` receives the `OnClipboardUpdate` event each time the clipboard content changes.
 */
 pub struct Clipboard;
 
@@ -220,6 +275,191 @@ impl Clipboard {
         data
     }
 
+    /**
+        Opens and empties the clipboard, and returns a writer that places data on the clipboard in one or more formats.
+        The clipboard is closed when the writer is dropped.
+
+        This function will panic if the control is not HWND based.
+    */
+    pub fn writer<C: Into<ControlHandle>>(handle: C) -> ClipboardWriter {
+        Clipboard::open(handle);
+        Clipboard::empty();
+        ClipboardWriter { _private: () }
+    }
+
+    /**
+        Fill the clipboard with an html fragment. See `ClipboardWriter::html`.
+
+        This is a high level function that handles `open` and `close`
+    */
+    pub fn set_data_html<C: Into<ControlHandle>>(handle: C, fragment: &str, source_url: Option<&str>) {
+        Clipboard::writer(handle).html(fragment, source_url);
+    }
+
+    /**
+        Return the html in the clipboard. Returns `None` if the clipboard has no html or if the html header is invalid.
+
+        This is a high level function that handles `open` and `close`
+    */
+    pub fn data_html<C: Into<ControlHandle>>(handle: C) -> Option<ClipboardHtml> {
+        Clipboard::data_bytes(handle, ClipboardFormat::Html)
+            .and_then(|data| formats::decode_html(&data))
+    }
+
+    /**
+        Fill the clipboard with a rich text document.
+
+        This is a high level function that handles `open` and `close`
+    */
+    pub fn set_data_rtf<C: Into<ControlHandle>>(handle: C, rtf: &str) {
+        Clipboard::writer(handle).rtf(rtf);
+    }
+
+    /**
+        Return the rich text document in the clipboard.
+
+        This is a high level function that handles `open` and `close`
+    */
+    pub fn data_rtf<C: Into<ControlHandle>>(handle: C) -> Option<String> {
+        Clipboard::data_bytes(handle, ClipboardFormat::Rtf)
+            .map(|data| String::from_utf8_lossy(until_null(&data)).into_owned())
+    }
+
+    /**
+        Fill the clipboard with a list of file paths. The files can then be pasted in the Windows explorer.
+
+        This is a high level function that handles `open` and `close`
+    */
+    pub fn set_data_files<C: Into<ControlHandle>, S: AsRef<str>>(handle: C, files: &[S]) {
+        Clipboard::writer(handle).files(files);
+    }
+
+    /**
+        Return the paths of the files in the clipboard.
+
+        This is a high level function that handles `open` and `close`
+    */
+    pub fn data_files<C: Into<ControlHandle>>(handle: C) -> Option<Vec<String>> {
+        Clipboard::data_bytes(handle, ClipboardFormat::FileList)
+            .and_then(|data| formats::decode_file_list(&data))
+    }
+
+    /**
+        Fill the clipboard with a copy of a bitmap. Other applications can read the image as a `Bitmap` or as a `Dib`.
+
+        This is a high level function that handles `open` and `close`
+    */
+    pub fn set_data_bitmap<C: Into<ControlHandle>>(handle: C, bitmap: &Bitmap) {
+        Clipboard::writer(handle).bitmap(bitmap);
+    }
+
+    /**
+        Return a copy of the image in the clipboard. The returned bitmap is owned.
+        The image must be in the `Bitmap` or the `Dib` format. See `data_image` to read png images.
+
+        This is a high level function that handles `open` and `close`
+    */
+    pub fn data_bitmap<C: Into<ControlHandle>>(handle: C) -> Option<Bitmap> {
+        use winapi::um::winuser::{GetClipboardData, CopyImage, IMAGE_BITMAP, LR_CREATEDIBSECTION};
+        use std::ptr;
+
+        Clipboard::open(handle);
+
+        let bitmap = unsafe {
+            let handle = GetClipboardData(CF_BITMAP);
+            match handle.is_null() {
+                true => ptr::null_mut(),
+                false => CopyImage(handle, IMAGE_BITMAP, 0, 0, LR_CREATEDIBSECTION)
+            }
+        };
+
+        Clipboard::close();
+
+        match bitmap.is_null() {
+            true => None,
+            false => Some(Bitmap { handle: bitmap as _, owned: true })
+        }
+    }
+
+    /**
+        Decode the image in the clipboard. The `Png` format is used first because it keeps the transparency,
+        then the `Dib` format.
+
+        To put an `ImageData` on the clipboard, convert it with `ImageData::as_bitmap` and use `set_data_bitmap`.
+
+        This is a high level function that handles `open` and `close`. Requires the `image-decoder` feature.
+    */
+    #[cfg(feature = "image-decoder")]
+    pub fn data_image<C: Into<ControlHandle>>(handle: C, decoder: &ImageDecoder) -> Option<ImageData> {
+        let handle = handle.into();
+        let file = match Clipboard::data_bytes(handle, ClipboardFormat::Png) {
+            Some(png) => png,
+            None => Clipboard::data_bytes(handle, ClipboardFormat::Dib).and_then(|dib| formats::dib_to_bmp(&dib))?
+        };
+
+        decoder.from_stream(&file)
+            .and_then(|image| image.frame(0))
+            .ok()
+    }
+
+    /**
+        Fill the clipboard with a copy of `data`. Unlike `set_data`, the data does not need to be `Copy`.
+
+        This is a high level function that handles `open` and `close`
+    */
+    pub fn set_data_bytes<C: Into<ControlHandle>>(handle: C, fmt: ClipboardFormat, data: &[u8]) {
+        Clipboard::writer(handle).bytes(fmt, data);
+    }
+
+    /**
+        Return a copy of the clipboard data in the selected format. The size of the data is rounded up by the system,
+        so it may be followed by padding bytes.
+
+        This is a high level function that handles `open` and `close`
+    */
+    pub fn data_bytes<C: Into<ControlHandle>>(handle: C, fmt: ClipboardFormat) -> Option<Vec<u8>> {
+        Clipboard::open(handle);
+        let data = unsafe { read_bytes(fmt.into_raw()) };
+        Clipboard::close();
+
+        data
+    }
+
+    /**
+        Read a value serialized with `ClipboardWriter::json`. Returns `None` if the clipboard does not have the format
+        or if the data cannot be deserialized into `T`.
+
+        This is a high level function that handles `open` and `close`. Requires the `clipboard-json` feature.
+    */
+    #[cfg(feature = "clipboard-json")]
+    pub fn data_json<C: Into<ControlHandle>, T: serde::de::DeserializeOwned>(handle: C, fmt: ClipboardFormat) -> Option<T> {
+        let data = Clipboard::data_bytes(handle, fmt)?;
+        serde_json::from_slice(until_null(&data)).ok()
+    }
+
+    /**
+        Send the `OnClipboardUpdate` event to a window each time the content of the clipboard changes.
+        Returns `false` if the window could not be registered.
+
+        This function will panic if the control is not HWND based.
+    */
+    pub fn add_listener<C: Into<ControlHandle>>(handle: C) -> bool {
+        use winapi::um::winuser::AddClipboardFormatListener;
+        let handle = handle.into().hwnd().expect("Control should be a window");
+        unsafe { AddClipboardFormatListener(handle) != 0 }
+    }
+
+    /**
+        Stop sending the `OnClipboardUpdate` event to a window registered with `add_listener`.
+
+        This function will panic if the control is not HWND based.
+    */
+    pub fn remove_listener<C: Into<ControlHandle>>(handle: C) {
+        use winapi::um::winuser::RemoveClipboardFormatListener;
+        let handle = handle.into().hwnd().expect("Control should be a window");
+        unsafe { RemoveClipboardFormatListener(handle); }
+    }
+
     /**
         Remove the current data in the clipboard
     */
@@ -373,6 +613,154 @@ impl Clipboard {
 }
 
 
+/**
+    Places data on the clipboard in one or more formats. Returned by `Clipboard::writer`.
+
+    Each call replaces the data previously written in the same format. The clipboard is closed when the writer is dropped.
+*/
+pub struct ClipboardWriter {
+    _private: ()
+}
+
+impl ClipboardWriter {
+
+    /// Write unicode text
+    pub fn text(&mut self, text: &str) -> &mut ClipboardWriter {
+        self.bytes(ClipboardFormat::UnicodeText, &formats::encode_text(text))
+    }
+
+    /**
+        Write an html fragment. The fragment is wrapped in a html document and the clipboard header is generated.
+        `source_url` is the address of the document the fragment was copied from.
+    */
+    pub fn html(&mut self, fragment: &str, source_url: Option<&str>) -> &mut ClipboardWriter {
+        let html = formats::encode_html(fragment, source_url);
+        self.bytes(ClipboardFormat::Html, &formats::null_terminated(&html))
+    }
+
+    /// Write a rich text document
+    pub fn rtf(&mut self, rtf: &str) -> &mut ClipboardWriter {
+        self.bytes(ClipboardFormat::Rtf, &formats::null_terminated(rtf))
+    }
+
+    /// Write a list of file paths
+    pub fn files<S: AsRef<str>>(&mut self, files: &[S]) -> &mut ClipboardWriter {
+        self.bytes(ClipboardFormat::FileList, &formats::encode_file_list(files))
+    }
+
+    /// Write a copy of a bitmap
+    pub fn bitmap(&mut self, bitmap: &Bitmap) -> &mut ClipboardWriter {
+        use winapi::um::winuser::{SetClipboardData, CopyImage, IMAGE_BITMAP};
+        use winapi::um::wingdi::DeleteObject;
+
+        unsafe {
+            // The clipboard owns the bitmap once it is set
+            let copy = CopyImage(bitmap.handle as _, IMAGE_BITMAP, 0, 0, 0);
+            if !copy.is_null() && SetClipboardData(CF_BITMAP, copy).is_null() {
+                DeleteObject(copy as _);
+            }
+        }
+
+        self
+    }
+
+    /// Write a png image. `png` is the content of a png file.
+    pub fn png(&mut self, png: &[u8]) -> &mut ClipboardWriter {
+        self.bytes(ClipboardFormat::Png, png)
+    }
+
+    /// Write a copy of `data` in the selected format
+    pub fn bytes(&mut self, fmt: ClipboardFormat, data: &[u8]) -> &mut ClipboardWriter {
+        unsafe { write_bytes(fmt.into_raw(), data); }
+        self
+    }
+
+    /**
+        Write a value serialized in json in a custom format. Read it back with `Clipboard::data_json`.
+
+        Requires the `clipboard-json` feature.
+    */
+    #[cfg(feature = "clipboard-json")]
+    pub fn json<T: serde::Serialize>(&mut self, fmt: ClipboardFormat, value: &T) -> Result<&mut ClipboardWriter, NwgError> {
+        let data = serde_json::to_vec(value)
+            .map_err(|e| NwgError::resource_create(format!("Failed to serialize the clipboard data: {}", e)))?;
+
+        Ok(self.bytes(fmt, &data))
+    }
+
+    /// Close the clipboard. Same as dropping the writer.
+    pub fn close(self) { /* See drop implementation */ }
+
+}
+
+impl Drop for ClipboardWriter {
+    fn drop(&mut self) {
+        Clipboard::close();
+    }
+}
+
+
+/// Copies `data` in a global allocation and places it on the clipboard. The clipboard must be open.
+unsafe fn write_bytes(fmt: u32, data: &[u8]) {
+    use winapi::um::winuser::SetClipboardData;
+    use winapi::um::winbase::GlobalFree;
+
+    let alloc = global_alloc(data);
+    if !alloc.is_null() && SetClipboardData(fmt, alloc).is_null() {
+        GlobalFree(alloc);
+    }
+}
+
+/// Copies the clipboard data of a format. The clipboard must be open.
+unsafe fn read_bytes(fmt: u32) -> Option<Vec<u8>> {
+    use winapi::um::winuser::GetClipboardData;
+    global_bytes(GetClipboardData(fmt))
+}
+
+/// Copies `data` in a new global allocation. Returns a null handle if the allocation failed.
+pub(crate) unsafe fn global_alloc(data: &[u8]) -> HANDLE {
+    use winapi::um::winbase::{GlobalAlloc, GlobalLock, GlobalUnlock, GMEM_MOVEABLE};
+    use std::ptr;
+
+    let alloc = GlobalAlloc(GMEM_MOVEABLE, data.len().max(1) as _);
+    if alloc.is_null() {
+        return alloc;
+    }
+
+    ptr::copy_nonoverlapping(data.as_ptr(), GlobalLock(alloc) as *mut u8, data.len());
+    GlobalUnlock(alloc);
+
+    alloc
+}
+
+/// Copies the content of a global allocation
+pub(crate) unsafe fn global_bytes(handle: HANDLE) -> Option<Vec<u8>> {
+    use winapi::um::winbase::{GlobalLock, GlobalUnlock, GlobalSize};
+    use std::slice::from_raw_parts;
+
+    if handle.is_null() {
+        return None;
+    }
+
+    let data = GlobalLock(handle) as *const u8;
+    if data.is_null() {
+        return None;
+    }
+
+    let bytes = from_raw_parts(data, GlobalSize(handle) as usize).to_vec();
+    GlobalUnlock(handle);
+
+    Some(bytes)
+}
+
+/// The text formats end with a null byte
+pub(crate) fn until_null(data: &[u8]) -> &[u8] {
+    match data.iter().position(|&b| b == 0) {
+        Some(end) => &data[..end],
+        None => data
+    }
+}
+
 unsafe fn from_wide_ptr(ptr: *const u16) -> Option<String> {
     use std::slice::from_raw_parts;
     use std::ffi::OsString;
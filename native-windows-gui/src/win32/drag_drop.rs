@@ -0,0 +1,701 @@
+/*!
+    OLE drag and drop. The COM objects are implemented by hand because the winapi declarations of
+    `IDropTarget` and `STGMEDIUM` do not match the system ABI, and `IDropSource` is not declared.
+*/
+use winapi::shared::guiddef::{GUID, REFIID, IsEqualGUID};
+use winapi::shared::minwindef::{DWORD, ULONG, BOOL, UINT, LPARAM};
+use winapi::shared::windef::{HWND, POINTL, POINT};
+use winapi::shared::winerror::{HRESULT, S_OK, E_NOINTERFACE, E_NOTIMPL, E_INVALIDARG};
+use winapi::shared::wtypes::DVASPECT_CONTENT;
+use winapi::um::objidl::{IDataObject, FORMATETC, IEnumFORMATETC, TYMED_HGLOBAL};
+use winapi::um::unknwnbase::IUnknown;
+use winapi::um::winnt::HANDLE;
+use winapi::ctypes::c_void;
+use winapi::Interface;
+use super::clipboard::{global_alloc, global_bytes, until_null};
+use crate::clipboard_formats::{self as formats, ClipboardHtml};
+use super::window_helper::{self as wh, NWG_DRAG_DROP};
+use crate::{ClipboardFormat, NwgError};
+use std::{ptr, cell::Cell};
+
+
+const DRAGDROP_S_DROP: HRESULT = 0x0004_0100;
+const DRAGDROP_S_CANCEL: HRESULT = 0x0004_0101;
+const DRAGDROP_S_USEDEFAULTCURSORS: HRESULT = 0x0004_0102;
+const DATA_S_SAMEFORMATETC: HRESULT = 0x0004_0130;
+const DV_E_FORMATETC: HRESULT = 0x8004_0064_u32 as HRESULT;
+const OLE_E_ADVISENOTSUPPORTED: HRESULT = 0x8004_0003_u32 as HRESULT;
+
+const IID_IDROPSOURCE: GUID = GUID { Data1: 0x00000121, Data2: 0x0000, Data3: 0x0000, Data4: [0xc0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x46] };
+
+/// The kind of drag event sent to the window in the `w` parameter of `NWG_DRAG_DROP`
+pub(crate) const DRAG_ENTER: usize = 0;
+pub(crate) const DRAG_OVER: usize = 1;
+pub(crate) const DRAG_LEAVE: usize = 2;
+pub(crate) const DRAG_DROP: usize = 3;
+
+/// `STGMEDIUM` with the union inlined. Every member of the union is a handle or a pointer.
+#[repr(C)]
+struct StgMedium {
+    tymed: DWORD,
+    handle: HANDLE,
+    unk_for_release: *mut IUnknown,
+}
+
+// Those functions are not declared by winapi. ole32 and shell32 are linked by the "ole2" and "shellapi" features.
+extern "system" {
+    fn DoDragDrop(data: *mut IDataObject, source: *mut IUnknown, ok_effects: DWORD, effect: *mut DWORD) -> HRESULT;
+    fn ReleaseStgMedium(medium: *mut StgMedium);
+    fn SHCreateStdEnumFmtEtc(count: UINT, formats: *const FORMATETC, enum_format: *mut *mut IEnumFORMATETC) -> HRESULT;
+}
+
+
+bitflags! {
+    /**
+        The operations of a drag and drop. The source allows one or more effects and the target picks one of them.
+
+        * NONE: The drop is refused
+        * COPY: The data is copied
+        * MOVE: The data is copied and the source removes it
+        * LINK: The target creates a link to the source data
+    */
+    pub struct DropEffect: u32 {
+        const NONE = 0;
+        const COPY = 1;
+        const MOVE = 2;
+        const LINK = 4;
+    }
+}
+
+/// The state of a drag operation over a drop target. Shared with the event handlers through `DragDropData`.
+pub(crate) struct DragDropState {
+    data: *mut IDataObject,
+    point: [i32; 2],
+    key_state: u32,
+    allowed: DropEffect,
+    effect: DropEffect,
+}
+
+/**
+    The data of the `OnDragEnter`, `OnDragOver`, `OnDragLeave` and `OnDragDrop` events.
+
+    The handlers of `OnDragEnter` and `OnDragOver` pick the effect shown to the user with `set_effect`.
+    The effect picked in `OnDragDrop` is returned to the drag source. The dragged data is not available in `OnDragLeave`.
+*/
+pub struct DragDropData {
+    pub(crate) inner: *mut DragDropState,
+}
+
+impl DragDropData {
+
+    fn state(&self) -> &DragDropState {
+        unsafe { &*self.inner }
+    }
+
+    /// The position of the mouse, local to the drop target window
+    pub fn point(&self) -> [i32; 2] {
+        self.state().point
+    }
+
+    /// The state of the mouse buttons and of the modifier keys (`MK_CONTROL`, `MK_SHIFT`, `MK_ALT`, `MK_LBUTTON`, ...)
+    pub fn key_state(&self) -> u32 {
+        self.state().key_state
+    }
+
+    /// The effects allowed by the drag source
+    pub fn allowed_effects(&self) -> DropEffect {
+        self.state().allowed
+    }
+
+    /// The effect picked by the drop target. Starts with the effect picked in the previous event.
+    pub fn effect(&self) -> DropEffect {
+        self.state().effect
+    }
+
+    /// Pick the effect of the drop. Effects not allowed by the source are ignored. Use `DropEffect::NONE` to refuse the drop.
+    pub fn set_effect(&self, effect: DropEffect) {
+        unsafe { (*self.inner).effect = effect & (*self.inner).allowed; }
+    }
+
+    /// Check if the dragged data is available in the selected format
+    pub fn has_format(&self, fmt: ClipboardFormat) -> bool {
+        let data = self.state().data;
+        if data.is_null() {
+            return false;
+        }
+
+        let format = format_etc(fmt.into_raw());
+        unsafe { (*data).QueryGetData(&format) == S_OK }
+    }
+
+    /// Return a copy of the dragged data in the selected format
+    pub fn bytes(&self, fmt: ClipboardFormat) -> Option<Vec<u8>> {
+        let data = self.state().data;
+        if data.is_null() {
+            return None;
+        }
+
+        let format = format_etc(fmt.into_raw());
+        let mut medium = StgMedium { tymed: 0, handle: ptr::null_mut(), unk_for_release: ptr::null_mut() };
+
+        unsafe {
+            if (*data).GetData(&format, &mut medium as *mut StgMedium as _) != S_OK {
+                return None;
+            }
+
+            let bytes = match medium.tymed {
+                TYMED_HGLOBAL => global_bytes(medium.handle),
+                _ => None
+            };
+
+            ReleaseStgMedium(&mut medium);
+            bytes
+        }
+    }
+
+    /// Return the dragged text
+    pub fn text(&self) -> Option<String> {
+        match self.bytes(ClipboardFormat::UnicodeText) {
+            Some(data) => Some(formats::decode_text(&data)),
+            None => self.bytes(ClipboardFormat::Text).map(|data| String::from_utf8_lossy(until_null(&data)).into_owned())
+        }
+    }
+
+    /// Return the dragged html
+    pub fn html(&self) -> Option<ClipboardHtml> {
+        self.bytes(ClipboardFormat::Html).and_then(|data| formats::decode_html(&data))
+    }
+
+    /// Return the paths of the dragged files
+    pub fn files(&self) -> Option<Vec<String>> {
+        self.bytes(ClipboardFormat::FileList).and_then(|data| formats::decode_file_list(&data))
+    }
+
+    /// Read a value written with `DragPayload::json`. Requires the `clipboard-json` feature.
+    #[cfg(feature = "clipboard-json")]
+    pub fn json<T: serde::de::DeserializeOwned>(&self, fmt: ClipboardFormat) -> Option<T> {
+        let data = self.bytes(fmt)?;
+        serde_json::from_slice(until_null(&data)).ok()
+    }
+
+}
+
+impl std::fmt::Debug for DragDropData {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "DragDropData {{ point: {:?}, effect: {:?} }}", self.point(), self.effect())
+    }
+}
+
+
+/**
+    The data of a drag operation started by the application. The data can be written in more than one format.
+    The target picks the format it understands.
+
+    `start` runs the drag operation and returns when the data was dropped or when the drag was cancelled.
+
+    ```rust
+    use native_windows_gui as nwg;
+
+    fn drag_link() {
+        let effect = nwg::DragPayload::new()
+            .text("https://github.com/gabdube/native-windows-gui")
+            .html("<a href=\"https://github.com/gabdube/native-windows-gui\">NWG</a>", None)
+            .start(nwg::DropEffect::COPY | nwg::DropEffect::LINK);
+
+        if effect.is_empty() {
+            println!("The drag was cancelled");
+        }
+    }
+    ```
+*/
+#[derive(Default, Clone, Debug)]
+pub struct DragPayload {
+    formats: Vec<(u32, Vec<u8>)>,
+}
+
+impl DragPayload {
+
+    pub fn new() -> DragPayload {
+        DragPayload::default()
+    }
+
+    /// Add unicode text
+    pub fn text(self, text: &str) -> DragPayload {
+        self.bytes(ClipboardFormat::UnicodeText, &formats::encode_text(text))
+    }
+
+    /// Add an html fragment. See `ClipboardWriter::html`
+    pub fn html(self, fragment: &str, source_url: Option<&str>) -> DragPayload {
+        let html = formats::encode_html(fragment, source_url);
+        self.bytes(ClipboardFormat::Html, &formats::null_terminated(&html))
+    }
+
+    /// Add a rich text document
+    pub fn rtf(self, rtf: &str) -> DragPayload {
+        self.bytes(ClipboardFormat::Rtf, &formats::null_terminated(rtf))
+    }
+
+    /// Add a list of file paths. The files can be dropped in the Windows explorer.
+    pub fn files<S: AsRef<str>>(self, files: &[S]) -> DragPayload {
+        self.bytes(ClipboardFormat::FileList, &formats::encode_file_list(files))
+    }
+
+    /// Add a png image. `png` is the content of a png file.
+    pub fn png(self, png: &[u8]) -> DragPayload {
+        self.bytes(ClipboardFormat::Png, png)
+    }
+
+    /// Add a copy of `data` in the selected format. Replaces the data previously added in the same format.
+    pub fn bytes(mut self, fmt: ClipboardFormat, data: &[u8]) -> DragPayload {
+        let fmt = fmt.into_raw();
+        self.formats.retain(|(f, _)| *f != fmt);
+        self.formats.push((fmt, data.to_vec()));
+        self
+    }
+
+    /// Add a value serialized in json in a custom format. Requires the `clipboard-json` feature.
+    #[cfg(feature = "clipboard-json")]
+    pub fn json<T: serde::Serialize>(self, fmt: ClipboardFormat, value: &T) -> Result<DragPayload, NwgError> {
+        let data = serde_json::to_vec(value)
+            .map_err(|e| NwgError::resource_create(format!("Failed to serialize the drag data: {}", e)))?;
+
+        Ok(self.bytes(fmt, &data))
+    }
+
+    /// Returns `true` if no data was added
+    pub fn is_empty(&self) -> bool {
+        self.formats.is_empty()
+    }
+
+    /**
+        Start a drag operation. This function returns once the data was dropped or the drag was cancelled.
+        Events are still dispatched during the drag.
+
+        Returns the effect picked by the target, or `DropEffect::NONE` if the drag was cancelled.
+        When the effect is `MOVE`, the application should remove the dragged items.
+        The drag should be started from a mouse button press (ex: `OnListViewBeginDrag`).
+    */
+    pub fn start(self, allowed: DropEffect) -> DropEffect {
+        if self.formats.is_empty() || ole_initialize().is_err() {
+            return DropEffect::NONE;
+        }
+
+        unsafe {
+            let data = com_new(DataObject { vtbl: &DATA_OBJECT_VTBL, refs: Cell::new(1), formats: self.formats });
+            let source = com_new(DropSource { vtbl: &DROP_SOURCE_VTBL, refs: Cell::new(1) });
+
+            let mut effect = 0;
+            let result = DoDragDrop(data as *mut IDataObject, source as *mut IUnknown, allowed.bits(), &mut effect);
+
+            com_release(data);
+            com_release(source);
+
+            match result {
+                DRAGDROP_S_DROP => DropEffect::from_bits_truncate(effect) & allowed,
+                _ => DropEffect::NONE
+            }
+        }
+    }
+
+}
+
+
+/// Registers a window as a drop target. The events are sent to the window with `NWG_DRAG_DROP`.
+pub(crate) fn register_drop_target(hwnd: HWND, default_effect: DropEffect) -> Result<(), NwgError> {
+    use winapi::um::ole2::RegisterDragDrop;
+
+    ole_initialize()?;
+
+    unsafe {
+        let target = com_new(DropTarget {
+            vtbl: &DROP_TARGET_VTBL,
+            refs: Cell::new(1),
+            hwnd,
+            default_effect,
+            effect: Cell::new(DropEffect::NONE),
+            data: Cell::new(ptr::null_mut()),
+        });
+
+        // The window holds a reference to the target until `revoke_drop_target` is called
+        let result = RegisterDragDrop(hwnd, target as _);
+        com_release(target);
+
+        match result {
+            S_OK => Ok(()),
+            e => Err(NwgError::control_create(format!("Failed to register the drop target (0x{:X})", e)))
+        }
+    }
+}
+
+/// Stops sending the drag events to a window
+pub(crate) fn revoke_drop_target(hwnd: HWND) {
+    use winapi::um::ole2::RevokeDragDrop;
+    unsafe { RevokeDragDrop(hwnd); }
+}
+
+fn ole_initialize() -> Result<(), NwgError> {
+    use winapi::um::ole2::OleInitialize;
+    use winapi::shared::winerror::S_FALSE;
+
+    match unsafe { OleInitialize(ptr::null_mut()) } {
+        S_OK | S_FALSE => Ok(()),
+        _ => Err(NwgError::initialization("OleInitialize failed"))
+    }
+}
+
+fn format_etc(fmt: u32) -> FORMATETC {
+    FORMATETC {
+        cfFormat: fmt as _,
+        ptd: ptr::null(),
+        dwAspect: DVASPECT_CONTENT,
+        lindex: -1,
+        tymed: TYMED_HGLOBAL,
+    }
+}
+
+
+//
+// COM objects
+//
+
+/// The reference counting shared by the COM objects. The objects are boxed and freed when their count reaches 0.
+trait ComObject: Sized {
+    fn refs(&self) -> &Cell<ULONG>;
+    fn supports(iid: &GUID) -> bool;
+}
+
+unsafe fn com_new<T: ComObject>(object: T) -> *mut T {
+    Box::into_raw(Box::new(object))
+}
+
+unsafe fn com_release<T: ComObject>(this: *mut T) {
+    release::<T>(this);
+}
+
+unsafe extern "system" fn query_interface<T: ComObject>(this: *mut T, iid: REFIID, out: *mut *mut c_void) -> HRESULT {
+    if out.is_null() {
+        return E_INVALIDARG;
+    }
+
+    let iid = &*iid;
+    match IsEqualGUID(iid, &IUnknown::uuidof()) || T::supports(iid) {
+        true => {
+            add_ref::<T>(this);
+            *out = this as _;
+            S_OK
+        },
+        false => {
+            *out = ptr::null_mut();
+            E_NOINTERFACE
+        }
+    }
+}
+
+unsafe extern "system" fn add_ref<T: ComObject>(this: *mut T) -> ULONG {
+    let refs = (*this).refs();
+    refs.set(refs.get() + 1);
+    refs.get()
+}
+
+unsafe extern "system" fn release<T: ComObject>(this: *mut T) -> ULONG {
+    let count = {
+        let refs = (*this).refs();
+        refs.set(refs.get() - 1);
+        refs.get()
+    };
+
+    if count == 0 {
+        drop(Box::from_raw(this));
+    }
+
+    count
+}
+
+
+#[repr(C)]
+struct DropTargetVtbl {
+    query_interface: unsafe extern "system" fn(*mut DropTarget, REFIID, *mut *mut c_void) -> HRESULT,
+    add_ref: unsafe extern "system" fn(*mut DropTarget) -> ULONG,
+    release: unsafe extern "system" fn(*mut DropTarget) -> ULONG,
+    drag_enter: unsafe extern "system" fn(*mut DropTarget, *mut IDataObject, DWORD, POINTL, *mut DWORD) -> HRESULT,
+    drag_over: unsafe extern "system" fn(*mut DropTarget, DWORD, POINTL, *mut DWORD) -> HRESULT,
+    drag_leave: unsafe extern "system" fn(*mut DropTarget) -> HRESULT,
+    drop: unsafe extern "system" fn(*mut DropTarget, *mut IDataObject, DWORD, POINTL, *mut DWORD) -> HRESULT,
+}
+
+/// `IDropTarget` implementation. Forwards the drag events to the window.
+#[repr(C)]
+struct DropTarget {
+    vtbl: *const DropTargetVtbl,
+    refs: Cell<ULONG>,
+    hwnd: HWND,
+    default_effect: DropEffect,
+
+    /// The effect picked by the last event
+    effect: Cell<DropEffect>,
+
+    /// The dragged data between `DragEnter` and `DragLeave` or `Drop`
+    data: Cell<*mut IDataObject>,
+}
+
+static DROP_TARGET_VTBL: DropTargetVtbl = DropTargetVtbl {
+    query_interface: query_interface::<DropTarget>,
+    add_ref: add_ref::<DropTarget>,
+    release: release::<DropTarget>,
+    drag_enter: DropTarget::drag_enter,
+    drag_over: DropTarget::drag_over,
+    drag_leave: DropTarget::drag_leave,
+    drop: DropTarget::drop_data,
+};
+
+impl ComObject for DropTarget {
+    fn refs(&self) -> &Cell<ULONG> { &self.refs }
+    fn supports(iid: &GUID) -> bool { IsEqualGUID(iid, &winapi::um::oleidl::IDropTarget::uuidof()) }
+}
+
+impl DropTarget {
+
+    unsafe extern "system" fn drag_enter(this: *mut DropTarget, data: *mut IDataObject, keys: DWORD, pt: POINTL, effect: *mut DWORD) -> HRESULT {
+        let this = &*this;
+        if !data.is_null() {
+            (*data).AddRef();
+        }
+
+        this.release_data();
+        this.data.set(data);
+        this.effect.set(this.default_effect);
+        this.send(DRAG_ENTER, keys, pt, effect);
+
+        S_OK
+    }
+
+    unsafe extern "system" fn drag_over(this: *mut DropTarget, keys: DWORD, pt: POINTL, effect: *mut DWORD) -> HRESULT {
+        (*this).send(DRAG_OVER, keys, pt, effect);
+        S_OK
+    }
+
+    unsafe extern "system" fn drag_leave(this: *mut DropTarget) -> HRESULT {
+        let this = &*this;
+        this.release_data();
+
+        let mut effect = 0;
+        this.send(DRAG_LEAVE, 0, POINTL { x: 0, y: 0 }, &mut effect);
+
+        S_OK
+    }
+
+    unsafe extern "system" fn drop_data(this: *mut DropTarget, data: *mut IDataObject, keys: DWORD, pt: POINTL, effect: *mut DWORD) -> HRESULT {
+        let this = &*this;
+        this.release_data();
+        this.data.set(data);
+        this.send(DRAG_DROP, keys, pt, effect);
+        this.data.set(ptr::null_mut());
+
+        S_OK
+    }
+
+    /// Sends a drag event to the window and writes back the effect picked by the handlers
+    unsafe fn send(&self, kind: usize, keys: DWORD, pt: POINTL, effect: *mut DWORD) {
+        use winapi::um::winuser::ScreenToClient;
+
+        let mut point = POINT { x: pt.x, y: pt.y };
+        ScreenToClient(self.hwnd, &mut point);
+
+        let allowed = DropEffect::from_bits_truncate(*effect);
+        let mut state = DragDropState {
+            data: self.data.get(),
+            point: [point.x, point.y],
+            key_state: keys,
+            allowed,
+            effect: self.effect.get() & allowed,
+        };
+
+        wh::send_message(self.hwnd, NWG_DRAG_DROP, kind, &mut state as *mut DragDropState as LPARAM);
+
+        self.effect.set(state.effect);
+        *effect = (state.effect & allowed).bits();
+    }
+
+    unsafe fn release_data(&self) {
+        let data = self.data.replace(ptr::null_mut());
+        if !data.is_null() {
+            (*data).Release();
+        }
+    }
+
+}
+
+impl Drop for DropTarget {
+    fn drop(&mut self) {
+        unsafe { self.release_data(); }
+    }
+}
+
+
+#[repr(C)]
+struct DropSourceVtbl {
+    query_interface: unsafe extern "system" fn(*mut DropSource, REFIID, *mut *mut c_void) -> HRESULT,
+    add_ref: unsafe extern "system" fn(*mut DropSource) -> ULONG,
+    release: unsafe extern "system" fn(*mut DropSource) -> ULONG,
+    query_continue_drag: unsafe extern "system" fn(*mut DropSource, BOOL, DWORD) -> HRESULT,
+    give_feedback: unsafe extern "system" fn(*mut DropSource, DWORD) -> HRESULT,
+}
+
+/// `IDropSource` implementation. Drops when the mouse buttons are released and cancels on escape.
+#[repr(C)]
+struct DropSource {
+    vtbl: *const DropSourceVtbl,
+    refs: Cell<ULONG>,
+}
+
+static DROP_SOURCE_VTBL: DropSourceVtbl = DropSourceVtbl {
+    query_interface: query_interface::<DropSource>,
+    add_ref: add_ref::<DropSource>,
+    release: release::<DropSource>,
+    query_continue_drag: DropSource::query_continue_drag,
+    give_feedback: DropSource::give_feedback,
+};
+
+impl ComObject for DropSource {
+    fn refs(&self) -> &Cell<ULONG> { &self.refs }
+    fn supports(iid: &GUID) -> bool { IsEqualGUID(iid, &IID_IDROPSOURCE) }
+}
+
+impl DropSource {
+
+    unsafe extern "system" fn query_continue_drag(_this: *mut DropSource, escape_pressed: BOOL, keys: DWORD) -> HRESULT {
+        use winapi::um::winuser::{MK_LBUTTON, MK_RBUTTON};
+
+        if escape_pressed != 0 {
+            DRAGDROP_S_CANCEL
+        } else if keys & (MK_LBUTTON | MK_RBUTTON) as DWORD == 0 {
+            DRAGDROP_S_DROP
+        } else {
+            S_OK
+        }
+    }
+
+    unsafe extern "system" fn give_feedback(_this: *mut DropSource, _effect: DWORD) -> HRESULT {
+        DRAGDROP_S_USEDEFAULTCURSORS
+    }
+
+}
+
+
+#[repr(C)]
+struct DataObjectVtbl {
+    query_interface: unsafe extern "system" fn(*mut DataObject, REFIID, *mut *mut c_void) -> HRESULT,
+    add_ref: unsafe extern "system" fn(*mut DataObject) -> ULONG,
+    release: unsafe extern "system" fn(*mut DataObject) -> ULONG,
+    get_data: unsafe extern "system" fn(*mut DataObject, *const FORMATETC, *mut StgMedium) -> HRESULT,
+    get_data_here: unsafe extern "system" fn(*mut DataObject, *const FORMATETC, *mut StgMedium) -> HRESULT,
+    query_get_data: unsafe extern "system" fn(*mut DataObject, *const FORMATETC) -> HRESULT,
+    get_canonical_format_etc: unsafe extern "system" fn(*mut DataObject, *const FORMATETC, *mut FORMATETC) -> HRESULT,
+    set_data: unsafe extern "system" fn(*mut DataObject, *const FORMATETC, *mut StgMedium, BOOL) -> HRESULT,
+    enum_format_etc: unsafe extern "system" fn(*mut DataObject, DWORD, *mut *mut IEnumFORMATETC) -> HRESULT,
+    d_advise: unsafe extern "system" fn(*mut DataObject, *const FORMATETC, DWORD, *mut c_void, *mut DWORD) -> HRESULT,
+    d_unadvise: unsafe extern "system" fn(*mut DataObject, DWORD) -> HRESULT,
+    enum_d_advise: unsafe extern "system" fn(*mut DataObject, *mut *mut c_void) -> HRESULT,
+}
+
+/// `IDataObject` implementation over the formats of a `DragPayload`. The data is only available in global memory.
+#[repr(C)]
+struct DataObject {
+    vtbl: *const DataObjectVtbl,
+    refs: Cell<ULONG>,
+    formats: Vec<(u32, Vec<u8>)>,
+}
+
+static DATA_OBJECT_VTBL: DataObjectVtbl = DataObjectVtbl {
+    query_interface: query_interface::<DataObject>,
+    add_ref: add_ref::<DataObject>,
+    release: release::<DataObject>,
+    get_data: DataObject::get_data,
+    get_data_here: DataObject::get_data_here,
+    query_get_data: DataObject::query_get_data,
+    get_canonical_format_etc: DataObject::get_canonical_format_etc,
+    set_data: DataObject::set_data,
+    enum_format_etc: DataObject::enum_format_etc,
+    d_advise: DataObject::d_advise,
+    d_unadvise: DataObject::d_unadvise,
+    enum_d_advise: DataObject::enum_d_advise,
+};
+
+impl ComObject for DataObject {
+    fn refs(&self) -> &Cell<ULONG> { &self.refs }
+    fn supports(iid: &GUID) -> bool { IsEqualGUID(iid, &IDataObject::uuidof()) }
+}
+
+impl DataObject {
+
+    unsafe fn find(&self, format: *const FORMATETC) -> Option<&[u8]> {
+        let format = &*format;
+        if format.tymed & TYMED_HGLOBAL == 0 || format.dwAspect != DVASPECT_CONTENT {
+            return None;
+        }
+
+        self.formats.iter()
+            .find(|(fmt, _)| *fmt == format.cfFormat as u32)
+            .map(|(_, data)| data.as_slice())
+    }
+
+    unsafe extern "system" fn get_data(this: *mut DataObject, format: *const FORMATETC, medium: *mut StgMedium) -> HRESULT {
+        let data = match (*this).find(format) {
+            Some(data) => data,
+            None => { return DV_E_FORMATETC; }
+        };
+
+        let handle = global_alloc(data);
+        if handle.is_null() {
+            return winapi::shared::winerror::E_OUTOFMEMORY;
+        }
+
+        *medium = StgMedium { tymed: TYMED_HGLOBAL, handle, unk_for_release: ptr::null_mut() };
+        S_OK
+    }
+
+    unsafe extern "system" fn get_data_here(_this: *mut DataObject, _format: *const FORMATETC, _medium: *mut StgMedium) -> HRESULT {
+        E_NOTIMPL
+    }
+
+    unsafe extern "system" fn query_get_data(this: *mut DataObject, format: *const FORMATETC) -> HRESULT {
+        match (*this).find(format) {
+            Some(_) => S_OK,
+            None => DV_E_FORMATETC
+        }
+    }
+
+    unsafe extern "system" fn get_canonical_format_etc(_this: *mut DataObject, _format: *const FORMATETC, out: *mut FORMATETC) -> HRESULT {
+        (*out).ptd = ptr::null();
+        DATA_S_SAMEFORMATETC
+    }
+
+    unsafe extern "system" fn set_data(_this: *mut DataObject, _format: *const FORMATETC, _medium: *mut StgMedium, _release: BOOL) -> HRESULT {
+        E_NOTIMPL
+    }
+
+    unsafe extern "system" fn enum_format_etc(this: *mut DataObject, direction: DWORD, out: *mut *mut IEnumFORMATETC) -> HRESULT {
+        use winapi::um::objidl::DATADIR_GET;
+
+        if direction != DATADIR_GET {
+            *out = ptr::null_mut();
+            return E_NOTIMPL;
+        }
+
+        let formats: Vec<FORMATETC> = (*this).formats.iter().map(|(fmt, _)| format_etc(*fmt)).collect();
+        SHCreateStdEnumFmtEtc(formats.len() as UINT, formats.as_ptr(), out)
+    }
+
+    unsafe extern "system" fn d_advise(_this: *mut DataObject, _format: *const FORMATETC, _advf: DWORD, _sink: *mut c_void, _connection: *mut DWORD) -> HRESULT {
+        OLE_E_ADVISENOTSUPPORTED
+    }
+
+    unsafe extern "system" fn d_unadvise(_this: *mut DataObject, _connection: DWORD) -> HRESULT {
+        OLE_E_ADVISENOTSUPPORTED
+    }
+
+    unsafe extern "system" fn enum_d_advise(_this: *mut DataObject, _out: *mut *mut c_void) -> HRESULT {
+        OLE_E_ADVISENOTSUPPORTED
+    }
+
+}
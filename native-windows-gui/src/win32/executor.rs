@@ -0,0 +1,64 @@
+/*!
+    The executor of the GUI thread. The executor runs when its message only window receives `NWG_EXECUTOR_WAKE`.
+
+    Other threads wake the executor with `SendNotifyMessageW`, like a `NoticeSender`. The GUI thread posts the
+    message instead, because `SendNotifyMessageW` would run the executor before the caller returns.
+*/
+use winapi::shared::windef::HWND;
+use winapi::shared::minwindef::DWORD;
+use super::window::{create_message_window, bind_raw_event_handler_inner};
+use super::window_helper::NWG_EXECUTOR_WAKE;
+use crate::executor::LocalExecutor;
+use std::{cell::RefCell, rc::Rc};
+
+
+thread_local! {
+    static EXECUTOR: RefCell<Option<Rc<LocalExecutor>>> = const { RefCell::new(None) };
+}
+
+/// Runs `f` with the executor of the current thread. The executor is created on the first call.
+pub(crate) fn with_executor<R, F: FnOnce(&LocalExecutor) -> R>(f: F) -> R {
+    let executor = EXECUTOR.with(|executor| {
+        executor.borrow_mut().get_or_insert_with(create_executor).clone()
+    });
+
+    f(&executor)
+}
+
+fn create_executor() -> Rc<LocalExecutor> {
+    use winapi::um::processthreadsapi::GetCurrentThreadId;
+
+    let window = create_message_window()
+        .expect("Failed to create the executor window. Was `nwg::init` called?");
+
+    let hwnd = window.hwnd().unwrap() as usize;
+    let thread = unsafe { GetCurrentThreadId() };
+    let executor = Rc::new(LocalExecutor::new(move || unsafe { wake(hwnd as HWND, thread) }));
+
+    // The window lives as long as the thread, so the handler is never unbound
+    let weak_executor = Rc::downgrade(&executor);
+    bind_raw_event_handler_inner(&window, 0, move |_hwnd, msg, _w, _l| {
+        if msg != NWG_EXECUTOR_WAKE {
+            return None;
+        }
+
+        if let Some(executor) = weak_executor.upgrade() {
+            executor.run();
+        }
+
+        Some(0)
+    }).expect("Failed to bind the executor window");
+
+    executor
+}
+
+unsafe fn wake(hwnd: HWND, thread: DWORD) {
+    use winapi::um::winuser::{PostMessageW, SendNotifyMessageW};
+    use winapi::um::processthreadsapi::GetCurrentThreadId;
+
+    if GetCurrentThreadId() == thread {
+        PostMessageW(hwnd, NWG_EXECUTOR_WAKE, 0, 0);
+    } else {
+        SendNotifyMessageW(hwnd, NWG_EXECUTOR_WAKE, 0, 0);
+    }
+}
@@ -190,6 +190,45 @@ pub unsafe fn menu_item_checked(parent_menu: HMENU, id: u32) -> bool {
     GetMenuState(parent_menu, id, MF_BYCOMMAND) & MF_CHECKED == MF_CHECKED
 }
 
+/**
+    Replace the shortcut label of a menu item (the text after the tab character). An empty label removes the shortcut label.
+*/
+pub unsafe fn set_menu_item_shortcut(parent_menu: HMENU, id: u32, shortcut: &str) {
+    use winapi::um::winuser::{MENUITEMINFOW, MIIM_STRING, GetMenuItemInfoW, SetMenuItemInfoW};
+    use winapi::shared::minwindef::BOOL;
+    use super::base_helper::from_utf16;
+
+    let mut info = MENUITEMINFOW { 
+        cbSize: mem::size_of::<MENUITEMINFOW>() as UINT,
+        fMask: MIIM_STRING, fType: 0, fState: 0,
+        wID: 0, hSubMenu: ptr::null_mut(), hbmpChecked: ptr::null_mut(),
+        hbmpUnchecked: ptr::null_mut(), dwItemData: 0, dwTypeData: ptr::null_mut(),
+        cch: 0, hbmpItem: ptr::null_mut()
+    };
+
+    // The first call returns the length of the text
+    if GetMenuItemInfoW(parent_menu, id, false as BOOL, &mut info) == 0 {
+        return;
+    }
+
+    let mut buffer: Vec<u16> = vec![0; info.cch as usize + 1];
+    info.dwTypeData = buffer.as_mut_ptr();
+    info.cch += 1;
+    GetMenuItemInfoW(parent_menu, id, false as BOOL, &mut info);
+
+    let text = from_utf16(&buffer);
+    let label = text.split('\t').next().unwrap_or("");
+    let text = match shortcut.is_empty() {
+        true => label.to_string(),
+        false => format!("{}\t{}", label, shortcut)
+    };
+
+    let mut text = to_utf16(&text);
+    info.dwTypeData = text.as_mut_ptr();
+    info.cch = 0;
+    SetMenuItemInfoW(parent_menu, id, false as BOOL, &mut info);
+}
+
 
 unsafe fn build_hmenu_separator(menu: HMENU) -> ControlHandle {
     use winapi::um::winuser::{GetMenuItemCount, SetMenuItemInfoW, AppendMenuW};
@@ -1,6 +1,6 @@
 use super::base_helper::to_utf16;
 use crate::controls::ControlHandle;
-use winapi::shared::windef::HWND;
+use crate::win32::sys::shared::windef::HWND;
 use std::ptr;
 
 
@@ -67,11 +67,11 @@ pub struct MessageParams<'a> {
 
 /// Inner function used by the message box function
 fn inner_message(parent: HWND, params: &MessageParams) -> MessageChoice {
-    use winapi::um::winuser::{MB_ABORTRETRYIGNORE, MB_CANCELTRYCONTINUE, MB_OK, MB_OKCANCEL, MB_RETRYCANCEL, MB_YESNO,
+    use crate::win32::sys::um::winuser::{MB_ABORTRETRYIGNORE, MB_CANCELTRYCONTINUE, MB_OK, MB_OKCANCEL, MB_RETRYCANCEL, MB_YESNO,
         MB_YESNOCANCEL, MB_ICONSTOP, MB_ICONINFORMATION, MB_ICONQUESTION, MB_ICONEXCLAMATION};
    
-       use winapi::um::winuser::{IDABORT, IDCANCEL, IDCONTINUE, IDIGNORE, IDNO, IDOK, IDRETRY, IDTRYAGAIN, IDYES};
-       use winapi::um::winuser::MessageBoxW;
+       use crate::win32::sys::um::winuser::{IDABORT, IDCANCEL, IDCONTINUE, IDIGNORE, IDNO, IDOK, IDRETRY, IDTRYAGAIN, IDYES};
+       use crate::win32::sys::um::winuser::MessageBoxW;
    
        let text = to_utf16(params.content);
        let title = to_utf16(params.title);
@@ -15,6 +15,15 @@ pub(crate) mod cursor;
 #[cfg(feature = "clipboard")]
 pub(crate) mod clipboard;
 
+#[cfg(feature = "accelerator")]
+pub(crate) mod accelerator;
+
+#[cfg(feature = "drag-drop")]
+pub(crate) mod drag_drop;
+
+#[cfg(feature = "executor")]
+pub(crate) mod executor;
+
 #[cfg(feature = "tabs")]
 pub(crate) mod tabs;
 
@@ -30,6 +39,9 @@ pub(crate) mod richedit;
 #[cfg(feature = "plotting")]
 pub(crate) mod plotters_d2d;
 
+/// Alias used by the modules shared with the `headless` backend. There, `sys` is a small pure-rust stand-in for winapi.
+pub(crate) use ::winapi as sys;
+
 use std::{fs, mem, ptr};
 use crate::errors::NwgError;
 
@@ -46,7 +58,7 @@ pub fn dispatch_thread_events() {
     unsafe {
         let mut msg: MSG = mem::zeroed();
         while GetMessageW(&mut msg, ptr::null_mut(), 0, 0) != 0 {
-            if IsDialogMessageW(GetAncestor(msg.hwnd, GA_ROOT), &mut msg) == 0 {
+            if !translate_accelerator(&mut msg) && IsDialogMessageW(GetAncestor(msg.hwnd, GA_ROOT), &mut msg) == 0 {
                 TranslateMessage(&msg); 
                 DispatchMessageW(&msg); 
             }
@@ -69,7 +81,7 @@ pub fn dispatch_thread_events_with_callback<F>(mut cb: F)
         let mut msg: MSG = mem::zeroed();
         while msg.message != WM_QUIT {
             let has_message = PeekMessageW(&mut msg, ptr::null_mut(), 0, 0, PM_REMOVE) != 0;
-            if has_message {
+            if has_message && !translate_accelerator(&mut msg) {
                 if IsDialogMessageW(GetAncestor(msg.hwnd, GA_ROOT), &mut msg) == 0 {
                     TranslateMessage(&msg); 
                     DispatchMessageW(&msg); 
@@ -81,6 +93,43 @@ pub fn dispatch_thread_events_with_callback<F>(mut cb: F)
     }
 }
 
+/**
+    Dispatch the events of the current thread until `done` returns true or until the window `hwnd` is hidden or destroyed.
+    Used by the modal dialogs.
+
+    If `stop_thread_dispatch` is called while the loop runs, the loop stops and the quit message is posted again
+    so that the outer loop stops too.
+*/
+pub(crate) fn dispatch_modal_events(hwnd: winapi::shared::windef::HWND, done: &dyn Fn() -> bool) {
+    use winapi::um::winuser::MSG;
+    use winapi::um::winuser::{GetMessageW, PostMessageW, IsWindow, IsWindowVisible, WM_QUIT};
+
+    unsafe {
+        let mut msg: MSG = mem::zeroed();
+        while !done() && IsWindow(hwnd) != 0 && IsWindowVisible(hwnd) != 0 {
+            if GetMessageW(&mut msg, ptr::null_mut(), 0, 0) == 0 {
+                PostMessageW(ptr::null_mut(), WM_QUIT, msg.wParam, msg.lParam);
+                return;
+            }
+
+            if !translate_accelerator(&mut msg) && IsDialogMessageW(GetAncestor(msg.hwnd, GA_ROOT), &mut msg) == 0 {
+                TranslateMessage(&msg);
+                DispatchMessageW(&msg);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "accelerator")]
+unsafe fn translate_accelerator(msg: &mut winapi::um::winuser::MSG) -> bool {
+    accelerator::translate(msg)
+}
+
+#[cfg(not(feature = "accelerator"))]
+unsafe fn translate_accelerator(_msg: &mut winapi::um::winuser::MSG) -> bool {
+    false
+}
+
 /**
     Break the events loop running on the current thread
 */
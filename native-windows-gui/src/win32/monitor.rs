@@ -1,8 +1,9 @@
-use winapi::shared::windef::HWND;
-use winapi::um::winuser::{GetSystemMetrics, MonitorFromWindow, GetMonitorInfoW, MONITORINFO,
+use crate::win32::sys::shared::windef::{HWND, HDC, HMONITOR, RECT};
+use crate::win32::sys::shared::minwindef::{BOOL, LPARAM};
+use crate::win32::sys::um::winuser::{GetSystemMetrics, MonitorFromWindow, GetMonitorInfoW, EnumDisplayMonitors, MONITORINFO,
     SM_CXSCREEN, SM_CYSCREEN, SM_CXVIRTUALSCREEN, SM_CYVIRTUALSCREEN, MONITOR_DEFAULTTONEAREST};
 use crate::ControlHandle;
-use std::mem;
+use std::{mem, ptr};
 
 /**
     Expose basic properties of the monitor(s) on the system and the virtual screen.
@@ -82,6 +83,30 @@ impl Monitor {
         ]
     }
 
+    /// Returns the [left, top, right, bottom] work area of every monitor, expressed in virtual-screen coordinates.
+    /// The work area is the monitor rectangle without the taskbar and the docked toolbars.
+    pub fn work_areas() -> Vec<[i32; 4]> {
+        unsafe extern "system" fn add_work_area(monitor: HMONITOR, _hdc: HDC, _rect: *mut RECT, data: LPARAM) -> BOOL {
+            let areas = &mut *(data as *mut Vec<[i32; 4]>);
+
+            let mut info: MONITORINFO = mem::zeroed();
+            info.cbSize = mem::size_of::<MONITORINFO>() as _;
+            if GetMonitorInfoW(monitor, &mut info) != 0 {
+                let w = info.rcWork;
+                areas.push([w.left, w.top, w.right, w.bottom]);
+            }
+
+            1
+        }
+
+        let mut areas: Vec<[i32; 4]> = Vec::new();
+        unsafe {
+            EnumDisplayMonitors(ptr::null_mut(), ptr::null(), Some(add_work_area), &mut areas as *mut Vec<[i32; 4]> as LPARAM);
+        }
+
+        areas
+    }
+
     /// Returns the primary monitor width in pixel
     /// Use `Monitor::virtual_width` to get the dimensions of the virtual screen
     pub fn width() -> i32 {
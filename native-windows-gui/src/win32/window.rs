@@ -9,7 +9,10 @@ use winapi::shared::basetsd::{DWORD_PTR, UINT_PTR};
 use winapi::um::winuser::{WNDPROC, NMHDR, IDCANCEL, IDOK};
 use winapi::um::commctrl::{NMTTDISPINFOW, SUBCLASSPROC};
 use super::base_helper::{CUSTOM_ID_BEGIN, to_utf16};
-use super::window_helper::{NOTICE_MESSAGE, NWG_INIT, NWG_TRAY, NWG_TIMER_TICK, NWG_TIMER_STOP};
+use super::window_helper::{NOTICE_MESSAGE, NWG_INIT, NWG_TRAY, NWG_TIMER_TICK, NWG_TIMER_STOP, nwg_simulate_message};
+
+#[cfg(feature = "drag-drop")]
+use super::window_helper::NWG_DRAG_DROP;
 use super::high_dpi;
 use crate::controls::ControlHandle;
 use crate::{Event, EventData, NwgError};
@@ -353,6 +356,40 @@ pub fn unbind_raw_event_handler(handler: &RawEventHandler) -> Result<(), NwgErro
     }
 }
 
+/**
+    Dispatch an event to the event handlers bound with `full_bind_event_handler` or `bind_event_handler`,
+    as if it was sent by the system. Meant to be used in tests.
+
+    The event is sent to the handlers bound on the control and then to the handlers bound on its parents.
+    Each handler receives the event once. The event data is returned after all the handlers were called
+    so that the changes done by the callbacks (ex: `WindowCloseData::close`) can be inspected.
+
+    Only the nwg event handlers are called. The control itself is not modified: simulating `OnWindowClose` does not close the window.
+
+    This function will panic if the control does not have a parent window (ex: a menu item).
+*/
+pub fn simulate_event(handle: &ControlHandle, evt: Event, data: EventData) -> EventData {
+    use super::window_helper::{get_window_parent, send_message};
+    use crate::events::SimulatedEvent;
+
+    let message = nwg_simulate_message();
+
+    let hwnd = match handle {
+        &ControlHandle::Hwnd(h) | &ControlHandle::PopMenu(h, _) | &ControlHandle::Notice(h, _) |
+        &ControlHandle::Timer(h, _) | &ControlHandle::SystemTray(h) => h,
+        htype => panic!("Cannot simulate events on a control with an handle of type {:?}.", htype)
+    };
+
+    let mut simulated = SimulatedEvent::new(evt, data, *handle);
+    let mut target = hwnd;
+    while !target.is_null() {
+        send_message(target, message, 0, &mut simulated as *mut SimulatedEvent as LPARAM);
+        target = get_window_parent(target);
+    }
+
+    simulated.data
+}
+
 /**
     High level function that handle the creation of custom window control or built in window control
 */
@@ -487,8 +524,8 @@ pub(crate) fn create_frame_classes() -> Result<(), NwgError> {
     Ok(())
 }
 
-#[cfg(feature = "message-window")]
-/// Create a message only window. Used with the `MessageWindow` control
+#[cfg(any(feature = "message-window", feature = "executor", feature = "notice"))]
+/// Create a message only window. Used with the `MessageWindow` control, the executor of the thread and the `UiReceiver` without parent
 pub(crate) fn create_message_window() -> Result<ControlHandle, NwgError> {
     use winapi::um::winuser::HWND_MESSAGE;
     use winapi::um::winuser::CreateWindowExW;
@@ -562,7 +599,7 @@ unsafe extern "system" fn process_events(hwnd: HWND, msg: UINT, w: WPARAM, l: LP
     use winapi::um::winuser::{GetClassNameW, GetMenuItemID, GetSubMenu};
     use winapi::um::winuser::{WM_CLOSE, WM_COMMAND, WM_MENUCOMMAND, WM_TIMER, WM_NOTIFY, WM_HSCROLL, WM_VSCROLL, WM_LBUTTONDOWN, WM_LBUTTONUP,
       WM_RBUTTONDOWN, WM_RBUTTONUP, WM_SIZE, WM_MOVE, WM_PAINT, WM_MOUSEMOVE, WM_CONTEXTMENU, WM_INITMENUPOPUP, WM_MENUSELECT, WM_EXITSIZEMOVE,
-      WM_ENTERSIZEMOVE, SIZE_MAXIMIZED, SIZE_MINIMIZED, WM_KEYDOWN, WM_KEYUP, WM_CHAR, WM_MOUSEWHEEL, WM_DROPFILES, GET_WHEEL_DELTA_WPARAM,
+      WM_ENTERSIZEMOVE, SIZE_MAXIMIZED, SIZE_MINIMIZED, WM_KEYDOWN, WM_KEYUP, WM_CHAR, WM_MOUSEWHEEL, WM_DROPFILES, WM_CLIPBOARDUPDATE, GET_WHEEL_DELTA_WPARAM,
       WM_GETMINMAXINFO, WM_ENTERMENULOOP, WM_EXITMENULOOP, WM_SYSKEYDOWN, WM_SYSKEYUP};
     use winapi::um::shellapi::{NIN_BALLOONSHOW, NIN_BALLOONHIDE, NIN_BALLOONTIMEOUT, NIN_BALLOONUSERCLICK};
     use winapi::um::winnt::WCHAR;
@@ -636,6 +673,14 @@ unsafe extern "system" fn process_events(hwnd: HWND, msg: UINT, w: WPARAM, l: LP
                 callback(Event::OnMenuHover, NO_DATA, ControlHandle::MenuItem(parent, index));
             }
         },
+        #[cfg(feature = "accelerator")]
+        WM_COMMAND if l == 0 && HIWORD(w as u32) == 1 => {
+            let id = LOWORD(w as u32);
+            match super::accelerator::menu_item_command(hwnd, id) {
+                Some(item) => callback(Event::OnMenuItemSelected, NO_DATA, item),
+                None => callback(Event::OnAccelerator, EventData::OnAccelerator(id), base_handle)
+            }
+        },
         WM_COMMAND => {
             let child_handle: HWND = l as HWND;
             let message = HIWORD(w as u32) as u16;
@@ -701,6 +746,7 @@ unsafe extern "system" fn process_events(hwnd: HWND, msg: UINT, w: WPARAM, l: LP
             let data = EventData::OnFileDrop(DropFiles { drop: w as _ });
             callback(Event::OnFileDrop, data, base_handle)
         },
+        WM_CLIPBOARDUPDATE => callback(Event::OnClipboardUpdate, NO_DATA, base_handle),
         WM_GETMINMAXINFO => {
             let data = EventData::OnMinMaxInfo(MinMaxInfo { inner: l as _ });
             callback(Event::OnMinMaxInfo, data, base_handle)
@@ -717,13 +763,49 @@ unsafe extern "system" fn process_events(hwnd: HWND, msg: UINT, w: WPARAM, l: LP
         WM_LBUTTONDOWN => callback(Event::OnMousePress(MousePressEvent::MousePressLeftDown), NO_DATA, base_handle), 
         WM_RBUTTONUP => callback(Event::OnMousePress(MousePressEvent::MousePressRightUp), NO_DATA, base_handle), 
         WM_RBUTTONDOWN => callback(Event::OnMousePress(MousePressEvent::MousePressRightDown), NO_DATA, base_handle),
+        #[cfg(feature="notice")]
+        NOTICE_MESSAGE => match crate::controls::ui_channel::notice_payloads(hwnd, w as u32) {
+            Some(payloads) => {
+                for payload in payloads.iter() {
+                    callback(Event::OnNotice, EventData::OnNotice(payload.clone()), ControlHandle::Notice(hwnd, w as u32));
+                }
+
+                // The next subclasses are the other event handlers of the window. They receive the same payloads while `payloads` is alive.
+                return DefSubclassProc(hwnd, msg, w, l);
+            },
+            None => callback(Event::OnNotice, NO_DATA, ControlHandle::Notice(hwnd, w as u32))
+        },
+        #[cfg(not(feature="notice"))]
         NOTICE_MESSAGE => callback(Event::OnNotice, NO_DATA, ControlHandle::Notice(hwnd, w as u32)),
         NWG_TIMER_STOP => callback(Event::OnTimerStop, NO_DATA, ControlHandle::Timer(hwnd, w as u32)),
         NWG_TIMER_TICK => callback(Event::OnTimerTick, NO_DATA, ControlHandle::Timer(hwnd, w as u32)),
         NWG_INIT => callback(Event::OnInit, NO_DATA, base_handle),
+        #[cfg(feature = "drag-drop")]
+        NWG_DRAG_DROP => {
+            use super::drag_drop::{DRAG_ENTER, DRAG_OVER, DRAG_LEAVE};
+
+            let evt = match w {
+                DRAG_ENTER => Event::OnDragEnter,
+                DRAG_OVER => Event::OnDragOver,
+                DRAG_LEAVE => Event::OnDragLeave,
+                _ => Event::OnDragDrop
+            };
+
+            let data = EventData::OnDragDrop(crate::DragDropData { inner: l as _ });
+            callback(evt, data, base_handle);
+            return 0;
+        },
+        _ if msg == nwg_simulate_message() => {
+            let simulated = &mut *(l as *mut SimulatedEvent);
+            simulated.dispatch(id, callback);
+
+            // The next subclasses are the other event handlers of the window. The control itself ignores the registered message.
+            DefSubclassProc(hwnd, msg, w, l);
+            return 0;
+        },
         WM_CLOSE => {
             let mut should_exit = true;
-            let data = EventData::OnWindowClose(WindowCloseData { data: &mut should_exit as *mut bool });
+            let data = EventData::OnWindowClose(WindowCloseData { data: &mut should_exit as *mut bool, _storage: None });
             callback(Event::OnWindowClose, data, base_handle);
 
             if !should_exit {
@@ -811,8 +893,8 @@ fn track_commands(m: u32) -> Event {
 
 fn tree_commands(m: u32) -> Event {
     use winapi::um::commctrl::{
-        NM_CLICK, NM_DBLCLK, NM_KILLFOCUS, NM_RCLICK, NM_SETFOCUS, TVN_BEGINLABELEDITW,
-        TVN_DELETEITEMW, TVN_ENDLABELEDITW, TVN_ITEMCHANGEDW, TVN_ITEMEXPANDEDW, TVN_SELCHANGEDW,
+        NM_CLICK, NM_DBLCLK, NM_KILLFOCUS, NM_RCLICK, NM_SETFOCUS, TVN_BEGINLABELEDITW, TVN_BEGINDRAGW,
+        TVN_DELETEITEMW, TVN_ENDLABELEDITW, TVN_ITEMCHANGEDW, TVN_ITEMEXPANDEDW, TVN_ITEMEXPANDINGW, TVN_SELCHANGEDW,
     };
 
     match m {
@@ -823,10 +905,12 @@ fn tree_commands(m: u32) -> Event {
         NM_RCLICK => Event::OnTreeViewRightClick,
         TVN_DELETEITEMW => Event::OnTreeItemDelete,
         TVN_ITEMEXPANDEDW => Event::OnTreeItemExpanded,
+        TVN_ITEMEXPANDINGW => Event::OnTreeItemExpanding,
         TVN_SELCHANGEDW => Event::OnTreeItemSelectionChanged,
         TVN_ITEMCHANGEDW => Event::OnTreeItemChanged,
         TVN_BEGINLABELEDITW => Event::OnTreeViewBeginItemEdit,
         TVN_ENDLABELEDITW => Event::OnTreeViewEndItemEdit,
+        TVN_BEGINDRAGW => Event::OnTreeViewBeginDrag,
         _ => Event::Unknown,
     }
 }
@@ -834,7 +918,8 @@ fn tree_commands(m: u32) -> Event {
 fn list_view_commands(m: u32) -> Event {
     use winapi::um::commctrl::{NM_KILLFOCUS, NM_SETFOCUS, LVN_DELETEALLITEMS,
         LVN_DELETEITEM, LVN_INSERTITEM, LVN_ITEMACTIVATE, LVN_ITEMCHANGED,
-        NM_CLICK, NM_DBLCLK, NM_RCLICK, LVN_COLUMNCLICK};
+        NM_CLICK, NM_DBLCLK, NM_RCLICK, LVN_COLUMNCLICK, LVN_BEGINDRAG};
+    use super::window_helper::{NWG_LVN_BEGINEDIT, NWG_LVN_ENDEDIT};
 
     match m {
         NM_CLICK => Event::OnListViewClick,
@@ -848,6 +933,9 @@ fn list_view_commands(m: u32) -> Event {
         LVN_ITEMCHANGED => Event::OnListViewItemChanged,
         NM_KILLFOCUS => Event::OnListViewFocusLost,
         NM_SETFOCUS => Event::OnListViewFocus,
+        NWG_LVN_BEGINEDIT => Event::OnListViewBeginEdit,
+        NWG_LVN_ENDEDIT => Event::OnListViewEndEdit,
+        LVN_BEGINDRAG => Event::OnListViewBeginDrag,
         _ => Event::Unknown
     }
 }
@@ -864,8 +952,8 @@ fn no_class_name_commands(m: usize) -> Event {
 fn tree_data(m: u32, notif_raw: *const NMHDR) -> EventData {
     use crate::{ExpandState, TreeItem, TreeItemAction, TreeItemState};
     use winapi::um::commctrl::{
-        NMTREEVIEWW, NMTVDISPINFOW, NMTVITEMCHANGE, TVE_COLLAPSE, TVE_EXPAND, TVN_DELETEITEMW,
-        TVN_ENDLABELEDITW, TVN_ITEMCHANGEDW, TVN_ITEMEXPANDEDW, TVN_SELCHANGEDW,
+        NMTREEVIEWW, NMTVDISPINFOW, NMTVITEMCHANGE, TVE_COLLAPSE, TVE_EXPAND, TVN_DELETEITEMW, TVN_BEGINDRAGW,
+        TVN_ENDLABELEDITW, TVN_ITEMCHANGEDW, TVN_ITEMEXPANDEDW, TVN_ITEMEXPANDINGW, TVN_SELCHANGEDW,
     };
 
     match m {
@@ -874,7 +962,7 @@ fn tree_data(m: u32, notif_raw: *const NMHDR) -> EventData {
             let item = TreeItem { handle: data.itemOld.hItem };
             EventData::OnTreeItemDelete(item)
         },
-        TVN_ITEMEXPANDEDW => {
+        TVN_ITEMEXPANDEDW | TVN_ITEMEXPANDINGW => {
             let data = unsafe { &*(notif_raw as *const NMTREEVIEWW) };
             let item = TreeItem { handle: data.itemNew.hItem };
 
@@ -892,6 +980,10 @@ fn tree_data(m: u32, notif_raw: *const NMHDR) -> EventData {
             let old = TreeItem { handle: data.itemOld.hItem };
             EventData::OnTreeItemSelectionChanged { old, new }
         },
+        TVN_BEGINDRAGW => {
+            let data = unsafe { &*(notif_raw as *const NMTREEVIEWW) };
+            EventData::OnTreeViewBeginDrag(TreeItem { handle: data.itemNew.hItem })
+        },
         TVN_ITEMCHANGEDW => {
             let data = unsafe { &*(notif_raw as *const NMTVITEMCHANGE) };
             let item = TreeItem { handle: data.hItem };
@@ -944,11 +1036,13 @@ fn tree_data(_m: u32, _notif_raw: *const NMHDR) -> EventData {
 #[cfg(feature="list-view")]
 fn list_view_data(m: u32, notif_raw: *const NMHDR) -> EventData {
     use winapi::um::commctrl::{NMLISTVIEW, NMITEMACTIVATE, LVN_DELETEITEM, LVN_ITEMACTIVATE,
-        LVN_INSERTITEM, LVN_ITEMCHANGED, LVIS_SELECTED, LVN_COLUMNCLICK,
+        LVN_INSERTITEM, LVN_ITEMCHANGED, LVIS_SELECTED, LVN_COLUMNCLICK, LVN_BEGINDRAG,
         NM_CLICK, NM_RCLICK, NM_DBLCLK};
+    use super::window_helper::{NWG_LVN_BEGINEDIT, NWG_LVN_ENDEDIT};
+    use crate::controls::ListViewEditNotify;
 
     match m {
-        LVN_DELETEITEM | LVN_INSERTITEM | LVN_COLUMNCLICK => {
+        LVN_DELETEITEM | LVN_INSERTITEM | LVN_COLUMNCLICK | LVN_BEGINDRAG => {
             let data: &NMLISTVIEW = unsafe { &*(notif_raw as *const NMLISTVIEW) };
             EventData::OnListViewItemIndex { 
                 row_index: data.iItem as _,
@@ -970,6 +1064,15 @@ fn list_view_data(m: u32, notif_raw: *const NMHDR) -> EventData {
                 selected: data.uNewState & LVIS_SELECTED == LVIS_SELECTED
             }
         },
+        NWG_LVN_BEGINEDIT | NWG_LVN_ENDEDIT => {
+            let data: &ListViewEditNotify = unsafe { &*(notif_raw as *const ListViewEditNotify) };
+            EventData::OnListViewEdit {
+                row_index: data.row_index,
+                column_index: data.column_index,
+                text: data.text.clone(),
+                cancelled: data.cancelled
+            }
+        },
         _ => NO_DATA
     }
 }
@@ -1037,6 +1140,35 @@ fn syslink_data(_m: u32, _notif_raw: *const NMHDR) -> EventData {
     NO_DATA
 }
 
+fn status_bar_commands(m: u32) -> Event {
+    use winapi::um::commctrl::{NM_CLICK, NM_DBLCLK};
+
+    match m {
+        NM_CLICK => Event::OnStatusBarClick,
+        NM_DBLCLK => Event::OnStatusBarDoubleClick,
+        _ => Event::Unknown
+    }
+}
+
+#[cfg(feature="status-bar")]
+fn status_bar_data(m: u32, notif_raw: *const NMHDR) -> EventData {
+    use winapi::um::commctrl::{NM_CLICK, NM_DBLCLK};
+    use winapi::um::commctrl::NMMOUSE;
+
+    match m {
+        NM_CLICK | NM_DBLCLK => {
+            let mouse: &NMMOUSE = unsafe { &*(notif_raw as *const NMMOUSE) };
+            EventData::OnStatusBarPart(mouse.dwItemSpec)
+        },
+        _ => NO_DATA
+    }
+}
+
+#[cfg(not(feature="status-bar"))]
+fn status_bar_data(_m: u32, _notif_raw: *const NMHDR) -> EventData {
+    NO_DATA
+}
+
 fn animation_commands(m: u16) -> Event {
     use winapi::um::commctrl::{ACN_START, ACN_STOP};
 
@@ -1142,6 +1274,7 @@ unsafe fn handle_default_notify_callback<'a>(notif_raw: *const NMHDR, callback:
         "SysLink" => callback(syslink_commands(code), syslink_data(code, notif_raw), handle),
         "SysIPAddress32" => callback(ipaddress_commands(code), NO_DATA, handle),
         "SysPager" => callback(pager_commands(code), NO_DATA, handle),
+        "msctls_statusbar32" => callback(status_bar_commands(code), status_bar_data(code, notif_raw), handle),
         _ => {}
     }
 }
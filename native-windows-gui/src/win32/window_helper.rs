@@ -5,6 +5,7 @@ use winapi::shared::minwindef::{UINT, WPARAM, LPARAM, LRESULT};
 use winapi::um::winuser::WM_USER;
 use winapi::ctypes::c_int;
 use std::{ptr, mem};
+use std::sync::atomic::{AtomicU32, Ordering};
 
 #[cfg(feature = "rich-textbox")]
 use winapi::um::winuser::WNDCLASSEXW;
@@ -15,6 +16,34 @@ pub const NWG_TRAY: UINT = WM_USER + 102;
 pub const NWG_TIMER_TICK: UINT = WM_USER + 103;
 pub const NWG_TIMER_STOP: UINT = WM_USER + 104;
 
+// Notification codes sent by the list view in-place editors (`WM_NOTIFY`)
+pub const NWG_LVN_BEGINEDIT: UINT = WM_USER + 106;
+pub const NWG_LVN_ENDEDIT: UINT = WM_USER + 107;
+
+// Sent by the drop targets to their window. `w` is the kind of drag event, `l` points to the drag state.
+pub const NWG_DRAG_DROP: UINT = WM_USER + 108;
+
+// Sent to the executor window of a thread when a task is woken
+pub const NWG_EXECUTOR_WAKE: UINT = WM_USER + 109;
+
+/// Returns the message sent by `simulate_event` to a control and to its parents. `l` points to the simulated event.
+/// The message is registered because it also reaches the built-in controls, which use the `WM_USER` range for their own messages.
+pub fn nwg_simulate_message() -> UINT {
+    use winapi::um::winuser::RegisterWindowMessageW;
+
+    static MESSAGE: AtomicU32 = AtomicU32::new(0);
+
+    match MESSAGE.load(Ordering::Relaxed) {
+        0 => {
+            let name = to_utf16("NWG_SIMULATE");
+            let message = unsafe { RegisterWindowMessageW(name.as_ptr()) };
+            MESSAGE.store(message, Ordering::Relaxed);
+            message
+        },
+        message => message
+    }
+}
+
 
 /// Returns the class info of a hwnd handle
 #[cfg(feature = "rich-textbox")]
@@ -153,7 +182,6 @@ pub fn iterate_window_children<F>(hwnd_parent: HWND, cb: F)
     }
 }
 
-#[cfg(any(feature="timer", feature="animation-timer", feature="notice"))]
 pub fn window_valid(hwnd: HWND) -> bool {
     use winapi::um::winuser::IsWindow;
 
@@ -167,6 +195,12 @@ pub fn get_window_parent(hwnd: HWND) -> HWND {
     unsafe { GetParent(hwnd) }
 }
 
+#[allow(unused)]
+pub fn set_window_parent(hwnd: HWND, parent: HWND) {
+    use winapi::um::winuser::SetParent;
+    unsafe { SetParent(hwnd, parent); }
+}
+
 pub fn get_window_font(handle: HWND) -> HFONT {
     use winapi::um::winuser::{ WM_GETFONT };
     unsafe { 
@@ -232,7 +266,7 @@ pub fn get_style(handle: HWND) -> UINT {
     get_window_long(handle, GWL_STYLE) as UINT
 }
 
-#[cfg(any(feature = "list-view", feature = "progress-bar"))]
+#[cfg(any(feature = "list-view", feature = "progress-bar", feature = "tree-view"))]
 pub fn set_style(handle: HWND, style: u32) {
     use ::winapi::um::winuser::GWL_STYLE;
     set_window_long(handle, GWL_STYLE, style as usize);
@@ -316,6 +350,59 @@ pub unsafe fn get_window_position(handle: HWND) -> (i32, i32) {
     high_dpi::physical_to_logical(x, y)
 }
 
+/// Offset between the workspace coordinates of `WINDOWPLACEMENT` and the screen coordinates on a monitor.
+/// Tool windows use screen coordinates.
+unsafe fn workspace_offset(handle: HWND, monitor: winapi::shared::windef::HMONITOR) -> (i32, i32) {
+    use winapi::um::winuser::{GetMonitorInfoW, MONITORINFO, GWL_EXSTYLE, WS_EX_TOOLWINDOW};
+
+    if get_window_long(handle, GWL_EXSTYLE) as u32 & WS_EX_TOOLWINDOW == WS_EX_TOOLWINDOW {
+        return (0, 0);
+    }
+
+    let mut info: MONITORINFO = mem::zeroed();
+    info.cbSize = mem::size_of::<MONITORINFO>() as _;
+    if GetMonitorInfoW(monitor, &mut info) == 0 {
+        return (0, 0);
+    }
+
+    (info.rcWork.left - info.rcMonitor.left, info.rcWork.top - info.rcMonitor.top)
+}
+
+/// Returns the [left, top, right, bottom] restored rectangle of a window in screen pixels, and its show command
+pub unsafe fn get_window_placement(handle: HWND) -> ([i32; 4], u32) {
+    use winapi::um::winuser::{GetWindowPlacement, MonitorFromWindow, WINDOWPLACEMENT, MONITOR_DEFAULTTONEAREST};
+
+    let mut placement: WINDOWPLACEMENT = mem::zeroed();
+    placement.length = mem::size_of::<WINDOWPLACEMENT>() as _;
+    GetWindowPlacement(handle, &mut placement);
+
+    let (x, y) = workspace_offset(handle, MonitorFromWindow(handle, MONITOR_DEFAULTTONEAREST));
+    let r = placement.rcNormalPosition;
+
+    ([r.left + x, r.top + y, r.right + x, r.bottom + y], placement.showCmd)
+}
+
+/// Sets the [left, top, right, bottom] restored rectangle of a window in screen pixels, and shows it with `show_cmd`
+pub unsafe fn set_window_placement(handle: HWND, rect: [i32; 4], show_cmd: u32) {
+    use winapi::um::winuser::{SetWindowPlacement, MonitorFromRect, WINDOWPLACEMENT, MONITOR_DEFAULTTONEAREST};
+    use winapi::shared::windef::RECT;
+
+    let [left, top, right, bottom] = rect;
+    let screen_rect = RECT { left, top, right, bottom };
+    let (x, y) = workspace_offset(handle, MonitorFromRect(&screen_rect, MONITOR_DEFAULTTONEAREST));
+
+    let mut placement: WINDOWPLACEMENT = mem::zeroed();
+    placement.length = mem::size_of::<WINDOWPLACEMENT>() as _;
+    placement.showCmd = show_cmd;
+    placement.ptMinPosition.x = -1;
+    placement.ptMinPosition.y = -1;
+    placement.ptMaxPosition.x = -1;
+    placement.ptMaxPosition.y = -1;
+    placement.rcNormalPosition = RECT { left: left - x, top: top - y, right: right - x, bottom: bottom - y };
+
+    SetWindowPlacement(handle, &placement);
+}
+
 pub unsafe fn set_window_size(handle: HWND, w: u32, h: u32, fix: bool) {
     use winapi::um::winuser::{SetWindowPos, AdjustWindowRectEx, GetWindowLongW};
     use winapi::um::winuser::{SWP_NOZORDER, SWP_NOMOVE, SWP_NOACTIVATE, SWP_NOCOPYBITS, GWL_STYLE, GWL_EXSTYLE, SWP_NOOWNERZORDER};
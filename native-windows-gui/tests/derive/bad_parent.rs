@@ -0,0 +1,22 @@
+use native_windows_gui as nwg;
+use native_windows_derive::NwgUi;
+
+#[derive(Default, NwgUi)]
+pub struct App {
+    #[nwg_control]
+    window: nwg::Window,
+
+    #[nwg_resource(family: "Arial")]
+    font: nwg::Font,
+
+    #[nwg_control(parent: windw)]
+    name: nwg::TextInput,
+
+    #[nwg_control(parent: font)]
+    button: nwg::Button,
+
+    #[nwg_layout]
+    layout: nwg::GridLayout,
+}
+
+fn main() {}
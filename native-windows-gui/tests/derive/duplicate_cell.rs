@@ -0,0 +1,25 @@
+use native_windows_gui as nwg;
+use native_windows_derive::NwgUi;
+
+#[derive(Default, NwgUi)]
+pub struct App {
+    #[nwg_control]
+    window: nwg::Window,
+
+    #[nwg_layout(parent: window)]
+    grid: nwg::GridLayout,
+
+    #[nwg_control]
+    #[nwg_layout_item(layout: grid, col: 0, row: 0, col_span: 2)]
+    label: nwg::Label,
+
+    #[nwg_control]
+    #[nwg_layout_item(layout: grid, col: 1, row: 0)]
+    name: nwg::TextInput,
+
+    #[nwg_control]
+    #[nwg_layout_item(layout: grid, col: 2, row: 0)]
+    button: nwg::Button,
+}
+
+fn main() {}
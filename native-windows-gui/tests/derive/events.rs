@@ -0,0 +1,24 @@
+use native_windows_gui as nwg;
+use native_windows_derive::NwgUi;
+
+#[derive(Default, NwgUi)]
+pub struct App {
+    #[nwg_control]
+    #[nwg_events( OnWindowClosed: [App::close] )]
+    window: nwg::Window,
+
+    #[nwg_control]
+    #[nwg_events( OnButtonClick: [App::click(SELF, CONTROL)] )]
+    button: nwg::Button,
+
+    #[nwg_resource]
+    #[nwg_events( OnInit: [App::close] )]
+    font: nwg::Font,
+}
+
+impl App {
+    fn close(&self) {}
+    fn click(&self) {}
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use native_windows_gui as nwg;
+use native_windows_derive::NwgUi;
+
+#[derive(Default, NwgUi)]
+pub struct App {
+    #[nwg_control(flags: "MAIN_WINDOW|VISIBEL")]
+    window: nwg::Window,
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use native_windows_gui as nwg;
+use native_windows_derive::NwgUi;
+
+#[derive(Default, NwgUi)]
+pub struct App {
+    #[nwg_control(flags: "MAIN_WINDOW|")]
+    window: nwg::Window,
+
+    #[nwg_control(flags: VISIBLE)]
+    button: nwg::Button,
+}
+
+fn main() {}
@@ -0,0 +1,28 @@
+use native_windows_gui as nwg;
+use native_windows_derive::NwgUi;
+
+#[derive(Default, NwgUi)]
+pub struct App {
+    #[nwg_control]
+    window: nwg::Window,
+
+    #[nwg_layout(parent: window)]
+    grid: nwg::GridLayout,
+
+    #[nwg_layout(parent: window)]
+    dock: nwg::DockLayout,
+
+    #[nwg_control]
+    #[nwg_layout_item(layout: window, col: 0, row: 0)]
+    label: nwg::Label,
+
+    #[nwg_control]
+    #[nwg_layout_item(layout: grid, col: -1, colspan: 2)]
+    name: nwg::TextInput,
+
+    #[nwg_control]
+    #[nwg_layout_item(layout: dock, size: 30)]
+    button: nwg::Button,
+}
+
+fn main() {}
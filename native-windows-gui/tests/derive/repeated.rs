@@ -0,0 +1,44 @@
+use native_windows_gui as nwg;
+use native_windows_derive::{NwgUi, NwgPartial};
+
+#[derive(Default, NwgPartial)]
+pub struct Panel {
+    #[nwg_control]
+    button: nwg::Button,
+}
+
+#[derive(Default, NwgUi)]
+pub struct App {
+    #[nwg_control]
+    window: nwg::Window,
+
+    #[nwg_control(parent: window)]
+    #[nwg_events( OnButtonClick: [App::click(SELF, IDX)] )]
+    labels: Vec<nwg::Label>,
+
+    #[nwg_control(parent: window, count: 2)]
+    buttons: [nwg::Button; 2],
+
+    #[nwg_control(parent: buttons)]
+    #[nwg_events( OnButtonClick: [App::click(SELF, IDX)] )]
+    ok: nwg::Button,
+
+    #[nwg_control(parent: window)]
+    status: Option<nwg::StatusBar>,
+
+    #[nwg_resource]
+    fonts: Vec<nwg::Font>,
+
+    #[nwg_partial(parent: window, when: true)]
+    #[nwg_events( (button, OnButtonClick): [App::click(SELF, IDX)] )]
+    panel: Option<Panel>,
+
+    #[nwg_partial(parent: window, when: true)]
+    panels: Vec<Panel>,
+}
+
+impl App {
+    fn click(&self, _idx: usize) {}
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use native_windows_gui as nwg;
+use native_windows_derive::NwgUi;
+
+#[derive(Default, NwgUi)]
+pub struct App {
+    #[nwg_control(titel: "App")]
+    window: nwg::Window,
+
+    #[nwg_control(text: "Ok", text: "Cancel", colour: [0, 0, 0])]
+    button: nwg::Button,
+
+    #[nwg_resource(family: "Arial", sise: 16)]
+    font: nwg::Font,
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+/*!
+    Compile errors reported by native-windows-derive on invalid attributes.
+    Each file in `tests/derive` must fail to build with the error in the matching `.stderr` file.
+
+    Run with `cargo test --no-default-features --features "headless listbox" --test derive_errors`
+    Use `TRYBUILD=overwrite` to regenerate the expected errors after a change.
+*/
+#![cfg(feature = "headless")]
+
+#[test]
+fn derive_errors() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/derive/*.rs");
+}
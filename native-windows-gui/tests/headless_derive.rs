@@ -0,0 +1,257 @@
+/*!
+    Events callbacks generated by native-windows-derive, driven by `simulate_event`.
+
+    Run with `cargo test --no-default-features --features "headless listbox"`
+*/
+#![cfg(feature = "headless")]
+
+extern crate native_windows_gui as nwg;
+extern crate native_windows_derive as nwd;
+
+use nwd::{NwgUi, NwgPartial};
+use nwg::{NativeUi, Event, EventData, WindowCloseData, simulate_event};
+use std::cell::RefCell;
+
+
+#[derive(Default, NwgUi)]
+pub struct Greeter {
+    #[nwg_control(title: "Greeter")]
+    #[nwg_events( OnWindowClose: [Greeter::close(SELF, EVT_DATA)] )]
+    window: nwg::Window,
+
+    #[nwg_control(parent: window)]
+    #[nwg_events( OnTextInput: [Greeter::text_changed] )]
+    name: nwg::TextInput,
+
+    #[nwg_control(parent: window, text: "Greet")]
+    #[nwg_events( OnButtonClick: [Greeter::greet] )]
+    button: nwg::Button,
+
+    log: RefCell<Vec<String>>,
+}
+
+impl Greeter {
+
+    fn text_changed(&self) {
+        self.log.borrow_mut().push(format!("text: {}", self.name.text()));
+    }
+
+    fn greet(&self) {
+        self.log.borrow_mut().push(format!("hello {}", self.name.text()));
+    }
+
+    fn close(&self, data: &EventData) {
+        // Only allow closing once a name was entered
+        data.on_window_close().close(!self.name.text().is_empty());
+    }
+
+}
+
+fn simulate_close(app: &Greeter) -> bool {
+    let data = simulate_event(&app.window.handle, Event::OnWindowClose, EventData::OnWindowClose(WindowCloseData::new(true)));
+    data.on_window_close().closing()
+}
+
+#[test]
+fn simulate_derived_ui() {
+    let app = Greeter::build_ui(Default::default()).expect("Failed to build UI");
+
+    assert!(!simulate_close(&app));
+
+    app.name.set_text("Ferris");
+    simulate_event(&app.button.handle, Event::OnButtonClick, EventData::NoData);
+
+    assert!(simulate_close(&app));
+    assert_eq!(&app.log.borrow()[..], &["text: Ferris".to_string(), "hello Ferris".to_string()]);
+}
+
+#[test]
+fn record_derived_ui() {
+    let app = Greeter::build_ui(Default::default()).expect("Failed to build UI");
+
+    let recorder = nwg::EventRecorder::new();
+    let handler = recorder.bind(&app.window.handle);
+
+    simulate_event(&app.button.handle, Event::OnButtonClick, EventData::NoData);
+    simulate_event(&app.name.handle, Event::OnTextInput, EventData::NoData);
+
+    let events: Vec<Event> = recorder.events().iter().map(|e| e.event).collect();
+    assert_eq!(events, vec![Event::OnButtonClick, Event::OnTextInput]);
+    assert_eq!(&app.log.borrow()[..], &["hello ".to_string(), "text: ".to_string()]);
+
+    nwg::unbind_event_handler(&handler);
+}
+
+
+#[derive(Default, NwgUi)]
+pub struct Form {
+    #[nwg_control(size: (400, 200))]
+    window: nwg::Window,
+
+    #[nwg_layout(parent: window, margin: [0, 0, 0, 0], spacing: 0)]
+    grid: nwg::GridLayout,
+
+    #[nwg_control(parent: window, text: "Name", size: (80, 20))]
+    #[nwg_layout_item(layout: grid, col: 0, row: 0, col_policy: Auto, row_policy: Fixed(30))]
+    label: nwg::Label,
+
+    #[nwg_control(parent: window)]
+    #[nwg_layout_item(layout: grid, col: 1, row: 0, col_policy: nwg::GridPolicy::Weight(3))]
+    name: nwg::TextInput,
+
+    #[nwg_control(parent: window, text: "Ok", size: (100, 25))]
+    #[nwg_layout_item(layout: grid, col: 0, row: 1, col_span: 2, align: Center)]
+    button: nwg::Button,
+}
+
+#[test]
+fn derived_grid_policies() {
+    let app = Form::build_ui(Default::default()).expect("Failed to build UI");
+
+    assert_eq!((app.label.position(), app.label.size()), ((0, 0), (80, 30)));
+    assert_eq!((app.name.position(), app.name.size()), ((80, 0), (320, 30)));
+    assert_eq!((app.button.position(), app.button.size()), ((150, 30), (100, 170)));
+
+    app.window.set_size(600, 100);
+    assert_eq!(app.name.size(), (520, 30));
+    assert_eq!(app.button.position(), (250, 30));
+}
+
+
+#[derive(Default, NwgUi)]
+pub struct ToolWindow {
+    #[nwg_control(size: (400, 300))]
+    window: nwg::Window,
+
+    #[nwg_layout(parent: window, spacing: 2)]
+    dock: nwg::DockLayout,
+
+    #[nwg_control(parent: window, text: "Status")]
+    #[nwg_layout_item(layout: dock, dock: Bottom, size: 20)]
+    status: nwg::Label,
+
+    #[nwg_control(parent: window, size: (120, 10))]
+    #[nwg_layout_item(layout: dock, dock: Left, min_size: 50)]
+    tree: nwg::TextInput,
+
+    #[nwg_control(parent: window)]
+    #[nwg_layout_item(layout: dock, dock: nwg::Dock::Fill)]
+    content: nwg::TextInput,
+}
+
+#[test]
+fn derived_dock_layout() {
+    let app = ToolWindow::build_ui(Default::default()).expect("Failed to build UI");
+
+    assert_eq!((app.status.position(), app.status.size()), ((0, 280), (400, 20)));
+    assert_eq!((app.tree.position(), app.tree.size()), ((0, 0), (120, 278)));
+    assert_eq!((app.content.position(), app.content.size()), ((122, 0), (278, 278)));
+
+    app.window.set_size(100, 100);
+    assert_eq!(app.tree.size(), (98, 78));
+    assert_eq!(app.content.size(), (0, 78));
+
+    // The tree keeps its min size, but cannot go outside of the window
+    app.window.set_size(20, 100);
+    assert_eq!(app.tree.size(), (20, 78));
+}
+
+
+#[derive(Default, NwgPartial)]
+pub struct Advanced {
+    #[nwg_control(text: "Reset")]
+    #[nwg_events( OnButtonClick: [Advanced::reset] )]
+    reset: nwg::Button,
+
+    resets: RefCell<u32>,
+}
+
+impl Advanced {
+    fn reset(&self) {
+        *self.resets.borrow_mut() += 1;
+    }
+}
+
+#[derive(Default, NwgUi)]
+pub struct Parameters {
+    names: Vec<String>,
+    advanced_mode: bool,
+
+    #[nwg_control(size: (300, 200))]
+    window: nwg::Window,
+
+    #[nwg_layout(parent: window, margin: [0, 0, 0, 0], spacing: 0)]
+    grid: nwg::GridLayout,
+
+    #[nwg_control(parent: window, count: data.names.len(), text: &data.names[idx])]
+    #[nwg_layout_item(layout: grid, col: 0, row: idx)]
+    labels: Vec<nwg::Label>,
+
+    #[nwg_control(parent: window, count: data.names.len())]
+    #[nwg_layout_item(layout: grid, col: 1, row: idx, col_span: 2)]
+    #[nwg_events( OnTextInput: [Parameters::edited(SELF, CTRL, IDX)] )]
+    values: Vec<nwg::TextInput>,
+
+    #[nwg_control(parent: window, text: ["Ok", "Cancel"][idx])]
+    #[nwg_events( OnButtonClick: [Parameters::clicked(SELF, IDX)] )]
+    buttons: [nwg::Button; 2],
+
+    #[nwg_partial(parent: window, when: data.advanced_mode)]
+    advanced: Option<Advanced>,
+
+    log: RefCell<Vec<String>>,
+}
+
+impl Parameters {
+
+    fn new(advanced_mode: bool) -> Parameters {
+        Parameters {
+            names: vec!["Width".into(), "Height".into(), "Depth".into()],
+            advanced_mode,
+            ..Default::default()
+        }
+    }
+
+    fn edited(&self, value: &nwg::TextInput, index: usize) {
+        self.log.borrow_mut().push(format!("{} = {}", self.names[index], value.text()));
+    }
+
+    fn clicked(&self, index: usize) {
+        self.log.borrow_mut().push(format!("button {}", index));
+    }
+
+}
+
+#[test]
+fn derived_repeated_controls() {
+    let app = Parameters::build_ui(Parameters::new(false)).expect("Failed to build UI");
+
+    assert_eq!(app.labels.len(), 3);
+    assert_eq!(app.values.len(), 3);
+    assert_eq!(app.labels[1].text(), "Height");
+    assert_eq!(app.buttons[1].text(), "Cancel");
+
+    // One row per item, the values use two columns
+    assert_eq!((app.labels[2].position(), app.labels[2].size()), ((0, 134), (100, 66)));
+    assert_eq!((app.values[2].position(), app.values[2].size()), ((100, 134), (200, 66)));
+
+    // Setting the text raises `OnTextInput`
+    app.values[1].set_text("20");
+    simulate_event(&app.buttons[0].handle, Event::OnButtonClick, EventData::NoData);
+    simulate_event(&app.buttons[1].handle, Event::OnButtonClick, EventData::NoData);
+
+    assert_eq!(&app.log.borrow()[..], &["Height = 20".to_string(), "button 0".to_string(), "button 1".to_string()]);
+}
+
+#[test]
+fn derived_optional_partial() {
+    let app = Parameters::build_ui(Parameters::new(false)).expect("Failed to build UI");
+    assert!(app.advanced.is_none());
+
+    let app = Parameters::build_ui(Parameters::new(true)).expect("Failed to build UI");
+    let advanced = app.advanced.as_ref().expect("Partial was not built");
+    assert_eq!(advanced.reset.text(), "Reset");
+
+    simulate_event(&advanced.reset.handle, Event::OnButtonClick, EventData::NoData);
+    assert_eq!(*advanced.resets.borrow(), 1);
+}